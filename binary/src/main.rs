@@ -8,7 +8,7 @@ use process_mining::{
         import_xes::{build_ignore_attributes, XESImportOptions},
         stream_xes::stream_xes_from_path,
     },
-    import_ocel_xml_file, import_xes_file, OCEL,
+    import_ocel_xml_file, import_xes_file, import_xes_file_parallel, OCEL,
 };
 
 fn main() {
@@ -26,6 +26,15 @@ fn main() {
         now.elapsed()
     );
 
+    // Parallel XES parsing (splitting traces into chunks parsed on multiple threads)
+    let now = Instant::now();
+    let log_parallel = import_xes_file_parallel(xes_path, XESImportOptions::default()).unwrap();
+    println!(
+        "Parsed XES in parallel with {} cases in {:#?}",
+        log_parallel.traces.len(),
+        now.elapsed()
+    );
+
     // Streaming XES Parsing (only counting number of traces)
     // Streaming enables very low memory consumption and sometimes also faster processing
     let now = Instant::now();
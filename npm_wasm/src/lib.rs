@@ -1,6 +1,11 @@
 mod utils;
 
-use process_mining::{event_log::ocel::xml_ocel_import::import_ocel_xml_slice, OCEL};
+use process_mining::{
+    dfg::DirectlyFollowsGraph,
+    event_log::ocel::xml_ocel_import::import_ocel_xml_slice,
+    visualization::svg_export::{dfg_to_svg, petri_net_to_svg},
+    PetriNet, SVGExportOptions, OCEL,
+};
 use utils::set_panic_hook;
 use wasm_bindgen::prelude::*;
 
@@ -108,3 +113,19 @@ pub unsafe fn wasm_destroy_ocel_pointer(addr: usize) -> JsValue {
     // OCEL implicitly detroyed/deallocated here
     true.into()
 }
+
+/// Render a Petri net (given as JSON) as an SVG image
+#[wasm_bindgen]
+pub fn wasm_petri_net_to_svg(petri_net_json: &str) -> String {
+    set_panic_hook();
+    let net: PetriNet = serde_json::from_str(petri_net_json).unwrap();
+    petri_net_to_svg(&net, &SVGExportOptions::default())
+}
+
+/// Render a directly-follows graph (given as JSON) as an SVG image
+#[wasm_bindgen]
+pub fn wasm_dfg_to_svg(dfg_json: &str) -> String {
+    set_panic_hook();
+    let dfg: DirectlyFollowsGraph = serde_json::from_str(dfg_json).unwrap();
+    dfg_to_svg(&dfg, &SVGExportOptions::default())
+}
@@ -46,5 +46,6 @@ export interface WorkerAPI {
     numThreads: number,
   ) => Promise<unknown>;
   petri_net_to_dot: (json: string) => string;
+  petri_net_to_svg: (json: string) => string;
   init: () => Promise<unknown>;
 }
@@ -4,7 +4,8 @@ import init, {
   wasm_discover_alphappp_petri_net_from_xes_vec,
   wasm_parse_ocel2_json,
   wasm_parse_ocel2_xml_to_json_vec,
-  wasm_petri_net_dot
+  wasm_petri_net_dot,
+  wasm_petri_net_svg
 } from "../../pkg/pm_wasm.js";
 import type { WorkerAPI } from "./types.js";
 
@@ -50,6 +51,9 @@ Comlink.expose({
   petri_net_to_dot: (json) => {
     return wasm_petri_net_dot(json)
   },
+  petri_net_to_svg: (json) => {
+    return wasm_petri_net_svg(json)
+  },
   init: async () => {
     await init();
   },
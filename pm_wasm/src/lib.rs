@@ -8,7 +8,8 @@ use process_mining::{
         stream_xes::{stream_xes_slice, stream_xes_slice_gz},
     },
     petri_net::image_export::{export_petri_net_to_dot_graph, graph_to_dot},
-    PetriNet, OCEL,
+    visualization::svg_export::petri_net_to_svg,
+    PetriNet, SVGExportOptions, OCEL,
 };
 use wasm_bindgen::prelude::*;
 // pub use wasm_bindgen_rayon::init_thread_pool;
@@ -203,3 +204,9 @@ pub fn wasm_petri_net_dot(pn: &str) -> String {
     let g = export_petri_net_to_dot_graph(&pn, None);
     graph_to_dot(&g)
 }
+
+#[wasm_bindgen]
+pub fn wasm_petri_net_svg(pn: &str) -> String {
+    let pn: PetriNet = serde_json::from_str(pn).unwrap();
+    petri_net_to_svg(&pn, &SVGExportOptions::default())
+}
@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    conformance::quality::{evaluate_model_quality_projection, ModelQuality},
     event_log::activity_projection::EventLogActivityProjection,
     petri_net::petri_net_struct::{PetriNet, Transition},
 };
@@ -141,6 +144,66 @@ pub fn alphappp_discover_with_auto_parameters(
     (best_config, best_pn)
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Evaluated candidate parameter configuration of [`alphappp_discover_with_auto_parameters_scored`]
+///
+pub struct AutoParameterCandidate {
+    /// Parameter configuration
+    pub config: AlphaPPPConfig,
+    /// Score of the heuristic used by [`alphappp_discover_with_auto_parameters`]
+    pub heuristic_score: f32,
+    /// Quality of the discovered net w.r.t. the event log
+    pub quality: ModelQuality,
+    /// Score assigned by the passed scoring function (used for selecting the best candidate)
+    pub score: f64,
+}
+
+/// Automatically select parameters for Alpha+++ using a custom scoring function and discover a [`PetriNet`] using the chosen parameters
+///
+/// Tests out multiple parameter configurations, evaluates the [`ModelQuality`] of each discovered net w.r.t. the passed log and
+/// selects the configuration with the highest score (according to `score_fn`).
+///
+/// Additionally returns all evaluated [`AutoParameterCandidate`]s, allowing to inspect why a configuration was chosen.
+/// See also [`alphappp_discover_with_auto_parameters_f_score`].
+pub fn alphappp_discover_with_auto_parameters_scored(
+    log_proj: &EventLogActivityProjection,
+    score_fn: &dyn Fn(&AutoParameterCandidate) -> f64,
+) -> (AlphaPPPConfig, PetriNet, Vec<AutoParameterCandidate>) {
+    let mut candidates: Vec<AutoParameterCandidate> = Vec::new();
+    let mut best: Option<(usize, PetriNet)> = None;
+    for c in AUTO_CONFIGS {
+        let (pn, _) = alphappp_discover_petri_net(log_proj, *c);
+        let mut candidate = AutoParameterCandidate {
+            config: *c,
+            heuristic_score: score_discovered_pn(&pn, c),
+            quality: evaluate_model_quality_projection(&pn, log_proj),
+            score: 0.0,
+        };
+        candidate.score = score_fn(&candidate);
+        let is_better = match &best {
+            Some((best_index, _)) => candidate.score > candidates[*best_index].score,
+            None => true,
+        };
+        if is_better {
+            best = Some((candidates.len(), pn));
+        }
+        candidates.push(candidate);
+    }
+    let (best_index, best_pn) = best.unwrap();
+    (candidates[best_index].config, best_pn, candidates)
+}
+
+/// Automatically select parameters for Alpha+++ based on the F-score and discover a [`PetriNet`] using the chosen parameters
+///
+/// Selects the configuration whose discovered net has the highest F-score (harmonic mean of fitness and precision, see [`ModelQuality::f_score`]).
+/// Additionally returns all evaluated [`AutoParameterCandidate`]s.
+pub fn alphappp_discover_with_auto_parameters_f_score(
+    log_proj: &EventLogActivityProjection,
+) -> (AlphaPPPConfig, PetriNet, Vec<AutoParameterCandidate>) {
+    alphappp_discover_with_auto_parameters_scored(log_proj, &|c| c.quality.f_score())
+}
+
 fn score_discovered_pn(pn: &PetriNet, config: &AlphaPPPConfig) -> f32 {
     fn is_transition_well_connected(pn: &PetriNet, t: &Transition) -> bool {
         if t.label.is_some() {
@@ -210,3 +273,30 @@ fn score_discovered_pn(pn: &PetriNet, config: &AlphaPPPConfig) -> f32 {
                     .count() as f32))
             .powf(2.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_utils::event_log_from_activities;
+
+    use super::*;
+
+    #[test]
+    fn f_score_selection() {
+        let log = event_log_from_activities(&[
+            &["a", "b", "c", "d"],
+            &["a", "c", "b", "d"],
+            &["a", "b", "c", "d"],
+            &["a", "e", "d"],
+        ]);
+        let log_proj: EventLogActivityProjection = (&log).into();
+        let (config, _, candidates) = alphappp_discover_with_auto_parameters_f_score(&log_proj);
+        assert_eq!(candidates.len(), AUTO_CONFIGS.len());
+        let best = candidates
+            .iter()
+            .map(|c| c.score)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let chosen = candidates.iter().find(|c| c.score == best).unwrap();
+        assert_eq!(chosen.config.to_json(), config.to_json());
+        assert!(candidates.iter().all(|c| c.score == c.quality.f_score()));
+    }
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    conversion::{convert_bpmn_to_petri_net, convert_petri_net_to_bpmn, BPMNConversionError},
+    export_bpmn::export_bpmn_to_path,
+    import_bpmn::{import_bpmn_from_path, BPMNParseError},
+};
+use crate::PetriNet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Type of a [`BPMNNode`]
+///
+pub enum BPMNNodeType {
+    /// Start event (process instances start here)
+    StartEvent,
+    /// End event (process instances end here)
+    EndEvent,
+    /// Task (i.e., an activity)
+    Task,
+    /// Exclusive gateway (XOR): Exactly one outgoing branch is taken, and each incoming branch activates it
+    ExclusiveGateway,
+    /// Parallel gateway (AND): All outgoing branches are taken, and all incoming branches are synchronized
+    ParallelGateway,
+    /// Inclusive gateway (OR): Any non-empty subset of the outgoing branches is taken, and the active incoming branches are synchronized
+    InclusiveGateway,
+}
+
+impl BPMNNodeType {
+    /// Local name of the corresponding BPMN 2.0 XML element (e.g., `exclusiveGateway`)
+    pub fn xml_tag(&self) -> &'static str {
+        match self {
+            BPMNNodeType::StartEvent => "startEvent",
+            BPMNNodeType::EndEvent => "endEvent",
+            BPMNNodeType::Task => "task",
+            BPMNNodeType::ExclusiveGateway => "exclusiveGateway",
+            BPMNNodeType::ParallelGateway => "parallelGateway",
+            BPMNNodeType::InclusiveGateway => "inclusiveGateway",
+        }
+    }
+
+    /// Check if this is a gateway type
+    pub fn is_gateway(&self) -> bool {
+        matches!(
+            self,
+            BPMNNodeType::ExclusiveGateway
+                | BPMNNodeType::ParallelGateway
+                | BPMNNodeType::InclusiveGateway
+        )
+    }
+
+    /// Check if this is an event type
+    pub fn is_event(&self) -> bool {
+        matches!(self, BPMNNodeType::StartEvent | BPMNNodeType::EndEvent)
+    }
+
+    /// Prefix used for generated IDs of nodes of this type
+    fn id_prefix(&self) -> &'static str {
+        match self {
+            BPMNNodeType::StartEvent | BPMNNodeType::EndEvent => "Event",
+            BPMNNodeType::Task => "Activity",
+            _ => "Gateway",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Node (i.e., flow node) in a [`BPMNModel`]
+///
+pub struct BPMNNode {
+    /// ID of the node (unique in the model)
+    pub id: String,
+    /// Name of the node (e.g., the activity of a task)
+    pub name: Option<String>,
+    /// Type of the node
+    pub node_type: BPMNNodeType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Sequence flow in a [`BPMNModel`], connecting two [`BPMNNode`]s
+///
+pub struct BPMNSequenceFlow {
+    /// ID of the sequence flow (unique in the model)
+    pub id: String,
+    /// Optional name of the sequence flow (e.g., a condition label)
+    pub name: Option<String>,
+    /// ID of the source node
+    pub source: String,
+    /// ID of the target node
+    pub target: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// A BPMN process model of [`BPMNNode`]s (events, tasks and gateways) connected by [`BPMNSequenceFlow`]s
+///
+/// See [`BPMNModel::from_petri_net`] and [`BPMNModel::to_petri_net`] for converting from/to [`PetriNet`]s.
+///
+/// ```rust
+/// use process_mining::bpmn::{bpmn_struct::BPMNNodeType, BPMNModel};
+///
+/// let mut model = BPMNModel::new();
+/// let start = model.add_node(BPMNNodeType::StartEvent, None);
+/// let task = model.add_node(BPMNNodeType::Task, Some("Register".to_string()));
+/// let end = model.add_node(BPMNNodeType::EndEvent, None);
+/// model.add_flow(&start, &task);
+/// model.add_flow(&task, &end);
+///
+/// let net = model.to_petri_net().unwrap();
+/// assert_eq!(net.transitions.len(), 1);
+/// ```
+pub struct BPMNModel {
+    /// ID of the process
+    pub id: String,
+    /// Optional name of the process
+    pub name: Option<String>,
+    /// Nodes (events, tasks and gateways)
+    pub nodes: Vec<BPMNNode>,
+    /// Sequence flows between nodes
+    pub flows: Vec<BPMNSequenceFlow>,
+}
+
+impl Default for BPMNModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BPMNModel {
+    /// Create new [`BPMNModel`] without any nodes or flows
+    pub fn new() -> Self {
+        Self {
+            id: generate_id("Process"),
+            name: None,
+            nodes: Vec::new(),
+            flows: Vec::new(),
+        }
+    }
+
+    /// Add a node with a generated ID and return this ID
+    pub fn add_node(&mut self, node_type: BPMNNodeType, name: Option<String>) -> String {
+        let id = generate_id(node_type.id_prefix());
+        self.nodes.push(BPMNNode {
+            id: id.clone(),
+            name,
+            node_type,
+        });
+        id
+    }
+
+    /// Add a sequence flow (with a generated ID) between the nodes with the given IDs and return the ID of the flow
+    pub fn add_flow(&mut self, source: &str, target: &str) -> String {
+        let id = generate_id("Flow");
+        self.flows.push(BPMNSequenceFlow {
+            id: id.clone(),
+            name: None,
+            source: source.to_string(),
+            target: target.to_string(),
+        });
+        id
+    }
+
+    /// Get the node with the given ID
+    pub fn get_node(&self, id: &str) -> Option<&BPMNNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Get all nodes of the given type
+    pub fn nodes_of_type(&self, node_type: BPMNNodeType) -> impl Iterator<Item = &BPMNNode> {
+        self.nodes.iter().filter(move |n| n.node_type == node_type)
+    }
+
+    /// Get the incoming sequence flows of the node with the given ID
+    pub fn incoming_flows<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a BPMNSequenceFlow> {
+        self.flows.iter().filter(move |f| f.target == id)
+    }
+
+    /// Get the outgoing sequence flows of the node with the given ID
+    pub fn outgoing_flows<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a BPMNSequenceFlow> {
+        self.flows.iter().filter(move |f| f.source == id)
+    }
+
+    /// Remove the node with the given ID and all sequence flows connected to it
+    pub fn remove_node(&mut self, id: &str) {
+        self.nodes.retain(|n| n.id != id);
+        self.flows.retain(|f| f.source != id && f.target != id);
+    }
+
+    ///
+    /// Convert a (workflow) [`PetriNet`] to a [`BPMNModel`]
+    ///
+    /// See [`convert_petri_net_to_bpmn`] for details.
+    pub fn from_petri_net(net: &PetriNet) -> Self {
+        convert_petri_net_to_bpmn(net)
+    }
+
+    ///
+    /// Convert this [`BPMNModel`] to a [`PetriNet`] (with initial and final marking)
+    ///
+    /// See [`convert_bpmn_to_petri_net`] for details.
+    pub fn to_petri_net(&self) -> Result<PetriNet, BPMNConversionError> {
+        convert_bpmn_to_petri_net(self)
+    }
+
+    /// Export BPMN model to a BPMN 2.0 XML file (incl. a generated diagram layout)
+    ///
+    /// The file is written to the specified filepath
+    ///
+    /// The resulting file can also be imported again (see [`BPMNModel::import_bpmn`]).
+    pub fn export_bpmn<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), quick_xml::Error> {
+        export_bpmn_to_path(self, path)
+    }
+
+    /// Import BPMN model from a BPMN 2.0 XML file
+    ///
+    /// The file is read from the specified filepath
+    ///
+    /// For the related export function, see [`BPMNModel::export_bpmn`])
+    pub fn import_bpmn<P: AsRef<std::path::Path>>(path: P) -> Result<BPMNModel, BPMNParseError> {
+        import_bpmn_from_path(path)
+    }
+}
+
+/// Generate a new ID with the given prefix (e.g., `Activity_<uuid>`), which is a valid XML ID
+fn generate_id(prefix: &str) -> String {
+    format!("{}_{}", prefix, Uuid::new_v4().simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bpmn_model() {
+        let mut model = BPMNModel::new();
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let split = model.add_node(BPMNNodeType::ParallelGateway, None);
+        let a = model.add_node(BPMNNodeType::Task, Some("a".to_string()));
+        let b = model.add_node(BPMNNodeType::Task, Some("b".to_string()));
+        model.add_flow(&start, &split);
+        model.add_flow(&split, &a);
+        model.add_flow(&split, &b);
+
+        assert_eq!(model.outgoing_flows(&split).count(), 2);
+        assert_eq!(model.incoming_flows(&a).count(), 1);
+        assert_eq!(model.nodes_of_type(BPMNNodeType::Task).count(), 2);
+        assert!(model.get_node(&split).unwrap().node_type.is_gateway());
+        assert_ne!(a, b);
+
+        model.remove_node(&split);
+        assert_eq!(model.nodes.len(), 3);
+        assert!(model.flows.is_empty());
+    }
+}
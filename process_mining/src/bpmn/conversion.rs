@@ -0,0 +1,570 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::bpmn_struct::{BPMNModel, BPMNNodeType};
+use crate::{
+    petri_net::{
+        petri_net_struct::{ArcType, Marking, PlaceID, TransitionID},
+        reduction::{reduce_petri_net, ReductionOptions, ReductionRule},
+    },
+    PetriNet,
+};
+
+/// Maximal number of incoming or outgoing sequence flows of an inclusive gateway
+/// supported by [`convert_bpmn_to_petri_net`]
+///
+/// Every non-empty subset of the flows becomes a silent transition (i.e., up to `4095` transitions per join or split).
+pub const MAX_INCLUSIVE_GATEWAY_FLOWS: usize = 12;
+
+///
+/// Error encountered while converting a [`BPMNModel`] to a [`PetriNet`]
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BPMNConversionError {
+    /// An inclusive gateway has more incoming or outgoing sequence flows than [`MAX_INCLUSIVE_GATEWAY_FLOWS`]
+    /// (gateway ID and number of flows included)
+    TooManyInclusiveGatewayFlows(String, usize),
+}
+
+impl std::fmt::Display for BPMNConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to convert BPMN to Petri net: {:?}", self)
+    }
+}
+
+impl std::error::Error for BPMNConversionError {}
+
+///
+/// Convert a (workflow) [`PetriNet`] to a [`BPMNModel`]
+///
+/// - Labeled transitions become tasks, silent transitions become parallel gateways
+/// - Places with multiple incoming or outgoing arcs become exclusive gateways (joins and splits)
+/// - Transitions with multiple input or output places are synchronized or split by parallel gateways
+/// - Places of the initial marking are connected to a start event and places of the final markings to end events.
+///   If the net has no initial or final marking, places without incoming or outgoing arcs are used instead.
+///
+/// Gateways with only one incoming and one outgoing sequence flow are omitted.
+///
+/// _Note_: This conversion is intended for workflow nets (e.g., as discovered by
+/// [`alphappp_discover_petri_net`](crate::alphappp_discover_petri_net)).
+/// Arc weights and the number of tokens in markings are not considered.
+///
+pub fn convert_petri_net_to_bpmn(net: &PetriNet) -> BPMNModel {
+    let mut model = BPMNModel::new();
+    // Entry (i.e., join) and exit (i.e., split) node of every place and transition
+    let mut entries: HashMap<Uuid, String> = HashMap::new();
+    let mut exits: HashMap<Uuid, String> = HashMap::new();
+
+    // Sorted for a deterministic order of the resulting nodes
+    let mut place_ids: Vec<Uuid> = net.places.keys().copied().collect();
+    place_ids.sort();
+    for p in &place_ids {
+        let join = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        let split = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        model.add_flow(&join, &split);
+        entries.insert(*p, join);
+        exits.insert(*p, split);
+    }
+    let mut transitions: Vec<_> = net.transitions.values().collect();
+    transitions.sort();
+    for t in transitions {
+        let t_id = TransitionID::from(t).get_uuid();
+        let join = model.add_node(BPMNNodeType::ParallelGateway, None);
+        let split = model.add_node(BPMNNodeType::ParallelGateway, None);
+        match &t.label {
+            Some(label) => {
+                let task = model.add_node(BPMNNodeType::Task, Some(label.clone()));
+                model.add_flow(&join, &task);
+                model.add_flow(&task, &split);
+            }
+            None => {
+                model.add_flow(&join, &split);
+            }
+        }
+        entries.insert(t_id, join);
+        exits.insert(t_id, split);
+    }
+    for arc in &net.arcs {
+        let (from, to) = match arc.from_to {
+            ArcType::PlaceTransition(from, to) => (from, to),
+            ArcType::TransitionPlace(from, to) => (from, to),
+        };
+        if let (Some(from), Some(to)) = (exits.get(&from), entries.get(&to)) {
+            model.add_flow(from, to);
+        }
+    }
+
+    let initial_places: Vec<Uuid> = match &net.initial_marking {
+        Some(marking) => marked_places(marking),
+        None => place_ids
+            .iter()
+            .filter(|p| net.preset_of_place(PlaceID(**p)).is_empty())
+            .copied()
+            .collect(),
+    };
+    if !initial_places.is_empty() {
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let start = if initial_places.len() > 1 {
+            let split = model.add_node(BPMNNodeType::ParallelGateway, None);
+            model.add_flow(&start, &split);
+            split
+        } else {
+            start
+        };
+        for p in &initial_places {
+            model.add_flow(&start, &entries[p]);
+        }
+    }
+
+    let final_places: Vec<Vec<Uuid>> = match &net.final_markings {
+        Some(markings) => markings.iter().map(marked_places).collect(),
+        None => vec![place_ids
+            .iter()
+            .filter(|p| net.postset_of_place(PlaceID(**p)).is_empty())
+            .copied()
+            .collect()],
+    };
+    let mut places_with_end_event: HashSet<Uuid> = HashSet::new();
+    for places in final_places {
+        if places.is_empty() || (places.len() == 1 && places_with_end_event.contains(&places[0])) {
+            continue;
+        }
+        let end = model.add_node(BPMNNodeType::EndEvent, None);
+        let end = if places.len() > 1 {
+            let join = model.add_node(BPMNNodeType::ParallelGateway, None);
+            model.add_flow(&join, &end);
+            join
+        } else {
+            places_with_end_event.insert(places[0]);
+            end
+        };
+        for p in &places {
+            model.add_flow(&exits[p], &end);
+        }
+    }
+
+    remove_pass_through_gateways(&mut model);
+    model
+}
+
+/// Places with at least one token in the marking (sorted)
+fn marked_places(marking: &Marking) -> Vec<Uuid> {
+    let mut places: Vec<Uuid> = marking
+        .iter()
+        .filter(|(_, tokens)| **tokens > 0)
+        .map(|(p, _)| p.get_uuid())
+        .collect();
+    places.sort();
+    places
+}
+
+///
+/// Remove all gateways with exactly one incoming and one outgoing sequence flow (connecting their predecessor and successor directly)
+///
+fn remove_pass_through_gateways(model: &mut BPMNModel) {
+    let mut in_degree: HashMap<&str, usize> = HashMap::new();
+    let mut out_flow: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, f) in model.flows.iter().enumerate() {
+        *in_degree.entry(&f.target).or_default() += 1;
+        out_flow.entry(&f.source).or_default().push(i);
+    }
+    let removable: HashSet<String> = model
+        .nodes
+        .iter()
+        .filter(|n| {
+            n.node_type.is_gateway()
+                && in_degree.get(n.id.as_str()) == Some(&1)
+                && out_flow.get(n.id.as_str()).is_some_and(|o| o.len() == 1)
+        })
+        .map(|n| n.id.clone())
+        .collect();
+    // Successor of each removable gateway
+    let successors: HashMap<String, String> = removable
+        .iter()
+        .map(|g| {
+            let flow = out_flow[g.as_str()][0];
+            (g.clone(), model.flows[flow].target.clone())
+        })
+        .collect();
+
+    model.flows.retain(|f| !removable.contains(&f.source));
+    for f in &mut model.flows {
+        // Flows from non-removable nodes can not enter a cycle of only removable gateways (as they have a single incoming flow)
+        while let Some(next) = successors.get(&f.target) {
+            f.target = next.clone();
+        }
+    }
+    model.nodes.retain(|n| !removable.contains(&n.id));
+}
+
+///
+/// Convert a [`BPMNModel`] to a [`PetriNet`] with initial and final marking
+///
+/// - Sequence flows become places
+/// - Tasks become transitions labeled with the task name (or the task ID, if the task has no name).
+///   Multiple incoming sequence flows of a task are merged (i.e., each of them activates the task).
+/// - Exclusive gateways become a place with silent transitions from all incoming and to all outgoing sequence flows
+/// - Parallel gateways become silent transitions
+/// - Inclusive gateways become one silent transition for every non-empty subset of the incoming (join) and outgoing (split) sequence flows
+/// - All start events share a single source place, which is the initial marking.
+///   All end events share a single sink place, which is the final marking.
+///
+/// Afterwards, silent transitions which only move a token between two places are removed where this does not change
+/// the language of the net (by fusing the two places, see [`ReductionRule::FusionOfSeriesPlaces`]).
+///
+/// _Note_: The non-local semantics of inclusive joins are approximated by the subset transitions.
+/// As the number of subsets grows exponentially, inclusive gateways with more than [`MAX_INCLUSIVE_GATEWAY_FLOWS`]
+/// incoming or outgoing flows are rejected (see [`BPMNConversionError::TooManyInclusiveGatewayFlows`]).
+///
+pub fn convert_bpmn_to_petri_net(model: &BPMNModel) -> Result<PetriNet, BPMNConversionError> {
+    let mut net = PetriNet::new();
+    let source = net.add_place(None);
+    let sink = net.add_place(None);
+    let mut incoming: HashMap<&str, Vec<PlaceID>> = HashMap::new();
+    let mut outgoing: HashMap<&str, Vec<PlaceID>> = HashMap::new();
+    for f in &model.flows {
+        let p = net.add_place(None);
+        incoming.entry(&f.target).or_default().push(p);
+        outgoing.entry(&f.source).or_default().push(p);
+    }
+
+    for node in &model.nodes {
+        let ins = incoming.get(node.id.as_str()).cloned().unwrap_or_default();
+        let outs = outgoing.get(node.id.as_str()).cloned().unwrap_or_default();
+        match node.node_type {
+            BPMNNodeType::Task => {
+                let label = node.name.clone().unwrap_or_else(|| node.id.clone());
+                let input = if ins.len() > 1 {
+                    vec![merge_into_place(&mut net, &ins)]
+                } else {
+                    ins
+                };
+                let t = net.add_transition(Some(label), None);
+                add_transition_arcs(&mut net, &input, t, &outs);
+            }
+            BPMNNodeType::ExclusiveGateway => {
+                let gateway = merge_into_place(&mut net, &ins);
+                for o in outs {
+                    let t = net.add_transition(None, None);
+                    add_transition_arcs(&mut net, &[gateway], t, &[o]);
+                }
+            }
+            BPMNNodeType::ParallelGateway => {
+                let t = net.add_transition(None, None);
+                add_transition_arcs(&mut net, &ins, t, &outs);
+            }
+            BPMNNodeType::InclusiveGateway => {
+                let flows = ins.len().max(outs.len());
+                if flows > MAX_INCLUSIVE_GATEWAY_FLOWS {
+                    return Err(BPMNConversionError::TooManyInclusiveGatewayFlows(
+                        node.id.clone(),
+                        flows,
+                    ));
+                }
+                if ins.len() <= 1 && outs.len() <= 1 {
+                    let t = net.add_transition(None, None);
+                    add_transition_arcs(&mut net, &ins, t, &outs);
+                    continue;
+                }
+                // Place between the join and the split part of the gateway
+                let mid = if ins.len() > 1 && outs.len() > 1 {
+                    vec![net.add_place(None)]
+                } else if ins.len() > 1 {
+                    outs.clone()
+                } else {
+                    ins.clone()
+                };
+                if ins.len() > 1 {
+                    for subset in non_empty_subsets(&ins) {
+                        let t = net.add_transition(None, None);
+                        add_transition_arcs(&mut net, &subset, t, &mid);
+                    }
+                }
+                if outs.len() > 1 {
+                    for subset in non_empty_subsets(&outs) {
+                        let t = net.add_transition(None, None);
+                        add_transition_arcs(&mut net, &mid, t, &subset);
+                    }
+                }
+            }
+            BPMNNodeType::StartEvent => {
+                let t = net.add_transition(None, None);
+                add_transition_arcs(&mut net, &[source], t, &outs);
+            }
+            BPMNNodeType::EndEvent => {
+                for i in ins {
+                    let t = net.add_transition(None, None);
+                    add_transition_arcs(&mut net, &[i], t, &[sink]);
+                }
+            }
+        }
+    }
+
+    net.initial_marking = Some(Marking::from([(source, 1)]));
+    net.final_markings = Some(vec![Marking::from([(sink, 1)])]);
+    reduce_petri_net(
+        &mut net,
+        &ReductionOptions {
+            rules: vec![
+                ReductionRule::EliminationOfSelfLoopTransitions,
+                ReductionRule::FusionOfSeriesPlaces,
+            ],
+        },
+    );
+    Ok(net)
+}
+
+/// Add a new place, which receives the tokens of all passed places (through silent transitions)
+fn merge_into_place(net: &mut PetriNet, places: &[PlaceID]) -> PlaceID {
+    let merged = net.add_place(None);
+    for p in places {
+        let t = net.add_transition(None, None);
+        add_transition_arcs(net, &[*p], t, &[merged]);
+    }
+    merged
+}
+
+/// Add arcs from the input places to the transition and from the transition to the output places
+///
+/// Places occurring multiple times result in arcs with a corresponding weight.
+fn add_transition_arcs(net: &mut PetriNet, pre: &[PlaceID], t: TransitionID, post: &[PlaceID]) {
+    for (p, weight) in count_places(pre) {
+        net.add_arc(ArcType::place_to_transition(p, t), Some(weight));
+    }
+    for (p, weight) in count_places(post) {
+        net.add_arc(ArcType::transition_to_place(t, p), Some(weight));
+    }
+}
+
+/// Count the occurrences of places (sorted by place)
+fn count_places(places: &[PlaceID]) -> Vec<(PlaceID, u32)> {
+    let mut counts: Vec<(PlaceID, u32)> = Vec::new();
+    let mut sorted = places.to_vec();
+    sorted.sort();
+    for p in sorted {
+        match counts.last_mut() {
+            Some((last, count)) if *last == p => *count += 1,
+            _ => counts.push((p, 1)),
+        }
+    }
+    counts
+}
+
+/// All non-empty subsets of the given places
+///
+/// Only called for at most [`MAX_INCLUSIVE_GATEWAY_FLOWS`] places.
+fn non_empty_subsets(places: &[PlaceID]) -> Vec<Vec<PlaceID>> {
+    (1..(1usize << places.len()))
+        .map(|mask| {
+            places
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, p)| *p)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    type TestMarking = BTreeMap<Uuid, u64>;
+
+    /// Markings reachable by firing enabled transitions with the given label (or silent transitions if `None`)
+    fn step(net: &PetriNet, marking: &TestMarking, label: Option<&str>) -> Vec<TestMarking> {
+        net.transitions
+            .values()
+            .filter(|t| t.label.as_deref() == label)
+            .filter_map(|t| {
+                let t = TransitionID::from(t).get_uuid();
+                let mut next = marking.clone();
+                for arc in &net.arcs {
+                    if let ArcType::PlaceTransition(p, to) = arc.from_to {
+                        if to == t {
+                            let tokens = next.entry(p).or_default();
+                            *tokens = tokens.checked_sub(arc.weight as u64)?;
+                        }
+                    }
+                }
+                for arc in &net.arcs {
+                    if let ArcType::TransitionPlace(from, p) = arc.from_to {
+                        if from == t {
+                            *next.entry(p).or_default() += arc.weight as u64;
+                        }
+                    }
+                }
+                next.retain(|_, tokens| *tokens > 0);
+                Some(next)
+            })
+            .collect()
+    }
+
+    /// Closure of the markings under firing silent transitions
+    fn silent_closure(net: &PetriNet, markings: BTreeSet<TestMarking>) -> BTreeSet<TestMarking> {
+        let mut ret = markings.clone();
+        let mut queue: Vec<TestMarking> = markings.into_iter().collect();
+        while let Some(m) = queue.pop() {
+            for next in step(net, &m, None) {
+                if ret.insert(next.clone()) {
+                    queue.push(next);
+                }
+            }
+        }
+        ret
+    }
+
+    /// Check if the net can replay the trace from the initial to a final marking
+    pub(crate) fn accepts(net: &PetriNet, trace: &[&str]) -> bool {
+        let to_test_marking = |m: &Marking| -> TestMarking {
+            m.iter()
+                .filter(|(_, tokens)| **tokens > 0)
+                .map(|(p, tokens)| (p.get_uuid(), *tokens))
+                .collect()
+        };
+        let initial = to_test_marking(net.initial_marking.as_ref().unwrap());
+        let mut markings = silent_closure(net, BTreeSet::from([initial]));
+        for activity in trace {
+            let next = markings
+                .iter()
+                .flat_map(|m| step(net, m, Some(activity)))
+                .collect();
+            markings = silent_closure(net, next);
+        }
+        net.final_markings
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|f| markings.contains(&to_test_marking(f)))
+    }
+
+    fn accepted(net: &PetriNet, traces: &[&[&str]]) -> Vec<bool> {
+        traces.iter().map(|t| accepts(net, t)).collect()
+    }
+
+    #[test]
+    fn petri_net_to_bpmn() {
+        let net = choice_net();
+        let model = convert_petri_net_to_bpmn(&net);
+        let count = |t| model.nodes_of_type(t).count();
+        assert_eq!(count(BPMNNodeType::Task), 4);
+        assert_eq!(count(BPMNNodeType::StartEvent), 1);
+        assert_eq!(count(BPMNNodeType::EndEvent), 1);
+        // Split after `a`, join and split between `b`/`c` and `d`/skip, and the final join
+        assert_eq!(count(BPMNNodeType::ExclusiveGateway), 4);
+        assert_eq!(count(BPMNNodeType::ParallelGateway), 0);
+        for f in &model.flows {
+            assert!(model.get_node(&f.source).is_some() && model.get_node(&f.target).is_some());
+        }
+
+        let back = convert_bpmn_to_petri_net(&model).unwrap();
+        assert_eq!(
+            accepted(&back, &[&["a", "b", "d"], &["a", "c"], &["a", "d"], &["b"]]),
+            vec![true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn bpmn_to_petri_net() {
+        let mut model = BPMNModel::new();
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let a = model.add_node(BPMNNodeType::Task, Some("a".to_string()));
+        let and_split = model.add_node(BPMNNodeType::ParallelGateway, None);
+        let b = model.add_node(BPMNNodeType::Task, Some("b".to_string()));
+        let c = model.add_node(BPMNNodeType::Task, Some("c".to_string()));
+        let and_join = model.add_node(BPMNNodeType::ParallelGateway, None);
+        let xor_split = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        let d = model.add_node(BPMNNodeType::Task, Some("d".to_string()));
+        let xor_join = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        let end = model.add_node(BPMNNodeType::EndEvent, None);
+        for (from, to) in [
+            (&start, &a),
+            (&a, &and_split),
+            (&and_split, &b),
+            (&and_split, &c),
+            (&b, &and_join),
+            (&c, &and_join),
+            (&and_join, &xor_split),
+            (&xor_split, &d),
+            (&xor_split, &xor_join),
+            (&d, &xor_join),
+            (&xor_join, &end),
+        ] {
+            model.add_flow(from, to);
+        }
+        let net = convert_bpmn_to_petri_net(&model).unwrap();
+        let labeled = net
+            .transitions
+            .values()
+            .filter(|t| t.label.is_some())
+            .count();
+        assert_eq!(labeled, 4);
+        // After reduction: tasks, parallel split and join, and the silent skip of `d`
+        assert_eq!(net.transitions.len(), 7);
+        assert!(net.initial_marking.is_some() && net.final_markings.is_some());
+        assert_eq!(
+            accepted(
+                &net,
+                &[
+                    &["a", "b", "c", "d"],
+                    &["a", "c", "b"],
+                    &["a", "b", "d"],
+                    &["a", "b", "c", "d", "d"]
+                ]
+            ),
+            vec![true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn inclusive_gateways() {
+        let mut model = BPMNModel::new();
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let or_split = model.add_node(BPMNNodeType::InclusiveGateway, None);
+        let x = model.add_node(BPMNNodeType::Task, Some("x".to_string()));
+        let y = model.add_node(BPMNNodeType::Task, Some("y".to_string()));
+        let or_join = model.add_node(BPMNNodeType::InclusiveGateway, None);
+        let end = model.add_node(BPMNNodeType::EndEvent, None);
+        for (from, to) in [
+            (&start, &or_split),
+            (&or_split, &x),
+            (&or_split, &y),
+            (&x, &or_join),
+            (&y, &or_join),
+            (&or_join, &end),
+        ] {
+            model.add_flow(from, to);
+        }
+        let net = convert_bpmn_to_petri_net(&model).unwrap();
+        // Three subsets for the split and the join each (after reduction)
+        assert_eq!(net.transitions.len(), 2 + 3 + 3);
+        assert_eq!(
+            accepted(&net, &[&["x"], &["y"], &["x", "y"], &["y", "x"], &[]]),
+            vec![true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn inclusive_gateway_with_too_many_flows() {
+        let mut model = BPMNModel::new();
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let or_split = model.add_node(BPMNNodeType::InclusiveGateway, None);
+        let end = model.add_node(BPMNNodeType::EndEvent, None);
+        model.add_flow(&start, &or_split);
+        for i in 0..64 {
+            let task = model.add_node(BPMNNodeType::Task, Some(format!("t{i}")));
+            model.add_flow(&or_split, &task);
+            model.add_flow(&task, &end);
+        }
+        assert_eq!(
+            convert_bpmn_to_petri_net(&model).unwrap_err(),
+            BPMNConversionError::TooManyInclusiveGatewayFlows(or_split, 64)
+        );
+    }
+}
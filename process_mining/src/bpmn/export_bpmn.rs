@@ -0,0 +1,434 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::Write,
+};
+
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Writer,
+};
+
+use crate::utils::xml_utils::XMLWriterWrapper;
+
+use super::bpmn_struct::{BPMNModel, BPMNNodeType};
+
+const BPMN_MODEL_NS: &str = "http://www.omg.org/spec/BPMN/20100524/MODEL";
+const BPMN_DI_NS: &str = "http://www.omg.org/spec/BPMN/20100524/DI";
+const DC_NS: &str = "http://www.omg.org/spec/DD/20100524/DC";
+const DI_NS: &str = "http://www.omg.org/spec/DD/20100524/DI";
+
+/// Horizontal distance between the centers of nodes in subsequent layers
+const LAYER_DISTANCE: f64 = 150.0;
+/// Vertical distance between the centers of nodes in the same layer
+const ROW_DISTANCE: f64 = 120.0;
+/// Margin around the diagram
+const MARGIN: f64 = 100.0;
+
+///
+/// Export a [`BPMNModel`] to BPMN 2.0 XML and write the result to the provided writer which implements into [`quick_xml::Writer`] / [`std::io::Write`]
+///
+/// The export includes diagram interchange information (`BPMNDiagram`) with a generated layered layout,
+/// such that the file can be opened in modeling tools like Camunda Modeler or bpmn.io.
+///
+pub fn export_bpmn<'a, W>(
+    model: &BPMNModel,
+    into_writer: impl Into<XMLWriterWrapper<'a, W>>,
+) -> Result<(), quick_xml::Error>
+where
+    W: Write + 'a,
+{
+    let mut xml_writer: XMLWriterWrapper<'_, W> = into_writer.into();
+    let writer = xml_writer.to_xml_writer();
+    let layout = compute_layout(model);
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("bpmn:definitions")
+        .with_attributes([
+            ("xmlns:bpmn", BPMN_MODEL_NS),
+            ("xmlns:bpmndi", BPMN_DI_NS),
+            ("xmlns:dc", DC_NS),
+            ("xmlns:di", DI_NS),
+            ("id", "Definitions_1"),
+            ("targetNamespace", "http://bpmn.io/schema/bpmn"),
+            ("exporter", "rust4pm"),
+        ])
+        .write_inner_content(|writer| {
+            let mut process = writer
+                .create_element("bpmn:process")
+                .with_attribute(("id", model.id.as_str()));
+            if let Some(name) = &model.name {
+                process = process.with_attribute(("name", name.as_str()));
+            }
+            process
+                .with_attribute(("isExecutable", "false"))
+                .write_inner_content(|writer| {
+                    for node in &model.nodes {
+                        let tag = format!("bpmn:{}", node.node_type.xml_tag());
+                        let mut element = writer
+                            .create_element(&tag)
+                            .with_attribute(("id", node.id.as_str()));
+                        if let Some(name) = &node.name {
+                            element = element.with_attribute(("name", name.as_str()));
+                        }
+                        element.write_inner_content(|writer| {
+                            for f in model.incoming_flows(&node.id) {
+                                writer
+                                    .create_element("bpmn:incoming")
+                                    .write_text_content(BytesText::new(&f.id))?;
+                            }
+                            for f in model.outgoing_flows(&node.id) {
+                                writer
+                                    .create_element("bpmn:outgoing")
+                                    .write_text_content(BytesText::new(&f.id))?;
+                            }
+                            Ok::<(), quick_xml::Error>(())
+                        })?;
+                    }
+                    for f in &model.flows {
+                        let mut element = writer
+                            .create_element("bpmn:sequenceFlow")
+                            .with_attribute(("id", f.id.as_str()));
+                        if let Some(name) = &f.name {
+                            element = element.with_attribute(("name", name.as_str()));
+                        }
+                        element
+                            .with_attribute(("sourceRef", f.source.as_str()))
+                            .with_attribute(("targetRef", f.target.as_str()))
+                            .write_empty()?;
+                    }
+                    Ok::<(), quick_xml::Error>(())
+                })?;
+
+            writer
+                .create_element("bpmndi:BPMNDiagram")
+                .with_attribute(("id", "BPMNDiagram_1"))
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("bpmndi:BPMNPlane")
+                        .with_attribute(("id", "BPMNPlane_1"))
+                        .with_attribute(("bpmnElement", model.id.as_str()))
+                        .write_inner_content(|writer| {
+                            write_diagram_elements(writer, model, &layout)
+                        })?;
+                    Ok::<(), quick_xml::Error>(())
+                })?;
+            Ok::<(), quick_xml::Error>(())
+        })?;
+    Ok(())
+}
+
+/// Write the `BPMNShape` and `BPMNEdge` elements of the diagram
+fn write_diagram_elements<W: Write>(
+    writer: &mut Writer<W>,
+    model: &BPMNModel,
+    layout: &Layout<'_>,
+) -> Result<(), quick_xml::Error> {
+    for node in &model.nodes {
+        let Some(bounds) = layout.shapes.get(node.id.as_str()) else {
+            continue;
+        };
+        let mut shape = writer
+            .create_element("bpmndi:BPMNShape")
+            .with_attribute(("id", format!("{}_di", node.id).as_str()))
+            .with_attribute(("bpmnElement", node.id.as_str()));
+        if node.node_type == BPMNNodeType::ExclusiveGateway {
+            shape = shape.with_attribute(("isMarkerVisible", "true"));
+        }
+        shape.write_inner_content(|writer| {
+            writer
+                .create_element("dc:Bounds")
+                .with_attributes([
+                    ("x", bounds.x.to_string().as_str()),
+                    ("y", bounds.y.to_string().as_str()),
+                    ("width", bounds.width.to_string().as_str()),
+                    ("height", bounds.height.to_string().as_str()),
+                ])
+                .write_empty()?;
+            Ok::<(), quick_xml::Error>(())
+        })?;
+    }
+    for f in &model.flows {
+        let Some(waypoints) = layout.edges.get(f.id.as_str()) else {
+            continue;
+        };
+        writer
+            .create_element("bpmndi:BPMNEdge")
+            .with_attribute(("id", format!("{}_di", f.id).as_str()))
+            .with_attribute(("bpmnElement", f.id.as_str()))
+            .write_inner_content(|writer| {
+                for (x, y) in waypoints {
+                    writer
+                        .create_element("di:waypoint")
+                        .with_attribute(("x", x.to_string().as_str()))
+                        .with_attribute(("y", y.to_string().as_str()))
+                        .write_empty()?;
+                }
+                Ok::<(), quick_xml::Error>(())
+            })?;
+    }
+    Ok(())
+}
+
+/// Export a [`BPMNModel`] to a BPMN 2.0 XML file (specified through path)
+///
+/// Also consider using [`BPMNModel::export_bpmn`] for convenience or [`export_bpmn`] for more control.
+pub fn export_bpmn_to_path<P: AsRef<std::path::Path>>(
+    model: &BPMNModel,
+    path: P,
+) -> Result<(), quick_xml::Error> {
+    let file = File::create(path)?;
+    let mut writer = Writer::new_with_indent(file, b' ', 2);
+    export_bpmn(model, &mut writer)
+}
+
+/// Export a [`BPMNModel`] to a BPMN 2.0 XML [`String`]
+pub fn export_bpmn_to_string(model: &BPMNModel) -> Result<String, quick_xml::Error> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    export_bpmn(model, &mut writer)?;
+    Ok(String::from_utf8_lossy(&writer.into_inner()).to_string())
+}
+
+/// Bounds of a shape in the diagram
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Generated diagram layout: Bounds of nodes and waypoints of sequence flows (by ID)
+#[derive(Debug, Default)]
+struct Layout<'a> {
+    shapes: HashMap<&'a str, Bounds>,
+    edges: HashMap<&'a str, Vec<(f64, f64)>>,
+}
+
+/// Size (width and height) of the shape of a node type
+fn shape_size(node_type: BPMNNodeType) -> (f64, f64) {
+    match node_type {
+        BPMNNodeType::Task => (100.0, 80.0),
+        BPMNNodeType::StartEvent | BPMNNodeType::EndEvent => (36.0, 36.0),
+        _ => (50.0, 50.0),
+    }
+}
+
+///
+/// Compute a layered left-to-right layout
+///
+/// Back edges (i.e., loops) are determined using a depth-first search starting at the start events.
+/// Nodes are then assigned to layers by their longest path from a source (ignoring back edges),
+/// and ordered inside of their layer by the average position of their predecessors.
+/// Back edges are routed below the diagram.
+///
+fn compute_layout(model: &BPMNModel) -> Layout<'_> {
+    let n = model.nodes.len();
+    let index: HashMap<&str, usize> = model
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id.as_str(), i))
+        .collect();
+    // (flow index, source, target) of all flows between known nodes
+    let edges: Vec<(usize, usize, usize)> = model
+        .flows
+        .iter()
+        .enumerate()
+        .filter_map(|(i, f)| {
+            Some((
+                i,
+                *index.get(f.source.as_str())?,
+                *index.get(f.target.as_str())?,
+            ))
+        })
+        .collect();
+    let mut successors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut has_incoming = vec![false; n];
+    for (i, from, to) in &edges {
+        successors[*from].push((*i, *to));
+        has_incoming[*to] = true;
+    }
+
+    // Determine back edges using an iterative depth-first search
+    let mut roots: Vec<usize> = (0..n)
+        .filter(|i| model.nodes[*i].node_type == BPMNNodeType::StartEvent)
+        .collect();
+    roots.extend((0..n).filter(|i| !has_incoming[*i]));
+    roots.extend(0..n);
+    let mut state = vec![0u8; n];
+    let mut back_edges: HashSet<usize> = HashSet::new();
+    for root in roots {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((node, next_succ)) = stack.last_mut() {
+            if let Some((flow, succ)) = successors[*node].get(*next_succ).copied() {
+                *next_succ += 1;
+                match state[succ] {
+                    0 => {
+                        state[succ] = 1;
+                        stack.push((succ, 0));
+                    }
+                    1 => {
+                        back_edges.insert(flow);
+                    }
+                    _ => {}
+                }
+            } else {
+                state[*node] = 2;
+                stack.pop();
+            }
+        }
+    }
+
+    // Longest path layering (Kahn's algorithm on the acyclic part)
+    let forward_edges: Vec<&(usize, usize, usize)> = edges
+        .iter()
+        .filter(|(i, _, _)| !back_edges.contains(i))
+        .collect();
+    let mut in_degree = vec![0usize; n];
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (_, from, to) in &forward_edges {
+        in_degree[*to] += 1;
+        predecessors[*to].push(*from);
+    }
+    let mut layer = vec![0usize; n];
+    let mut queue: Vec<usize> = (0..n).filter(|i| in_degree[*i] == 0).collect();
+    let mut topological_order = Vec::with_capacity(n);
+    while let Some(node) = queue.pop() {
+        topological_order.push(node);
+        for (flow, succ) in &successors[node] {
+            if back_edges.contains(flow) {
+                continue;
+            }
+            layer[*succ] = layer[*succ].max(layer[node] + 1);
+            in_degree[*succ] -= 1;
+            if in_degree[*succ] == 0 {
+                queue.push(*succ);
+            }
+        }
+    }
+
+    // Order nodes inside of their layer by the average row of their predecessors
+    let num_layers = layer.iter().max().map(|l| l + 1).unwrap_or_default();
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); num_layers];
+    for node in 0..n {
+        layers[layer[node]].push(node);
+    }
+    let mut row = vec![0.0f64; n];
+    for nodes in &mut layers {
+        let barycenter = |node: usize| -> f64 {
+            if predecessors[node].is_empty() {
+                node as f64
+            } else {
+                predecessors[node].iter().map(|p| row[*p]).sum::<f64>()
+                    / predecessors[node].len() as f64
+            }
+        };
+        let mut keyed: Vec<(f64, usize)> = nodes.iter().map(|v| (barycenter(*v), *v)).collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        for (r, (_, node)) in keyed.iter().enumerate() {
+            row[*node] = r as f64;
+        }
+        *nodes = keyed.into_iter().map(|(_, node)| node).collect();
+    }
+
+    let mut layout = Layout::default();
+    let mut centers = vec![(0.0, 0.0); n];
+    for (i, node) in model.nodes.iter().enumerate() {
+        let (width, height) = shape_size(node.node_type);
+        let center = (
+            MARGIN + layer[i] as f64 * LAYER_DISTANCE,
+            MARGIN + row[i] * ROW_DISTANCE,
+        );
+        centers[i] = center;
+        layout.shapes.insert(
+            node.id.as_str(),
+            Bounds {
+                x: center.0 - width / 2.0,
+                y: center.1 - height / 2.0,
+                width,
+                height,
+            },
+        );
+    }
+    let bottom = layout
+        .shapes
+        .values()
+        .map(|b| b.y + b.height)
+        .fold(0.0, f64::max)
+        + ROW_DISTANCE / 3.0;
+    for (i, from, to) in edges {
+        let (from_width, from_height) = shape_size(model.nodes[from].node_type);
+        let (to_width, to_height) = shape_size(model.nodes[to].node_type);
+        let (from_center, to_center) = (centers[from], centers[to]);
+        let waypoints = if back_edges.contains(&i) {
+            let start = (from_center.0, from_center.1 + from_height / 2.0);
+            let end = (to_center.0, to_center.1 + to_height / 2.0);
+            vec![start, (start.0, bottom), (end.0, bottom), end]
+        } else {
+            let start = (from_center.0 + from_width / 2.0, from_center.1);
+            let end = (to_center.0 - to_width / 2.0, to_center.1);
+            if start.1 == end.1 {
+                vec![start, end]
+            } else {
+                let mid_x = (start.0 + end.0) / 2.0;
+                vec![start, (mid_x, start.1), (mid_x, end.1), end]
+            }
+        };
+        layout.edges.insert(model.flows[i].id.as_str(), waypoints);
+    }
+    layout
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bpmn::{conversion::convert_petri_net_to_bpmn, import_bpmn::import_bpmn_slice};
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn export_and_import_bpmn() {
+        let mut model = convert_petri_net_to_bpmn(&choice_net());
+        model.name = Some("Choice <&> Skip".to_string());
+        let xml = export_bpmn_to_string(&model).unwrap();
+        assert!(xml.contains("<bpmndi:BPMNShape"));
+        assert!(xml.contains("<di:waypoint"));
+
+        let imported = import_bpmn_slice(xml.as_bytes()).unwrap();
+        assert_eq!(imported, model);
+    }
+
+    #[test]
+    fn layout_with_loop() {
+        let mut model = BPMNModel::new();
+        let start = model.add_node(BPMNNodeType::StartEvent, None);
+        let join = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        let a = model.add_node(BPMNNodeType::Task, Some("a".to_string()));
+        let split = model.add_node(BPMNNodeType::ExclusiveGateway, None);
+        let end = model.add_node(BPMNNodeType::EndEvent, None);
+        model.add_flow(&start, &join);
+        model.add_flow(&join, &a);
+        model.add_flow(&a, &split);
+        let back = model.add_flow(&split, &join);
+        model.add_flow(&split, &end);
+
+        let layout = compute_layout(&model);
+        let x = |id: &str| layout.shapes[id].x + layout.shapes[id].width / 2.0;
+        assert!(x(&start) < x(&join) && x(&join) < x(&a) && x(&a) < x(&split));
+        assert!(x(&split) < x(&end));
+        // The loop is routed below all nodes
+        let max_y = layout
+            .shapes
+            .values()
+            .map(|b| b.y + b.height)
+            .fold(0.0, f64::max);
+        assert_eq!(layout.edges[back.as_str()].len(), 4);
+        assert!(layout.edges[back.as_str()][1].1 > max_y);
+    }
+}
@@ -0,0 +1,266 @@
+use std::{collections::HashSet, io::BufRead};
+
+use quick_xml::{events::BytesStart, Error as QuickXMLError, Reader};
+
+use super::bpmn_struct::{BPMNModel, BPMNNode, BPMNNodeType, BPMNSequenceFlow};
+
+///
+/// Error encountered while parsing BPMN 2.0 XML
+///
+#[derive(Debug, Clone)]
+pub enum BPMNParseError {
+    /// IO error
+    IOError(std::rc::Rc<std::io::Error>),
+    /// XML error (e.g., incorrect XML format )
+    XMLParsingError(QuickXMLError),
+    /// Missing key on XML element (with expected key included)
+    MissingKey(&'static str),
+    /// Encountered a flow node element which is not supported (e.g., `subProcess`; element name included)
+    UnsupportedElement(String),
+    /// Sequence flow refers to a node which does not exist (sequence flow ID and node ID included)
+    UnknownNode(String, String),
+    /// Encountered no `process` tag (i.e., the parsed data was not a BPMN file with a process)
+    NoProcessTag,
+}
+
+impl std::fmt::Display for BPMNParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse BPMN: {:?}", self)
+    }
+}
+
+impl std::error::Error for BPMNParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BPMNParseError::IOError(e) => Some(e.as_ref()),
+            BPMNParseError::XMLParsingError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for BPMNParseError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IOError(std::rc::Rc::new(e))
+    }
+}
+
+impl From<QuickXMLError> for BPMNParseError {
+    fn from(e: QuickXMLError) -> Self {
+        Self::XMLParsingError(e)
+    }
+}
+
+/// Flow node elements which can not be represented in a [`BPMNModel`]
+const UNSUPPORTED_ELEMENTS: &[&str] = &[
+    "subProcess",
+    "adHocSubProcess",
+    "transaction",
+    "callActivity",
+    "intermediateCatchEvent",
+    "intermediateThrowEvent",
+    "boundaryEvent",
+    "eventBasedGateway",
+    "complexGateway",
+];
+
+/// Map the local name of a flow node element to the corresponding [`BPMNNodeType`]
+///
+/// All task types (e.g., `userTask` or `serviceTask`) are mapped to [`BPMNNodeType::Task`].
+fn node_type_of_element(local_name: &str) -> Option<BPMNNodeType> {
+    match local_name {
+        "startEvent" => Some(BPMNNodeType::StartEvent),
+        "endEvent" => Some(BPMNNodeType::EndEvent),
+        "exclusiveGateway" => Some(BPMNNodeType::ExclusiveGateway),
+        "parallelGateway" => Some(BPMNNodeType::ParallelGateway),
+        "inclusiveGateway" => Some(BPMNNodeType::InclusiveGateway),
+        "task" | "userTask" | "serviceTask" | "manualTask" | "scriptTask" | "sendTask"
+        | "receiveTask" | "businessRuleTask" => Some(BPMNNodeType::Task),
+        _ => None,
+    }
+}
+
+fn get_attribute(b: &BytesStart<'_>, key: &str) -> Result<Option<String>, BPMNParseError> {
+    match b.try_get_attribute(key).unwrap_or_default() {
+        Some(attr) => Ok(Some(attr.unescape_value()?.to_string())),
+        None => Ok(None),
+    }
+}
+
+///
+/// Import a BPMN 2.0 XML file from the given XML reader ([`quick_xml::Reader`])
+///
+/// Also consider using [`BPMNModel::import_bpmn`] for importing from a filepath directly for convenience.
+///
+/// The following elements are imported (regardless of the used namespace prefix):
+///
+/// - Start and end events
+/// - Tasks (all task types, e.g., `userTask`, are imported as tasks)
+/// - Exclusive, parallel and inclusive gateways
+/// - Sequence flows
+///
+/// Diagram information (`BPMNDiagram`), lanes, data objects and documentation are ignored.
+/// If the file contains multiple processes, their nodes and sequence flows are combined into one [`BPMNModel`]
+/// (with the ID and name of the first process).
+/// Other flow nodes (e.g., sub-processes or intermediate events) are not supported and result in a
+/// [`BPMNParseError::UnsupportedElement`] error.
+///
+/// Also see [`import_bpmn_reader`] for an alternative version of this function, which takes a (standard) buffered reader implementing [`std::io::BufRead`] instead
+pub fn import_bpmn<T>(reader: &mut Reader<T>) -> Result<BPMNModel, BPMNParseError>
+where
+    T: BufRead,
+{
+    reader.trim_text(true);
+    reader.expand_empty_elements(true);
+    let mut buf: Vec<u8> = Vec::new();
+
+    let mut model: Option<BPMNModel> = None;
+    // Only elements inside of a `process` element are considered
+    let mut in_process = false;
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            quick_xml::events::Event::Start(b) => {
+                let local_name = b.local_name();
+                let local_name = String::from_utf8_lossy(local_name.as_ref()).to_string();
+                if local_name == "process" {
+                    in_process = true;
+                    if model.is_none() {
+                        let mut m = BPMNModel::new();
+                        m.id = get_attribute(&b, "id")?.ok_or(BPMNParseError::MissingKey("id"))?;
+                        m.name = get_attribute(&b, "name")?;
+                        model = Some(m);
+                    }
+                } else if let (true, Some(m)) = (in_process, model.as_mut()) {
+                    if let Some(node_type) = node_type_of_element(&local_name) {
+                        m.nodes.push(BPMNNode {
+                            id: get_attribute(&b, "id")?.ok_or(BPMNParseError::MissingKey("id"))?,
+                            name: get_attribute(&b, "name")?,
+                            node_type,
+                        });
+                    } else if local_name == "sequenceFlow" {
+                        m.flows.push(BPMNSequenceFlow {
+                            id: get_attribute(&b, "id")?.ok_or(BPMNParseError::MissingKey("id"))?,
+                            name: get_attribute(&b, "name")?,
+                            source: get_attribute(&b, "sourceRef")?
+                                .ok_or(BPMNParseError::MissingKey("sourceRef"))?,
+                            target: get_attribute(&b, "targetRef")?
+                                .ok_or(BPMNParseError::MissingKey("targetRef"))?,
+                        });
+                    } else if UNSUPPORTED_ELEMENTS.contains(&local_name.as_str()) {
+                        return Err(BPMNParseError::UnsupportedElement(local_name));
+                    }
+                }
+            }
+            quick_xml::events::Event::End(b) if b.local_name().as_ref() == b"process" => {
+                in_process = false;
+            }
+            quick_xml::events::Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let model = model.ok_or(BPMNParseError::NoProcessTag)?;
+    let node_ids: HashSet<&str> = model.nodes.iter().map(|n| n.id.as_str()).collect();
+    for f in &model.flows {
+        for node_id in [&f.source, &f.target] {
+            if !node_ids.contains(node_id.as_str()) {
+                return Err(BPMNParseError::UnknownNode(f.id.clone(), node_id.clone()));
+            }
+        }
+    }
+    Ok(model)
+}
+
+///
+/// Import a BPMN 2.0 XML file from the given standard buffered reader (implementing [`std::io::BufRead`])
+///
+/// Also consider using [`BPMNModel::import_bpmn`] for importing from a filepath directly for convenience.
+///
+///
+/// Also see [`import_bpmn`] for an alternative version of this function, which takes a XML specific reader [`quick_xml::Reader`] instead
+pub fn import_bpmn_reader<T>(std_reader: &mut T) -> Result<BPMNModel, BPMNParseError>
+where
+    T: BufRead,
+{
+    let mut xml_reader = Reader::from_reader(std_reader);
+    import_bpmn(&mut xml_reader)
+}
+
+///
+/// Import a BPMN 2.0 XML file from a byte slice
+///
+pub fn import_bpmn_slice(data: &[u8]) -> Result<BPMNModel, BPMNParseError> {
+    import_bpmn(&mut Reader::from_reader(data))
+}
+
+///  Import a BPMN 2.0 XML file from the given filepath
+///
+/// Also consider using [`BPMNModel::import_bpmn`] for convenience or [`import_bpmn`] for more control over the reader.
+pub fn import_bpmn_from_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<BPMNModel, BPMNParseError> {
+    import_bpmn(&mut quick_xml::Reader::from_file(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAMUNDA_BPMN: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<bpmn:definitions xmlns:bpmn="http://www.omg.org/spec/BPMN/20100524/MODEL" xmlns:bpmndi="http://www.omg.org/spec/BPMN/20100524/DI" xmlns:dc="http://www.omg.org/spec/DD/20100524/DC" id="Definitions_1" targetNamespace="http://bpmn.io/schema/bpmn">
+  <bpmn:process id="Process_1" name="Repair &amp; Test" isExecutable="false">
+    <bpmn:startEvent id="StartEvent_1">
+      <bpmn:outgoing>Flow_1</bpmn:outgoing>
+    </bpmn:startEvent>
+    <bpmn:userTask id="Activity_1" name="Register">
+      <bpmn:incoming>Flow_1</bpmn:incoming>
+      <bpmn:outgoing>Flow_2</bpmn:outgoing>
+    </bpmn:userTask>
+    <bpmn:exclusiveGateway id="Gateway_1" />
+    <bpmn:endEvent id="Event_1" />
+    <bpmn:sequenceFlow id="Flow_1" sourceRef="StartEvent_1" targetRef="Activity_1" />
+    <bpmn:sequenceFlow id="Flow_2" name="ok" sourceRef="Activity_1" targetRef="Gateway_1" />
+    <bpmn:sequenceFlow id="Flow_3" sourceRef="Gateway_1" targetRef="Event_1" />
+  </bpmn:process>
+  <bpmndi:BPMNDiagram id="BPMNDiagram_1">
+    <bpmndi:BPMNPlane id="BPMNPlane_1" bpmnElement="Process_1">
+      <bpmndi:BPMNShape id="Activity_1_di" bpmnElement="Activity_1">
+        <dc:Bounds x="270" y="77" width="100" height="80" />
+      </bpmndi:BPMNShape>
+    </bpmndi:BPMNPlane>
+  </bpmndi:BPMNDiagram>
+</bpmn:definitions>"#;
+
+    #[test]
+    fn import_camunda_bpmn() {
+        let model = import_bpmn_slice(CAMUNDA_BPMN.as_bytes()).unwrap();
+        assert_eq!(model.id, "Process_1");
+        assert_eq!(model.name.as_deref(), Some("Repair & Test"));
+        assert_eq!(model.nodes.len(), 4);
+        assert_eq!(model.flows.len(), 3);
+        let task = model.get_node("Activity_1").unwrap();
+        assert_eq!(task.node_type, BPMNNodeType::Task);
+        assert_eq!(task.name.as_deref(), Some("Register"));
+        assert_eq!(model.flows[1].name.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn import_invalid_bpmn() {
+        let unknown_node = CAMUNDA_BPMN.replace(r#"targetRef="Event_1""#, r#"targetRef="Event_2""#);
+        assert!(matches!(
+            import_bpmn_slice(unknown_node.as_bytes()),
+            Err(BPMNParseError::UnknownNode(flow, node)) if flow == "Flow_3" && node == "Event_2"
+        ));
+        let sub_process = CAMUNDA_BPMN.replace("bpmn:userTask", "bpmn:subProcess");
+        assert!(matches!(
+            import_bpmn_slice(sub_process.as_bytes()),
+            Err(BPMNParseError::UnsupportedElement(e)) if e == "subProcess"
+        ));
+        assert!(matches!(
+            import_bpmn_slice(b"<definitions></definitions>"),
+            Err(BPMNParseError::NoProcessTag)
+        ));
+    }
+}
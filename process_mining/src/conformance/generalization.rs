@@ -0,0 +1,74 @@
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    petri_net::petri_net_struct::PetriNet,
+    EventLog,
+};
+
+use super::token_replay::{token_replay, token_replay_projection, TokenReplayResult};
+
+///
+/// Compute the replay-based generalization of a [`PetriNet`] w.r.t. an [`EventLog`]
+///
+/// Generalization is `1 - (sum over all transitions t of 1 / sqrt(#executions of t)) / #transitions`,
+/// where executions are counted using token-based replay (see [`token_replay`]).
+/// Transitions that are never executed contribute `1` to the sum.
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`].
+pub fn generalization(net: &PetriNet, log: &EventLog, classifier: &EventLogClassifier) -> f64 {
+    generalization_from_replay(&token_replay(net, log, classifier))
+}
+
+///
+/// Compute the replay-based generalization of a [`PetriNet`] w.r.t. an [`EventLogActivityProjection`]
+///
+/// See [`generalization`] for details.
+pub fn generalization_projection(net: &PetriNet, log: &EventLogActivityProjection) -> f64 {
+    generalization_from_replay(&token_replay_projection(net, log))
+}
+
+///
+/// Compute the generalization based on the transition firings of an existing [`TokenReplayResult`]
+///
+/// Returns `0.0` for nets without transitions.
+pub fn generalization_from_replay(replay: &TokenReplayResult) -> f64 {
+    if replay.transition_firings.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = replay
+        .transition_firings
+        .values()
+        .map(|n| {
+            if *n == 0 {
+                1.0
+            } else {
+                1.0 / (*n as f64).sqrt()
+            }
+        })
+        .sum();
+    1.0 - sum / replay.transition_firings.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::{replay_traces, tests::choice_net, ReplayNet};
+
+    use super::*;
+
+    #[test]
+    fn generalization_of_choice() {
+        let net = choice_net();
+        let replay = replay_traces(
+            &ReplayNet::new(&net),
+            &[(vec!["a", "b", "d"], 16), (vec!["a", "c"], 9)],
+        );
+        // a: 25, b: 16, c: 9, d: 16, tau: 9 executions
+        let expected = 1.0 - (0.2 + 0.25 + 1.0 / 3.0 + 0.25 + 1.0 / 3.0) / 5.0;
+        assert!((generalization_from_replay(&replay) - expected).abs() < 1e-9);
+        let replay = replay_traces(&ReplayNet::new(&net), &[(vec!["a", "b", "d"], 4)]);
+        // c and tau are never executed
+        let expected = 1.0 - (0.5 + 0.5 + 1.0 + 0.5 + 1.0) / 5.0;
+        assert!((generalization_from_replay(&replay) - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    petri_net::petri_net_struct::PetriNet,
+    EventLog,
+};
+
+use super::token_replay::{projection_traces, ReplayNet};
+
+///
+/// Compute the escaping-edges precision (`ETConformance`) of a [`PetriNet`] w.r.t. an [`EventLog`]
+///
+/// For every prefix of the log, the activities enabled in the net after replaying the prefix are compared
+/// with the activities actually following the prefix in the log.
+/// Enabled activities that never follow the prefix in the log are _escaping edges_.
+///
+/// Precision is `1 - (escaping edges / enabled activities)`, both weighted by the frequency of the prefix.
+/// Prefixes that cannot be replayed without missing tokens are ignored.
+/// Returns `1.0` if no activities are enabled for any (fitting) prefix.
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`].
+pub fn etc_precision(net: &PetriNet, log: &EventLog, classifier: &EventLogClassifier) -> f64 {
+    let traces: Vec<(Vec<String>, u64)> = log
+        .traces
+        .par_iter()
+        .map(|t| {
+            (
+                t.events
+                    .iter()
+                    .map(|e| classifier.get_class_identity_with_globals(e, &log.global_event_attrs))
+                    .collect(),
+                1,
+            )
+        })
+        .collect();
+    etc_precision_traces(&ReplayNet::new(net), &traces)
+}
+
+///
+/// Compute the escaping-edges precision (`ETConformance`) of a [`PetriNet`] w.r.t. an [`EventLogActivityProjection`]
+///
+/// See [`etc_precision`] for details.
+pub fn etc_precision_projection(net: &PetriNet, log: &EventLogActivityProjection) -> f64 {
+    etc_precision_traces(&ReplayNet::new(net), &projection_traces(log))
+}
+
+/// Node of the prefix tree of a log
+#[derive(Debug, Default)]
+struct PrefixNode<'a> {
+    /// Frequency of the prefix (only counting traces in which it is followed by an activity)
+    weight: u64,
+    /// Activities following the prefix and the corresponding child nodes
+    children: HashMap<&'a str, usize>,
+}
+
+pub(crate) fn etc_precision_traces<S: AsRef<str> + Sync>(
+    net: &ReplayNet<'_>,
+    traces: &[(Vec<S>, u64)],
+) -> f64 {
+    // Prefix tree of the log, the root node (index 0) is the empty prefix
+    let mut nodes: Vec<PrefixNode<'_>> = vec![PrefixNode::default()];
+    // Traces to replay, together with the depth of the first prefix node introduced by them
+    // (so that every node is evaluated by exactly one trace)
+    let mut to_replay: Vec<(&[S], usize)> = Vec::new();
+    for (trace, freq) in traces {
+        let mut first_new = to_replay.is_empty().then_some(0);
+        let mut node = 0;
+        for (depth, act) in trace.iter().enumerate() {
+            let act = act.as_ref();
+            nodes[node].weight += freq;
+            node = match nodes[node].children.get(act) {
+                Some(child) => *child,
+                None => {
+                    first_new.get_or_insert(depth + 1);
+                    let child = nodes.len();
+                    nodes[node].children.insert(act, child);
+                    nodes.push(PrefixNode::default());
+                    child
+                }
+            };
+        }
+        if let Some(first_new) = first_new {
+            to_replay.push((trace, first_new));
+        }
+    }
+    let (escaping, enabled) = to_replay
+        .par_iter()
+        .map(|(trace, first_new)| {
+            let mut firings = vec![0; net.transitions.len()];
+            let mut state = net.initial_state();
+            let mut node = 0;
+            let (mut escaping, mut enabled) = (0, 0);
+            for depth in 0..=trace.len() {
+                if depth >= *first_new {
+                    let PrefixNode { weight, children } = &nodes[node];
+                    let enabled_labels = net.enabled_labels(&state.marking);
+                    escaping += weight
+                        * enabled_labels
+                            .iter()
+                            .filter(|a| !children.contains_key(*a))
+                            .count() as u64;
+                    enabled += weight * enabled_labels.len() as u64;
+                }
+                let Some(act) = trace.get(depth) else {
+                    break;
+                };
+                let act = act.as_ref();
+                net.fire_activity(&mut state, act, &mut firings);
+                // Prefixes that cannot be replayed without missing tokens are ignored
+                if state.counts.missing > 0 {
+                    break;
+                }
+                node = nodes[node].children[act];
+            }
+            (escaping, enabled)
+        })
+        .reduce(|| (0, 0), |(a1, b1), (a2, b2)| (a1 + a2, b1 + b2));
+    if enabled == 0 {
+        return 1.0;
+    }
+    1.0 - escaping as f64 / enabled as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn precision_of_choice() {
+        let net = choice_net();
+        let replay_net = ReplayNet::new(&net);
+        // Choice between b and c is fully used
+        let traces = vec![(vec!["a", "b", "d"], 2), (vec!["a", "c", "d"], 2)];
+        assert_eq!(etc_precision_traces(&replay_net, &traces), 1.0);
+        // Only b is used, c is an escaping edge after a
+        let traces = vec![(vec!["a", "b", "d"], 2)];
+        assert_eq!(etc_precision_traces(&replay_net, &traces), 0.75);
+        // Unfitting prefixes (here: `<d>`) are ignored, the empty prefix is now more frequent
+        let traces = vec![(vec!["a", "b", "d"], 2), (vec!["d", "a"], 5)];
+        assert_eq!(etc_precision_traces(&replay_net, &traces), 1.0 - 2.0 / 13.0);
+        // Shared prefixes are only counted for traces in which they are followed by an activity
+        let traces = vec![(vec!["a", "b"], 1), (vec!["a", "b", "d"], 1)];
+        assert_eq!(etc_precision_traces(&replay_net, &traces), 1.0 - 2.0 / 7.0);
+        let traces = vec![(vec!["a", "b", "d"], 1), (vec!["a", "b"], 1)];
+        assert_eq!(etc_precision_traces(&replay_net, &traces), 1.0 - 2.0 / 7.0);
+    }
+}
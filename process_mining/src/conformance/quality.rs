@@ -0,0 +1,109 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    petri_net::petri_net_struct::PetriNet,
+    EventLog,
+};
+
+use super::{
+    generalization::generalization_from_replay,
+    precision::etc_precision_traces,
+    simplicity::{simplicity_metrics, SimplicityMetrics},
+    token_replay::{projection_traces, replay_traces, ReplayNet},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Quality of a [`PetriNet`] w.r.t. an event log
+///
+pub struct ModelQuality {
+    /// Token-based replay fitness (see [`super::token_replay`])
+    pub fitness: f64,
+    /// Escaping-edges precision (see [`super::precision::etc_precision`])
+    pub precision: f64,
+    /// Replay-based generalization (see [`super::generalization::generalization`])
+    pub generalization: f64,
+    /// Simplicity metrics of the net
+    pub simplicity: SimplicityMetrics,
+}
+
+impl ModelQuality {
+    ///
+    /// F-score: Harmonic mean of fitness and precision (`0.0` if both are `0.0`)
+    ///
+    pub fn f_score(&self) -> f64 {
+        if self.fitness + self.precision == 0.0 {
+            return 0.0;
+        }
+        2.0 * self.fitness * self.precision / (self.fitness + self.precision)
+    }
+}
+
+///
+/// Evaluate the [`ModelQuality`] of a [`PetriNet`] w.r.t. an [`EventLog`]
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`].
+pub fn evaluate_model_quality(
+    net: &PetriNet,
+    log: &EventLog,
+    classifier: &EventLogClassifier,
+) -> ModelQuality {
+    let traces: Vec<(Vec<String>, u64)> = log
+        .traces
+        .par_iter()
+        .map(|t| {
+            (
+                t.events
+                    .iter()
+                    .map(|e| classifier.get_class_identity_with_globals(e, &log.global_event_attrs))
+                    .collect(),
+                1,
+            )
+        })
+        .collect();
+    evaluate_model_quality_traces(net, &traces)
+}
+
+///
+/// Evaluate the [`ModelQuality`] of a [`PetriNet`] w.r.t. an [`EventLogActivityProjection`]
+///
+pub fn evaluate_model_quality_projection(
+    net: &PetriNet,
+    log: &EventLogActivityProjection,
+) -> ModelQuality {
+    evaluate_model_quality_traces(net, &projection_traces(log))
+}
+
+fn evaluate_model_quality_traces<S: AsRef<str> + Sync>(
+    net: &PetriNet,
+    traces: &[(Vec<S>, u64)],
+) -> ModelQuality {
+    let replay_net = ReplayNet::new(net);
+    let replay = replay_traces(&replay_net, traces);
+    ModelQuality {
+        fitness: replay.fitness(),
+        precision: etc_precision_traces(&replay_net, traces),
+        generalization: generalization_from_replay(&replay),
+        simplicity: simplicity_metrics(net),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn quality_of_choice() {
+        let quality = evaluate_model_quality_traces(&choice_net(), &[(vec!["a", "b", "d"], 2)]);
+        assert_eq!(quality.fitness, 1.0);
+        assert_eq!(quality.precision, 0.75);
+        assert!((quality.f_score() - 2.0 * 0.75 / 1.75).abs() < 1e-9);
+        assert_eq!(quality.simplicity.num_transitions, 5);
+    }
+}
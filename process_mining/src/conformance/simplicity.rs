@@ -0,0 +1,116 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::petri_net::petri_net_struct::{ArcType, PetriNet, PlaceID, TransitionID};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Simplicity metrics of a [`PetriNet`]
+///
+pub struct SimplicityMetrics {
+    /// Number of places
+    pub num_places: usize,
+    /// Number of transitions
+    pub num_transitions: usize,
+    /// Number of arcs
+    pub num_arcs: usize,
+    /// Number of nodes (places and transitions)
+    pub num_nodes: usize,
+    /// Mean number of arcs connected to a node
+    pub mean_arc_degree: f64,
+    /// Arc degree simplicity (see [`arc_degree_simplicity`])
+    pub arc_degree_simplicity: f64,
+    /// Extended Cardoso metric (see [`extended_cardoso_metric`])
+    pub extended_cardoso_metric: usize,
+}
+
+///
+/// Compute all [`SimplicityMetrics`] of a [`PetriNet`]
+///
+pub fn simplicity_metrics(net: &PetriNet) -> SimplicityMetrics {
+    let num_nodes = net.places.len() + net.transitions.len();
+    SimplicityMetrics {
+        num_places: net.places.len(),
+        num_transitions: net.transitions.len(),
+        num_arcs: net.arcs.len(),
+        num_nodes,
+        mean_arc_degree: mean_arc_degree(net),
+        arc_degree_simplicity: arc_degree_simplicity(net),
+        extended_cardoso_metric: extended_cardoso_metric(net),
+    }
+}
+
+/// Mean number of arcs connected to a node (`0.0` for empty nets)
+pub fn mean_arc_degree(net: &PetriNet) -> f64 {
+    let num_nodes = net.places.len() + net.transitions.len();
+    if num_nodes == 0 {
+        return 0.0;
+    }
+    // Every arc contributes to the degree of two nodes
+    2.0 * net.arcs.len() as f64 / num_nodes as f64
+}
+
+///
+/// Arc degree simplicity: `1 / (1 + max(mean arc degree - 2, 0))`
+///
+/// A net in which every node has one incoming and one outgoing arc (i.e., a sequence) has simplicity `1.0`.
+pub fn arc_degree_simplicity(net: &PetriNet) -> f64 {
+    1.0 / (1.0 + (mean_arc_degree(net) - 2.0).max(0.0))
+}
+
+///
+/// Extended Cardoso metric (`ECaM`, also called extended cardinality metric)
+///
+/// Sum over all places `p` of the number of distinct postsets of the transitions in the postset of `p`,
+/// i.e., the number of different states that can be reached from `p`.
+///
+/// See also: Lassen, K. B., & van der Aalst, W. M. P. (2009). Complexity metrics for Workflow nets.
+pub fn extended_cardoso_metric(net: &PetriNet) -> usize {
+    let mut place_postsets: HashMap<PlaceID, Vec<TransitionID>> = HashMap::new();
+    let mut transition_postsets: HashMap<TransitionID, Vec<PlaceID>> = HashMap::new();
+    for arc in &net.arcs {
+        match arc.from_to {
+            ArcType::PlaceTransition(p, t) => place_postsets
+                .entry(PlaceID(p))
+                .or_default()
+                .push(TransitionID(t)),
+            ArcType::TransitionPlace(t, p) => transition_postsets
+                .entry(TransitionID(t))
+                .or_default()
+                .push(PlaceID(p)),
+        }
+    }
+    place_postsets
+        .values()
+        .map(|ts| {
+            ts.iter()
+                .map(|t| {
+                    let mut postset = transition_postsets.get(t).cloned().unwrap_or_default();
+                    postset.sort();
+                    postset.dedup();
+                    postset
+                })
+                .collect::<HashSet<_>>()
+                .len()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn simplicity_of_choice() {
+        let metrics = simplicity_metrics(&choice_net());
+        assert_eq!(metrics.num_nodes, 9);
+        assert_eq!(metrics.num_arcs, 10);
+        assert!((metrics.mean_arc_degree - 20.0 / 9.0).abs() < 1e-9);
+        assert!((metrics.arc_degree_simplicity - 1.0 / (1.0 + 2.0 / 9.0)).abs() < 1e-9);
+        // p0: {p1}, p1: {p2} (b and c lead to the same place), p2: {p3}
+        assert_eq!(metrics.extended_cardoso_metric, 3);
+    }
+}
@@ -0,0 +1,551 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    petri_net::petri_net_struct::{ArcType, PetriNet, TransitionID},
+    EventLog,
+};
+
+/// Maximal number of markings explored when searching for silent transitions to fire
+const MAX_SILENT_EXPLORATION: usize = 256;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Token counts of replaying a trace (or a whole event log) on a [`PetriNet`]
+///
+pub struct TokenCounts {
+    /// Produced tokens (including the tokens of the initial marking)
+    pub produced: u64,
+    /// Consumed tokens (including the tokens of the reached final marking)
+    pub consumed: u64,
+    /// Tokens that were missing to fire a transition (or to reach the final marking)
+    pub missing: u64,
+    /// Tokens remaining after replay (i.e., not part of the final marking)
+    pub remaining: u64,
+}
+
+impl TokenCounts {
+    ///
+    /// Token-based fitness: `0.5 * (1 - missing / consumed) + 0.5 * (1 - remaining / produced)`
+    ///
+    pub fn fitness(&self) -> f64 {
+        let missing_ratio = if self.consumed == 0 {
+            0.0
+        } else {
+            self.missing as f64 / self.consumed as f64
+        };
+        let remaining_ratio = if self.produced == 0 {
+            0.0
+        } else {
+            self.remaining as f64 / self.produced as f64
+        };
+        0.5 * (1.0 - missing_ratio) + 0.5 * (1.0 - remaining_ratio)
+    }
+
+    /// Check if the replay was perfectly fitting (i.e., no missing or remaining tokens)
+    pub fn is_fitting(&self) -> bool {
+        self.missing == 0 && self.remaining == 0
+    }
+
+    fn add_times(&mut self, other: &TokenCounts, times: u64) {
+        self.produced += other.produced * times;
+        self.consumed += other.consumed * times;
+        self.missing += other.missing * times;
+        self.remaining += other.remaining * times;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Result of token-based replay of an event log on a [`PetriNet`]
+///
+pub struct TokenReplayResult {
+    /// Token counts of each trace
+    pub traces: Vec<TokenCounts>,
+    /// Frequency of each trace
+    ///
+    /// `1` for the traces of an [`EventLog`] and the variant frequency for an [`EventLogActivityProjection`]
+    pub trace_frequencies: Vec<u64>,
+    /// Number of times each transition was fired during replay (weighted by trace frequency)
+    ///
+    /// Transitions that were never fired are included with `0`
+    pub transition_firings: HashMap<TransitionID, u64>,
+}
+
+impl TokenReplayResult {
+    /// Token counts of the whole log (weighted by trace frequency)
+    pub fn total_counts(&self) -> TokenCounts {
+        let mut ret = TokenCounts::default();
+        for (counts, freq) in self.traces.iter().zip(&self.trace_frequencies) {
+            ret.add_times(counts, *freq);
+        }
+        ret
+    }
+
+    /// Log-level token-based fitness (see [`TokenCounts::fitness`])
+    pub fn fitness(&self) -> f64 {
+        self.total_counts().fitness()
+    }
+
+    /// Fraction of traces (weighted by frequency) that fit perfectly (`1.0` for empty logs)
+    pub fn fitting_traces_ratio(&self) -> f64 {
+        let total: u64 = self.trace_frequencies.iter().sum();
+        if total == 0 {
+            return 1.0;
+        }
+        let fitting: u64 = self
+            .traces
+            .iter()
+            .zip(&self.trace_frequencies)
+            .filter(|(c, _)| c.is_fitting())
+            .map(|(_, f)| *f)
+            .sum();
+        fitting as f64 / total as f64
+    }
+}
+
+///
+/// Replay a [`PetriNet`] on all traces of an [`EventLog`] using token-based replay
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`] and matched against transition labels.
+///
+/// If a transition is not enabled, silent transitions (i.e., without a label) are fired if that enables it.
+/// Otherwise, the transition is fired anyway and the missing tokens are recorded.
+/// Activities without a corresponding transition count as one missing and one remaining token.
+pub fn token_replay(
+    net: &PetriNet,
+    log: &EventLog,
+    classifier: &EventLogClassifier,
+) -> TokenReplayResult {
+    let traces: Vec<(Vec<String>, u64)> = log
+        .traces
+        .par_iter()
+        .map(|t| {
+            (
+                t.events
+                    .iter()
+                    .map(|e| classifier.get_class_identity_with_globals(e, &log.global_event_attrs))
+                    .collect(),
+                1,
+            )
+        })
+        .collect();
+    replay_traces(&ReplayNet::new(net), &traces)
+}
+
+///
+/// Replay a [`PetriNet`] on all trace variants of an [`EventLogActivityProjection`] using token-based replay
+///
+/// See [`token_replay`] for details.
+pub fn token_replay_projection(
+    net: &PetriNet,
+    log: &EventLogActivityProjection,
+) -> TokenReplayResult {
+    let traces = projection_traces(log);
+    replay_traces(&ReplayNet::new(net), &traces)
+}
+
+/// Activity sequences (with frequencies) of an [`EventLogActivityProjection`]
+pub(crate) fn projection_traces(log: &EventLogActivityProjection) -> Vec<(Vec<&str>, u64)> {
+    log.traces
+        .iter()
+        .map(|(t, f)| (t.iter().map(|a| log.activities[*a].as_str()).collect(), *f))
+        .collect()
+}
+
+pub(crate) fn replay_traces<S: AsRef<str> + Sync>(
+    net: &ReplayNet<'_>,
+    traces: &[(Vec<S>, u64)],
+) -> TokenReplayResult {
+    let (counts, firings) = traces
+        .par_iter()
+        .fold(
+            || (Vec::new(), vec![0; net.transitions.len()]),
+            |(mut counts, mut firings), (trace, freq)| {
+                let mut trace_firings = vec![0; net.transitions.len()];
+                let mut state = net.initial_state();
+                for act in trace {
+                    net.fire_activity(&mut state, act.as_ref(), &mut trace_firings);
+                }
+                counts.push(net.finish(state, &mut trace_firings));
+                for (f, tf) in firings.iter_mut().zip(trace_firings) {
+                    *f += tf * freq;
+                }
+                (counts, firings)
+            },
+        )
+        .reduce(
+            || (Vec::new(), vec![0; net.transitions.len()]),
+            |(mut counts_a, mut firings_a), (counts_b, firings_b)| {
+                counts_a.extend(counts_b);
+                for (a, b) in firings_a.iter_mut().zip(firings_b) {
+                    *a += b;
+                }
+                (counts_a, firings_a)
+            },
+        );
+    TokenReplayResult {
+        traces: counts,
+        trace_frequencies: traces.iter().map(|(_, f)| *f).collect(),
+        transition_firings: net.transitions.iter().copied().zip(firings).collect(),
+    }
+}
+
+/// Current state during token-based replay
+#[derive(Debug, Clone)]
+pub(crate) struct ReplayState {
+    pub(crate) marking: Vec<u64>,
+    pub(crate) counts: TokenCounts,
+}
+
+///
+/// Index-based representation of a [`PetriNet`] for efficient replay
+///
+#[derive(Debug)]
+pub(crate) struct ReplayNet<'a> {
+    pub(crate) transitions: Vec<TransitionID>,
+    labels: Vec<Option<&'a str>>,
+    preset: Vec<Vec<(usize, u64)>>,
+    postset: Vec<Vec<(usize, u64)>>,
+    initial_marking: Vec<u64>,
+    final_markings: Vec<Vec<u64>>,
+    by_label: HashMap<&'a str, Vec<usize>>,
+    silent: Vec<usize>,
+}
+
+impl<'a> ReplayNet<'a> {
+    pub(crate) fn new(net: &'a PetriNet) -> Self {
+        let mut places: Vec<_> = net.places.keys().copied().collect();
+        places.sort();
+        let place_index: HashMap<_, _> = places.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+        let mut transitions: Vec<_> = net.transitions.keys().copied().collect();
+        transitions.sort();
+        let transition_index: HashMap<_, _> = transitions
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (*t, i))
+            .collect();
+        let labels: Vec<Option<&str>> = transitions
+            .iter()
+            .map(|t| net.transitions[t].label.as_deref())
+            .collect();
+        let mut preset = vec![Vec::new(); transitions.len()];
+        let mut postset = vec![Vec::new(); transitions.len()];
+        for arc in &net.arcs {
+            match arc.from_to {
+                ArcType::PlaceTransition(p, t) => {
+                    if let (Some(p), Some(t)) = (place_index.get(&p), transition_index.get(&t)) {
+                        preset[*t].push((*p, arc.weight as u64));
+                    }
+                }
+                ArcType::TransitionPlace(t, p) => {
+                    if let (Some(p), Some(t)) = (place_index.get(&p), transition_index.get(&t)) {
+                        postset[*t].push((*p, arc.weight as u64));
+                    }
+                }
+            }
+        }
+        let to_vec = |m: &crate::petri_net::petri_net_struct::Marking| {
+            let mut ret = vec![0; places.len()];
+            for (p, n) in m {
+                if let Some(i) = place_index.get(&p.get_uuid()) {
+                    ret[*i] += n;
+                }
+            }
+            ret
+        };
+        let initial_marking = net
+            .initial_marking
+            .as_ref()
+            .map(to_vec)
+            .unwrap_or_else(|| vec![0; places.len()]);
+        let final_markings = net.final_markings.iter().flatten().map(to_vec).collect();
+        let mut by_label: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut silent = Vec::new();
+        for (i, l) in labels.iter().enumerate() {
+            match l {
+                Some(l) => by_label.entry(l).or_default().push(i),
+                None => silent.push(i),
+            }
+        }
+        Self {
+            transitions: transitions.into_iter().map(TransitionID).collect(),
+            labels,
+            preset,
+            postset,
+            initial_marking,
+            final_markings,
+            by_label,
+            silent,
+        }
+    }
+
+    pub(crate) fn initial_state(&self) -> ReplayState {
+        ReplayState {
+            counts: TokenCounts {
+                produced: self.initial_marking.iter().sum(),
+                ..Default::default()
+            },
+            marking: self.initial_marking.clone(),
+        }
+    }
+
+    fn is_enabled(&self, marking: &[u64], t: usize) -> bool {
+        self.preset[t].iter().all(|(p, w)| marking[*p] >= *w)
+    }
+
+    fn num_missing(&self, marking: &[u64], t: usize) -> u64 {
+        self.preset[t]
+            .iter()
+            .map(|(p, w)| w.saturating_sub(marking[*p]))
+            .sum()
+    }
+
+    /// Fire transition `t`, creating missing tokens if it is not enabled
+    fn fire(&self, state: &mut ReplayState, t: usize, firings: &mut [u64]) {
+        for (p, w) in &self.preset[t] {
+            if state.marking[*p] < *w {
+                state.counts.missing += w - state.marking[*p];
+                state.marking[*p] = 0;
+            } else {
+                state.marking[*p] -= w;
+            }
+            state.counts.consumed += w;
+        }
+        for (p, w) in &self.postset[t] {
+            state.marking[*p] += w;
+            state.counts.produced += w;
+        }
+        firings[t] += 1;
+    }
+
+    /// Apply firing transition `t` to the passed marking (assumes `t` is enabled)
+    fn fire_on_marking(&self, marking: &mut [u64], t: usize) {
+        for (p, w) in &self.preset[t] {
+            marking[*p] -= w;
+        }
+        for (p, w) in &self.postset[t] {
+            marking[*p] += w;
+        }
+    }
+
+    ///
+    /// Search for a (shortest) sequence of enabled silent transitions leading to a marking satisfying `goal`
+    ///
+    /// Returns `None` if no such sequence is found within [`MAX_SILENT_EXPLORATION`] explored markings
+    fn silent_path<F: Fn(&[u64]) -> bool>(&self, marking: &[u64], goal: F) -> Option<Vec<usize>> {
+        let mut visited: HashSet<Vec<u64>> = HashSet::new();
+        let mut queue: VecDeque<(Vec<u64>, Vec<usize>)> = VecDeque::new();
+        visited.insert(marking.to_vec());
+        queue.push_back((marking.to_vec(), Vec::new()));
+        while let Some((m, path)) = queue.pop_front() {
+            for t in &self.silent {
+                if !self.is_enabled(&m, *t) {
+                    continue;
+                }
+                let mut next = m.clone();
+                self.fire_on_marking(&mut next, *t);
+                if visited.contains(&next) {
+                    continue;
+                }
+                let mut next_path = path.clone();
+                next_path.push(*t);
+                if goal(&next) {
+                    return Some(next_path);
+                }
+                if visited.len() >= MAX_SILENT_EXPLORATION {
+                    return None;
+                }
+                visited.insert(next.clone());
+                queue.push_back((next, next_path));
+            }
+        }
+        None
+    }
+
+    /// Replay a single activity
+    pub(crate) fn fire_activity(
+        &self,
+        state: &mut ReplayState,
+        activity: &str,
+        firings: &mut [u64],
+    ) {
+        let candidates = match self.by_label.get(activity) {
+            Some(c) => c,
+            None => {
+                state.counts.missing += 1;
+                state.counts.consumed += 1;
+                state.counts.produced += 1;
+                state.counts.remaining += 1;
+                return;
+            }
+        };
+        if let Some(t) = candidates
+            .iter()
+            .find(|t| self.is_enabled(&state.marking, **t))
+        {
+            self.fire(state, *t, firings);
+            return;
+        }
+        if let Some(path) = self.silent_path(&state.marking, |m| {
+            candidates.iter().any(|t| self.is_enabled(m, *t))
+        }) {
+            for s in path {
+                self.fire(state, s, firings);
+            }
+            let t = candidates
+                .iter()
+                .find(|t| self.is_enabled(&state.marking, **t))
+                .unwrap();
+            self.fire(state, *t, firings);
+            return;
+        }
+        let t = candidates
+            .iter()
+            .min_by_key(|t| self.num_missing(&state.marking, **t))
+            .unwrap();
+        self.fire(state, *t, firings);
+    }
+
+    /// Finish replay of a trace by consuming the (closest) final marking
+    pub(crate) fn finish(&self, mut state: ReplayState, firings: &mut [u64]) -> TokenCounts {
+        if self.final_markings.is_empty() {
+            return state.counts;
+        }
+        if !self.final_markings.contains(&state.marking) {
+            if let Some(path) = self.silent_path(&state.marking, |m| {
+                self.final_markings.iter().any(|f| f == m)
+            }) {
+                for s in path {
+                    self.fire(&mut state, s, firings);
+                }
+            }
+        }
+        let diff = |f: &Vec<u64>| -> (u64, u64) {
+            state
+                .marking
+                .iter()
+                .zip(f)
+                .fold((0, 0), |(missing, remaining), (m, f)| {
+                    (
+                        missing + f.saturating_sub(*m),
+                        remaining + m.saturating_sub(*f),
+                    )
+                })
+        };
+        let (final_marking, (missing, remaining)) = self
+            .final_markings
+            .iter()
+            .map(|f| (f, diff(f)))
+            .min_by_key(|(_, (missing, remaining))| missing + remaining)
+            .unwrap();
+        state.counts.consumed += final_marking.iter().sum::<u64>();
+        state.counts.missing += missing;
+        state.counts.remaining += remaining;
+        state.counts
+    }
+
+    /// Labels of all visible transitions that are enabled in the marking (possibly after firing silent transitions)
+    pub(crate) fn enabled_labels(&self, marking: &[u64]) -> HashSet<&'a str> {
+        let mut ret = HashSet::new();
+        let mut visited: HashSet<Vec<u64>> = HashSet::new();
+        let mut queue: VecDeque<Vec<u64>> = VecDeque::new();
+        visited.insert(marking.to_vec());
+        queue.push_back(marking.to_vec());
+        while let Some(m) = queue.pop_front() {
+            for (t, label) in self.labels.iter().enumerate() {
+                if !self.is_enabled(&m, t) {
+                    continue;
+                }
+                match label {
+                    Some(l) => {
+                        ret.insert(*l);
+                    }
+                    None => {
+                        let mut next = m.clone();
+                        self.fire_on_marking(&mut next, t);
+                        if visited.len() < MAX_SILENT_EXPLORATION && visited.insert(next.clone()) {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::petri_net::petri_net_struct::{Marking, PlaceID};
+
+    use super::*;
+
+    /// Petri net with transitions `a`, then a choice between `b` and `c`, and an optional silent skip of `d`
+    pub(crate) fn choice_net() -> PetriNet {
+        let mut net = PetriNet::new();
+        let places: Vec<PlaceID> = (0..4).map(|_| net.add_place(None)).collect();
+        let a = net.add_transition(Some("a".to_string()), None);
+        let b = net.add_transition(Some("b".to_string()), None);
+        let c = net.add_transition(Some("c".to_string()), None);
+        let d = net.add_transition(Some("d".to_string()), None);
+        let tau = net.add_transition(None, None);
+        net.add_arc(ArcType::place_to_transition(places[0], a), None);
+        net.add_arc(ArcType::transition_to_place(a, places[1]), None);
+        for t in [b, c] {
+            net.add_arc(ArcType::place_to_transition(places[1], t), None);
+            net.add_arc(ArcType::transition_to_place(t, places[2]), None);
+        }
+        for t in [d, tau] {
+            net.add_arc(ArcType::place_to_transition(places[2], t), None);
+            net.add_arc(ArcType::transition_to_place(t, places[3]), None);
+        }
+        net.initial_marking = Some(Marking::from([(places[0], 1)]));
+        net.final_markings = Some(vec![Marking::from([(places[3], 1)])]);
+        net
+    }
+
+    #[test]
+    fn replay_fitting_and_unfitting() {
+        let net = choice_net();
+        let replay_net = ReplayNet::new(&net);
+        let traces = vec![
+            (vec!["a", "b", "d"], 2),
+            (vec!["a", "c"], 1),
+            (vec!["a", "d"], 1),
+            (vec!["a", "x", "b"], 1),
+        ];
+        let res = replay_traces(&replay_net, &traces);
+        assert!(res.traces[0].is_fitting());
+        assert_eq!(
+            res.traces[0],
+            TokenCounts {
+                produced: 4,
+                consumed: 4,
+                missing: 0,
+                remaining: 0
+            }
+        );
+        // Silent transition skips d
+        assert!(res.traces[1].is_fitting());
+        // d is fired without its token in p2 (and the token in p1 remains)
+        assert_eq!(res.traces[2].missing, 1);
+        assert_eq!(res.traces[2].remaining, 1);
+        // Unknown activity x
+        assert_eq!(res.traces[3].missing, 1);
+        assert_eq!(res.traces[3].remaining, 1);
+        assert_eq!(res.fitting_traces_ratio(), 3.0 / 5.0);
+        assert!(res.fitness() < 1.0);
+        let a = net
+            .transitions
+            .values()
+            .find(|t| t.label.as_deref() == Some("a"))
+            .unwrap();
+        assert_eq!(res.transition_firings[&a.into()], 5);
+    }
+}
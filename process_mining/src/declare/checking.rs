@@ -0,0 +1,508 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    EventLog,
+};
+
+use super::declare_model::{DeclareConstraint, DeclareModel, DeclareTemplate};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Number of activations, fulfilments and violations of a constraint
+///
+/// For binary templates, every event of the activating activity is an activation
+/// (e.g., every `A` for `Response[A, B]` and every `B` for `Precedence[A, B]`),
+/// which is then either fulfilled or violated.
+///
+/// Unary templates (as well as [`DeclareTemplate::Choice`] and [`DeclareTemplate::ExclusiveChoice`]) are evaluated on the trace level:
+/// Every trace counts as a single activation.
+///
+pub struct ConstraintActivations {
+    /// Number of activations
+    pub activations: u64,
+    /// Number of fulfilled activations
+    pub fulfilments: u64,
+    /// Number of violated activations
+    pub violations: u64,
+}
+
+impl ConstraintActivations {
+    /// Whether the constraint is satisfied (i.e., there are no violations)
+    pub fn is_satisfied(&self) -> bool {
+        self.violations == 0
+    }
+
+    /// Whether the constraint is satisfied without being activated
+    pub fn is_vacuously_satisfied(&self) -> bool {
+        self.activations == 0
+    }
+
+    fn activate(&mut self, fulfilled: bool) {
+        self.activations += 1;
+        if fulfilled {
+            self.fulfilments += 1;
+        } else {
+            self.violations += 1;
+        }
+    }
+
+    fn add_weighted(&mut self, other: &ConstraintActivations, weight: u64) {
+        self.activations += other.activations * weight;
+        self.fulfilments += other.fulfilments * weight;
+        self.violations += other.violations * weight;
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Result of checking a [`DeclareModel`] against an event log
+///
+pub struct DeclareCheckingResult {
+    /// Per-trace results
+    ///
+    /// `traces[t][c]` contains the activations of constraint `c` (index in [`DeclareModel::constraints`]) in trace `t`
+    pub traces: Vec<Vec<ConstraintActivations>>,
+    /// Frequency of each trace
+    ///
+    /// For an [`EventLog`] all frequencies are 1, for an [`EventLogActivityProjection`] these are the number of occurrences of the trace variant
+    pub trace_frequencies: Vec<u64>,
+}
+
+impl DeclareCheckingResult {
+    /// Whether the trace with the given index satisfies all constraints
+    pub fn is_trace_compliant(&self, trace: usize) -> bool {
+        self.traces[trace].iter().all(|c| c.is_satisfied())
+    }
+
+    /// Indices of all traces violating the constraint with the given index
+    pub fn violating_traces(&self, constraint: usize) -> Vec<usize> {
+        self.traces
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| !t[constraint].is_satisfied())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Total activations, fulfilments and violations of the constraint with the given index (weighted by trace frequency)
+    pub fn constraint_totals(&self, constraint: usize) -> ConstraintActivations {
+        let mut ret = ConstraintActivations::default();
+        for (t, freq) in self.traces.iter().zip(&self.trace_frequencies) {
+            ret.add_weighted(&t[constraint], *freq);
+        }
+        ret
+    }
+
+    /// Number of traces (weighted by frequency)
+    pub fn num_traces(&self) -> u64 {
+        self.trace_frequencies.iter().sum()
+    }
+
+    ///
+    /// Fraction of traces (weighted by frequency) satisfying the constraint with the given index
+    ///
+    /// Returns `1.0` if there are no traces.
+    pub fn satisfaction_ratio(&self, constraint: usize) -> f64 {
+        self.ratio(constraint, |c| c.is_satisfied())
+    }
+
+    ///
+    /// Fraction of traces (weighted by frequency) in which the constraint with the given index is activated at least once
+    ///
+    /// Returns `1.0` if there are no traces.
+    pub fn activation_ratio(&self, constraint: usize) -> f64 {
+        self.ratio(constraint, |c| !c.is_vacuously_satisfied())
+    }
+
+    fn ratio<F: Fn(&ConstraintActivations) -> bool>(&self, constraint: usize, f: F) -> f64 {
+        let total = self.num_traces();
+        if total == 0 {
+            return 1.0;
+        }
+        let matching: u64 = self
+            .traces
+            .iter()
+            .zip(&self.trace_frequencies)
+            .filter(|(t, _)| f(&t[constraint]))
+            .map(|(_, freq)| *freq)
+            .sum();
+        matching as f64 / total as f64
+    }
+}
+
+///
+/// Check a single constraint on a trace (with activities represented by `usize` indices)
+///
+/// `a` and `b` are the indices of the first and second parameter of the constraint (`b` is ignored for unary templates).
+///
+pub fn check_constraint_on_trace(
+    template: &DeclareTemplate,
+    a: usize,
+    b: usize,
+    trace: &[usize],
+) -> ConstraintActivations {
+    let mut ret = ConstraintActivations::default();
+    let count = |x: usize| trace.iter().filter(|e| **e == x).count() as u32;
+    let contains = |x: usize| trace.contains(&x);
+    match template {
+        DeclareTemplate::Existence(n) => ret.activate(count(a) >= *n),
+        DeclareTemplate::Absence(n) => ret.activate(count(a) < *n),
+        DeclareTemplate::Exactly(n) => ret.activate(count(a) == *n),
+        DeclareTemplate::Init => ret.activate(trace.first() == Some(&a)),
+        DeclareTemplate::End => ret.activate(trace.last() == Some(&a)),
+        DeclareTemplate::Choice => ret.activate(contains(a) || contains(b)),
+        DeclareTemplate::ExclusiveChoice => ret.activate(contains(a) != contains(b)),
+        DeclareTemplate::RespondedExistence | DeclareTemplate::NotRespondedExistence => {
+            let fulfilled = contains(b) == (*template == DeclareTemplate::RespondedExistence);
+            for _ in trace.iter().filter(|e| **e == a) {
+                ret.activate(fulfilled);
+            }
+        }
+        DeclareTemplate::CoExistence | DeclareTemplate::NotCoExistence => {
+            let positive = *template == DeclareTemplate::CoExistence;
+            let (has_a, has_b) = (contains(a), contains(b));
+            for e in trace {
+                if *e == a {
+                    ret.activate(has_b == positive);
+                } else if *e == b {
+                    ret.activate(has_a == positive);
+                }
+            }
+        }
+        DeclareTemplate::Response => response(&mut ret, a, b, trace, true),
+        DeclareTemplate::NotResponse => response(&mut ret, a, b, trace, false),
+        DeclareTemplate::Precedence => precedence(&mut ret, a, b, trace, true),
+        DeclareTemplate::NotPrecedence => precedence(&mut ret, a, b, trace, false),
+        DeclareTemplate::Succession => {
+            response(&mut ret, a, b, trace, true);
+            precedence(&mut ret, a, b, trace, true);
+        }
+        DeclareTemplate::NotSuccession => {
+            response(&mut ret, a, b, trace, false);
+            precedence(&mut ret, a, b, trace, false);
+        }
+        DeclareTemplate::AlternateResponse => alternate_response(&mut ret, a, b, trace),
+        DeclareTemplate::AlternatePrecedence => alternate_precedence(&mut ret, a, b, trace),
+        DeclareTemplate::AlternateSuccession => {
+            alternate_response(&mut ret, a, b, trace);
+            alternate_precedence(&mut ret, a, b, trace);
+        }
+        DeclareTemplate::ChainResponse => chain_response(&mut ret, a, b, trace, true),
+        DeclareTemplate::NotChainResponse => chain_response(&mut ret, a, b, trace, false),
+        DeclareTemplate::ChainPrecedence => chain_precedence(&mut ret, a, b, trace, true),
+        DeclareTemplate::NotChainPrecedence => chain_precedence(&mut ret, a, b, trace, false),
+        DeclareTemplate::ChainSuccession => {
+            chain_response(&mut ret, a, b, trace, true);
+            chain_precedence(&mut ret, a, b, trace, true);
+        }
+        DeclareTemplate::NotChainSuccession => {
+            chain_response(&mut ret, a, b, trace, false);
+            chain_precedence(&mut ret, a, b, trace, false);
+        }
+    }
+    ret
+}
+
+/// Every `a` is (`positive`) or is not (`!positive`) eventually followed by `b`
+fn response(ret: &mut ConstraintActivations, a: usize, b: usize, trace: &[usize], positive: bool) {
+    let last_b = trace.iter().rposition(|e| *e == b);
+    for (i, _) in trace.iter().enumerate().filter(|(_, e)| **e == a) {
+        let followed = last_b.is_some_and(|j| j > i);
+        ret.activate(followed == positive);
+    }
+}
+
+/// Every `b` is (`positive`) or is not (`!positive`) preceded by `a`
+fn precedence(
+    ret: &mut ConstraintActivations,
+    a: usize,
+    b: usize,
+    trace: &[usize],
+    positive: bool,
+) {
+    let first_a = trace.iter().position(|e| *e == a);
+    for (i, _) in trace.iter().enumerate().filter(|(_, e)| **e == b) {
+        let preceded = first_a.is_some_and(|j| j < i);
+        ret.activate(preceded == positive);
+    }
+}
+
+fn alternate_response(ret: &mut ConstraintActivations, a: usize, b: usize, trace: &[usize]) {
+    let mut pending = false;
+    for e in trace {
+        if *e == b && pending {
+            ret.activate(true);
+            pending = false;
+        }
+        if *e == a {
+            if pending {
+                ret.activate(false);
+            }
+            pending = true;
+        }
+    }
+    if pending {
+        ret.activate(false);
+    }
+}
+
+fn alternate_precedence(ret: &mut ConstraintActivations, a: usize, b: usize, trace: &[usize]) {
+    let mut a_since_last_b = false;
+    for e in trace {
+        if *e == b {
+            ret.activate(a_since_last_b);
+            a_since_last_b = false;
+        }
+        if *e == a {
+            a_since_last_b = true;
+        }
+    }
+}
+
+fn chain_response(
+    ret: &mut ConstraintActivations,
+    a: usize,
+    b: usize,
+    trace: &[usize],
+    positive: bool,
+) {
+    for (i, _) in trace.iter().enumerate().filter(|(_, e)| **e == a) {
+        let directly_followed = trace.get(i + 1) == Some(&b);
+        ret.activate(directly_followed == positive);
+    }
+}
+
+fn chain_precedence(
+    ret: &mut ConstraintActivations,
+    a: usize,
+    b: usize,
+    trace: &[usize],
+    positive: bool,
+) {
+    for (i, _) in trace.iter().enumerate().filter(|(_, e)| **e == b) {
+        let directly_preceded = i > 0 && trace[i - 1] == a;
+        ret.activate(directly_preceded == positive);
+    }
+}
+
+///
+/// Indexed representation of a [`DeclareModel`] used for checking
+///
+/// Activities not mentioned in any constraint are mapped to a shared "other" index
+struct IndexedModel<'a> {
+    act_to_index: HashMap<&'a str, usize>,
+    constraints: Vec<(DeclareTemplate, usize, usize)>,
+}
+
+impl<'a> IndexedModel<'a> {
+    fn new(constraints: &'a [DeclareConstraint]) -> Self {
+        let mut act_to_index: HashMap<&str, usize> = HashMap::new();
+        let constraints = constraints
+            .iter()
+            .map(|c| {
+                let mut indices = c.parameters.iter().map(|p| {
+                    let n = act_to_index.len();
+                    *act_to_index.entry(p.as_str()).or_insert(n)
+                });
+                let a = indices.next().unwrap_or(usize::MAX);
+                let b = indices.next().unwrap_or(usize::MAX);
+                (c.template, a, b)
+            })
+            .collect();
+        Self {
+            act_to_index,
+            constraints,
+        }
+    }
+
+    fn index_of(&self, activity: &str) -> usize {
+        *self
+            .act_to_index
+            .get(activity)
+            .unwrap_or(&self.act_to_index.len())
+    }
+
+    fn check(&self, trace: &[usize]) -> Vec<ConstraintActivations> {
+        self.constraints
+            .iter()
+            .map(|(t, a, b)| check_constraint_on_trace(t, *a, *b, trace))
+            .collect()
+    }
+}
+
+///
+/// Check all constraints of a [`DeclareModel`] on a single trace, given as a sequence of activities
+///
+pub fn check_declare_model_on_trace<S: AsRef<str>>(
+    model: &DeclareModel,
+    trace: &[S],
+) -> Vec<ConstraintActivations> {
+    let indexed = IndexedModel::new(&model.constraints);
+    let trace: Vec<usize> = trace.iter().map(|a| indexed.index_of(a.as_ref())).collect();
+    indexed.check(&trace)
+}
+
+///
+/// Check all constraints of a [`DeclareModel`] on an [`EventLog`]
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`].
+///
+pub fn check_declare_model(
+    log: &EventLog,
+    model: &DeclareModel,
+    classifier: &EventLogClassifier,
+) -> DeclareCheckingResult {
+    let indexed = IndexedModel::new(&model.constraints);
+    let traces = log
+        .traces
+        .par_iter()
+        .map(|t| {
+            let trace: Vec<usize> = t
+                .events
+                .iter()
+                .map(|e| {
+                    indexed.index_of(
+                        &classifier.get_class_identity_with_globals(e, &log.global_event_attrs),
+                    )
+                })
+                .collect();
+            indexed.check(&trace)
+        })
+        .collect();
+    DeclareCheckingResult {
+        traces,
+        trace_frequencies: vec![1; log.traces.len()],
+    }
+}
+
+///
+/// Check all constraints of a [`DeclareModel`] on an [`EventLogActivityProjection`]
+///
+/// Each trace variant of the projection is checked once (see [`DeclareCheckingResult::trace_frequencies`]).
+///
+pub fn check_declare_model_projection(
+    log: &EventLogActivityProjection,
+    model: &DeclareModel,
+) -> DeclareCheckingResult {
+    let indexed = IndexedModel::new(&model.constraints);
+    let mapping: Vec<usize> = log.activities.iter().map(|a| indexed.index_of(a)).collect();
+    let traces = log
+        .traces
+        .par_iter()
+        .map(|(t, _)| {
+            let trace: Vec<usize> = t.iter().map(|a| mapping[*a]).collect();
+            indexed.check(&trace)
+        })
+        .collect();
+    DeclareCheckingResult {
+        traces,
+        trace_frequencies: log.traces.iter().map(|(_, freq)| *freq).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_utils::event_log_from_activities;
+
+    use super::*;
+
+    fn check(constraint: DeclareConstraint, trace: &[&str]) -> ConstraintActivations {
+        let mut model = DeclareModel::new();
+        model.add_constraint(constraint);
+        check_declare_model_on_trace(&model, trace)[0]
+    }
+
+    fn counts(activations: u64, fulfilments: u64, violations: u64) -> ConstraintActivations {
+        ConstraintActivations {
+            activations,
+            fulfilments,
+            violations,
+        }
+    }
+
+    #[test]
+    fn binary_templates() {
+        use DeclareTemplate::*;
+        let trace = ["a", "b", "a", "c", "b", "a"];
+        let cases = [
+            (Response, counts(3, 2, 1)),
+            (Precedence, counts(2, 2, 0)),
+            (Succession, counts(5, 4, 1)),
+            (AlternateResponse, counts(3, 2, 1)),
+            (AlternatePrecedence, counts(2, 2, 0)),
+            (ChainResponse, counts(3, 1, 2)),
+            (ChainPrecedence, counts(2, 1, 1)),
+            (RespondedExistence, counts(3, 3, 0)),
+            (CoExistence, counts(5, 5, 0)),
+            (NotCoExistence, counts(5, 0, 5)),
+            (NotChainSuccession, counts(5, 3, 2)),
+            (NotSuccession, counts(5, 1, 4)),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                check(DeclareConstraint::binary(template, "a", "b"), &trace),
+                expected,
+                "{:?}",
+                template
+            );
+        }
+        assert_eq!(
+            check(
+                DeclareConstraint::binary(AlternateResponse, "a", "b"),
+                &["a", "a", "b"]
+            ),
+            counts(2, 1, 1)
+        );
+        assert_eq!(
+            check(DeclareConstraint::binary(Response, "a", "d"), &["b", "c"]),
+            counts(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn unary_templates() {
+        use DeclareTemplate::*;
+        let trace = ["a", "b", "a"];
+        assert!(check(DeclareConstraint::unary(Existence(2), "a"), &trace).is_satisfied());
+        assert!(!check(DeclareConstraint::unary(Existence(3), "a"), &trace).is_satisfied());
+        assert!(check(DeclareConstraint::unary(Absence(2), "b"), &trace).is_satisfied());
+        assert!(!check(DeclareConstraint::unary(Absence(1), "b"), &trace).is_satisfied());
+        assert!(check(DeclareConstraint::unary(Exactly(1), "b"), &trace).is_satisfied());
+        assert!(check(DeclareConstraint::unary(Init, "a"), &trace).is_satisfied());
+        assert!(!check(DeclareConstraint::unary(End, "b"), &trace).is_satisfied());
+        assert!(check(DeclareConstraint::binary(Choice, "c", "b"), &trace).is_satisfied());
+        assert!(
+            !check(DeclareConstraint::binary(ExclusiveChoice, "a", "b"), &trace).is_satisfied()
+        );
+    }
+
+    #[test]
+    fn check_log_and_projection() {
+        let log = event_log_from_activities(&[&["a", "b"], &["a", "c"], &["a", "b"]]);
+        let mut model = DeclareModel::new();
+        model.add_constraint(DeclareConstraint::binary(
+            DeclareTemplate::Response,
+            "a",
+            "b",
+        ));
+        model.add_constraint(DeclareConstraint::unary(DeclareTemplate::Init, "a"));
+
+        let res = check_declare_model(&log, &model, &EventLogClassifier::default());
+        assert_eq!(res.violating_traces(0), vec![1]);
+        assert!(res.is_trace_compliant(0));
+        assert_eq!(res.constraint_totals(0), counts(3, 2, 1));
+
+        let proj: EventLogActivityProjection = (&log).into();
+        let res_proj = check_declare_model_projection(&proj, &model);
+        assert_eq!(res_proj.num_traces(), 3);
+        assert_eq!(res_proj.constraint_totals(0), counts(3, 2, 1));
+        assert!((res_proj.satisfaction_ratio(0) - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(res_proj.satisfaction_ratio(1), 1.0);
+    }
+}
@@ -0,0 +1,333 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    export_decl::export_decl_path, import_decl::import_decl_from_path, import_decl::DeclParseError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Declare constraint templates
+///
+/// Unary templates (e.g., [`DeclareTemplate::Existence`]) take one activity parameter,
+/// all other templates take two (e.g., `Response[A, B]`: Every `A` is eventually followed by a `B`).
+///
+pub enum DeclareTemplate {
+    /// Activity occurs at least `n` times
+    Existence(u32),
+    /// Activity occurs less than `n` times (i.e., `Absence(1)` forbids the activity)
+    Absence(u32),
+    /// Activity occurs exactly `n` times
+    Exactly(u32),
+    /// Activity is the first event of every trace
+    Init,
+    /// Activity is the last event of every trace
+    End,
+    /// At least one of the two activities occurs
+    Choice,
+    /// Exactly one of the two activities occurs (but not both)
+    ExclusiveChoice,
+    /// If `A` occurs, `B` occurs as well (before or after)
+    RespondedExistence,
+    /// `A` and `B` either both occur or both do not occur
+    CoExistence,
+    /// Every `A` is eventually followed by a `B`
+    Response,
+    /// Every `B` is preceded by an `A`
+    Precedence,
+    /// Both [`DeclareTemplate::Response`] and [`DeclareTemplate::Precedence`]
+    Succession,
+    /// Every `A` is followed by a `B` before the next `A`
+    AlternateResponse,
+    /// Every `B` is preceded by an `A` after the previous `B`
+    AlternatePrecedence,
+    /// Both [`DeclareTemplate::AlternateResponse`] and [`DeclareTemplate::AlternatePrecedence`]
+    AlternateSuccession,
+    /// Every `A` is directly followed by a `B`
+    ChainResponse,
+    /// Every `B` is directly preceded by an `A`
+    ChainPrecedence,
+    /// Both [`DeclareTemplate::ChainResponse`] and [`DeclareTemplate::ChainPrecedence`]
+    ChainSuccession,
+    /// If `A` occurs, `B` does not occur
+    NotRespondedExistence,
+    /// `A` and `B` do not occur together
+    NotCoExistence,
+    /// No `A` is eventually followed by a `B`
+    NotResponse,
+    /// No `B` is preceded by an `A`
+    NotPrecedence,
+    /// Both [`DeclareTemplate::NotResponse`] and [`DeclareTemplate::NotPrecedence`]
+    NotSuccession,
+    /// No `A` is directly followed by a `B`
+    NotChainResponse,
+    /// No `B` is directly preceded by an `A`
+    NotChainPrecedence,
+    /// Both [`DeclareTemplate::NotChainResponse`] and [`DeclareTemplate::NotChainPrecedence`]
+    NotChainSuccession,
+}
+
+impl DeclareTemplate {
+    /// All binary templates (i.e., templates with two activity parameters)
+    pub const BINARY_TEMPLATES: [DeclareTemplate; 21] = [
+        DeclareTemplate::Choice,
+        DeclareTemplate::ExclusiveChoice,
+        DeclareTemplate::RespondedExistence,
+        DeclareTemplate::CoExistence,
+        DeclareTemplate::Response,
+        DeclareTemplate::Precedence,
+        DeclareTemplate::Succession,
+        DeclareTemplate::AlternateResponse,
+        DeclareTemplate::AlternatePrecedence,
+        DeclareTemplate::AlternateSuccession,
+        DeclareTemplate::ChainResponse,
+        DeclareTemplate::ChainPrecedence,
+        DeclareTemplate::ChainSuccession,
+        DeclareTemplate::NotRespondedExistence,
+        DeclareTemplate::NotCoExistence,
+        DeclareTemplate::NotResponse,
+        DeclareTemplate::NotPrecedence,
+        DeclareTemplate::NotSuccession,
+        DeclareTemplate::NotChainResponse,
+        DeclareTemplate::NotChainPrecedence,
+        DeclareTemplate::NotChainSuccession,
+    ];
+
+    /// Number of activity parameters of the template (1 or 2)
+    pub fn arity(&self) -> usize {
+        match self {
+            DeclareTemplate::Existence(_)
+            | DeclareTemplate::Absence(_)
+            | DeclareTemplate::Exactly(_)
+            | DeclareTemplate::Init
+            | DeclareTemplate::End => 1,
+            _ => 2,
+        }
+    }
+
+    ///
+    /// Whether the template is symmetric in its two parameters (e.g., `CoExistence[A, B]` is equivalent to `CoExistence[B, A]`)
+    ///
+    pub fn is_symmetric(&self) -> bool {
+        matches!(
+            self,
+            DeclareTemplate::Choice
+                | DeclareTemplate::ExclusiveChoice
+                | DeclareTemplate::CoExistence
+                | DeclareTemplate::NotCoExistence
+        )
+    }
+
+    ///
+    /// Name of the template as used in the `.decl` format (e.g., `Chain Response` or `Existence2`)
+    ///
+    pub fn name(&self) -> String {
+        match self {
+            DeclareTemplate::Existence(1) => "Existence".to_string(),
+            DeclareTemplate::Existence(n) => format!("Existence{n}"),
+            DeclareTemplate::Absence(1) => "Absence".to_string(),
+            DeclareTemplate::Absence(n) => format!("Absence{n}"),
+            DeclareTemplate::Exactly(n) => format!("Exactly{n}"),
+            DeclareTemplate::Init => "Init".to_string(),
+            DeclareTemplate::End => "End".to_string(),
+            DeclareTemplate::Choice => "Choice".to_string(),
+            DeclareTemplate::ExclusiveChoice => "Exclusive Choice".to_string(),
+            DeclareTemplate::RespondedExistence => "Responded Existence".to_string(),
+            DeclareTemplate::CoExistence => "Co-Existence".to_string(),
+            DeclareTemplate::Response => "Response".to_string(),
+            DeclareTemplate::Precedence => "Precedence".to_string(),
+            DeclareTemplate::Succession => "Succession".to_string(),
+            DeclareTemplate::AlternateResponse => "Alternate Response".to_string(),
+            DeclareTemplate::AlternatePrecedence => "Alternate Precedence".to_string(),
+            DeclareTemplate::AlternateSuccession => "Alternate Succession".to_string(),
+            DeclareTemplate::ChainResponse => "Chain Response".to_string(),
+            DeclareTemplate::ChainPrecedence => "Chain Precedence".to_string(),
+            DeclareTemplate::ChainSuccession => "Chain Succession".to_string(),
+            DeclareTemplate::NotRespondedExistence => "Not Responded Existence".to_string(),
+            DeclareTemplate::NotCoExistence => "Not Co-Existence".to_string(),
+            DeclareTemplate::NotResponse => "Not Response".to_string(),
+            DeclareTemplate::NotPrecedence => "Not Precedence".to_string(),
+            DeclareTemplate::NotSuccession => "Not Succession".to_string(),
+            DeclareTemplate::NotChainResponse => "Not Chain Response".to_string(),
+            DeclareTemplate::NotChainPrecedence => "Not Chain Precedence".to_string(),
+            DeclareTemplate::NotChainSuccession => "Not Chain Succession".to_string(),
+        }
+    }
+
+    ///
+    /// Parse a template from its name (see [`DeclareTemplate::name`])
+    ///
+    /// Matching ignores case, whitespace, hyphens and underscores (i.e., `Chain Response`, `ChainResponse` and `chain_response` are all accepted).
+    ///
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-' && *c != '_')
+            .collect::<String>()
+            .to_lowercase();
+        let split = normalized
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(normalized.len());
+        let (base, number) = normalized.split_at(split);
+        let n: Option<u32> = if number.is_empty() {
+            None
+        } else {
+            Some(number.parse().ok()?)
+        };
+        let template = match (base, n) {
+            ("existence", n) => DeclareTemplate::Existence(n.unwrap_or(1)),
+            ("absence", n) => DeclareTemplate::Absence(n.unwrap_or(1)),
+            ("exactly", n) => DeclareTemplate::Exactly(n.unwrap_or(1)),
+            (_, Some(_)) => return None,
+            ("init", _) => DeclareTemplate::Init,
+            ("end" | "last", _) => DeclareTemplate::End,
+            ("choice", _) => DeclareTemplate::Choice,
+            ("exclusivechoice", _) => DeclareTemplate::ExclusiveChoice,
+            ("respondedexistence", _) => DeclareTemplate::RespondedExistence,
+            ("coexistence", _) => DeclareTemplate::CoExistence,
+            ("response", _) => DeclareTemplate::Response,
+            ("precedence", _) => DeclareTemplate::Precedence,
+            ("succession", _) => DeclareTemplate::Succession,
+            ("alternateresponse", _) => DeclareTemplate::AlternateResponse,
+            ("alternateprecedence", _) => DeclareTemplate::AlternatePrecedence,
+            ("alternatesuccession", _) => DeclareTemplate::AlternateSuccession,
+            ("chainresponse", _) => DeclareTemplate::ChainResponse,
+            ("chainprecedence", _) => DeclareTemplate::ChainPrecedence,
+            ("chainsuccession", _) => DeclareTemplate::ChainSuccession,
+            ("notrespondedexistence", _) => DeclareTemplate::NotRespondedExistence,
+            ("notcoexistence", _) => DeclareTemplate::NotCoExistence,
+            ("notresponse", _) => DeclareTemplate::NotResponse,
+            ("notprecedence", _) => DeclareTemplate::NotPrecedence,
+            ("notsuccession", _) => DeclareTemplate::NotSuccession,
+            ("notchainresponse", _) => DeclareTemplate::NotChainResponse,
+            ("notchainprecedence", _) => DeclareTemplate::NotChainPrecedence,
+            ("notchainsuccession", _) => DeclareTemplate::NotChainSuccession,
+            _ => return None,
+        };
+        Some(template)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// A Declare constraint, i.e., a [`DeclareTemplate`] instantiated with activities
+///
+pub struct DeclareConstraint {
+    /// Template of the constraint
+    pub template: DeclareTemplate,
+    /// Activity parameters (one for unary templates, two for binary templates)
+    pub parameters: Vec<String>,
+    /// Data and time conditions (as in the `.decl` format, e.g., `A.grade > 5`)
+    ///
+    /// _Note_: Conditions are preserved on import/export, but __not__ considered during conformance checking.
+    #[serde(default)]
+    pub conditions: Vec<String>,
+}
+
+impl DeclareConstraint {
+    /// Create a new constraint of a unary template (e.g., `Existence[A]`)
+    pub fn unary<S: Into<String>>(template: DeclareTemplate, a: S) -> Self {
+        Self {
+            template,
+            parameters: vec![a.into()],
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Create a new constraint of a binary template (e.g., `Response[A, B]`)
+    pub fn binary<S: Into<String>, T: Into<String>>(template: DeclareTemplate, a: S, b: T) -> Self {
+        Self {
+            template,
+            parameters: vec![a.into(), b.into()],
+            conditions: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for DeclareConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}[{}]",
+            self.template.name(),
+            self.parameters.join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+///
+/// A Declare process model, i.e., a set of [`DeclareConstraint`]s over activities
+///
+pub struct DeclareModel {
+    /// Activities of the model
+    pub activities: Vec<String>,
+    /// Constraints of the model
+    pub constraints: Vec<DeclareConstraint>,
+    /// Data definitions of the `.decl` format (e.g., `bind A: grade` or `grade: integer between 1 and 5`)
+    ///
+    /// These lines are preserved as-is on import/export.
+    #[serde(default)]
+    pub data_definitions: Vec<String>,
+}
+
+impl DeclareModel {
+    /// Create a new empty [`DeclareModel`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an activity to the model (if not yet present)
+    pub fn add_activity<S: Into<String>>(&mut self, activity: S) {
+        let activity = activity.into();
+        if !self.activities.contains(&activity) {
+            self.activities.push(activity);
+        }
+    }
+
+    /// Add a constraint to the model (also adding its parameters as activities, if not yet present)
+    pub fn add_constraint(&mut self, constraint: DeclareConstraint) {
+        for a in &constraint.parameters {
+            self.add_activity(a.clone());
+        }
+        self.constraints.push(constraint);
+    }
+
+    /// Import a Declare model from a `.decl` file
+    ///
+    /// For more control, see [`super::import_decl::import_decl_reader`]
+    pub fn import_decl<P: AsRef<std::path::Path>>(path: P) -> Result<Self, DeclParseError> {
+        import_decl_from_path(path)
+    }
+
+    /// Export the Declare model to a `.decl` file
+    ///
+    /// The resulting file can be imported again (see [`DeclareModel::import_decl`]).
+    pub fn export_decl<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        export_decl_path(self, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_names() {
+        for t in DeclareTemplate::BINARY_TEMPLATES.iter().chain(&[
+            DeclareTemplate::Existence(1),
+            DeclareTemplate::Existence(3),
+            DeclareTemplate::Absence(2),
+            DeclareTemplate::Exactly(1),
+            DeclareTemplate::Init,
+            DeclareTemplate::End,
+        ]) {
+            assert_eq!(DeclareTemplate::from_name(&t.name()), Some(*t));
+        }
+        assert_eq!(
+            DeclareTemplate::from_name("chain_response"),
+            Some(DeclareTemplate::ChainResponse)
+        );
+        assert_eq!(DeclareTemplate::from_name("Response2"), None);
+        assert_eq!(DeclareTemplate::from_name("Foo"), None);
+    }
+}
@@ -0,0 +1,219 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::event_log::activity_projection::EventLogActivityProjection;
+
+use super::{
+    checking::check_constraint_on_trace,
+    declare_model::{DeclareConstraint, DeclareModel, DeclareTemplate},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Options for discovering a [`DeclareModel`] (see [`discover_declare_model`])
+///
+pub struct DeclareDiscoveryOptions {
+    /// Templates to instantiate with all (pairs of) activities of the log
+    pub templates: Vec<DeclareTemplate>,
+    /// Minimum support of a constraint (fraction of traces satisfying the constraint)
+    pub min_support: f64,
+    /// Minimum confidence of a constraint (support multiplied with the fraction of traces activating the constraint)
+    ///
+    /// Used to filter out constraints that are mostly satisfied vacuously (e.g., `Response[A, B]` for a rare activity `A`)
+    pub min_confidence: f64,
+}
+
+impl Default for DeclareDiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            templates: vec![
+                DeclareTemplate::Existence(1),
+                DeclareTemplate::Init,
+                DeclareTemplate::End,
+                DeclareTemplate::RespondedExistence,
+                DeclareTemplate::Response,
+                DeclareTemplate::Precedence,
+                DeclareTemplate::Succession,
+                DeclareTemplate::AlternateResponse,
+                DeclareTemplate::AlternatePrecedence,
+                DeclareTemplate::ChainResponse,
+                DeclareTemplate::ChainPrecedence,
+                DeclareTemplate::CoExistence,
+            ],
+            min_support: 0.9,
+            min_confidence: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// A discovered [`DeclareConstraint`] together with its support and confidence
+///
+pub struct DiscoveredConstraint {
+    /// The constraint
+    pub constraint: DeclareConstraint,
+    /// Fraction of traces satisfying the constraint
+    pub support: f64,
+    /// Support multiplied with the fraction of traces activating the constraint
+    pub confidence: f64,
+}
+
+///
+/// Discover Declare constraints (including their support and confidence) from an [`EventLogActivityProjection`]
+///
+/// All templates of [`DeclareDiscoveryOptions::templates`] are instantiated with all activities (unary templates)
+/// or pairs of distinct activities (binary templates; symmetric templates only once per pair).
+/// Constraints reaching both [`DeclareDiscoveryOptions::min_support`] and [`DeclareDiscoveryOptions::min_confidence`] are returned.
+///
+/// Also see [`discover_declare_model`].
+pub fn discover_declare_constraints(
+    log: &EventLogActivityProjection,
+    options: &DeclareDiscoveryOptions,
+) -> Vec<DiscoveredConstraint> {
+    let num_acts = log.activities.len();
+    let mut candidates: Vec<(DeclareTemplate, usize, usize)> = Vec::new();
+    for template in &options.templates {
+        if template.arity() == 1 {
+            candidates.extend((0..num_acts).map(|a| (*template, a, a)));
+        } else {
+            for a in 0..num_acts {
+                for b in 0..num_acts {
+                    if a != b && (a < b || !template.is_symmetric()) {
+                        candidates.push((*template, a, b));
+                    }
+                }
+            }
+        }
+    }
+    let total: u64 = log.traces.iter().map(|(_, freq)| *freq).sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    candidates
+        .into_par_iter()
+        .filter_map(|(template, a, b)| {
+            let mut satisfied = 0;
+            let mut activated = 0;
+            for (trace, freq) in &log.traces {
+                let res = check_constraint_on_trace(&template, a, b, trace);
+                if res.is_satisfied() {
+                    satisfied += freq;
+                }
+                if !res.is_vacuously_satisfied() {
+                    activated += freq;
+                }
+            }
+            let support = satisfied as f64 / total as f64;
+            let confidence = support * (activated as f64 / total as f64);
+            if support < options.min_support || confidence < options.min_confidence {
+                return None;
+            }
+            let constraint = if template.arity() == 1 {
+                DeclareConstraint::unary(template, log.activities[a].clone())
+            } else {
+                DeclareConstraint::binary(
+                    template,
+                    log.activities[a].clone(),
+                    log.activities[b].clone(),
+                )
+            };
+            Some(DiscoveredConstraint {
+                constraint,
+                support,
+                confidence,
+            })
+        })
+        .collect()
+}
+
+///
+/// Discover a [`DeclareModel`] from an [`EventLogActivityProjection`]
+///
+/// See [`discover_declare_constraints`] for details on the discovery and its parameters.
+///
+/// An [`EventLogActivityProjection`] can be constructed from an [`crate::EventLog`] using `(&log).into()`.
+pub fn discover_declare_model(
+    log: &EventLogActivityProjection,
+    options: &DeclareDiscoveryOptions,
+) -> DeclareModel {
+    let mut model = DeclareModel::new();
+    for a in &log.activities {
+        model.add_activity(a.clone());
+    }
+    for c in discover_declare_constraints(log, options) {
+        model.add_constraint(c.constraint);
+    }
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discover_simple_model() {
+        let activities: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        let log = EventLogActivityProjection {
+            act_to_index: activities
+                .iter()
+                .enumerate()
+                .map(|(i, a)| (a.clone(), i))
+                .collect(),
+            activities,
+            traces: vec![(vec![0, 1, 2], 5), (vec![0, 2], 4), (vec![0, 2, 1], 1)],
+        };
+        let model = discover_declare_model(
+            &log,
+            &DeclareDiscoveryOptions {
+                min_support: 1.0,
+                ..Default::default()
+            },
+        );
+        let contains = |c: DeclareConstraint| model.constraints.contains(&c);
+        assert!(contains(DeclareConstraint::unary(
+            DeclareTemplate::Init,
+            "a"
+        )));
+        assert!(contains(DeclareConstraint::unary(
+            DeclareTemplate::Existence(1),
+            "c"
+        )));
+        assert!(contains(DeclareConstraint::binary(
+            DeclareTemplate::Response,
+            "a",
+            "c"
+        )));
+        assert!(contains(DeclareConstraint::binary(
+            DeclareTemplate::Precedence,
+            "a",
+            "b"
+        )));
+        assert!(!contains(DeclareConstraint::binary(
+            DeclareTemplate::Response,
+            "b",
+            "c"
+        )));
+        assert!(!contains(DeclareConstraint::unary(
+            DeclareTemplate::Existence(1),
+            "b"
+        )));
+
+        let with_measures = discover_declare_constraints(
+            &log,
+            &DeclareDiscoveryOptions {
+                templates: vec![DeclareTemplate::ChainResponse],
+                min_support: 0.5,
+                min_confidence: 0.0,
+            },
+        );
+        let b_c = with_measures
+            .iter()
+            .find(|c| {
+                c.constraint == DeclareConstraint::binary(DeclareTemplate::ChainResponse, "b", "c")
+            })
+            .unwrap();
+        assert!((b_c.support - 0.9).abs() < 1e-9);
+        assert!((b_c.confidence - 0.9 * 0.6).abs() < 1e-9);
+    }
+}
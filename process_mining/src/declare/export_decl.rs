@@ -0,0 +1,86 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use super::declare_model::DeclareModel;
+
+///
+/// Export a [`DeclareModel`] in the `.decl` format to the provided writer
+///
+/// Constraints without conditions are written with empty conditions (e.g., `Response[A, B] | | |`), as expected by `RuM`.
+///
+pub fn export_decl<W: Write>(model: &DeclareModel, mut writer: W) -> Result<(), std::io::Error> {
+    for a in &model.activities {
+        writeln!(writer, "activity {a}")?;
+    }
+    for d in &model.data_definitions {
+        writeln!(writer, "{d}")?;
+    }
+    for c in &model.constraints {
+        write!(writer, "{c}")?;
+        if c.conditions.is_empty() {
+            // Unary templates: activation and time condition; Binary templates: additionally correlation condition
+            for _ in 0..=c.template.arity() {
+                write!(writer, " |")?;
+            }
+        } else {
+            for cond in &c.conditions {
+                write!(writer, " |{cond}")?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+///
+/// Export a [`DeclareModel`] in the `.decl` format to a String
+///
+pub fn export_decl_to_string(model: &DeclareModel) -> String {
+    let mut out = Vec::new();
+    export_decl(model, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+///
+/// Export a [`DeclareModel`] to a `.decl` file (specified through path)
+///
+/// Also consider using [`DeclareModel::export_decl`] for convenience.
+pub fn export_decl_path<P: AsRef<std::path::Path>>(
+    model: &DeclareModel,
+    path: P,
+) -> Result<(), std::io::Error> {
+    export_decl(model, BufWriter::new(File::create(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::declare::{
+        declare_model::{DeclareConstraint, DeclareModel, DeclareTemplate},
+        import_decl::import_decl_str,
+    };
+
+    use super::export_decl_to_string;
+
+    #[test]
+    fn test_decl_export_roundtrip() {
+        let mut model = DeclareModel::new();
+        model.add_constraint(DeclareConstraint::unary(DeclareTemplate::Init, "a"));
+        model.add_constraint(DeclareConstraint::binary(
+            DeclareTemplate::NotCoExistence,
+            "a",
+            "b c",
+        ));
+        let mut with_condition = DeclareConstraint::binary(DeclareTemplate::Response, "a", "d");
+        with_condition.conditions = vec!["A.x > 2".to_string(), String::new(), String::new()];
+        model.add_constraint(with_condition);
+        model.data_definitions.push("bind a: x".to_string());
+
+        let exported = export_decl_to_string(&model);
+        assert!(exported.contains("Init[a] | |\n"));
+        assert!(exported.contains("Not Co-Existence[a, b c] | | |\n"));
+        assert!(exported.contains("Response[a, d] |A.x > 2 | |\n"));
+        assert_eq!(import_decl_str(&exported).unwrap(), model);
+    }
+}
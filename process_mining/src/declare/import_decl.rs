@@ -0,0 +1,171 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use super::declare_model::{DeclareConstraint, DeclareModel, DeclareTemplate};
+
+#[derive(Debug, Clone)]
+///
+/// Errors that can occur when importing a `.decl` file
+///
+pub enum DeclParseError {
+    /// IO error
+    IOError(std::rc::Rc<std::io::Error>),
+    /// Line could not be parsed as a constraint (line number and line content included)
+    InvalidConstraint(usize, String),
+    /// Unknown Declare template name (line number and template name included)
+    UnknownTemplate(usize, String),
+    /// Number of activity parameters does not match the template (line number and constraint included)
+    InvalidParameterCount(usize, String),
+}
+
+impl std::fmt::Display for DeclParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse Declare model: {:?}", self)
+    }
+}
+
+impl std::error::Error for DeclParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeclParseError::IOError(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DeclParseError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IOError(std::rc::Rc::new(e))
+    }
+}
+
+///
+/// Import a Declare model in the `.decl` format (as used by, e.g., `RuM` or `Declare4Py`) from a buffered reader
+///
+/// The format consists of lines of the following kinds:
+///
+/// - Activity declarations (e.g., `activity A`)
+/// - Constraints (e.g., `Response[A, B] | | |`), optionally with data/time conditions separated by `|`
+/// - Data definitions (e.g., `bind A: grade` or `grade: integer between 1 and 5`)
+///
+/// Data definitions and conditions are preserved in the resulting [`DeclareModel`], but not interpreted.
+///
+/// Also see [`import_decl_str`] and [`import_decl_from_path`].
+pub fn import_decl_reader<R: BufRead>(reader: R) -> Result<DeclareModel, DeclParseError> {
+    let mut model = DeclareModel::new();
+    for (line_index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        let line_num = line_index + 1;
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(activity) = line.strip_prefix("activity ") {
+            model.add_activity(activity.trim());
+        } else if line.starts_with("bind ") || !line.contains('[') {
+            model.data_definitions.push(line.to_string());
+        } else {
+            model.add_constraint(parse_constraint(line, line_num)?);
+        }
+    }
+    Ok(model)
+}
+
+fn parse_constraint(line: &str, line_num: usize) -> Result<DeclareConstraint, DeclParseError> {
+    let invalid = || DeclParseError::InvalidConstraint(line_num, line.to_string());
+    let (name, rest) = line.split_once('[').ok_or_else(invalid)?;
+    let (params, conditions) = rest.split_once(']').ok_or_else(invalid)?;
+    let template = DeclareTemplate::from_name(name.trim())
+        .ok_or_else(|| DeclParseError::UnknownTemplate(line_num, name.trim().to_string()))?;
+    let parameters: Vec<String> = params.split(',').map(|p| p.trim().to_string()).collect();
+    if parameters.len() != template.arity() || parameters.iter().any(|p| p.is_empty()) {
+        return Err(DeclParseError::InvalidParameterCount(
+            line_num,
+            line.to_string(),
+        ));
+    }
+    let conditions = conditions.trim();
+    let conditions: Vec<String> = match conditions.strip_prefix('|') {
+        Some(c) => c.split('|').map(|c| c.trim().to_string()).collect(),
+        None if conditions.is_empty() => Vec::new(),
+        None => return Err(invalid()),
+    };
+    Ok(DeclareConstraint {
+        template,
+        parameters,
+        conditions: if conditions.iter().all(|c| c.is_empty()) {
+            Vec::new()
+        } else {
+            conditions
+        },
+    })
+}
+
+///
+/// Import a Declare model in the `.decl` format from a string
+///
+/// See [`import_decl_reader`] for details.
+pub fn import_decl_str(data: &str) -> Result<DeclareModel, DeclParseError> {
+    import_decl_reader(data.as_bytes())
+}
+
+///
+/// Import a Declare model from a `.decl` file
+///
+/// Also consider using [`DeclareModel::import_decl`] for convenience. See [`import_decl_reader`] for details.
+pub fn import_decl_from_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<DeclareModel, DeclParseError> {
+    import_decl_reader(BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const SAMPLE_DECL: &str = "activity Register
+bind Register: amount
+activity Check
+activity Pay
+amount: integer between 0 and 1000
+Init[Register] | |
+Response[Register, Check] |A.amount > 100 | |
+Chain Precedence[Check, Pay] | | |
+Absence2[Pay] | |
+";
+
+    #[test]
+    fn test_decl_import() {
+        let model = import_decl_str(SAMPLE_DECL).unwrap();
+        assert_eq!(model.activities, vec!["Register", "Check", "Pay"]);
+        assert_eq!(model.constraints.len(), 4);
+        assert_eq!(model.data_definitions.len(), 2);
+        assert_eq!(
+            model.constraints[1].conditions,
+            vec!["A.amount > 100".to_string(), String::new(), String::new()]
+        );
+        assert_eq!(
+            model.constraints[2],
+            DeclareConstraint::binary(DeclareTemplate::ChainPrecedence, "Check", "Pay")
+        );
+        assert_eq!(model.constraints[3].template, DeclareTemplate::Absence(2));
+    }
+
+    #[test]
+    fn test_decl_import_errors() {
+        assert!(matches!(
+            import_decl_str("Foo[A, B] | | |"),
+            Err(DeclParseError::UnknownTemplate(1, _))
+        ));
+        assert!(matches!(
+            import_decl_str("activity A\nResponse[A] | |"),
+            Err(DeclParseError::InvalidParameterCount(2, _))
+        ));
+        assert!(matches!(
+            import_decl_str("Response[A, B"),
+            Err(DeclParseError::InvalidConstraint(1, _))
+        ));
+    }
+}
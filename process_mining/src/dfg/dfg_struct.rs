@@ -1,3 +1,4 @@
+use crate::event_log::columnar_log::ColumnarEventLog;
 use crate::event_log::event_log_struct::EventLogClassifier;
 use crate::EventLog;
 use serde::{Deserialize, Serialize};
@@ -50,11 +51,38 @@ impl<'a> DirectlyFollowsGraph<'a> {
     ///
     /// If there is no special classifier to be used, the default (`&EventLogClassifier::default()`) can also simply be passed in
     pub fn create_from_log(event_log: &EventLog, classifier: &EventLogClassifier) -> Self {
+        Self::create_from_activity_traces(
+            event_log
+                .traces
+                .iter()
+                .map(|t| t.events.iter().map(|e| classifier.get_class_identity(e))),
+        )
+    }
+
+    /// Construct a [`DirectlyFollowsGraph`] from a [`ColumnarEventLog`] using the specified [`EventLogClassifier`] to derive the 'activity' names
+    ///
+    /// The activities are read directly from the attribute columns, i.e., no [`crate::event_log::Event`]s are materialized.
+    pub fn create_from_columnar_log(
+        event_log: &ColumnarEventLog,
+        classifier: &EventLogClassifier,
+    ) -> Self {
+        let classified = event_log.classify(classifier);
+        Self::create_from_activity_traces(classified.trace_activities())
+    }
+
+    /// Construct a [`DirectlyFollowsGraph`] from traces given as sequences of activity names
+    ///
+    /// Owned activity names (i.e., [`String`]s) are moved into the graph without copying them.
+    pub fn create_from_activity_traces<T, S>(traces: impl IntoIterator<Item = T>) -> Self
+    where
+        T: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
         let mut result = Self::new();
-        event_log.traces.iter().for_each(|t| {
+        traces.into_iter().for_each(|t| {
             let mut last_event_identity: Option<String> = None;
-            t.events.iter().for_each(|e| {
-                let curr_event_identity = classifier.get_class_identity(e);
+            t.into_iter().for_each(|e| {
+                let curr_event_identity: String = e.into();
                 result.add_activity(curr_event_identity.clone(), 1);
 
                 if let Some(last_ev_id) = last_event_identity.take() {
@@ -63,7 +91,7 @@ impl<'a> DirectlyFollowsGraph<'a> {
                     result.add_start_activity(curr_event_identity.clone());
                 }
 
-                last_event_identity = Some(curr_event_identity.clone());
+                last_event_identity = Some(curr_event_identity);
             });
             if let Some(last_ev_id) = last_event_identity.take() {
                 result.add_end_activity(last_ev_id);
@@ -209,6 +237,16 @@ impl<'a> DirectlyFollowsGraph<'a> {
     pub fn export_svg<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
         super::image_export::export_dfg_image_svg(self, path)
     }
+
+    #[cfg(feature = "svg-export")]
+    /// Render directly-follows graph as an SVG image (without requiring Graphviz)
+    ///
+    /// _Note_: This is an export method for __visualizing__ the directly-follows graph.
+    ///
+    /// Only available with the `svg-export` feature.
+    pub fn to_svg(&self, options: &crate::SVGExportOptions) -> String {
+        crate::visualization::svg_export::dfg_to_svg(self, options)
+    }
 }
 
 #[cfg(test)]
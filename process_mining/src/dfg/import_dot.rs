@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+
+use crate::utils::dot_utils::{parse_number, DOTParseError, FlatDOTGraph};
+
+use super::dfg_struct::DirectlyFollowsGraph;
+
+/// Fill color of start activities (as used by [`export_dfg_to_dot_graph`](super::image_export::export_dfg_to_dot_graph))
+const START_COLOR: &str = "#4B9969";
+/// Fill color of end activities (as used by [`export_dfg_to_dot_graph`](super::image_export::export_dfg_to_dot_graph))
+const END_COLOR: &str = "#D4001F";
+
+///
+/// Import a [`DirectlyFollowsGraph`] from DOT source
+///
+/// Follows the conventions of [`export_dfg_to_dot_graph`](super::image_export::export_dfg_to_dot_graph),
+/// such that exported directly-follows graphs can be imported again:
+///
+/// - Every node is an activity, identified by its node ID.
+///   If the label of the node has the form `<activity>: <frequency>`, the frequency of the activity is taken from it;
+///   otherwise, the frequency is 1.
+/// - Activities with fill color `#4B9969` are start activities, activities with fill color `#D4001F` are end activities
+///   (activities which are both start and end activities have the fill color `#4B9969:#D4001F`).
+/// - Edges are directly-follows relations, with an optional numeric label as frequency (otherwise, the frequency is 1).
+///
+/// __Requires the `graphviz-export` feature to be enabled__ (but no Graphviz installation)
+///
+pub fn import_dfg_from_dot(dot: &str) -> Result<DirectlyFollowsGraph<'static>, DOTParseError> {
+    let graph = FlatDOTGraph::parse(dot)?;
+    let mut dfg = DirectlyFollowsGraph::new();
+    for node in &graph.nodes {
+        let frequency = match node
+            .attributes
+            .get("label")
+            .and_then(|l| l.strip_prefix(node.id.as_str()))
+            .and_then(|l| l.strip_prefix(": "))
+        {
+            Some(frequency) => parse_number(frequency)?,
+            None => 1,
+        };
+        dfg.add_activity(node.id.clone(), frequency);
+        if let Some(fill_color) = node.attributes.get("fillcolor") {
+            for color in fill_color.split(':') {
+                if color.eq_ignore_ascii_case(START_COLOR) {
+                    dfg.add_start_activity(node.id.clone());
+                } else if color.eq_ignore_ascii_case(END_COLOR) {
+                    dfg.add_end_activity(node.id.clone());
+                }
+            }
+        }
+    }
+    for edge in &graph.edges {
+        let frequency = match edge.attributes.get("label") {
+            Some(frequency) => parse_number(frequency)?,
+            None => 1,
+        };
+        dfg.add_df_relation(
+            Cow::Owned(edge.from.clone()),
+            Cow::Owned(edge.to.clone()),
+            frequency,
+        );
+    }
+    Ok(dfg)
+}
+
+///
+/// Import a [`DirectlyFollowsGraph`] from a DOT file at the given filepath
+///
+/// See [`import_dfg_from_dot`] for the supported DOT conventions.
+///
+pub fn import_dfg_from_dot_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<DirectlyFollowsGraph<'static>, DOTParseError> {
+    import_dfg_from_dot(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dfg::image_export::{export_dfg_to_dot_graph, graph_to_dot};
+
+    use super::*;
+
+    #[test]
+    fn dot_round_trip() {
+        let mut dfg = DirectlyFollowsGraph::new();
+        dfg.add_activity("Register: Online".into(), 3);
+        dfg.add_activity("Pay".into(), 2);
+        dfg.add_activity("Ship".into(), 1);
+        dfg.add_start_activity("Register: Online".into());
+        dfg.add_start_activity("Pay".into());
+        dfg.add_end_activity("Pay".into());
+        dfg.add_end_activity("Ship".into());
+        dfg.add_df_relation("Register: Online".into(), "Pay".into(), 2);
+        dfg.add_df_relation("Pay".into(), "Ship".into(), 1);
+        let dot = graph_to_dot(&export_dfg_to_dot_graph(&dfg, None));
+        let imported = import_dfg_from_dot(&dot).unwrap();
+        assert_eq!(imported.activities, dfg.activities);
+        assert_eq!(imported.start_activities, dfg.start_activities);
+        assert_eq!(imported.end_activities, dfg.end_activities);
+        assert_eq!(
+            imported.directly_follows_relations,
+            dfg.directly_follows_relations
+        );
+    }
+
+    #[test]
+    fn hand_written_dot() {
+        let dfg = import_dfg_from_dot(r#"digraph { a -> b [label=5]; b -> a; a -> c }"#).unwrap();
+        assert_eq!(dfg.activities.len(), 3);
+        assert_eq!(dfg.activities["a"], 1);
+        assert_eq!(dfg.directly_follows_relations[&("a".into(), "b".into())], 5);
+        assert_eq!(dfg.directly_follows_relations[&("b".into(), "a".into())], 1);
+        assert!(dfg.start_activities.is_empty());
+        assert!(matches!(
+            import_dfg_from_dot(r#"digraph { a -> b [label="often"] }"#),
+            Err(DOTParseError::InvalidNumber(n)) if n == "often"
+        ));
+    }
+}
@@ -0,0 +1,782 @@
+use std::{borrow::Borrow, collections::HashMap, ops::Range};
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    activity_projection::EventLogActivityProjection,
+    constants::ACTIVITY_NAME,
+    event_log_struct::{EventLogClassifier, EventLogExtension},
+    stream_xes::XESOuterLogData,
+    Attribute, AttributeValue, Attributes, Event, EventLog, Trace,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// Interner for strings, mapping each distinct string to a `u32` index
+///
+pub struct StringInterner {
+    /// All interned strings (the index of a string is its `u32` representation)
+    strings: Vec<String>,
+    /// Mapping of strings to their `u32` representation
+    indices: HashMap<String, u32>,
+}
+
+impl StringInterner {
+    /// Get the index of `s`, interning it if it was not interned before
+    pub fn get_or_intern(&mut self, s: &str) -> u32 {
+        if let Some(i) = self.indices.get(s) {
+            return *i;
+        }
+        let i = self.strings.len() as u32;
+        self.strings.push(s.to_string());
+        self.indices.insert(s.to_string(), i);
+        i
+    }
+
+    /// Get the index of `s`, if it was interned before
+    pub fn get(&self, s: &str) -> Option<u32> {
+        self.indices.get(s).copied()
+    }
+
+    /// Get the string with the given index
+    ///
+    /// Panics if the index is out of bounds
+    pub fn resolve(&self, i: u32) -> &str {
+        &self.strings[i as usize]
+    }
+
+    /// Number of interned strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Check if no strings are interned
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Column of attribute values (one entry per row, i.e., per trace or event; `None` if the attribute is not present)
+///
+/// Attributes with nested attributes, lists, containers and invalid values, as well as
+/// columns containing values of different types, are stored as [`AttributeColumn::Other`].
+///
+pub enum AttributeColumn {
+    /// String values (interned, see [`ColumnarEventLog::strings`])
+    String(Vec<Option<u32>>),
+    /// `DateTime` values
+    Date(Vec<Option<DateTime<FixedOffset>>>),
+    /// Integer values
+    Int(Vec<Option<i64>>),
+    /// Float values
+    Float(Vec<Option<f64>>),
+    /// Boolean values
+    Boolean(Vec<Option<bool>>),
+    /// IDs (UUIDs)
+    ID(Vec<Option<Uuid>>),
+    /// Any other attributes
+    Other(Vec<Option<Attribute>>),
+}
+
+impl AttributeColumn {
+    /// Create a new column, matching the type of `attribute`, with `len` missing values
+    fn new_for(attribute: &Attribute, len: usize) -> Self {
+        if attribute.own_attributes.is_some() {
+            return Self::Other(vec![None; len]);
+        }
+        match attribute.value {
+            AttributeValue::String(_) => Self::String(vec![None; len]),
+            AttributeValue::Date(_) => Self::Date(vec![None; len]),
+            AttributeValue::Int(_) => Self::Int(vec![None; len]),
+            AttributeValue::Float(_) => Self::Float(vec![None; len]),
+            AttributeValue::Boolean(_) => Self::Boolean(vec![None; len]),
+            AttributeValue::ID(_) => Self::ID(vec![None; len]),
+            _ => Self::Other(vec![None; len]),
+        }
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        match self {
+            AttributeColumn::String(v) => v.len(),
+            AttributeColumn::Date(v) => v.len(),
+            AttributeColumn::Int(v) => v.len(),
+            AttributeColumn::Float(v) => v.len(),
+            AttributeColumn::Boolean(v) => v.len(),
+            AttributeColumn::ID(v) => v.len(),
+            AttributeColumn::Other(v) => v.len(),
+        }
+    }
+
+    /// Check if the column has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push_none(&mut self) {
+        match self {
+            AttributeColumn::String(v) => v.push(None),
+            AttributeColumn::Date(v) => v.push(None),
+            AttributeColumn::Int(v) => v.push(None),
+            AttributeColumn::Float(v) => v.push(None),
+            AttributeColumn::Boolean(v) => v.push(None),
+            AttributeColumn::ID(v) => v.push(None),
+            AttributeColumn::Other(v) => v.push(None),
+        }
+    }
+
+    /// Push the value of `attribute`, returning `false` (without modifying the column) if its type does not match
+    fn try_push(&mut self, attribute: &Attribute, strings: &mut StringInterner) -> bool {
+        if let AttributeColumn::Other(v) = self {
+            v.push(Some(attribute.clone()));
+            return true;
+        }
+        if attribute.own_attributes.is_some() {
+            return false;
+        }
+        match (self, &attribute.value) {
+            (AttributeColumn::String(v), AttributeValue::String(s)) => {
+                v.push(Some(strings.get_or_intern(s)))
+            }
+            (AttributeColumn::Date(v), AttributeValue::Date(d)) => v.push(Some(*d)),
+            (AttributeColumn::Int(v), AttributeValue::Int(i)) => v.push(Some(*i)),
+            (AttributeColumn::Float(v), AttributeValue::Float(f)) => v.push(Some(*f)),
+            (AttributeColumn::Boolean(v), AttributeValue::Boolean(b)) => v.push(Some(*b)),
+            (AttributeColumn::ID(v), AttributeValue::ID(id)) => v.push(Some(*id)),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Get the attribute value in the given row
+    pub fn get(&self, row: usize, strings: &StringInterner) -> Option<AttributeValue> {
+        match self {
+            AttributeColumn::String(v) => {
+                v[row].map(|s| AttributeValue::String(strings.resolve(s).to_string()))
+            }
+            AttributeColumn::Date(v) => v[row].map(AttributeValue::Date),
+            AttributeColumn::Int(v) => v[row].map(AttributeValue::Int),
+            AttributeColumn::Float(v) => v[row].map(AttributeValue::Float),
+            AttributeColumn::Boolean(v) => v[row].map(AttributeValue::Boolean),
+            AttributeColumn::ID(v) => v[row].map(AttributeValue::ID),
+            AttributeColumn::Other(v) => v[row].as_ref().map(|a| a.value.clone()),
+        }
+    }
+
+    fn get_attribute(&self, row: usize, key: &str, strings: &StringInterner) -> Option<Attribute> {
+        match self {
+            AttributeColumn::Other(v) => v[row].clone(),
+            _ => Some(Attribute::new(key.to_string(), self.get(row, strings)?)),
+        }
+    }
+
+    /// Convert to an [`AttributeColumn::Other`] column
+    fn into_other(self, key: &str, strings: &StringInterner) -> Self {
+        if let AttributeColumn::Other(_) = self {
+            return self;
+        }
+        AttributeColumn::Other(
+            (0..self.len())
+                .map(|row| self.get_attribute(row, key, strings))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// Table of attribute columns (one row per trace or event)
+///
+pub struct AttributeTable {
+    /// Columns with the interned key of the attribute (see [`ColumnarEventLog::keys`])
+    columns: Vec<(u32, AttributeColumn)>,
+    /// Mapping of interned attribute keys to the index of their column
+    column_of_key: HashMap<u32, usize>,
+    /// Number of rows
+    len: usize,
+}
+
+impl AttributeTable {
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// All columns with the interned key of the attribute (see [`ColumnarEventLog::keys`])
+    pub fn columns(&self) -> &[(u32, AttributeColumn)] {
+        &self.columns
+    }
+
+    /// Get the column of the attribute with the given interned key
+    pub fn column(&self, key: u32) -> Option<&AttributeColumn> {
+        self.column_of_key.get(&key).map(|c| &self.columns[*c].1)
+    }
+
+    /// Append a row containing the given attributes
+    fn push_row(
+        &mut self,
+        attributes: &[Attribute],
+        keys: &mut StringInterner,
+        strings: &mut StringInterner,
+    ) {
+        let row = self.len;
+        for a in attributes {
+            let key = keys.get_or_intern(&a.key);
+            let c = match self.column_of_key.get(&key) {
+                Some(c) => *c,
+                None => {
+                    self.columns.push((key, AttributeColumn::new_for(a, row)));
+                    self.column_of_key.insert(key, self.columns.len() - 1);
+                    self.columns.len() - 1
+                }
+            };
+            let column = &mut self.columns[c].1;
+            if column.len() > row {
+                // Duplicate key in the same row: Only the first attribute is kept
+                continue;
+            }
+            if !column.try_push(a, strings) {
+                let other = std::mem::replace(column, AttributeColumn::Other(Vec::new()));
+                *column = other.into_other(&a.key, strings);
+                column.try_push(a, strings);
+            }
+        }
+        self.len += 1;
+        for (_, column) in self.columns.iter_mut() {
+            if column.len() < self.len {
+                column.push_none();
+            }
+        }
+    }
+
+    fn get(&self, row: usize, key: u32, strings: &StringInterner) -> Option<AttributeValue> {
+        self.column(key)?.get(row, strings)
+    }
+
+    fn get_str<'a>(&self, row: usize, key: u32, strings: &'a StringInterner) -> Option<&'a str> {
+        match self.column(key)? {
+            AttributeColumn::String(v) => v[row].map(|s| strings.resolve(s)),
+            _ => None,
+        }
+    }
+
+    fn row_attributes(
+        &self,
+        row: usize,
+        keys: &StringInterner,
+        strings: &StringInterner,
+    ) -> Attributes {
+        self.columns
+            .iter()
+            .filter_map(|(key, column)| column.get_attribute(row, keys.resolve(*key), strings))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Columnar event log with interned attribute keys and string values
+///
+/// Instead of storing every [`Event`] as list of [`Attribute`]s, the event (and trace) attributes are stored in
+/// typed columns (see [`AttributeColumn`]).
+/// The events of all traces are stored consecutively; the events of a trace are given by [`ColumnarEventLog::trace_offsets`].
+/// This greatly reduces memory usage for large event logs.
+///
+/// Can be converted from and to an [`EventLog`] and constructed directly from a trace stream (e.g., from [`super::stream_xes::stream_xes_from_path`])
+/// using [`ColumnarEventLog::from_trace_stream`].
+///
+/// _Note_: The order of attributes of an event (or trace) is not preserved
+/// and only the first of multiple attributes with the same key is kept.
+///
+pub struct ColumnarEventLog {
+    /// Top-level attributes
+    pub attributes: Attributes,
+    /// XES Extensions
+    pub extensions: Option<Vec<EventLogExtension>>,
+    /// XES Event classifiers
+    pub classifiers: Option<Vec<EventLogClassifier>>,
+    /// Global trace attributes
+    pub global_trace_attrs: Option<Attributes>,
+    /// Global event attributes
+    pub global_event_attrs: Option<Attributes>,
+    /// Interned attribute keys
+    pub keys: StringInterner,
+    /// Interned string attribute values (e.g., activity names)
+    pub strings: StringInterner,
+    /// Offsets of the traces into the event rows
+    ///
+    /// The events of trace `i` are the event rows `trace_offsets[i]..trace_offsets[i + 1]`
+    pub trace_offsets: Vec<usize>,
+    /// Trace attributes (one row per trace)
+    pub trace_attributes: AttributeTable,
+    /// Event attributes (one row per event)
+    pub event_attributes: AttributeTable,
+}
+
+impl Default for ColumnarEventLog {
+    fn default() -> Self {
+        Self {
+            attributes: Attributes::new(),
+            extensions: None,
+            classifiers: None,
+            global_trace_attrs: None,
+            global_event_attrs: None,
+            keys: StringInterner::default(),
+            strings: StringInterner::default(),
+            trace_offsets: vec![0],
+            trace_attributes: AttributeTable::default(),
+            event_attributes: AttributeTable::default(),
+        }
+    }
+}
+
+impl ColumnarEventLog {
+    /// Initializes a new columnar event log with no attributes and no traces
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Construct a [`ColumnarEventLog`] from a trace stream and [`XESOuterLogData`]
+    ///
+    /// Traces are added to the columns one by one, i.e., the stream is never fully materialized as [`EventLog`].
+    ///
+    pub fn from_trace_stream<T: Borrow<Trace>, I: IntoIterator<Item = T>>(
+        traces: I,
+        log_data: XESOuterLogData,
+    ) -> Self {
+        let mut ret = Self {
+            attributes: log_data.log_attributes,
+            extensions: Some(log_data.extensions),
+            classifiers: Some(log_data.classifiers),
+            global_trace_attrs: (!log_data.global_trace_attrs.is_empty())
+                .then_some(log_data.global_trace_attrs),
+            global_event_attrs: (!log_data.global_event_attrs.is_empty())
+                .then_some(log_data.global_event_attrs),
+            ..Self::new()
+        };
+        for t in traces {
+            ret.push_trace(t.borrow());
+        }
+        ret
+    }
+
+    /// Append a [`Trace`]
+    pub fn push_trace(&mut self, trace: &Trace) {
+        self.trace_attributes
+            .push_row(&trace.attributes, &mut self.keys, &mut self.strings);
+        for e in &trace.events {
+            self.event_attributes
+                .push_row(&e.attributes, &mut self.keys, &mut self.strings);
+        }
+        self.trace_offsets.push(self.event_attributes.len());
+    }
+
+    /// Number of traces
+    pub fn num_traces(&self) -> usize {
+        self.trace_offsets.len().saturating_sub(1)
+    }
+
+    /// Number of events (in all traces)
+    pub fn num_events(&self) -> usize {
+        self.event_attributes.len()
+    }
+
+    /// Range of the event rows of the trace with the given index
+    pub fn trace_event_range(&self, trace_index: usize) -> Range<usize> {
+        self.trace_offsets[trace_index]..self.trace_offsets[trace_index + 1]
+    }
+
+    /// Iterate over views of all traces
+    pub fn traces(&self) -> impl ExactSizeIterator<Item = ColumnarTrace<'_>> {
+        (0..self.num_traces()).map(|index| ColumnarTrace { log: self, index })
+    }
+
+    /// Materialize the [`Trace`] with the given index
+    pub fn to_trace(&self, trace_index: usize) -> Trace {
+        Trace {
+            attributes: self.trace_attributes.row_attributes(
+                trace_index,
+                &self.keys,
+                &self.strings,
+            ),
+            events: self
+                .trace_event_range(trace_index)
+                .map(|row| Event {
+                    attributes: self.event_attributes.row_attributes(
+                        row,
+                        &self.keys,
+                        &self.strings,
+                    ),
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert to an [`EventLog`]
+    pub fn to_event_log(&self) -> EventLog {
+        EventLog {
+            attributes: self.attributes.clone(),
+            traces: (0..self.num_traces()).map(|t| self.to_trace(t)).collect(),
+            extensions: self.extensions.clone(),
+            classifiers: self.classifiers.clone(),
+            global_trace_attrs: self.global_trace_attrs.clone(),
+            global_event_attrs: self.global_event_attrs.clone(),
+        }
+    }
+
+    ///
+    /// Classify all events using the given [`EventLogClassifier`]
+    ///
+    /// As for [`EventLogClassifier::get_class_identity`], missing attributes and attributes with a type different than
+    /// [`AttributeValue::String`] are represented by an empty String.
+    ///
+    pub fn classify(&self, classifier: &EventLogClassifier) -> ClassifiedColumnarLog<'_> {
+        let key_columns: Vec<Option<&AttributeColumn>> = classifier
+            .keys
+            .iter()
+            .map(|k| self.event_attributes.column(self.keys.get(k)?))
+            .collect();
+        let string_of = |column: &Option<&AttributeColumn>, row: usize| match column {
+            Some(AttributeColumn::String(v)) => v[row],
+            _ => None,
+        };
+        let mut classes = StringInterner::default();
+        let event_classes = (0..self.num_events())
+            .map(|row| match key_columns.as_slice() {
+                [column] => classes
+                    .get_or_intern(string_of(column, row).map_or("", |s| self.strings.resolve(s))),
+                _ => {
+                    let identity: Vec<&str> = key_columns
+                        .iter()
+                        .map(|c| string_of(c, row).map_or("", |s| self.strings.resolve(s)))
+                        .collect();
+                    classes.get_or_intern(&identity.join(EventLogClassifier::DELIMITER))
+                }
+            })
+            .collect();
+        ClassifiedColumnarLog {
+            classes,
+            event_classes,
+            trace_offsets: &self.trace_offsets,
+        }
+    }
+}
+
+impl From<&EventLog> for ColumnarEventLog {
+    fn from(log: &EventLog) -> Self {
+        let mut ret = Self {
+            attributes: log.attributes.clone(),
+            extensions: log.extensions.clone(),
+            classifiers: log.classifiers.clone(),
+            global_trace_attrs: log.global_trace_attrs.clone(),
+            global_event_attrs: log.global_event_attrs.clone(),
+            ..Self::new()
+        };
+        for t in &log.traces {
+            ret.push_trace(t);
+        }
+        ret
+    }
+}
+
+impl From<&ColumnarEventLog> for EventLog {
+    fn from(log: &ColumnarEventLog) -> Self {
+        log.to_event_log()
+    }
+}
+
+impl From<&ColumnarEventLog> for EventLogActivityProjection {
+    fn from(log: &ColumnarEventLog) -> Self {
+        let column = log
+            .keys
+            .get(ACTIVITY_NAME)
+            .and_then(|k| log.event_attributes.column(k));
+        let mut activities: Vec<String> = Vec::new();
+        let mut act_to_index: HashMap<String, usize> = HashMap::new();
+        // Activity index of interned strings (`None` for events without string activity)
+        let mut act_of_string: HashMap<Option<u32>, usize> = HashMap::new();
+        let mut traces: HashMap<Vec<usize>, u64> = HashMap::new();
+        for t in 0..log.num_traces() {
+            let trace: Vec<usize> = log
+                .trace_event_range(t)
+                .map(|row| {
+                    let s = match column {
+                        Some(AttributeColumn::String(v)) => v[row],
+                        _ => None,
+                    };
+                    *act_of_string.entry(s).or_insert_with(|| {
+                        let act = s.map_or("No Activity", |s| log.strings.resolve(s));
+                        *act_to_index.entry(act.to_string()).or_insert_with(|| {
+                            activities.push(act.to_string());
+                            activities.len() - 1
+                        })
+                    })
+                })
+                .collect();
+            *traces.entry(trace).or_insert(0) += 1;
+        }
+        EventLogActivityProjection {
+            activities,
+            act_to_index,
+            traces: traces.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+///
+/// View of a trace in a [`ColumnarEventLog`]
+///
+pub struct ColumnarTrace<'a> {
+    /// The columnar log
+    pub log: &'a ColumnarEventLog,
+    /// Index of the trace
+    pub index: usize,
+}
+
+impl<'a> ColumnarTrace<'a> {
+    /// Number of events in the trace
+    pub fn len(&self) -> usize {
+        self.log.trace_event_range(self.index).len()
+    }
+
+    /// Check if the trace has no events
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a trace attribute value by key
+    pub fn get(&self, key: &str) -> Option<AttributeValue> {
+        self.log
+            .trace_attributes
+            .get(self.index, self.log.keys.get(key)?, &self.log.strings)
+    }
+
+    /// Get a string trace attribute value by key (without allocating)
+    pub fn get_str(&self, key: &str) -> Option<&'a str> {
+        self.log
+            .trace_attributes
+            .get_str(self.index, self.log.keys.get(key)?, &self.log.strings)
+    }
+
+    /// Iterate over views of all events of the trace
+    pub fn events(&self) -> impl ExactSizeIterator<Item = ColumnarEvent<'a>> {
+        let log = self.log;
+        log.trace_event_range(self.index)
+            .map(move |row| ColumnarEvent { log, row })
+    }
+
+    /// Materialize as [`Trace`]
+    pub fn to_trace(&self) -> Trace {
+        self.log.to_trace(self.index)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+///
+/// View of an event in a [`ColumnarEventLog`]
+///
+pub struct ColumnarEvent<'a> {
+    /// The columnar log
+    pub log: &'a ColumnarEventLog,
+    /// Row of the event (i.e., index of the event among all events of the log)
+    pub row: usize,
+}
+
+impl<'a> ColumnarEvent<'a> {
+    /// Get an event attribute value by key
+    pub fn get(&self, key: &str) -> Option<AttributeValue> {
+        self.log
+            .event_attributes
+            .get(self.row, self.log.keys.get(key)?, &self.log.strings)
+    }
+
+    /// Get a string event attribute value by key (without allocating)
+    pub fn get_str(&self, key: &str) -> Option<&'a str> {
+        self.log
+            .event_attributes
+            .get_str(self.row, self.log.keys.get(key)?, &self.log.strings)
+    }
+
+    /// Materialize as [`Event`]
+    pub fn to_event(&self) -> Event {
+        Event {
+            attributes: self.log.event_attributes.row_attributes(
+                self.row,
+                &self.log.keys,
+                &self.log.strings,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+///
+/// Event classes of all events of a [`ColumnarEventLog`] (see [`ColumnarEventLog::classify`])
+///
+pub struct ClassifiedColumnarLog<'a> {
+    /// Interned event classes
+    pub classes: StringInterner,
+    /// Interned event class of every event row
+    pub event_classes: Vec<u32>,
+    trace_offsets: &'a [usize],
+}
+
+impl ClassifiedColumnarLog<'_> {
+    /// Iterate over the (interned) event classes of all traces
+    pub fn traces(&self) -> impl ExactSizeIterator<Item = &[u32]> {
+        self.trace_offsets
+            .windows(2)
+            .map(|w| &self.event_classes[w[0]..w[1]])
+    }
+
+    /// Iterate over the event classes of all traces
+    pub fn trace_activities(&self) -> impl Iterator<Item = impl Iterator<Item = &str>> {
+        self.traces()
+            .map(|t| t.iter().map(|c| self.classes.resolve(*c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        dfg::DirectlyFollowsGraph, event_log::XESEditableAttribute, stream_xes_slice,
+        XESImportOptions,
+    };
+
+    use super::*;
+
+    const XES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<log xes.version="2.0" xmlns="http://www.xes-standard.org/">
+    <string key="concept:name" value="Test log"/>
+    <trace>
+        <string key="concept:name" value="1"/>
+        <event>
+            <string key="concept:name" value="a"/>
+            <int key="cost" value="3"/>
+        </event>
+        <event>
+            <string key="concept:name" value="b"/>
+            <string key="cost" value="high"/>
+            <list key="items">
+                <string key="x" value="y"/>
+            </list>
+        </event>
+    </trace>
+    <trace>
+        <string key="concept:name" value="2"/>
+        <event>
+            <string key="concept:name" value="a"/>
+        </event>
+        <event>
+            <date key="time:timestamp" value="2024-01-01T00:00:00.000+00:00"/>
+        </event>
+    </trace>
+</log>
+"#;
+
+    fn sorted(mut attributes: Attributes) -> Attributes {
+        attributes.sort_by(|a, b| a.key.cmp(&b.key));
+        attributes
+    }
+
+    #[test]
+    fn columnar_round_trip() {
+        let (stream, log_data) = stream_xes_slice(XES.as_bytes(), XESImportOptions::default())
+            .map(|(mut s, d)| ((&mut s).collect::<Vec<_>>(), d))
+            .unwrap();
+        let log = EventLog::from_traces_and_log_data(stream.clone(), log_data.clone());
+        let columnar = ColumnarEventLog::from_trace_stream(stream, log_data);
+        assert_eq!(columnar.num_traces(), 2);
+        assert_eq!(columnar.num_events(), 4);
+        // Mixed types (int and string) fall back to the `Other` column type
+        let cost = columnar.keys.get("cost").unwrap();
+        assert!(matches!(
+            columnar.event_attributes.column(cost),
+            Some(AttributeColumn::Other(_))
+        ));
+
+        let back = columnar.to_event_log();
+        assert_eq!(back.attributes, log.attributes);
+        for (t1, t2) in back.traces.iter().zip(log.traces.iter()) {
+            assert_eq!(sorted(t1.attributes.clone()), sorted(t2.attributes.clone()));
+            assert_eq!(t1.events.len(), t2.events.len());
+            for (e1, e2) in t1.events.iter().zip(t2.events.iter()) {
+                assert_eq!(sorted(e1.attributes.clone()), sorted(e2.attributes.clone()));
+            }
+        }
+
+        let trace = columnar.traces().next().unwrap();
+        assert_eq!(trace.get_str("concept:name"), Some("1"));
+        let events: Vec<_> = trace.events().collect();
+        assert_eq!(events[1].get_str("concept:name"), Some("b"));
+        assert_eq!(events[0].get("cost"), Some(AttributeValue::Int(3)));
+        assert!(events[0].get("items").is_none());
+        assert_eq!(events[1].to_event().attributes.len(), 3);
+    }
+
+    #[test]
+    fn columnar_dfg_and_projection() {
+        let mut log = EventLog::new();
+        for acts in [
+            vec!["a", "b", "c"],
+            vec!["a", "c"],
+            vec!["a", "b", "c"],
+            vec![],
+        ] {
+            let mut trace = Trace::new();
+            trace.events = acts
+                .into_iter()
+                .map(|a| Event::new(a.to_string()))
+                .collect();
+            log.traces.push(trace);
+        }
+        log.traces[1].events[1].attributes.add_to_attributes(
+            "org:resource".to_string(),
+            AttributeValue::String("r".to_string()),
+        );
+        let columnar = ColumnarEventLog::from(&log);
+        let classifier = EventLogClassifier::default();
+
+        let dfg = DirectlyFollowsGraph::create_from_log(&log, &classifier);
+        let columnar_dfg = DirectlyFollowsGraph::create_from_columnar_log(&columnar, &classifier);
+        assert_eq!(dfg.activities, columnar_dfg.activities);
+        assert_eq!(
+            dfg.directly_follows_relations,
+            columnar_dfg.directly_follows_relations
+        );
+        assert_eq!(dfg.start_activities, columnar_dfg.start_activities);
+        assert_eq!(dfg.end_activities, columnar_dfg.end_activities);
+
+        let multi_key = EventLogClassifier {
+            name: "Activity and resource".to_string(),
+            keys: vec![ACTIVITY_NAME.to_string(), "org:resource".to_string()],
+        };
+        assert_eq!(
+            DirectlyFollowsGraph::create_from_log(&log, &multi_key).activities,
+            DirectlyFollowsGraph::create_from_columnar_log(&columnar, &multi_key).activities
+        );
+
+        let proj = EventLogActivityProjection::from(&log);
+        let columnar_proj = EventLogActivityProjection::from(&columnar);
+        let named_traces = |p: &EventLogActivityProjection| -> HashSet<(Vec<String>, u64)> {
+            p.traces
+                .iter()
+                .map(|(t, w)| (t.iter().map(|a| p.activities[*a].clone()).collect(), *w))
+                .collect()
+        };
+        assert_eq!(named_traces(&proj), named_traces(&columnar_proj));
+    }
+}
@@ -19,3 +19,17 @@ pub const TRACE_ID_NAME: &str = "concept:name";
 ///
 /// Primarily used only for interoperability with `PM4Py`
 pub const PREFIXED_TRACE_ID_NAME: &str = "case:concept:name";
+/// Common identifying field for the resource executing an event (from the org XES extension)
+///
+/// __Usage Generally Discouraged__: _Instead, try to use event log classifiers or extension information present in the event log itself_
+///
+/// See also [`ACTIVITY_NAME`]
+pub const RESOURCE_NAME: &str = "org:resource";
+/// Common field for the lifecycle transition of an event (from the lifecycle XES extension)
+///
+/// See also [`ACTIVITY_NAME`]
+pub const LIFECYCLE_TRANSITION_NAME: &str = "lifecycle:transition";
+/// Common field for the timestamp of an event (from the time XES extension)
+///
+/// See also [`ACTIVITY_NAME`]
+pub const TIMESTAMP_NAME: &str = "time:timestamp";
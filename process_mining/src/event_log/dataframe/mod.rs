@@ -1,4 +1,7 @@
-use std::{collections::HashSet, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
 
 use crate::{
     event_log::{Attribute, AttributeValue, XESEditableAttribute},
@@ -10,47 +13,184 @@ use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use super::{constants::PREFIXED_TRACE_ID_NAME, Attributes, Event, Trace};
 
+/// Apache Parquet and Arrow IPC import/export of [`EventLog`]s (incl. streaming Parquet export of traces)
+pub mod parquet_ipc;
+
 ///
 /// Prefix to attribute keys for trace-level attributes (e.g., when "flattening" the log to a [`DataFrame`])
 ///
 pub const TRACE_PREFIX: &str = "case:";
 
 ///
-/// Convert a attribute ([Attribute]) to an [`AnyValue`]
+/// Suffix to attribute keys for the columns holding the meta-attributes of an attribute (see [`convert_log_to_dataframe`])
+///
+pub const META_ATTRIBUTES_SUFFIX: &str = "@meta";
+
+///
+/// Name of the struct field holding the key of a list entry (see [`attribute_value_dtype`])
+///
+const LIST_KEY_FIELD: &str = "key";
+
+///
+/// Name of the struct field holding the value of a list entry (see [`attribute_value_dtype`])
+///
+const LIST_VALUE_FIELD: &str = "value";
+
+///
+/// Get the [`DataType`] used for representing an attribute value ([`AttributeValue`]) in a `DataFrame`
+///
+/// Lists ([`AttributeValue::List`]) are represented as `List` columns of `Struct`s with a [`LIST_KEY_FIELD`] and [`LIST_VALUE_FIELD`] field
+/// (as list entries have a key, which might also occur multiple times).
+/// Containers ([`AttributeValue::Container`]) are represented as `Struct` columns with one field per child attribute key.
+///
+/// `conflict` is set to `true` if nested values of different types had to be unified (see [`unify_dtypes`])
+///
+fn attribute_value_dtype(from: &AttributeValue, conflict: &mut bool) -> DataType {
+    match from {
+        AttributeValue::String(_) | AttributeValue::ID(_) => DataType::String,
+        AttributeValue::Date(_) => DataType::Datetime(TimeUnit::Nanoseconds, None),
+        AttributeValue::Int(_) => DataType::Int64,
+        AttributeValue::Float(_) => DataType::Float64,
+        AttributeValue::Boolean(_) => DataType::Boolean,
+        AttributeValue::List(l) => {
+            let value_dtype = l.iter().fold(DataType::Null, |dtype, a| {
+                let value_dtype = attribute_value_dtype(&a.value, conflict);
+                unify_dtypes(dtype, value_dtype, conflict)
+            });
+            DataType::List(Box::new(DataType::Struct(vec![
+                Field::new(LIST_KEY_FIELD.into(), DataType::String),
+                Field::new(LIST_VALUE_FIELD.into(), value_dtype),
+            ])))
+        }
+        AttributeValue::Container(c) => {
+            let mut fields = Vec::new();
+            for a in c {
+                let dtype = attribute_value_dtype(&a.value, conflict);
+                merge_field(&mut fields, &a.key, dtype, conflict);
+            }
+            DataType::Struct(fields)
+        }
+        AttributeValue::None() => DataType::Null,
+    }
+}
+
+///
+/// Unify two [`DataType`]s, such that values of both types can be stored in one column
+///
+/// `Int64` and `Float64` are unified to `Float64`, `List`s and `Struct`s are unified recursively (`Struct` fields are merged by name).
+/// All other combinations of different types fall back to `String`.
+/// In both cases, `conflict` is set to `true`.
 ///
-/// Used for converting values and data types to the `DataFrame` equivalent
+fn unify_dtypes(a: DataType, b: DataType, conflict: &mut bool) -> DataType {
+    match (a, b) {
+        (DataType::Null, dtype) | (dtype, DataType::Null) => dtype,
+        (a, b) if a == b => a,
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            *conflict = true;
+            DataType::Float64
+        }
+        (DataType::List(a), DataType::List(b)) => {
+            DataType::List(Box::new(unify_dtypes(*a, *b, conflict)))
+        }
+        (DataType::Struct(mut fields), DataType::Struct(other_fields)) => {
+            for f in other_fields {
+                merge_field(&mut fields, &f.name, f.dtype, conflict);
+            }
+            DataType::Struct(fields)
+        }
+        _ => {
+            *conflict = true;
+            DataType::String
+        }
+    }
+}
+
 ///
-/// The UTC timezone argument is used to correctly convert to `AnyValue::Datetime` with UTC timezone
+/// Add a `Struct` field, unifying its type with an existing field of the same name
 ///
-fn attribute_to_any_value<'a>(from_option: Option<&Attribute>) -> AnyValue<'a> {
-    match from_option {
-        Some(from) => {
-            let x = attribute_value_to_any_value(&from.value);
-            x
+fn merge_field(fields: &mut Vec<Field>, name: &str, dtype: DataType, conflict: &mut bool) {
+    match fields.iter_mut().find(|f| f.name() == name) {
+        Some(f) => {
+            let existing = std::mem::replace(&mut f.dtype, DataType::Null);
+            f.dtype = unify_dtypes(existing, dtype, conflict);
         }
-        None => AnyValue::Null,
+        None => fields.push(Field::new(name.into(), dtype)),
     }
 }
 
 ///
-/// Convert a attribute ([`AttributeValue`]) to an [`AnyValue`]
+/// Convert a attribute ([`AttributeValue`]) to an [`AnyValue`] of the given [`DataType`]
+///
+/// The data type is expected to be obtained using [`attribute_value_dtype`] (and [`unify_dtypes`]) for this value.
+/// Values of a different type are converted to the given data type (i.e., `Int64` to `Float64` or any value to `String`).
+///
+fn attribute_value_to_any_value(
+    from: &AttributeValue,
+    dtype: &DataType,
+) -> Result<AnyValue<'static>, PolarsError> {
+    Ok(match (from, dtype) {
+        (AttributeValue::None(), _) => AnyValue::Null,
+        (AttributeValue::Int(v), DataType::Float64) => AnyValue::Float64(*v as f64),
+        (AttributeValue::List(l), DataType::List(entry_dtype)) => {
+            let DataType::Struct(fields) = entry_dtype.as_ref() else {
+                polars_bail!(SchemaMismatch: "expected struct entries for list attribute, got {}", entry_dtype);
+            };
+            let value_dtype = fields
+                .iter()
+                .find(|f| f.name() == LIST_VALUE_FIELD)
+                .map(|f| f.dtype().clone())
+                .unwrap_or(DataType::Null);
+            let entries = l
+                .iter()
+                .map(|a| {
+                    Ok(AnyValue::StructOwned(Box::new((
+                        vec![
+                            AnyValue::StringOwned(a.key.as_str().into()),
+                            attribute_value_to_any_value(&a.value, &value_dtype)?,
+                        ],
+                        fields.clone(),
+                    ))))
+                })
+                .collect::<Result<Vec<_>, PolarsError>>()?;
+            AnyValue::List(Series::from_any_values_and_dtype(
+                PlSmallStr::EMPTY,
+                &entries,
+                entry_dtype,
+                true,
+            )?)
+        }
+        (AttributeValue::Container(c), DataType::Struct(fields)) => {
+            let values = fields
+                .iter()
+                .map(|f| match c.get_by_key(f.name()) {
+                    Some(a) => attribute_value_to_any_value(&a.value, f.dtype()),
+                    None => Ok(AnyValue::Null),
+                })
+                .collect::<Result<Vec<_>, PolarsError>>()?;
+            AnyValue::StructOwned(Box::new((values, fields.clone())))
+        }
+        (from, DataType::String) => match scalar_attribute_value_to_any_value(from) {
+            AnyValue::StringOwned(s) => AnyValue::StringOwned(s),
+            x => AnyValue::StringOwned(x.to_string().into()),
+        },
+        (from, _) => scalar_attribute_value_to_any_value(from),
+    })
+}
+
 ///
-/// Used for converting values and data types to the `DataFrame` equivalent
+/// Convert a non-nested attribute ([`AttributeValue`]) to an [`AnyValue`]
 ///
-/// The UTC timezone argument is used to correctly convert to `AnyValue::Datetime` with UTC timezone
+/// Lists and containers are converted to their debug representation (only used if they are mixed with other
+/// values in one column, see [`unify_dtypes`])
 ///
-fn attribute_value_to_any_value<'a>(from: &AttributeValue) -> AnyValue<'a> {
+fn scalar_attribute_value_to_any_value(from: &AttributeValue) -> AnyValue<'static> {
     match from {
         AttributeValue::String(v) => AnyValue::StringOwned(v.into()),
-        AttributeValue::Date(v) => {
-            // Fallback for testing:
-            // return AnyValue::StringOwned(v.to_string().into());
-            AnyValue::Datetime(
-                v.timestamp_nanos_opt().unwrap(),
-                polars::prelude::TimeUnit::Nanoseconds,
-                None,
-            )
-        }
+        AttributeValue::Date(v) => AnyValue::Datetime(
+            v.timestamp_nanos_opt().unwrap(),
+            polars::prelude::TimeUnit::Nanoseconds,
+            None,
+        ),
         AttributeValue::Int(v) => AnyValue::Int64(*v),
         AttributeValue::Float(v) => AnyValue::Float64(*v),
         AttributeValue::Boolean(v) => AnyValue::Boolean(*v),
@@ -58,18 +198,82 @@ fn attribute_value_to_any_value<'a>(from: &AttributeValue) -> AnyValue<'a> {
             let s = v.to_string();
             AnyValue::StringOwned(s.into())
         }
-        // TODO: Add proper List/Container support
         AttributeValue::List(l) => AnyValue::StringOwned(format!("{:?}", l).into()),
         AttributeValue::Container(c) => AnyValue::StringOwned(format!("{:?}", c).into()),
         AttributeValue::None() => AnyValue::Null,
     }
 }
 
+///
+/// Convert attribute values to a [`Series`], unifying their types (see [`unify_dtypes`])
+///
+fn attribute_values_to_series(
+    name: &str,
+    values: &[Option<&AttributeValue>],
+) -> Result<Series, PolarsError> {
+    let mut conflict = false;
+    let dtype = values.iter().flatten().fold(DataType::Null, |dtype, v| {
+        let value_dtype = attribute_value_dtype(v, &mut conflict);
+        unify_dtypes(dtype, value_dtype, &mut conflict)
+    });
+    if conflict {
+        eprintln!(
+            "Warning: Attribute {} contains values of different dtypes (unified to {:?})",
+            name, dtype
+        );
+    }
+    let entries = values
+        .iter()
+        .map(|v| match v {
+            Some(v) => attribute_value_to_any_value(v, &dtype),
+            None => Ok(AnyValue::Null),
+        })
+        .collect::<Result<Vec<_>, PolarsError>>()?;
+    Series::from_any_values_and_dtype(name.into(), &entries, &dtype, true)
+}
+
+///
+/// Unify the [`DataType`]s of a column in different `DataFrame`s (e.g., row groups of a Parquet file)
+///
+/// The types are unified in the same way as the values of an attribute (see [`unify_dtypes`]).
+///
+pub(crate) fn unify_column_dtypes(a: &DataType, b: &DataType) -> DataType {
+    unify_dtypes(a.clone(), b.clone(), &mut false)
+}
+
+///
+/// Cast a column to a [`DataType`] obtained using [`unify_column_dtypes`]
+///
+pub(crate) fn cast_attribute_column(
+    column: &Column,
+    dtype: &DataType,
+) -> Result<Column, PolarsError> {
+    if column.dtype() == dtype {
+        return Ok(column.clone());
+    }
+    if *column.dtype() == DataType::Null {
+        return column.strict_cast(dtype);
+    }
+    let values = column
+        .as_materialized_series()
+        .iter()
+        .map(|v| attribute_value_to_any_value(&any_value_to_attribute_value(&v), dtype))
+        .collect::<Result<Vec<_>, PolarsError>>()?;
+    Ok(Series::from_any_values_and_dtype(column.name().clone(), &values, dtype, true)?.into())
+}
+
 ///
 /// Convert an [`EventLog`] to a Polars [`DataFrame`]
 ///
 /// Flattens event log and adds trace-level attributes to events with prefixed attribute key (see [`TRACE_PREFIX`])
 ///
+/// Nested attributes are preserved: Lists are converted to `List` columns of `Struct`s with a `key` and `value` field
+/// and containers to `Struct` columns (with one field per child attribute key).
+/// If an attribute has values of different types, they are unified (i.e., `Int64` and `Float64` to `Float64`, otherwise to `String`).
+///
+/// Meta-attributes of an attribute are stored separately in a `Struct` column (like a container),
+/// named by the attribute key with the [`META_ATTRIBUTES_SUFFIX`] (e.g., `cost@meta`).
+///
 /// Note: This function is only available if the `dataframes` feature is enabled.
 ///
 pub fn convert_log_to_dataframe(
@@ -80,23 +284,24 @@ pub fn convert_log_to_dataframe(
         println!("Starting converting log to DataFrame");
     }
     let mut now = Instant::now();
-    let all_attributes: HashSet<String> = log
+    // Attribute keys (with trace prefix) and whether the column holds their meta-attributes
+    let all_attributes: HashSet<(String, bool)> = log
         .traces
         .par_iter()
         .flat_map(|t| {
-            let trace_attrs: HashSet<String> = t
+            let trace_attrs: HashSet<(String, bool)> = t
                 .attributes
                 .iter()
-                .map(|a| TRACE_PREFIX.to_string() + a.key.as_str())
+                .flat_map(|a| attribute_columns(TRACE_PREFIX.to_string() + a.key.as_str(), a))
                 .collect();
-            let m: HashSet<String> = t
+            let m: HashSet<(String, bool)> = t
                 .events
                 .iter()
                 .flat_map(|e| {
                     e.attributes
                         .iter()
-                        .map(|a| a.key.clone())
-                        .collect::<Vec<String>>()
+                        .flat_map(|a| attribute_columns(a.key.clone(), a))
+                        .collect::<Vec<(String, bool)>>()
                 })
                 .collect();
             [trace_attrs, m]
@@ -109,67 +314,47 @@ pub fn convert_log_to_dataframe(
     now = Instant::now();
     let x: Vec<Series> = all_attributes
         .par_iter()
-        .map(|k: &String| {
-            let mut entries: Vec<AnyValue<'_>> = log
+        .map(|(k, is_meta): &(String, bool)| {
+            let values: Vec<Option<&Attribute>> = log
                 .traces
                 .iter()
-                .flat_map(|t| -> Vec<AnyValue<'_>> {
+                .flat_map(|t| -> Vec<Option<&Attribute>> {
                     if k.starts_with(TRACE_PREFIX) {
                         let trace_k: String = k.chars().skip(TRACE_PREFIX.len()).collect();
                         vec![
-                            attribute_to_any_value(
-                                t.attributes
-                                    .get_by_key_or_global(&trace_k, &log.global_trace_attrs),
-                            );
+                            t.attributes
+                                .get_by_key_or_global(&trace_k, &log.global_trace_attrs);
                             t.events.len()
                         ]
                     } else {
                         t.events
                             .iter()
                             .map(|e| {
-                                attribute_to_any_value(
-                                    e.attributes
-                                        .get_by_key_or_global(k, &log.global_event_attrs),
-                                )
+                                e.attributes
+                                    .get_by_key_or_global(k, &log.global_event_attrs)
                             })
                             .collect()
                     }
                 })
                 .collect();
-
-            let mut unique_dtypes: HashSet<DataType> = entries.iter().map(|v| v.dtype()).collect();
-            unique_dtypes.remove(&DataType::Null);
-            if unique_dtypes.len() > 1 {
-                eprintln!(
-                    "Warning: Attribute {} contains values of different dtypes ({:?})",
-                    k, unique_dtypes
-                );
-                if unique_dtypes
-                    == vec![DataType::Float64, DataType::Int64]
-                        .into_iter()
-                        .collect()
-                {
-                    entries = entries
-                        .into_iter()
-                        .map(|val| match val {
-                            AnyValue::Int64(n) => AnyValue::Float64(n as f64),
-                            x => x,
-                        })
-                        .collect();
-                } else {
-                    entries = entries
-                        .into_iter()
-                        .map(|val| match val {
-                            AnyValue::Null => AnyValue::Null,
-                            AnyValue::String(s) => AnyValue::String(s),
-                            x => AnyValue::StringOwned(x.to_string().into()),
-                        })
-                        .collect();
-                }
+            if *is_meta {
+                let meta: Vec<Option<AttributeValue>> = values
+                    .iter()
+                    .map(|a| {
+                        a.and_then(|a| a.own_attributes.as_ref())
+                            .filter(|m| !m.is_empty())
+                            .map(|m| AttributeValue::Container(m.clone()))
+                    })
+                    .collect();
+                let meta: Vec<Option<&AttributeValue>> = meta.iter().map(Option::as_ref).collect();
+                attribute_values_to_series(&(k.clone() + META_ATTRIBUTES_SUFFIX), &meta)
+            } else {
+                let values: Vec<Option<&AttributeValue>> =
+                    values.iter().map(|a| a.map(|a| &a.value)).collect();
+                attribute_values_to_series(k, &values)
             }
-            Series::new(k.into(), entries)
         })
-        .collect();
+        .collect::<Result<_, PolarsError>>()?;
     if print_debug {
         println!(
             "Creating a Series for every Attribute took {:.2?}",
@@ -192,6 +377,21 @@ pub fn convert_log_to_dataframe(
     Ok(df)
 }
 
+///
+/// Get the columns (see [`convert_log_to_dataframe`]) of an attribute with the passed (prefixed) key
+///
+fn attribute_columns(key: String, attribute: &Attribute) -> Vec<(String, bool)> {
+    if attribute
+        .own_attributes
+        .as_ref()
+        .is_some_and(|m| !m.is_empty())
+    {
+        vec![(key.clone(), false), (key, true)]
+    } else {
+        vec![(key, false)]
+    }
+}
+
 fn any_value_to_attribute_value(from: &AnyValue<'_>) -> AttributeValue {
     match from {
         AnyValue::Null => AttributeValue::None(),
@@ -208,27 +408,79 @@ fn any_value_to_attribute_value(from: &AnyValue<'_>) -> AttributeValue {
         AnyValue::Int64(v) => AttributeValue::Int(*v),
         AnyValue::Float32(v) => AttributeValue::Float((*v).into()),
         AnyValue::Float64(v) => AttributeValue::Float(*v),
-        AnyValue::Datetime(ns, _, _) => {
+        AnyValue::Datetime(ns, _, _) | AnyValue::DatetimeOwned(ns, _, _) => {
             // Convert nanos to micros; tz is not used!
             let d: DateTime<_> = DateTime::from_timestamp_micros(ns / 1000)
                 .unwrap()
                 .fixed_offset();
             AttributeValue::Date(d)
         }
+        AnyValue::List(entries) => AttributeValue::List(
+            entries
+                .iter()
+                .map(|entry| {
+                    let mut key = String::new();
+                    let mut value = AttributeValue::None();
+                    for (name, v) in struct_any_value_fields(&entry) {
+                        if name == LIST_KEY_FIELD {
+                            key = v.get_str().unwrap_or_default().to_string();
+                        } else if name == LIST_VALUE_FIELD {
+                            value = any_value_to_attribute_value(&v);
+                        }
+                    }
+                    Attribute::new(key, value)
+                })
+                .collect(),
+        ),
+        AnyValue::Struct(_, _, _) | AnyValue::StructOwned(_) => AttributeValue::Container(
+            struct_any_value_fields(from)
+                .into_iter()
+                .filter_map(|(name, v)| match any_value_to_attribute_value(&v) {
+                    AttributeValue::None() => None,
+                    value => Some(Attribute::new(name.to_string(), value)),
+                })
+                .collect(),
+        ),
         x => AttributeValue::String(format!("{:?}", x)),
     }
 }
 
+///
+/// Get the field names and values of a `Struct` [`AnyValue`] (or an empty [`Vec`] for other values)
+///
+fn struct_any_value_fields(from: &AnyValue<'_>) -> Vec<(PlSmallStr, AnyValue<'static>)> {
+    match from.clone().into_static() {
+        AnyValue::StructOwned(payload) => {
+            let (values, fields) = *payload;
+            fields.into_iter().map(|f| f.name).zip(values).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Convert Polars [`DataFrame`] to [`EventLog`]
 ///
-///  - Extracts attributes as Strings (converting other formats using debug format macro)
+///  - Reconstructs nested list and container attributes from `List` and `Struct` columns (see [`convert_log_to_dataframe`])
+///  - Reconstructs meta-attributes from the columns with the [`META_ATTRIBUTES_SUFFIX`]
+///  - Extracts attributes of other unsupported types as Strings (converting them using debug format macro)
 ///  - Assumes valid `EventLog` structure of `DataFrame` (i.e., assuming that [`PREFIXED_TRACE_ID_NAME`] is present)
 ///
 /// Note: This function is only available if the `dataframes` feature is enabled.
 ///
 pub fn convert_dataframe_to_log(df: &DataFrame) -> Result<EventLog, PolarsError> {
     let groups = df.partition_by_stable([PREFIXED_TRACE_ID_NAME], true)?;
-    let columns = df.get_column_names();
+    // Column names and, for columns of meta-attributes, the name of the column of the attribute
+    let columns: Vec<(&PlSmallStr, Option<&str>)> = df
+        .get_columns()
+        .iter()
+        .map(|c| {
+            let meta_of = c
+                .name()
+                .strip_suffix(META_ATTRIBUTES_SUFFIX)
+                .filter(|base| matches!(c.dtype(), DataType::Struct(_)) && df.column(base).is_ok());
+            (c.name(), meta_of)
+        })
+        .collect();
     let mut log = EventLog {
         attributes: Attributes::default(),
         traces: vec![],
@@ -244,27 +496,32 @@ pub fn convert_dataframe_to_log(df: &DataFrame) -> Result<EventLog, PolarsError>
             let events: Vec<Event> = (0..g.height())
                 .map(|i| {
                     let mut event_attributes: HashSet<Attribute> = HashSet::new();
+                    let row = g.get_row(i).unwrap().0;
+                    let mut meta: HashMap<&str, Attributes> = HashMap::new();
+                    for ((_, meta_of), v) in columns.iter().zip(row.iter()) {
+                        if let (Some(base), AttributeValue::Container(m)) =
+                            (meta_of, any_value_to_attribute_value(v))
+                        {
+                            meta.insert(base, m);
+                        }
+                    }
                     columns
                         .iter()
-                        .zip(g.get_row(i).unwrap().0.iter())
-                        .for_each(|(c, v)| {
-                            let value = any_value_to_attribute_value(v);
-                            match value {
-                                AttributeValue::None() => {
-                                    // Skip!
-                                }
-                                value => {
-                                    if c.starts_with(TRACE_PREFIX) {
-                                        // e.g.,
-                                        let (_, c) = c.split_once(TRACE_PREFIX).unwrap();
-                                        trace_attributes
-                                            .insert(Attribute::new(c.to_string(), value));
-                                    } else {
-                                        event_attributes
-                                            .insert(Attribute::new(c.to_string(), value));
-                                    }
-                                }
+                        .zip(row.iter())
+                        .for_each(|((c, meta_of), v)| {
+                            if meta_of.is_some() || matches!(v, AnyValue::Null) {
+                                // Skip!
+                                return;
                             }
+                            let (key, attributes) = match c.strip_prefix(TRACE_PREFIX) {
+                                Some(key) => (key, &mut trace_attributes),
+                                None => (c.as_str(), &mut event_attributes),
+                            };
+                            attributes.insert(Attribute {
+                                key: key.to_string(),
+                                value: any_value_to_attribute_value(v),
+                                own_attributes: meta.remove(c.as_str()),
+                            });
                         });
 
                     Event {
@@ -286,9 +543,21 @@ pub fn convert_dataframe_to_log(df: &DataFrame) -> Result<EventLog, PolarsError>
 mod df_xes_tests {
     use std::time::Instant;
 
+    use std::io::Cursor;
+
+    use polars::prelude::DataType;
+
     use crate::{
-        event_log::dataframe::convert_log_to_dataframe, import_xes_file,
-        utils::test_utils::get_test_data_path, XESImportOptions,
+        event_log::{
+            dataframe::{
+                convert_dataframe_to_log, convert_log_to_dataframe,
+                parquet_ipc::{export_log_to_parquet, import_log_from_parquet},
+            },
+            AttributeValue, Attributes, Event, EventLog, Trace,
+        },
+        import_xes_file, import_xes_slice,
+        utils::test_utils::{event_log_from_events, event_with_attributes, get_test_data_path},
+        XESImportOptions,
     };
 
     #[test]
@@ -323,4 +592,127 @@ mod df_xes_tests {
         println!("Total: {:?}\n\n", now_total.elapsed());
         assert_eq!(converted_log.shape(), (num_events, 16));
     }
+
+    #[test]
+    fn nested_attributes_round_trip() {
+        let xes = r#"<?xml version="1.0" encoding="UTF-8"?>
+<log xes.version="2.0" xes.features="nested-attributes">
+  <trace>
+    <string key="concept:name" value="t1"/>
+    <container key="info">
+      <string key="origin" value="web"/>
+      <int key="priority" value="2"/>
+    </container>
+    <event>
+      <string key="concept:name" value="a"/>
+      <int key="amount" value="5">
+        <string key="currency" value="EUR"/>
+      </int>
+      <list key="items">
+        <int key="item" value="1"/>
+        <float key="item" value="2.5"/>
+      </list>
+      <container key="meta">
+        <string key="source" value="x"/>
+        <container key="inner">
+          <boolean key="flag" value="true"/>
+        </container>
+      </container>
+    </event>
+    <event>
+      <string key="concept:name" value="b"/>
+      <float key="amount" value="2.5"/>
+      <container key="meta">
+        <date key="at" value="2024-01-01T12:00:00.000+00:00"/>
+      </container>
+    </event>
+  </trace>
+  <trace>
+    <string key="concept:name" value="t2"/>
+    <event>
+      <string key="concept:name" value="c"/>
+      <list key="items"></list>
+    </event>
+  </trace>
+</log>"#;
+        let log = import_xes_slice(xes.as_bytes(), false, XESImportOptions::default()).unwrap();
+        let df = convert_log_to_dataframe(&log, false).unwrap();
+        assert!(matches!(
+            df.column("items").unwrap().dtype(),
+            DataType::List(_)
+        ));
+        assert!(matches!(
+            df.column("meta").unwrap().dtype(),
+            DataType::Struct(fields) if fields.len() == 3
+        ));
+        assert!(matches!(
+            df.column("case:info").unwrap().dtype(),
+            DataType::Struct(fields) if fields.len() == 2
+        ));
+        // Ints are unified with floats, and meta-attributes do not change the type of the attribute
+        assert_eq!(df.column("amount").unwrap().dtype(), &DataType::Float64);
+        assert!(matches!(
+            df.column("amount@meta").unwrap().dtype(),
+            DataType::Struct(fields) if fields.len() == 1
+        ));
+
+        // Ints are unified with floats
+        let mut expected = log.clone();
+        for e in expected.traces.iter_mut().flat_map(|t| t.events.iter_mut()) {
+            for a in e.attributes.iter_mut() {
+                let values: Vec<&mut AttributeValue> = match &mut a.value {
+                    AttributeValue::List(l) => l.iter_mut().map(|a| &mut a.value).collect(),
+                    v => vec![v],
+                };
+                for v in values {
+                    if let AttributeValue::Int(i) = v {
+                        *v = AttributeValue::Float(*i as f64);
+                    }
+                }
+            }
+        }
+
+        let mut parquet_data = Vec::new();
+        export_log_to_parquet(&log, &mut parquet_data).unwrap();
+        for converted in [
+            convert_dataframe_to_log(&df).unwrap(),
+            import_log_from_parquet(Cursor::new(parquet_data)).unwrap(),
+        ] {
+            assert_eq!(sorted_traces(&converted), sorted_traces(&expected));
+        }
+    }
+
+    #[test]
+    fn mixed_type_attributes() {
+        let log = event_log_from_events(vec![vec![
+            event_with_attributes("a", &[("code", AttributeValue::Int(1))]),
+            event_with_attributes("b", &[("code", AttributeValue::String("x".to_string()))]),
+        ]]);
+        let df = convert_log_to_dataframe(&log, false).unwrap();
+        let code = df.column("code").unwrap();
+        assert_eq!(code.dtype(), &DataType::String);
+        assert_eq!(code.get(0).unwrap().get_str(), Some("1"));
+    }
+
+    /// Traces with top-level attributes sorted by key (as their order is not preserved by the columns of a `DataFrame`)
+    fn sorted_traces(log: &EventLog) -> Vec<Trace> {
+        let sorted = |attributes: &Attributes| {
+            let mut attributes = attributes.clone();
+            attributes.sort_by(|a, b| a.key.cmp(&b.key));
+            attributes
+        };
+        log.traces
+            .iter()
+            .map(|t| Trace {
+                attributes: sorted(&t.attributes),
+                events: t
+                    .events
+                    .iter()
+                    .map(|e| Event {
+                        attributes: sorted(&e.attributes),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,569 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+    ops::Range,
+    path::PathBuf,
+};
+
+use polars::{
+    io::{mmap::MmapBytesReader, parquet::write::BatchedWriter},
+    prelude::*,
+};
+use uuid::Uuid;
+
+use crate::{
+    event_log::{constants::PREFIXED_TRACE_ID_NAME, stream_xes::XESOuterLogData, Trace},
+    EventLog,
+};
+
+use super::{
+    cast_attribute_column, convert_dataframe_to_log, convert_log_to_dataframe, unify_column_dtypes,
+};
+
+///
+/// Export an [`EventLog`] as Apache Parquet to a writer
+///
+/// The log is flattened using [`convert_log_to_dataframe`] (i.e., trace attributes are prefixed with [`super::TRACE_PREFIX`]).
+/// Traces without events are not included, as they do not have any rows.
+///
+pub fn export_log_to_parquet<W: Write>(log: &EventLog, writer: W) -> Result<(), PolarsError> {
+    let mut df = convert_log_to_dataframe(log, false)?;
+    ParquetWriter::new(writer).finish(&mut df)?;
+    Ok(())
+}
+
+///
+/// Export an [`EventLog`] as Apache Parquet to a filepath
+///
+/// See [`export_log_to_parquet`] for details.
+///
+pub fn export_log_to_parquet_path<P: AsRef<std::path::Path>>(
+    log: &EventLog,
+    path: P,
+) -> Result<(), PolarsError> {
+    export_log_to_parquet(log, File::create(path)?)
+}
+
+///
+/// Import an [`EventLog`] from Apache Parquet
+///
+/// The data is converted using [`convert_dataframe_to_log`], so the case identifier column ([`PREFIXED_TRACE_ID_NAME`]) is required.
+///
+pub fn import_log_from_parquet<R: MmapBytesReader>(reader: R) -> Result<EventLog, PolarsError> {
+    let df = ParquetReader::new(reader).finish()?;
+    convert_dataframe_to_log(&df)
+}
+
+///
+/// Import an [`EventLog`] from an Apache Parquet file
+///
+/// See [`import_log_from_parquet`] for details.
+///
+pub fn import_log_from_parquet_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<EventLog, PolarsError> {
+    import_log_from_parquet(File::open(path)?)
+}
+
+///
+/// Export an [`EventLog`] as Apache Arrow IPC (i.e., Feather v2) to a writer
+///
+/// The log is flattened using [`convert_log_to_dataframe`] (i.e., trace attributes are prefixed with [`super::TRACE_PREFIX`]).
+/// Traces without events are not included, as they do not have any rows.
+///
+pub fn export_log_to_ipc<W: Write>(log: &EventLog, writer: W) -> Result<(), PolarsError> {
+    let mut df = convert_log_to_dataframe(log, false)?;
+    IpcWriter::new(writer).finish(&mut df)
+}
+
+///
+/// Export an [`EventLog`] as Apache Arrow IPC (i.e., Feather v2) to a filepath
+///
+/// See [`export_log_to_ipc`] for details.
+///
+pub fn export_log_to_ipc_path<P: AsRef<std::path::Path>>(
+    log: &EventLog,
+    path: P,
+) -> Result<(), PolarsError> {
+    export_log_to_ipc(log, File::create(path)?)
+}
+
+///
+/// Import an [`EventLog`] from Apache Arrow IPC (i.e., Feather v2)
+///
+/// The data is converted using [`convert_dataframe_to_log`], so the case identifier column ([`PREFIXED_TRACE_ID_NAME`]) is required.
+///
+pub fn import_log_from_ipc<R: MmapBytesReader>(reader: R) -> Result<EventLog, PolarsError> {
+    let df = IpcReader::new(reader).finish()?;
+    convert_dataframe_to_log(&df)
+}
+
+///
+/// Import an [`EventLog`] from an Apache Arrow IPC (i.e., Feather v2) file
+///
+/// See [`import_log_from_ipc`] for details.
+///
+pub fn import_log_from_ipc_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<EventLog, PolarsError> {
+    import_log_from_ipc(File::open(path)?)
+}
+
+/// Default number of events per row group written by [`ParquetTraceStreamWriter`]
+const DEFAULT_ROW_GROUP_EVENTS: usize = 100_000;
+
+///
+/// Streaming Apache Parquet writer for [`Trace`]s
+///
+/// Traces are buffered until enough events for a row group are collected, which is then converted
+/// (using [`convert_log_to_dataframe`]) and appended to the Parquet file.
+/// Thus, only one row group is held in memory at a time, e.g., when converting a XES trace stream to Parquet:
+///
+/// ```rust,no_run
+/// use process_mining::{
+///     event_log::dataframe::parquet_ipc::ParquetTraceStreamWriter, stream_xes_from_path,
+/// };
+/// let (mut stream, log_data) = stream_xes_from_path("log.xes.gz", Default::default()).unwrap();
+/// let file = std::fs::File::create("log.parquet").unwrap();
+/// let mut writer = ParquetTraceStreamWriter::new(file, &log_data);
+/// writer.write_traces(&mut stream).unwrap();
+/// writer.finish().unwrap();
+/// ```
+///
+/// As all row groups of a Parquet file must have the same schema, but attributes might first occur in later traces,
+/// the row groups are first written to a temporary file (as Arrow IPC).
+/// Once all traces are written (see [`ParquetTraceStreamWriter::finish`]), the schema is unified over all row groups,
+/// which are then written to the Parquet file one at a time.
+/// Columns missing in a row group are filled with null values and the types of attributes in different row groups
+/// are unified as within one `DataFrame` (see [`convert_log_to_dataframe`]).
+///
+/// If the schema is set explicitly (see [`ParquetTraceStreamWriter::with_schema`]), row groups are directly written to the Parquet file instead.
+/// In this case, writing a row group with attributes not contained in the schema (or with values which cannot be cast to the type in the schema) fails.
+///
+pub struct ParquetTraceStreamWriter<W: Write> {
+    /// Underlying writer (until the Parquet writer is created)
+    writer: Option<W>,
+    /// Parquet writer (created once the schema is known)
+    batched_writer: Option<BatchedWriter<W>>,
+    /// Explicitly set schema of all row groups
+    schema: Option<Schema>,
+    /// Row groups written to a temporary file (if the schema is not set explicitly)
+    spilled: Option<SpilledRowGroups>,
+    /// Log holding the global attributes and the buffered traces
+    buffer: EventLog,
+    /// Number of events in the buffered traces
+    buffered_events: usize,
+    /// Minimal number of events per row group
+    row_group_events: usize,
+}
+
+impl<W: Write> std::fmt::Debug for ParquetTraceStreamWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetTraceStreamWriter")
+            .field("schema", &self.schema)
+            .field("spilled", &self.spilled)
+            .field("buffered_traces", &self.buffer.traces.len())
+            .field("buffered_events", &self.buffered_events)
+            .field("row_group_events", &self.row_group_events)
+            .finish()
+    }
+}
+
+impl<W: Write> ParquetTraceStreamWriter<W> {
+    ///
+    /// Create a new streaming Parquet writer
+    ///
+    /// The global trace and event attributes of the passed `log_data` are used as default values for missing attributes.
+    ///
+    pub fn new(writer: W, log_data: &XESOuterLogData) -> Self {
+        let mut buffer = EventLog::new();
+        buffer.global_trace_attrs = Some(log_data.global_trace_attrs.clone());
+        buffer.global_event_attrs = Some(log_data.global_event_attrs.clone());
+        Self {
+            writer: Some(writer),
+            batched_writer: None,
+            schema: None,
+            spilled: None,
+            buffer,
+            buffered_events: 0,
+            row_group_events: DEFAULT_ROW_GROUP_EVENTS,
+        }
+    }
+
+    /// Set the schema of the Parquet file (instead of inferring it from all row groups)
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Set the (minimal) number of events per row group (default: `100_000`)
+    pub fn with_row_group_events(mut self, row_group_events: usize) -> Self {
+        self.row_group_events = row_group_events.max(1);
+        self
+    }
+
+    /// Write a [`Trace`], appending a row group if enough events are buffered
+    pub fn write_trace(&mut self, trace: Trace) -> Result<(), PolarsError> {
+        self.buffered_events += trace.events.len();
+        self.buffer.traces.push(trace);
+        if self.buffered_events >= self.row_group_events {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Write all [`Trace`]s of an iterator (e.g., a XES trace stream)
+    pub fn write_traces<I: IntoIterator<Item = Trace>>(
+        &mut self,
+        traces: I,
+    ) -> Result<(), PolarsError> {
+        for trace in traces {
+            self.write_trace(trace)?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffered traces as row group
+    fn flush_row_group(&mut self) -> Result<(), PolarsError> {
+        let df = convert_log_to_dataframe(&self.buffer, false)?;
+        self.buffer.traces.clear();
+        self.buffered_events = 0;
+        if df.height() == 0 {
+            return Ok(());
+        }
+        match self.schema.clone() {
+            Some(schema) => {
+                let df = align_to_schema(df, &schema)?;
+                self.get_batched_writer(&schema)?.write_batch(&df)
+            }
+            None => {
+                if self.spilled.is_none() {
+                    self.spilled = Some(SpilledRowGroups::new()?);
+                }
+                self.spilled.as_mut().unwrap().write(df)
+            }
+        }
+    }
+
+    fn get_batched_writer(
+        &mut self,
+        schema: &Schema,
+    ) -> Result<&mut BatchedWriter<W>, PolarsError> {
+        if self.batched_writer.is_none() {
+            let writer = self
+                .writer
+                .take()
+                .ok_or_else(|| polars_err!(ComputeError: "Parquet writer was already finished"))?;
+            self.batched_writer = Some(ParquetWriter::new(writer).batched(schema)?);
+        }
+        Ok(self.batched_writer.as_mut().unwrap())
+    }
+
+    ///
+    /// Write all remaining buffered traces and finish the Parquet file
+    ///
+    /// Returns the size of the written file in bytes.
+    ///
+    pub fn finish(mut self) -> Result<u64, PolarsError> {
+        self.flush_row_group()?;
+        if let Some(mut spilled) = self.spilled.take() {
+            let schema = spilled.schema();
+            for i in 0..spilled.row_groups.len() {
+                let df = align_to_schema(spilled.read(i)?, &schema)?;
+                self.get_batched_writer(&schema)?.write_batch(&df)?;
+            }
+            self.schema = Some(schema);
+        }
+        let schema = self.schema.take().unwrap_or_else(|| {
+            Schema::from_iter([Field::new(PREFIXED_TRACE_ID_NAME.into(), DataType::String)])
+        });
+        self.get_batched_writer(&schema)?.finish()
+    }
+}
+
+///
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] as Apache Parquet to a writer
+///
+/// Traces are written in row groups while they are consumed from the iterator (see [`ParquetTraceStreamWriter`]).
+///
+pub fn export_trace_stream_to_parquet<W: Write, I: IntoIterator<Item = Trace>>(
+    traces: I,
+    log_data: &XESOuterLogData,
+    writer: W,
+) -> Result<(), PolarsError> {
+    let mut stream_writer = ParquetTraceStreamWriter::new(writer, log_data);
+    stream_writer.write_traces(traces)?;
+    stream_writer.finish()?;
+    Ok(())
+}
+
+///
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] as Apache Parquet to a filepath
+///
+/// See [`export_trace_stream_to_parquet`] for details.
+///
+pub fn export_trace_stream_to_parquet_path<
+    I: IntoIterator<Item = Trace>,
+    P: AsRef<std::path::Path>,
+>(
+    traces: I,
+    log_data: &XESOuterLogData,
+    path: P,
+) -> Result<(), PolarsError> {
+    export_trace_stream_to_parquet(traces, log_data, File::create(path)?)
+}
+
+/// Number of attempts to create a temporary file with a new name (see [`SpilledRowGroups`])
+const SPILL_FILE_ATTEMPTS: usize = 16;
+
+///
+/// Row groups written to a temporary file (as Arrow IPC) until the schema of all row groups is known
+///
+/// The temporary file is created with a random name (failing if it already exists) and removed once this is dropped.
+///
+#[derive(Debug)]
+struct SpilledRowGroups {
+    /// Path of the temporary file
+    path: PathBuf,
+    /// Temporary file
+    file: File,
+    /// Byte ranges of the row groups in the temporary file
+    row_groups: Vec<Range<u64>>,
+    /// Unified data types of all columns of the row groups so far
+    dtypes: PlIndexMap<PlSmallStr, DataType>,
+}
+
+impl SpilledRowGroups {
+    fn new() -> Result<Self, PolarsError> {
+        // Unpredictable file names which must not exist yet (e.g., as a planted symlink in the shared temporary directory)
+        let mut attempts = 0;
+        let (path, file) = loop {
+            let path =
+                std::env::temp_dir().join(format!("process_mining-{}.arrow", Uuid::new_v4()));
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => break (path, file),
+                Err(e)
+                    if e.kind() == ErrorKind::AlreadyExists && attempts < SPILL_FILE_ATTEMPTS =>
+                {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+        Ok(Self {
+            path,
+            file,
+            row_groups: Vec::new(),
+            dtypes: PlIndexMap::default(),
+        })
+    }
+
+    /// Append a row group
+    fn write(&mut self, mut df: DataFrame) -> Result<(), PolarsError> {
+        for (name, dtype) in df.schema().iter() {
+            let unified = match self.dtypes.get(name) {
+                Some(existing) => unify_column_dtypes(existing, dtype),
+                None => dtype.clone(),
+            };
+            self.dtypes.insert(name.clone(), unified);
+        }
+        let start = self.file.seek(SeekFrom::End(0))?;
+        IpcWriter::new(&mut self.file).finish(&mut df)?;
+        let end = self.file.stream_position()?;
+        self.row_groups.push(start..end);
+        Ok(())
+    }
+
+    /// Read the `i`-th row group
+    fn read(&mut self, i: usize) -> Result<DataFrame, PolarsError> {
+        let range = &self.row_groups[i];
+        let mut data = vec![0; (range.end - range.start) as usize];
+        self.file.seek(SeekFrom::Start(range.start))?;
+        self.file.read_exact(&mut data)?;
+        IpcReader::new(Cursor::new(data)).finish()
+    }
+
+    /// Schema for all row groups (sorting columns by name and using strings for columns with only null values)
+    fn schema(&self) -> Schema {
+        let mut fields: Vec<Field> = self
+            .dtypes
+            .iter()
+            .map(|(name, dtype)| match dtype {
+                DataType::Null => Field::new(name.clone(), DataType::String),
+                _ => Field::new(name.clone(), dtype.clone()),
+            })
+            .collect();
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        Schema::from_iter(fields)
+    }
+}
+
+impl Drop for SpilledRowGroups {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Reorder and cast the columns of a [`DataFrame`] to match the given schema (adding missing columns with null values)
+fn align_to_schema(df: DataFrame, schema: &Schema) -> Result<DataFrame, PolarsError> {
+    if let Some(name) = df
+        .get_column_names()
+        .into_iter()
+        .find(|name| !schema.contains(name))
+    {
+        polars_bail!(SchemaMismatch: "attribute {} is not contained in the schema of the Parquet file", name);
+    }
+    let height = df.height();
+    let columns = schema
+        .iter()
+        .map(|(name, dtype)| match df.column(name) {
+            Ok(c) => cast_attribute_column(c, dtype),
+            Err(_) => Ok(Column::full_null(name.clone(), height, dtype)),
+        })
+        .collect::<Result<Vec<Column>, PolarsError>>()?;
+    DataFrame::new(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::event_log::{Attribute, AttributeValue, Event, XESEditableAttribute};
+
+    use super::*;
+
+    fn test_log() -> EventLog {
+        let mut log = EventLog::new();
+        for i in 0..5 {
+            let mut trace = Trace::new();
+            trace.attributes.add_to_attributes(
+                "concept:name".to_string(),
+                AttributeValue::String(format!("case_{i}")),
+            );
+            for j in 0..=i {
+                let mut event = Event::new(format!("act_{j}"));
+                event
+                    .attributes
+                    .add_to_attributes("cost".to_string(), AttributeValue::Int(j as i64));
+                // Containers with different fields (and meta-attributes) in the first and last traces
+                let details = match i {
+                    0 => vec![Attribute::new(
+                        "origin".to_string(),
+                        AttributeValue::String("web".to_string()),
+                    )],
+                    4 => vec![Attribute::new(
+                        "priority".to_string(),
+                        AttributeValue::Int(j as i64),
+                    )],
+                    _ => vec![],
+                };
+                if !details.is_empty() {
+                    event.attributes.push(Attribute {
+                        key: "details".to_string(),
+                        value: AttributeValue::Container(details),
+                        own_attributes: (i == 4).then(|| {
+                            vec![Attribute::new(
+                                "source".to_string(),
+                                AttributeValue::String("erp".to_string()),
+                            )]
+                        }),
+                    });
+                }
+                if i == 3 {
+                    event.attributes.add_to_attributes(
+                        "time:timestamp".to_string(),
+                        AttributeValue::Date("2024-01-02T03:04:05Z".parse().unwrap()),
+                    );
+                }
+                trace.events.push(event);
+            }
+            log.traces.push(trace);
+        }
+        log
+    }
+
+    fn sorted_traces(log: &EventLog) -> Vec<Vec<Vec<String>>> {
+        // Attribute order is not preserved by the DataFrame conversion
+        let mut traces: Vec<Vec<Vec<String>>> = log
+            .traces
+            .iter()
+            .map(|t| {
+                let mut attrs: Vec<String> =
+                    t.attributes.iter().map(|a| format!("{a:?}")).collect();
+                attrs.sort();
+                let mut rows = vec![attrs];
+                rows.extend(t.events.iter().map(|e| {
+                    let mut attrs: Vec<String> =
+                        e.attributes.iter().map(|a| format!("{a:?}")).collect();
+                    attrs.sort();
+                    attrs
+                }));
+                rows
+            })
+            .collect();
+        traces.sort();
+        traces
+    }
+
+    #[test]
+    fn parquet_and_ipc_round_trip() {
+        let log = test_log();
+        let mut parquet = Vec::new();
+        export_log_to_parquet(&log, &mut parquet).unwrap();
+        let parquet_log = import_log_from_parquet(Cursor::new(parquet)).unwrap();
+        assert_eq!(sorted_traces(&parquet_log), sorted_traces(&log));
+
+        let mut ipc = Vec::new();
+        export_log_to_ipc(&log, &mut ipc).unwrap();
+        let ipc_log = import_log_from_ipc(Cursor::new(ipc)).unwrap();
+        assert_eq!(sorted_traces(&ipc_log), sorted_traces(&log));
+    }
+
+    #[test]
+    fn parquet_stream_writer() {
+        let log = test_log();
+        let schema = convert_log_to_dataframe(&log, false)
+            .unwrap()
+            .schema()
+            .as_ref()
+            .clone();
+        let mut parquet = Vec::new();
+        let mut writer = ParquetTraceStreamWriter::new(&mut parquet, &XESOuterLogData::default())
+            .with_schema(schema)
+            .with_row_group_events(2);
+        writer.write_traces(log.traces.clone()).unwrap();
+        writer.finish().unwrap();
+        let df = ParquetReader::new(Cursor::new(parquet.clone()))
+            .finish()
+            .unwrap();
+        assert_eq!(df.height(), 15);
+        assert_eq!(df.column("time:timestamp").unwrap().null_count(), 15 - 4);
+        let parquet_log = import_log_from_parquet(Cursor::new(parquet)).unwrap();
+        assert_eq!(sorted_traces(&parquet_log), sorted_traces(&log));
+
+        // Without an explicit schema, attributes first occurring in later row groups are added
+        // and the types of attributes in different row groups are unified
+        let mut parquet = Vec::new();
+        let mut writer = ParquetTraceStreamWriter::new(&mut parquet, &XESOuterLogData::default())
+            .with_row_group_events(2);
+        writer.write_traces(log.traces.clone()).unwrap();
+        writer.finish().unwrap();
+        let parquet_log = import_log_from_parquet(Cursor::new(parquet)).unwrap();
+        assert_eq!(sorted_traces(&parquet_log), sorted_traces(&log));
+
+        let mut parquet = Vec::new();
+        export_trace_stream_to_parquet(
+            log.traces.clone(),
+            &XESOuterLogData::default(),
+            &mut parquet,
+        )
+        .unwrap();
+        let parquet_log = import_log_from_parquet(Cursor::new(parquet)).unwrap();
+        assert_eq!(sorted_traces(&parquet_log), sorted_traces(&log));
+    }
+}
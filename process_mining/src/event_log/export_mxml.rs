@@ -0,0 +1,270 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use quick_xml::{
+    events::{BytesDecl, BytesText},
+    Writer,
+};
+
+use crate::{utils::xml_utils::XMLWriterWrapper, EventLog};
+
+use super::{
+    constants::{
+        ACTIVITY_NAME, LIFECYCLE_TRANSITION_NAME, RESOURCE_NAME, TIMESTAMP_NAME, TRACE_ID_NAME,
+    },
+    export_xes::{is_gz_path, write_gz},
+    Attribute, AttributeValue,
+};
+const OK: Result<(), quick_xml::Error> = Ok::<(), quick_xml::Error>(());
+
+/// Event types supported by MXML
+///
+/// Other lifecycle transitions are exported as `unknown` event type (with an `unknowntype` attribute)
+const MXML_EVENT_TYPES: [&str; 13] = [
+    "schedule",
+    "assign",
+    "withdraw",
+    "reassign",
+    "start",
+    "suspend",
+    "resume",
+    "pi_abort",
+    "ate_abort",
+    "complete",
+    "autoskip",
+    "manualskip",
+    "unknown",
+];
+
+/// Convert an [`AttributeValue`] to the (string) value of an MXML data attribute
+///
+/// Returns `None` for nested values (i.e., lists and containers) and `None` values, which cannot be represented in MXML
+fn attribute_value_to_mxml_string(value: &AttributeValue) -> Option<String> {
+    match value {
+        AttributeValue::String(s) => Some(s.clone()),
+        AttributeValue::Date(d) => Some(d.to_rfc3339()),
+        AttributeValue::Int(i) => Some(i.to_string()),
+        AttributeValue::Float(f) => Some(f.to_string()),
+        AttributeValue::Boolean(b) => Some(b.to_string()),
+        AttributeValue::ID(id) => Some(id.to_string()),
+        AttributeValue::List(_) | AttributeValue::Container(_) | AttributeValue::None() => None,
+    }
+}
+
+/// Write all attributes (except the ones with a key in `skip_keys`) as MXML `<Data>` element
+fn write_mxml_data<T: Write>(
+    w: &mut Writer<T>,
+    attributes: &[Attribute],
+    skip_keys: &[&str],
+) -> Result<(), quick_xml::Error> {
+    let data: Vec<(&str, String)> = attributes
+        .iter()
+        .filter(|a| !skip_keys.contains(&a.key.as_str()))
+        .filter_map(|a| Some((a.key.as_str(), attribute_value_to_mxml_string(&a.value)?)))
+        .collect();
+    if data.is_empty() {
+        return OK;
+    }
+    w.create_element("Data").write_inner_content(|w| {
+        for (key, value) in &data {
+            w.create_element("Attribute")
+                .with_attribute(("name", *key))
+                .write_text_content(BytesText::new(value))?;
+        }
+        OK
+    })?;
+    OK
+}
+
+///
+/// Export an [`EventLog`] as MXML to a writer
+///
+/// Both [`quick_xml::Writer`] as well as [`std::io::Write`] are accepted.
+///
+/// The XES attributes `concept:name`, `lifecycle:transition`, `org:resource` and `time:timestamp` of events are
+/// exported as `WorkflowModelElement`, `EventType`, `Originator` and `Timestamp`, respectively.
+/// Lifecycle transitions not supported by MXML are exported as event type `unknown` (with an `unknowntype` attribute)
+/// and events without lifecycle transition are exported as `complete` events.
+/// All traces are exported as `ProcessInstance` of a single `Process` (named after the `concept:name` of the log, if present).
+///
+/// All other attributes are exported as MXML data attributes.
+/// As MXML does not support nested attributes, list and container attributes are omitted.
+pub fn export_mxml<'a, W>(
+    writer: impl Into<XMLWriterWrapper<'a, W>>,
+    log: &'a EventLog,
+) -> Result<(), quick_xml::Error>
+where
+    W: Write + 'a,
+{
+    let mut xml_writer = writer.into();
+    let writer: &mut quick_xml::Writer<_> = xml_writer.to_xml_writer();
+    writer.write_event(quick_xml::events::Event::Decl(BytesDecl::new(
+        "1.0",
+        Some("UTF-8"),
+        None,
+    )))?;
+    let process_id = log
+        .attributes
+        .iter()
+        .find(|a| a.key == ACTIVITY_NAME)
+        .and_then(|a| attribute_value_to_mxml_string(&a.value))
+        .unwrap_or_else(|| "process".to_string());
+    writer
+        .create_element("WorkflowLog")
+        .write_inner_content(|w| {
+            write_mxml_data(w, &log.attributes, &[ACTIVITY_NAME])?;
+            w.create_element("Source")
+                .with_attribute(("program", "process_mining"))
+                .write_empty()?;
+            w.create_element("Process")
+                .with_attribute(("id", process_id.as_str()))
+                .write_inner_content(|w| {
+                    for (i, t) in log.traces.iter().enumerate() {
+                        let trace_id = t
+                            .attributes
+                            .iter()
+                            .find(|a| a.key == TRACE_ID_NAME)
+                            .and_then(|a| attribute_value_to_mxml_string(&a.value))
+                            .unwrap_or_else(|| i.to_string());
+                        w.create_element("ProcessInstance")
+                            .with_attribute(("id", trace_id.as_str()))
+                            .write_inner_content(|w| {
+                                write_mxml_data(w, &t.attributes, &[TRACE_ID_NAME])?;
+                                for e in &t.events {
+                                    write_mxml_audit_trail_entry(w, &e.attributes)?;
+                                }
+                                OK
+                            })?;
+                    }
+                    OK
+                })?;
+            OK
+        })?;
+    OK
+}
+
+fn write_mxml_audit_trail_entry<T: Write>(
+    w: &mut Writer<T>,
+    attributes: &[Attribute],
+) -> Result<(), quick_xml::Error> {
+    let get = |key: &str| {
+        attributes
+            .iter()
+            .find(|a| a.key == key)
+            .and_then(|a| attribute_value_to_mxml_string(&a.value))
+    };
+    let transition = get(LIFECYCLE_TRANSITION_NAME).unwrap_or_else(|| "complete".to_string());
+    w.create_element("AuditTrailEntry")
+        .write_inner_content(|w| {
+            write_mxml_data(
+                w,
+                attributes,
+                &[
+                    ACTIVITY_NAME,
+                    LIFECYCLE_TRANSITION_NAME,
+                    RESOURCE_NAME,
+                    TIMESTAMP_NAME,
+                ],
+            )?;
+            w.create_element("WorkflowModelElement")
+                .write_text_content(BytesText::new(&get(ACTIVITY_NAME).unwrap_or_default()))?;
+            if MXML_EVENT_TYPES.contains(&transition.as_str()) {
+                w.create_element("EventType")
+                    .write_text_content(BytesText::new(&transition))?;
+            } else {
+                w.create_element("EventType")
+                    .with_attribute(("unknowntype", transition.as_str()))
+                    .write_text_content(BytesText::new("unknown"))?;
+            }
+            if let Some(timestamp) = get(TIMESTAMP_NAME) {
+                w.create_element("Timestamp")
+                    .write_text_content(BytesText::new(&timestamp))?;
+            }
+            if let Some(resource) = get(RESOURCE_NAME) {
+                w.create_element("Originator")
+                    .write_text_content(BytesText::new(&resource))?;
+            }
+            OK
+        })?;
+    OK
+}
+
+/// Export an [`EventLog`] as MXML to a [`File`]
+///
+/// If `compress_gz` is `true`, the MXML will be compressed to a `.mxml.gz` file before writing to file
+pub fn export_mxml_to_file(
+    log: &EventLog,
+    file: File,
+    compress_gz: bool,
+) -> Result<(), quick_xml::Error> {
+    let mut writer = BufWriter::new(file);
+    if compress_gz {
+        writer = write_gz(writer, |w| export_mxml(w, log))?;
+    } else {
+        export_mxml(&mut writer, log)?;
+    }
+    writer.flush()?;
+    OK
+}
+
+/// Export an [`EventLog`] as MXML to a filepath
+///
+/// Automatically selects gz-compression if filepath ends with `.gz`
+///
+/// See also [`export_mxml_to_file`], which accepts a [`File`] and boolean flag for gz-compression.
+pub fn export_mxml_to_file_path<P: AsRef<std::path::Path>>(
+    log: &EventLog,
+    path: P,
+) -> Result<(), quick_xml::Error> {
+    let is_gz = is_gz_path(&path);
+    let file = File::create(path)?;
+    export_mxml_to_file(log, file, is_gz)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_log::{
+        import_mxml::{import_mxml_slice, tests::MXML},
+        XESEditableAttribute,
+    };
+
+    use super::*;
+
+    #[test]
+    fn mxml_round_trip() {
+        let log = import_mxml_slice(MXML.as_bytes(), false, Default::default()).unwrap();
+        let mut exported = Vec::new();
+        export_mxml(&mut exported, &log).unwrap();
+        let exported_str = String::from_utf8(exported.clone()).unwrap();
+        assert!(exported_str.contains(r#"<EventType unknowntype="custom">unknown</EventType>"#));
+        assert!(exported_str.contains("A &amp; B"));
+        let reimported = import_mxml_slice(&exported, false, Default::default()).unwrap();
+        assert_eq!(reimported.traces, log.traces);
+        assert_eq!(reimported.attributes, log.attributes);
+
+        let gz = write_gz(Vec::new(), |w| export_mxml(w, &log)).unwrap();
+        let reimported = import_mxml_slice(&gz, true, Default::default()).unwrap();
+        assert_eq!(reimported.traces, log.traces);
+    }
+
+    #[test]
+    fn mxml_export_defaults() {
+        let mut log = EventLog::new();
+        let mut trace = crate::event_log::Trace::new();
+        let mut event = crate::event_log::Event::new("a".to_string());
+        event.attributes.add_attribute(Attribute::new(
+            "items".to_string(),
+            AttributeValue::List(Vec::new()),
+        ));
+        trace.events.push(event);
+        log.traces.push(trace);
+        let mut exported = Vec::new();
+        export_mxml(&mut exported, &log).unwrap();
+        let exported_str = String::from_utf8(exported).unwrap();
+        assert!(exported_str.contains(r#"<Process id="process"><ProcessInstance id="0">"#));
+        assert!(exported_str.contains("<EventType>complete</EventType>"));
+        assert!(!exported_str.contains("items"));
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     borrow::Borrow,
+    fmt::Write as _,
     fs::File,
     io::{BufWriter, Write},
 };
 
 use flate2::{write::GzEncoder, Compression};
 use quick_xml::{events::BytesDecl, Writer};
+use serde::{Deserialize, Serialize};
 
 use crate::{utils::xml_utils::XMLWriterWrapper, EventLog};
 
@@ -16,6 +18,57 @@ use super::{
 };
 const OK: Result<(), quick_xml::Error> = Ok::<(), quick_xml::Error>(());
 
+///
+/// XES version written to the `xes.version` attribute of the exported log
+///
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum XESVersion {
+    /// XES 1.0
+    V1_0,
+    /// XES 2.0 (IEEE 1849-2016)
+    #[default]
+    V2_0,
+}
+
+impl XESVersion {
+    /// Version string (e.g., `2.0`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            XESVersion::V1_0 => "1.0",
+            XESVersion::V2_0 => "2.0",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+///
+/// Options for XES Export
+///
+/// The default options produce the same output as the export functions without options.
+/// For the same input and options, the output is always byte-identical.
+///
+pub struct XESExportOptions {
+    /// If Some: Pretty-print the XML, indenting every nesting level by the given number of the given character (e.g., `(b' ', 2)`)
+    ///
+    /// Only applies if the XES is not exported to a passed [`quick_xml::Writer`] (which has its own indentation settings)
+    pub indent: Option<(u8, usize)>,
+    /// Sort all attributes (incl. nested attributes in containers) by their key
+    ///
+    /// Otherwise, attributes are written in their order of insertion. The order of list items is always preserved.
+    pub sort_attributes: bool,
+    /// XES version written to the `xes.version` attribute
+    pub xes_version: XESVersion,
+    /// Only declare the `nested-attributes` feature (`xes.features`) if nested attributes are actually present
+    ///
+    /// Otherwise, the feature is always declared.
+    /// As trace streams are not known in advance, the feature is always declared when exporting a trace stream.
+    pub declare_only_used_features: bool,
+    /// Optional date format to use for `date` attributes (instead of RFC 3339)
+    ///
+    /// See <https://docs.rs/chrono/latest/chrono/format/strftime/index.html> for all available Specifiers.
+    pub date_format: Option<String>,
+}
+
 ///
 /// Export XES (from log data and an iterator over traces) to a XML writer
 ///
@@ -32,23 +85,103 @@ where
     I: Iterator<Item = T>,
     W: Write + 'b,
 {
-    let mut xml_writer = writer.into();
+    write_xes(
+        writer,
+        log_extensions,
+        log_global_trace_attrs,
+        log_global_event_attrs,
+        log_classifiers,
+        log_attributes,
+        traces,
+        &XESExportOptions::default(),
+        true,
+    )
+}
+
+///
+/// Export XES (from log data and an iterator over traces) to a XML writer using the passed [`XESExportOptions`]
+///
+/// The `nested-attributes` feature is declared unless [`XESExportOptions::declare_only_used_features`] is set and
+/// neither the log data nor the traces contain nested attributes.
+/// Note that, to check the traces, they are iterated twice.
+/// For trace streams, which cannot be iterated twice, see [`export_xes_trace_stream_with_options`].
+///
+#[allow(clippy::too_many_arguments)]
+pub fn export_xes_with_options<'a, 'b, W, T: Borrow<Trace>, I>(
+    writer: impl Into<XMLWriterWrapper<'b, W>>,
+    log_extensions: &'a Option<&'a Vec<EventLogExtension>>,
+    log_global_trace_attrs: &'a Option<&'a Attributes>,
+    log_global_event_attrs: &'a Option<&'a Attributes>,
+    log_classifiers: &'a Option<&'a Vec<EventLogClassifier>>,
+    log_attributes: &'a Attributes,
+    traces: I,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    I: Iterator<Item = T> + Clone,
+    W: Write + 'b,
+{
+    let nested_attributes = !options.declare_only_used_features
+        || [log_global_trace_attrs, log_global_event_attrs]
+            .into_iter()
+            .any(|attrs| attrs.is_some_and(|attrs| has_nested_attributes(attrs)))
+        || has_nested_attributes(log_attributes)
+        || traces.clone().any(|t| {
+            let t = t.borrow();
+            has_nested_attributes(&t.attributes)
+                || t.events
+                    .iter()
+                    .any(|e| has_nested_attributes(&e.attributes))
+        });
+    write_xes(
+        writer,
+        log_extensions,
+        log_global_trace_attrs,
+        log_global_event_attrs,
+        log_classifiers,
+        log_attributes,
+        traces,
+        options,
+        nested_attributes,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_xes<'a, 'b, W, T: Borrow<Trace>, I>(
+    writer: impl Into<XMLWriterWrapper<'b, W>>,
+    log_extensions: &'a Option<&'a Vec<EventLogExtension>>,
+    log_global_trace_attrs: &'a Option<&'a Attributes>,
+    log_global_event_attrs: &'a Option<&'a Attributes>,
+    log_classifiers: &'a Option<&'a Vec<EventLogClassifier>>,
+    log_attributes: &'a Attributes,
+    traces: I,
+    options: &XESExportOptions,
+    nested_attributes: bool,
+) -> Result<(), quick_xml::Error>
+where
+    I: Iterator<Item = T>,
+    W: Write + 'b,
+{
+    let mut xml_writer = match (writer.into(), options.indent) {
+        (XMLWriterWrapper::Owned(w), Some((indent_char, indent_size))) => XMLWriterWrapper::Owned(
+            Writer::new_with_indent(w.into_inner(), indent_char, indent_size),
+        ),
+        (w, _) => w,
+    };
     let writer: &mut quick_xml::Writer<_> = xml_writer.to_xml_writer();
-    writer
-        .write_event(quick_xml::events::Event::Decl(BytesDecl::new(
-            "1.0",
-            Some("UTF-8"),
-            None,
-        )))
-        .unwrap();
+    writer.write_event(quick_xml::events::Event::Decl(BytesDecl::new(
+        "1.0",
+        Some("UTF-8"),
+        None,
+    )))?;
+    let mut log_tag_attributes = vec![("xes.version", options.xes_version.as_str())];
+    if nested_attributes {
+        log_tag_attributes.push(("xes.features", "nested-attributes"));
+    }
+    log_tag_attributes.push(("xmlns", "http://www.xes-standard.org/"));
     writer
         .create_element("log")
-        .with_attributes(vec![
-            ("xes.version", "2.0"),
-            // nested-attributes are not always present, but they might be so let's just say we use them
-            ("xes.features", "nested-attributes"),
-            ("xmlns", "http://www.xes-standard.org/"),
-        ])
+        .with_attributes(log_tag_attributes)
         .write_inner_content(|w| {
             // Extensions
             if let Some(extensions) = log_extensions {
@@ -67,10 +200,7 @@ where
                 w.create_element("global")
                     .with_attribute(("scope", "trace"))
                     .write_inner_content(|w| {
-                        for a in global_trace_attrs.iter() {
-                            write_xes_attribute(w, a)?;
-                        }
-                        OK
+                        write_xes_attributes(w, global_trace_attrs, options)
                     })?;
             }
             // Global event attributes
@@ -78,10 +208,7 @@ where
                 w.create_element("global")
                     .with_attribute(("scope", "event"))
                     .write_inner_content(|w| {
-                        for a in global_event_attrs.iter() {
-                            write_xes_attribute(w, a)?;
-                        }
-                        OK
+                        write_xes_attributes(w, global_event_attrs, options)
                     })?;
             }
             // Classifiers
@@ -96,20 +223,13 @@ where
                 }
             }
             // Log attributes
-            for a in log_attributes {
-                write_xes_attribute(w, a)?;
-            }
+            write_xes_attributes(w, log_attributes, options)?;
             for t in traces {
                 w.create_element("trace").write_inner_content(|w| {
-                    for a in &t.borrow().attributes {
-                        write_xes_attribute(w, a)?;
-                    }
+                    write_xes_attributes(w, &t.borrow().attributes, options)?;
                     for e in &t.borrow().events {
                         w.create_element("event").write_inner_content(|w| {
-                            for a in &e.attributes {
-                                write_xes_attribute(w, a)?;
-                            }
-                            OK
+                            write_xes_attributes(w, &e.attributes, options)
                         })?;
                     }
                     OK
@@ -121,13 +241,65 @@ where
     OK
 }
 
-fn write_xes_attribute<T>(w: &mut Writer<T>, a: &Attribute) -> Result<(), quick_xml::Error>
+/// Whether any of the attributes is nested (i.e., a list, a container or has own nested attributes)
+fn has_nested_attributes(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|a| {
+        matches!(
+            a.value,
+            AttributeValue::List(_) | AttributeValue::Container(_)
+        ) || a.own_attributes.is_some()
+    })
+}
+
+/// Write all attributes (sorted by key if [`XESExportOptions::sort_attributes`] is set)
+fn write_xes_attributes<T>(
+    w: &mut Writer<T>,
+    attributes: &[Attribute],
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    T: Write,
+{
+    if options.sort_attributes {
+        let mut sorted: Vec<&Attribute> = attributes.iter().collect();
+        sorted.sort_by(|a, b| a.key.cmp(&b.key));
+        for a in sorted {
+            write_xes_attribute(w, a, options)?;
+        }
+    } else {
+        for a in attributes {
+            write_xes_attribute(w, a, options)?;
+        }
+    }
+    OK
+}
+
+fn write_xes_attribute<T>(
+    w: &mut Writer<T>,
+    a: &Attribute,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
 where
     T: Write,
 {
     let (tag_name, value_opt): (&str, Option<String>) = match &a.value {
         super::AttributeValue::String(s) => ("string", Some(s.clone())),
-        super::AttributeValue::Date(d) => ("date", Some(d.to_rfc3339())),
+        super::AttributeValue::Date(d) => (
+            "date",
+            Some(match &options.date_format {
+                Some(date_format) => {
+                    let mut formatted = String::new();
+                    write!(formatted, "{}", d.format(date_format)).map_err(|_| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("Invalid date format {:?}", date_format),
+                        )
+                    })?;
+                    formatted
+                }
+                None => d.to_rfc3339(),
+            }),
+        ),
         super::AttributeValue::Int(i) => ("int", Some(i.to_string())),
         super::AttributeValue::Float(f) => ("float", Some(f.to_string())),
         super::AttributeValue::Boolean(b) => ("boolean", Some(b.to_string())),
@@ -146,25 +318,16 @@ where
     };
     if let AttributeValue::List(c) = &a.value {
         e.write_inner_content(|inner_w| {
+            // The order of list items is meaningful and thus never changed
             for attr in c {
-                write_xes_attribute(inner_w, attr)?;
+                write_xes_attribute(inner_w, attr, options)?;
             }
             OK
         })?;
     } else if let AttributeValue::Container(c) = &a.value {
-        e.write_inner_content(|inner_w| {
-            for attr in c {
-                write_xes_attribute(inner_w, attr)?;
-            }
-            OK
-        })?;
+        e.write_inner_content(|inner_w| write_xes_attributes(inner_w, c, options))?;
     } else if let Some(own_nested_attrs) = &a.own_attributes {
-        e.write_inner_content(|inner_w| {
-            for own_attr in own_nested_attrs {
-                write_xes_attribute(inner_w, own_attr)?;
-            }
-            OK
-        })?;
+        e.write_inner_content(|inner_w| write_xes_attributes(inner_w, own_nested_attrs, options))?;
     } else {
         e.write_empty()?;
     }
@@ -195,17 +358,69 @@ where
     )
 }
 
+///
+/// Export an [`EventLog`] to a writer using the passed [`XESExportOptions`]
+///
+/// Both [`quick_xml::Writer`] as well as [`std::io::Write`] are accepted
+///
+pub fn export_xes_event_log_with_options<'a, W>(
+    writer: impl Into<XMLWriterWrapper<'a, W>>,
+    log: &'a EventLog,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    W: Write + 'a,
+{
+    export_xes_with_options(
+        writer,
+        &log.extensions.as_ref(),
+        &log.global_trace_attrs.as_ref(),
+        &log.global_event_attrs.as_ref(),
+        &log.classifiers.as_ref(),
+        &log.attributes,
+        log.traces.iter(),
+        options,
+    )
+}
+
+/// Export an [`EventLog`] to a writer, compressing the XES using gzip (i.e., as `.xes.gz`)
+///
+/// The gzip stream is finished before returning, so the written data is a complete `.xes.gz` archive.
+/// Returns the passed writer (e.g., to retrieve the compressed bytes when writing to a [`Vec<u8>`]).
+pub fn export_xes_event_log_gz<W: Write>(writer: W, log: &EventLog) -> Result<W, quick_xml::Error> {
+    write_gz(writer, |w| export_xes_event_log(w, log))
+}
+
 /// Export an [`EventLog`] to a [`File`]
+///
+/// If `compress_gz` is `true`, the XES will be compressed to a `.xes.gz` file before writing to file
 pub fn export_xes_event_log_to_file(
     log: &EventLog,
     file: File,
     compress_gz: bool,
 ) -> Result<(), quick_xml::Error> {
+    export_xes_event_log_to_file_with_options(log, file, compress_gz, &XESExportOptions::default())
+}
+
+/// Export an [`EventLog`] to a [`File`] using the passed [`XESExportOptions`]
+///
+/// If `compress_gz` is `true`, the XES will be compressed to a `.xes.gz` file before writing to file
+pub fn export_xes_event_log_to_file_with_options(
+    log: &EventLog,
+    file: File,
+    compress_gz: bool,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error> {
+    let mut writer = BufWriter::new(file);
     if compress_gz {
-        let encoder = GzEncoder::new(BufWriter::new(file), Compression::fast());
-        return export_xes_event_log(&mut Writer::new(BufWriter::new(encoder)), log);
+        writer = write_gz(writer, |w| {
+            export_xes_event_log_with_options(w, log, options)
+        })?;
+    } else {
+        export_xes_event_log_with_options(&mut writer, log, options)?;
     }
-    export_xes_event_log(&mut Writer::new(BufWriter::new(file)), log)
+    writer.flush()?;
+    OK
 }
 
 /// Export an [`EventLog`] to a filepath
@@ -217,13 +432,20 @@ pub fn export_xes_event_log_to_file_path<P: AsRef<std::path::Path>>(
     log: &EventLog,
     path: P,
 ) -> Result<(), quick_xml::Error> {
-    let is_gz = path
-        .as_ref()
-        .as_os_str()
-        .to_str()
-        .is_some_and(|p| p.ends_with(".gz"));
+    export_xes_event_log_to_file_path_with_options(log, path, &XESExportOptions::default())
+}
+
+/// Export an [`EventLog`] to a filepath using the passed [`XESExportOptions`]
+///
+/// Automatically selects gz-compression if filepath ends with `.gz`
+pub fn export_xes_event_log_to_file_path_with_options<P: AsRef<std::path::Path>>(
+    log: &EventLog,
+    path: P,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error> {
+    let is_gz = is_gz_path(&path);
     let file = File::create(path)?;
-    export_xes_event_log_to_file(log, file, is_gz)
+    export_xes_event_log_to_file_with_options(log, file, is_gz, options)
 }
 
 /// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a XML [`Writer`]
@@ -236,7 +458,28 @@ where
     W: Write + 'a,
     I: Iterator<Item = T>,
 {
-    export_xes(
+    export_xes_trace_stream_with_options(
+        writer,
+        trace_stream,
+        log_data,
+        &XESExportOptions::default(),
+    )
+}
+
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a XML [`Writer`] using the passed [`XESExportOptions`]
+///
+/// As the traces are not known in advance, the `nested-attributes` feature is always declared.
+pub fn export_xes_trace_stream_with_options<'a, W, T: Borrow<Trace>, I>(
+    writer: impl Into<XMLWriterWrapper<'a, W>>,
+    trace_stream: I,
+    log_data: XESOuterLogData,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    W: Write + 'a,
+    I: Iterator<Item = T>,
+{
+    write_xes(
         writer,
         &Some(log_data.extensions.as_ref()),
         &Some(log_data.global_trace_attrs.as_ref()),
@@ -244,9 +487,29 @@ where
         &Some(log_data.classifiers.as_ref()),
         &log_data.log_attributes,
         trace_stream,
+        options,
+        true,
     )
 }
 
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a writer, compressing the XES using gzip
+///
+/// Traces are compressed while they are consumed from the iterator, i.e., the uncompressed XES is never fully held in memory.
+/// The gzip stream is finished before returning, so the written data is a complete `.xes.gz` archive.
+/// Returns the passed writer (e.g., to retrieve the compressed bytes when writing to a [`Vec<u8>`]).
+pub fn export_xes_trace_stream_gz<W: Write, T: Borrow<Trace>, I>(
+    writer: W,
+    trace_stream: I,
+    log_data: XESOuterLogData,
+) -> Result<W, quick_xml::Error>
+where
+    I: Iterator<Item = T>,
+{
+    write_gz(writer, |w| {
+        export_xes_trace_stream(w, trace_stream, log_data)
+    })
+}
+
 /// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a [`File`]
 ///
 /// If `compress_gz` is `true`, the XES will be compressed to a `.xes.gz` file before writing to file
@@ -259,21 +522,106 @@ pub fn export_xes_trace_stream_to_file<T: Borrow<Trace>, I>(
 where
     I: Iterator<Item = T>,
 {
+    export_xes_trace_stream_to_file_with_options(
+        trace_stream,
+        log_data,
+        file,
+        compress_gz,
+        &XESExportOptions::default(),
+    )
+}
+
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a [`File`] using the passed [`XESExportOptions`]
+///
+/// If `compress_gz` is `true`, the XES will be compressed to a `.xes.gz` file before writing to file
+pub fn export_xes_trace_stream_to_file_with_options<T: Borrow<Trace>, I>(
+    trace_stream: I,
+    log_data: XESOuterLogData,
+    file: File,
+    compress_gz: bool,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    I: Iterator<Item = T>,
+{
+    let mut writer = BufWriter::new(file);
     if compress_gz {
-        let encoder = GzEncoder::new(BufWriter::new(file), Compression::fast());
-        return export_xes_trace_stream(
-            &mut Writer::new(BufWriter::new(encoder)),
-            trace_stream,
-            log_data,
-        );
+        writer = write_gz(writer, |w| {
+            export_xes_trace_stream_with_options(w, trace_stream, log_data, options)
+        })?;
+    } else {
+        export_xes_trace_stream_with_options(&mut writer, trace_stream, log_data, options)?;
     }
-    export_xes_trace_stream(
-        &mut Writer::new(BufWriter::new(file)),
+    writer.flush()?;
+    OK
+}
+
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a filepath
+///
+/// Automatically selects gz-compression if filepath ends with `.gz`
+///
+/// See also [`export_xes_trace_stream_to_file`], which accepts a [`File`] and boolean flag for gz-compression.
+pub fn export_xes_trace_stream_to_file_path<T: Borrow<Trace>, I, P: AsRef<std::path::Path>>(
+    trace_stream: I,
+    log_data: XESOuterLogData,
+    path: P,
+) -> Result<(), quick_xml::Error>
+where
+    I: Iterator<Item = T>,
+{
+    export_xes_trace_stream_to_file_path_with_options(
         trace_stream,
         log_data,
+        path,
+        &XESExportOptions::default(),
     )
 }
 
+/// Export a trace stream (i.e., [`Iterator`] over [`Trace`]) and [`XESOuterLogData`] to a filepath using the passed [`XESExportOptions`]
+///
+/// Automatically selects gz-compression if filepath ends with `.gz`
+pub fn export_xes_trace_stream_to_file_path_with_options<
+    T: Borrow<Trace>,
+    I,
+    P: AsRef<std::path::Path>,
+>(
+    trace_stream: I,
+    log_data: XESOuterLogData,
+    path: P,
+    options: &XESExportOptions,
+) -> Result<(), quick_xml::Error>
+where
+    I: Iterator<Item = T>,
+{
+    let is_gz = is_gz_path(&path);
+    let file = File::create(path)?;
+    export_xes_trace_stream_to_file_with_options(trace_stream, log_data, file, is_gz, options)
+}
+
+pub(crate) fn is_gz_path<P: AsRef<std::path::Path>>(path: P) -> bool {
+    path.as_ref()
+        .as_os_str()
+        .to_str()
+        .is_some_and(|p| p.ends_with(".gz"))
+}
+
+///
+/// Run `write_fn` on a buffered gzip encoder wrapping `writer`
+///
+/// Afterwards, the buffer is flushed and the gzip stream is finished (propagating any IO errors).
+/// Returns the underlying `writer`.
+pub(crate) fn write_gz<W: Write, F>(writer: W, write_fn: F) -> Result<W, quick_xml::Error>
+where
+    F: FnOnce(&mut BufWriter<&mut GzEncoder<W>>) -> Result<(), quick_xml::Error>,
+{
+    let mut encoder = GzEncoder::new(writer, Compression::fast());
+    let mut buf_writer = BufWriter::new(&mut encoder);
+    write_fn(&mut buf_writer)?;
+    buf_writer.flush()?;
+    drop(buf_writer);
+    Ok(encoder.finish()?)
+}
+
 fn serialize_classifier(classifier_keys: &[String]) -> String {
     let should_quote = classifier_keys.iter().any(|key| key.contains(' '));
     if should_quote {
@@ -298,13 +646,135 @@ mod export_xes_tests {
             event_log_struct::EventLogExtension,
             export_xes::{export_xes_event_log, serialize_classifier},
             stream_xes::{parse_classifier_key, XESOuterLogData},
+            AttributeValue, XESEditableAttribute,
         },
         import_xes_file, stream_xes_from_path,
-        utils::test_utils::get_test_data_path,
+        utils::test_utils::{event_log_from_activities, get_test_data_path},
         XESImportOptions,
     };
 
-    use super::export_xes_trace_stream_to_file;
+    use super::{
+        export_xes_event_log_gz, export_xes_event_log_with_options, export_xes_trace_stream_gz,
+        export_xes_trace_stream_to_file, export_xes_trace_stream_to_file_path, XESExportOptions,
+        XESVersion,
+    };
+
+    fn small_log() -> crate::EventLog {
+        let mut log = event_log_from_activities(&[&["a", "b"], &["a", "c", "b"]]);
+        for (i, trace) in log.traces.iter_mut().enumerate() {
+            trace.attributes.add_to_attributes(
+                "concept:name".to_string(),
+                AttributeValue::String(format!("case_{i}")),
+            );
+        }
+        log
+    }
+
+    #[test]
+    fn test_xes_export_options() {
+        let log = small_log();
+        let export = |log: &crate::EventLog, options: &XESExportOptions| {
+            let mut data = Vec::new();
+            export_xes_event_log_with_options(&mut data, log, options).map(|_| data)
+        };
+        // Default options do not change the output
+        let mut default_data = Vec::new();
+        export_xes_event_log(&mut default_data, &log).unwrap();
+        assert_eq!(
+            export(&log, &XESExportOptions::default()).unwrap(),
+            default_data
+        );
+
+        let options = XESExportOptions {
+            indent: Some((b' ', 2)),
+            sort_attributes: true,
+            xes_version: XESVersion::V1_0,
+            declare_only_used_features: true,
+            date_format: Some("%Y-%m-%d".to_string()),
+        };
+        let mut reordered_log = small_log();
+        for t in &mut reordered_log.traces {
+            t.attributes.reverse();
+            for e in &mut t.events {
+                e.attributes.add_to_attributes(
+                    "time:timestamp".to_string(),
+                    AttributeValue::Date("2024-01-02T03:04:05Z".parse().unwrap()),
+                );
+                e.attributes.reverse();
+            }
+        }
+        let data = String::from_utf8(export(&reordered_log, &options).unwrap()).unwrap();
+        assert!(data.contains(r#"<log xes.version="1.0" xmlns="http://www.xes-standard.org/">"#));
+        assert!(data.contains(
+            "\n    <event>\n      <string key=\"concept:name\" value=\"a\"/>\n      <date key=\"time:timestamp\" value=\"2024-01-02\"/>"
+        ));
+        // Byte-stable output, independent of the insertion order of attributes
+        let mut log_with_timestamps = small_log();
+        for e in log_with_timestamps
+            .traces
+            .iter_mut()
+            .flat_map(|t| t.events.iter_mut())
+        {
+            e.attributes.add_to_attributes(
+                "time:timestamp".to_string(),
+                AttributeValue::Date("2024-01-02T03:04:05Z".parse().unwrap()),
+            );
+        }
+        assert_eq!(
+            export(&log_with_timestamps, &options).unwrap(),
+            data.as_bytes()
+        );
+
+        // The nested-attributes feature is declared if nested attributes are present
+        reordered_log.traces[1].events[2]
+            .attributes
+            .add_to_attributes("items".to_string(), AttributeValue::List(Vec::new()));
+        let data = String::from_utf8(export(&reordered_log, &options).unwrap()).unwrap();
+        assert!(data.contains(r#"xes.features="nested-attributes""#));
+
+        // Errors are returned instead of panicking
+        let invalid_options = XESExportOptions {
+            date_format: Some("%Q".to_string()),
+            ..options
+        };
+        assert!(export(&reordered_log, &invalid_options).is_err());
+    }
+
+    #[test]
+    fn test_xes_export_gz_round_trip() {
+        let log = small_log();
+        let data = export_xes_event_log_gz(Vec::new(), &log).unwrap();
+        // gzip magic bytes
+        assert_eq!(data[..2], [0x1f, 0x8b]);
+        let log2 = crate::import_xes_slice(&data, true, XESImportOptions::default()).unwrap();
+        assert!(log2.traces == log.traces);
+
+        let streamed =
+            export_xes_trace_stream_gz(Vec::new(), log.traces.iter(), XESOuterLogData::default())
+                .unwrap();
+        let log3 = crate::import_xes_slice(&streamed, true, XESImportOptions::default()).unwrap();
+        assert!(log3.traces == log.traces);
+    }
+
+    #[test]
+    fn test_trace_stream_export_path_detects_gz() {
+        let log = small_log();
+        let dir = std::env::temp_dir();
+        for name in ["trace-stream-export.xes.gz", "trace-stream-export.xes"] {
+            let path = dir.join(format!("{}-{}", std::process::id(), name));
+            export_xes_trace_stream_to_file_path(
+                log.traces.iter(),
+                XESOuterLogData::default(),
+                &path,
+            )
+            .unwrap();
+            let data = std::fs::read(&path).unwrap();
+            assert_eq!(data[..2] == [0x1f, 0x8b], name.ends_with(".gz"));
+            let log2 = import_xes_file(&path, XESImportOptions::default()).unwrap();
+            assert!(log2.traces == log.traces);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
 
     #[test]
     fn test_xes_export_std_writer() {
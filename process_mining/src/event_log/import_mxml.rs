@@ -0,0 +1,454 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use flate2::bufread::GzDecoder;
+use quick_xml::events::BytesStart;
+use quick_xml::Error as QuickXMLError;
+use quick_xml::Reader;
+
+use super::{
+    constants::{
+        ACTIVITY_NAME, LIFECYCLE_TRANSITION_NAME, RESOURCE_NAME, TIMESTAMP_NAME, TRACE_ID_NAME,
+    },
+    event_log_struct::{EventLogClassifier, EventLogExtension},
+    export_xes::is_gz_path,
+    import_xes::XESImportOptions,
+    stream_xes::parse_date_from_str,
+    xes_extensions::{
+        standard_log_extensions, CONCEPT_EXTENSION_URI, LIFECYCLE_EXTENSION_URI, ORG_EXTENSION_URI,
+        TIME_EXTENSION_URI,
+    },
+    AttributeValue, Attributes, Event, EventLog, Trace, XESEditableAttribute,
+};
+
+///
+/// Error encountered while parsing MXML
+///
+#[derive(Debug, Clone)]
+pub enum MXMLParseError {
+    /// There is no top-level `<WorkflowLog>`
+    NoTopLevelWorkflowLog,
+    /// An `<AuditTrailEntry>` was encountered outside a `<ProcessInstance>`
+    AuditTrailEntryOutsideProcessInstance,
+    /// IO errror
+    IOError(std::rc::Rc<std::io::Error>),
+    /// XML error (e.g., incorrect XML format )
+    XMLParsingError(QuickXMLError),
+}
+
+impl std::fmt::Display for MXMLParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse MXML: {:?}", self)
+    }
+}
+
+impl std::error::Error for MXMLParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MXMLParseError::IOError(e) => Some(e.as_ref()),
+            MXMLParseError::XMLParsingError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MXMLParseError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IOError(std::rc::Rc::new(e))
+    }
+}
+
+impl From<QuickXMLError> for MXMLParseError {
+    fn from(e: QuickXMLError) -> Self {
+        Self::XMLParsingError(e)
+    }
+}
+
+/// XES extensions of the attributes to which MXML elements are mapped
+pub(crate) fn mxml_extensions() -> Vec<EventLogExtension> {
+    standard_log_extensions(&[
+        CONCEPT_EXTENSION_URI,
+        LIFECYCLE_EXTENSION_URI,
+        ORG_EXTENSION_URI,
+        TIME_EXTENSION_URI,
+    ])
+}
+
+fn add_attribute(
+    attributes: &mut Attributes,
+    key: &str,
+    value: AttributeValue,
+    not_ignored: &Option<HashSet<String>>,
+) {
+    if not_ignored
+        .as_ref()
+        .is_some_and(|not_ignored| !not_ignored.contains(key))
+    {
+        return;
+    }
+    attributes.add_to_attributes(key.to_string(), value);
+}
+
+fn get_attribute_string(t: &BytesStart<'_>, key: &str) -> Result<Option<String>, MXMLParseError> {
+    match t.try_get_attribute(key)? {
+        Some(a) => Ok(Some(a.unescape_value()?.to_string())),
+        None => Ok(None),
+    }
+}
+
+///
+/// Parse MXML from the given reader
+///
+/// MXML elements are mapped to XES concepts as follows:
+///
+/// - `ProcessInstance` to [`Trace`] (with its `id` as `concept:name`)
+/// - `AuditTrailEntry` to [`Event`]
+/// - `WorkflowModelElement` to `concept:name`
+/// - `EventType` to `lifecycle:transition` (using the `unknowntype` attribute for the event type `unknown`, if present)
+/// - `Originator` to `org:resource`
+/// - `Timestamp` to `time:timestamp`
+/// - `Data` attributes to string attributes (of the log, trace or event)
+///
+/// The `id` of the (first) `Process` is used as `concept:name` of the log.
+/// The attribute ignore lists, date format and event sorting of the passed [`XESImportOptions`] are applied as for XES.
+pub fn import_mxml<T>(reader: T, options: XESImportOptions) -> Result<EventLog, MXMLParseError>
+where
+    T: BufRead,
+{
+    let mut reader = Reader::from_reader(reader);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut log = EventLog {
+        extensions: Some(mxml_extensions()),
+        classifiers: Some(vec![EventLogClassifier {
+            name: "MXML Legacy Classifier".to_string(),
+            keys: vec![
+                ACTIVITY_NAME.to_string(),
+                LIFECYCLE_TRANSITION_NAME.to_string(),
+            ],
+        }]),
+        ..Default::default()
+    };
+    let mut encountered_log = false;
+    let mut current_trace: Option<Trace> = None;
+    let mut current_event: Option<Event> = None;
+    // Key of the currently open data attribute or element (e.g., `Originator`)
+    let mut current_key: Option<String> = None;
+    let mut unknown_type: Option<String> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            quick_xml::events::Event::Start(t) => {
+                let name = t.local_name();
+                if !encountered_log && name.as_ref() != b"WorkflowLog" {
+                    return Err(MXMLParseError::NoTopLevelWorkflowLog);
+                }
+                text.clear();
+                match name.as_ref() {
+                    b"WorkflowLog" => encountered_log = true,
+                    b"Process" => {
+                        if let Some(id) = get_attribute_string(&t, "id")? {
+                            if log.attributes.get_by_key(ACTIVITY_NAME).is_none() {
+                                add_attribute(
+                                    &mut log.attributes,
+                                    ACTIVITY_NAME,
+                                    AttributeValue::String(id),
+                                    &options.ignore_log_attributes_except,
+                                );
+                            }
+                        }
+                    }
+                    b"ProcessInstance" => {
+                        let mut trace = Trace::new();
+                        if let Some(id) = get_attribute_string(&t, "id")? {
+                            add_attribute(
+                                &mut trace.attributes,
+                                TRACE_ID_NAME,
+                                AttributeValue::String(id),
+                                &options.ignore_trace_attributes_except,
+                            );
+                        }
+                        current_trace = Some(trace);
+                    }
+                    b"AuditTrailEntry" => {
+                        if current_trace.is_none() {
+                            return Err(MXMLParseError::AuditTrailEntryOutsideProcessInstance);
+                        }
+                        current_event = Some(Event {
+                            attributes: Attributes::new(),
+                        });
+                    }
+                    b"Attribute" => current_key = get_attribute_string(&t, "name")?,
+                    b"WorkflowModelElement" => current_key = Some(ACTIVITY_NAME.to_string()),
+                    b"EventType" => {
+                        current_key = Some(LIFECYCLE_TRANSITION_NAME.to_string());
+                        unknown_type = get_attribute_string(&t, "unknowntype")?;
+                    }
+                    b"Originator" => current_key = Some(RESOURCE_NAME.to_string()),
+                    b"Timestamp" => current_key = Some(TIMESTAMP_NAME.to_string()),
+                    _ => {}
+                }
+            }
+            quick_xml::events::Event::Empty(t) => {
+                let name = t.local_name();
+                match name.as_ref() {
+                    b"WorkflowLog" => encountered_log = true,
+                    b"ProcessInstance" => {
+                        let mut trace = Trace::new();
+                        if let Some(id) = get_attribute_string(&t, "id")? {
+                            add_attribute(
+                                &mut trace.attributes,
+                                TRACE_ID_NAME,
+                                AttributeValue::String(id),
+                                &options.ignore_trace_attributes_except,
+                            );
+                        }
+                        log.traces.push(trace);
+                    }
+                    b"Attribute" => {
+                        if let Some(key) = get_attribute_string(&t, "name")? {
+                            let (attributes, not_ignored) =
+                                match (&mut current_event, &mut current_trace) {
+                                    (Some(e), _) => {
+                                        (&mut e.attributes, &options.ignore_event_attributes_except)
+                                    }
+                                    (None, Some(t)) => {
+                                        (&mut t.attributes, &options.ignore_trace_attributes_except)
+                                    }
+                                    (None, None) => {
+                                        (&mut log.attributes, &options.ignore_log_attributes_except)
+                                    }
+                                };
+                            add_attribute(
+                                attributes,
+                                &key,
+                                AttributeValue::String(String::new()),
+                                not_ignored,
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            quick_xml::events::Event::Text(t) => {
+                text.push_str(&t.unescape()?);
+            }
+            quick_xml::events::Event::CData(t) => {
+                text.push_str(&String::from_utf8_lossy(&t));
+            }
+            quick_xml::events::Event::End(t) => match t.local_name().as_ref() {
+                b"Attribute"
+                | b"WorkflowModelElement"
+                | b"EventType"
+                | b"Originator"
+                | b"Timestamp" => {
+                    if let Some(key) = current_key.take() {
+                        let value = if key == LIFECYCLE_TRANSITION_NAME && text == "unknown" {
+                            AttributeValue::String(
+                                unknown_type.take().unwrap_or_else(|| text.clone()),
+                            )
+                        } else if key == TIMESTAMP_NAME && current_event.is_some() {
+                            match parse_date_from_str(&text, &options.date_format) {
+                                Some(dt) => AttributeValue::Date(dt),
+                                None => AttributeValue::String(text.clone()),
+                            }
+                        } else {
+                            AttributeValue::String(text.clone())
+                        };
+                        let (attributes, not_ignored) =
+                            match (&mut current_event, &mut current_trace) {
+                                (Some(e), _) => {
+                                    (&mut e.attributes, &options.ignore_event_attributes_except)
+                                }
+                                (None, Some(t)) => {
+                                    (&mut t.attributes, &options.ignore_trace_attributes_except)
+                                }
+                                (None, None) => {
+                                    (&mut log.attributes, &options.ignore_log_attributes_except)
+                                }
+                            };
+                        add_attribute(attributes, &key, value, not_ignored);
+                    }
+                    text.clear();
+                }
+                b"AuditTrailEntry" => {
+                    if let (Some(e), Some(t)) = (current_event.take(), &mut current_trace) {
+                        t.events.push(e);
+                    }
+                }
+                b"ProcessInstance" => {
+                    if let Some(mut trace) = current_trace.take() {
+                        if let Some(key) = &options.sort_events_with_timestamp_key {
+                            trace.events.sort_by_key(|e| {
+                                e.attributes
+                                    .get_by_key(key)
+                                    .and_then(|a| a.value.try_as_date().copied())
+                            });
+                        }
+                        log.traces.push(trace);
+                    }
+                }
+                _ => {}
+            },
+            quick_xml::events::Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    if !encountered_log {
+        return Err(MXMLParseError::NoTopLevelWorkflowLog);
+    }
+    Ok(log)
+}
+
+///
+/// Import an MXML [`EventLog`] from a file path
+///
+/// Automatically decompresses files ending with `.gz` (e.g., `.mxml.gz`)
+///
+pub fn import_mxml_file<P: AsRef<std::path::Path>>(
+    path: P,
+    options: XESImportOptions,
+) -> Result<EventLog, MXMLParseError> {
+    let is_gz = is_gz_path(&path);
+    let file = File::open(path)?;
+    if is_gz {
+        let dec = GzDecoder::new(BufReader::new(file));
+        import_mxml(BufReader::new(dec), options)
+    } else {
+        import_mxml(BufReader::new(file), options)
+    }
+}
+
+///
+/// Import an MXML [`EventLog`] from a byte slice (&\[u8\])
+///
+/// * `is_compressed_gz`: Parse the passed `mxml_data` as a compressed .gz archive
+///
+pub fn import_mxml_slice(
+    mxml_data: &[u8],
+    is_compressed_gz: bool,
+    options: XESImportOptions,
+) -> Result<EventLog, MXMLParseError> {
+    if is_compressed_gz {
+        let gz = GzDecoder::new(mxml_data);
+        return import_mxml(BufReader::new(gz), options);
+    }
+    import_mxml(BufReader::new(mxml_data), options)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::event_log::import_xes::build_ignore_attributes;
+
+    use super::*;
+
+    pub(crate) const MXML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<WorkflowLog xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="WorkflowLog.xsd">
+    <Data>
+        <Attribute name="app.name">Legacy ProM</Attribute>
+    </Data>
+    <Source program="ProM"/>
+    <Process id="order handling">
+        <ProcessInstance id="case 1">
+            <Data>
+                <Attribute name="customer">A &amp; B</Attribute>
+            </Data>
+            <AuditTrailEntry>
+                <WorkflowModelElement>ship</WorkflowModelElement>
+                <EventType>complete</EventType>
+                <Timestamp>2008-01-02T10:00:00.000+01:00</Timestamp>
+                <Originator>Pete</Originator>
+            </AuditTrailEntry>
+            <AuditTrailEntry>
+                <Data>
+                    <Attribute name="amount">100</Attribute>
+                </Data>
+                <WorkflowModelElement>register</WorkflowModelElement>
+                <EventType unknowntype="custom">unknown</EventType>
+                <Timestamp>2008-01-01T10:00:00.000+01:00</Timestamp>
+                <Originator>Sue</Originator>
+            </AuditTrailEntry>
+        </ProcessInstance>
+        <ProcessInstance id="case 2"/>
+    </Process>
+</WorkflowLog>
+"#;
+
+    #[test]
+    fn import_mxml_str() {
+        let log = import_mxml_slice(MXML.as_bytes(), false, XESImportOptions::default()).unwrap();
+        assert_eq!(
+            log.attributes.get_by_key(ACTIVITY_NAME).unwrap().value,
+            AttributeValue::String("order handling".to_string())
+        );
+        assert!(log.attributes.get_by_key("app.name").is_some());
+        assert_eq!(log.traces.len(), 2);
+        let trace = &log.traces[0];
+        assert_eq!(
+            trace.attributes.get_by_key("customer").unwrap().value,
+            AttributeValue::String("A & B".to_string())
+        );
+        assert_eq!(trace.events.len(), 2);
+        let e = &trace.events[1];
+        assert_eq!(
+            e.attributes.get_by_key(ACTIVITY_NAME).unwrap().value,
+            AttributeValue::String("register".to_string())
+        );
+        assert_eq!(
+            e.attributes
+                .get_by_key(LIFECYCLE_TRANSITION_NAME)
+                .unwrap()
+                .value,
+            AttributeValue::String("custom".to_string())
+        );
+        assert_eq!(
+            e.attributes.get_by_key(RESOURCE_NAME).unwrap().value,
+            AttributeValue::String("Sue".to_string())
+        );
+        assert!(matches!(
+            e.attributes.get_by_key(TIMESTAMP_NAME).unwrap().value,
+            AttributeValue::Date(_)
+        ));
+        assert!(log.traces[1].events.is_empty());
+    }
+
+    #[test]
+    fn import_mxml_with_options() {
+        let log = import_mxml_slice(
+            MXML.as_bytes(),
+            false,
+            XESImportOptions {
+                ignore_event_attributes_except: Some(build_ignore_attributes([
+                    ACTIVITY_NAME,
+                    TIMESTAMP_NAME,
+                ])),
+                ignore_trace_attributes_except: Some(build_ignore_attributes(Vec::<&str>::new())),
+                sort_events_with_timestamp_key: Some(TIMESTAMP_NAME.to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let trace = &log.traces[0];
+        assert!(trace.attributes.is_empty());
+        assert_eq!(trace.events[0].attributes.len(), 2);
+        assert_eq!(
+            trace.events[0]
+                .attributes
+                .get_by_key(ACTIVITY_NAME)
+                .unwrap()
+                .value,
+            AttributeValue::String("register".to_string())
+        );
+    }
+
+    #[test]
+    fn import_invalid_mxml() {
+        assert!(matches!(
+            import_mxml_slice(b"<log></log>", false, XESImportOptions::default()),
+            Err(MXMLParseError::NoTopLevelWorkflowLog)
+        ));
+    }
+}
@@ -46,6 +46,88 @@ impl std::fmt::Display for XESParseError {
     }
 }
 
+///
+/// Location in XES data (e.g., at which an [`XESParseError`] was encountered)
+///
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct XESParseLocation {
+    /// Byte offset (in the uncompressed XES data) of the XML tag
+    pub byte_offset: u64,
+    /// Line of the XML tag (starting at 1)
+    pub line: u64,
+    /// Column of the XML tag in bytes (starting at 1)
+    pub column: u64,
+    /// Path of the XML element, e.g., `log/trace[1042]/event[3]/date`
+    ///
+    /// The indices of traces and events start at 0 and count all traces of the log (or events of the trace, respectively),
+    /// including traces and events which were skipped or filtered out.
+    pub path: String,
+}
+
+impl std::fmt::Display for XESParseLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (line {}, column {}, byte offset {})",
+            self.path, self.line, self.column, self.byte_offset
+        )
+    }
+}
+
+///
+/// [`XESParseError`] together with the location in the XES data at which it was encountered
+///
+/// Returned by the XES import functions (e.g., [`import_xes`] and [`import_xes_with_warnings`], both as error and as warnings).
+/// For trace streams, see [`super::stream_xes::XESParsingTraceStream::last_error_location`].
+///
+#[derive(Debug, Clone)]
+pub struct LocatedXESParseError {
+    /// The encountered error
+    pub error: XESParseError,
+    /// Location in the XES data at which the error was encountered
+    ///
+    /// Empty (i.e., the default location) for errors not encountered in the XES data (e.g., if the file could not be opened)
+    pub location: XESParseLocation,
+    /// Additional details on the error, e.g., the key and the value of an attribute with an invalid value
+    pub detail: Option<Box<str>>,
+}
+
+impl std::fmt::Display for LocatedXESParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to parse XES at {}: {:?}",
+            self.location, self.error
+        )?;
+        if let Some(detail) = &self.detail {
+            write!(f, " ({detail})")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<XESParseError> for LocatedXESParseError {
+    fn from(error: XESParseError) -> Self {
+        Self {
+            error,
+            location: XESParseLocation::default(),
+            detail: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LocatedXESParseError {
+    fn from(e: std::io::Error) -> Self {
+        XESParseError::from(e).into()
+    }
+}
+
+impl std::error::Error for LocatedXESParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
 impl std::error::Error for XESParseError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -106,6 +188,16 @@ pub struct XESImportOptions {
     ///   * If no value is present or it is invalid, the global default event attribute value with the provided key will be used (if it exists)
     ///   * if no valid timestamp is available from the event or the global default, it will be sorted before all other events (in stable ordering)
     pub sort_events_with_timestamp_key: Option<String>,
+    /// Lenient parsing: Skip malformed attributes and traces instead of aborting the import
+    ///
+    /// * Attributes with invalid values (e.g., dates which cannot be parsed) are skipped, instead of using a default value
+    /// * Malformed traces (e.g., invalid XML or unexpected XES structure) are skipped and parsing continues with the next trace
+    ///
+    /// All skipped problems are collected as warnings (see [`import_xes_with_warnings`] and [`super::stream_xes::XESParsingTraceStream::warnings`]).
+    /// Problems in the log header (i.e., before the first trace) and IO errors are not recovered from and still abort parsing.
+    /// After recovering from an XML error, the names of closing XML tags are no longer checked.
+    #[serde(default)]
+    pub lenient: bool,
 }
 ///
 /// Construct a `HashSet<Vec<u8>>` from a _collection_ of String, &str, ...
@@ -120,22 +212,53 @@ where
 }
 
 /// Parse XES from the given reader
-pub fn import_xes<T>(reader: T, options: XESImportOptions) -> Result<EventLog, XESParseError>
+///
+/// The returned error includes its location in the XES data (see [`LocatedXESParseError`]).
+pub fn import_xes<T>(reader: T, options: XESImportOptions) -> Result<EventLog, LocatedXESParseError>
 where
     T: BufRead,
 {
-    let (mut trace_stream, log_data) = super::stream_xes::XESParsingTraceStream::try_new(
+    import_xes_with_warnings(reader, options).map(|(log, _warnings)| log)
+}
+
+///
+/// Parse XES from the given reader, also returning all warnings encountered while parsing
+///
+/// Warnings include attributes with invalid values and, in lenient mode (see [`XESImportOptions::lenient`]), skipped traces.
+/// The returned error and all warnings include their location in the XES data (see [`LocatedXESParseError`]).
+///
+/// Note that errors in the log header (i.e., before the first trace) are returned as error even in lenient mode.
+///
+pub fn import_xes_with_warnings<T>(
+    reader: T,
+    options: XESImportOptions,
+) -> Result<(EventLog, Vec<LocatedXESParseError>), LocatedXESParseError>
+where
+    T: BufRead,
+{
+    let (mut trace_stream, log_data) = super::stream_xes::XESParsingTraceStream::try_new_located(
         Box::new(Reader::from_reader(Box::new(reader))),
         options,
     )?;
 
     let traces: Vec<Trace> = trace_stream.collect();
 
-    if let Some(e) = trace_stream.error {
-        return Err(e);
+    if let Some(error) = trace_stream.error.take() {
+        let location = trace_stream
+            .last_error_location()
+            .cloned()
+            .unwrap_or_default();
+        return Err(LocatedXESParseError {
+            error,
+            location,
+            detail: None,
+        });
     }
 
-    Ok(EventLog::from_traces_and_log_data(traces, log_data))
+    Ok((
+        EventLog::from_traces_and_log_data(traces, log_data),
+        trace_stream.take_warnings(),
+    ))
 }
 
 ///
@@ -144,7 +267,7 @@ where
 pub fn import_xes_file<P: AsRef<std::path::Path>>(
     path: P,
     options: XESImportOptions,
-) -> Result<EventLog, XESParseError> {
+) -> Result<EventLog, LocatedXESParseError> {
     if path
         .as_ref()
         .as_os_str()
@@ -165,7 +288,10 @@ pub fn import_xes_file<P: AsRef<std::path::Path>>(
 ///
 /// Import a XES [`EventLog`] directly from a string
 ///
-pub fn import_xes_str(xes_str: &str, options: XESImportOptions) -> Result<EventLog, XESParseError> {
+pub fn import_xes_str(
+    xes_str: &str,
+    options: XESImportOptions,
+) -> Result<EventLog, LocatedXESParseError> {
     let reader = BufReader::new(xes_str.as_bytes());
     import_xes(reader, options)
 }
@@ -179,7 +305,7 @@ pub fn import_xes_slice(
     xes_data: &[u8],
     is_compressed_gz: bool,
     options: XESImportOptions,
-) -> Result<EventLog, XESParseError> {
+) -> Result<EventLog, LocatedXESParseError> {
     // let buf_reader = BufReader::new(reader);
     if is_compressed_gz {
         let gz: GzDecoder<&[u8]> = GzDecoder::new(xes_data);
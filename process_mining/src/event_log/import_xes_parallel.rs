@@ -0,0 +1,448 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    ops::Range,
+};
+
+use flate2::bufread::GzDecoder;
+use rayon::prelude::*;
+
+use crate::event_log::event_log_struct::{EventLog, Trace};
+
+use super::{
+    export_xes::is_gz_path,
+    import_xes::{
+        import_xes_with_warnings, LocatedXESParseError, XESImportOptions, XESParseLocation,
+    },
+};
+
+/// Minimal size (in bytes) of a chunk of traces parsed by one thread
+const MIN_CHUNK_BYTES: usize = 256 * 1024;
+
+///
+/// Import a XES [`EventLog`] from an (uncompressed) byte slice, parsing traces in parallel
+///
+/// The XES data is split into chunks at `<trace>` boundaries, which are then parsed on the rayon thread pool.
+/// The resulting [`EventLog`] is identical to the one returned by [`super::import_xes::import_xes_slice`] (including the order of traces and
+/// the handling of all [`XESImportOptions`]).
+///
+/// If the XES data does not have the expected structure (i.e., log-level data, then all traces, then the closing `</log>` tag),
+/// it is parsed sequentially instead.
+///
+/// For gzipped data, see [`import_xes_file_parallel`] or decompress the data first.
+pub fn import_xes_slice_parallel(
+    xes_data: &[u8],
+    options: XESImportOptions,
+) -> Result<EventLog, LocatedXESParseError> {
+    import_xes_slice_parallel_with_warnings(xes_data, options).map(|(log, _warnings)| log)
+}
+
+///
+/// Import a XES [`EventLog`] from an (uncompressed) byte slice, parsing traces in parallel and also returning all warnings
+///
+/// The returned error and warnings are the same as for [`import_xes_with_warnings`] (including their location in the XES data),
+/// except that errors and warnings encountered in the traces are returned as [`XESParseError::IOError`](super::import_xes::XESParseError::IOError) with the message of the original error
+/// (as errors cannot be sent between threads).
+///
+/// See [`import_xes_slice_parallel`] for details.
+pub fn import_xes_slice_parallel_with_warnings(
+    xes_data: &[u8],
+    options: XESImportOptions,
+) -> Result<(EventLog, Vec<LocatedXESParseError>), LocatedXESParseError> {
+    import_xes_slice_parallel_with_chunk_size(xes_data, options, MIN_CHUNK_BYTES)
+}
+
+///
+/// Import a XES [`EventLog`] from a file path, parsing traces in parallel
+///
+/// Gzipped files (ending with `.gz`) are decompressed into memory first.
+/// Uncompressed files are memory-mapped if the `memmap` feature is enabled and read into memory otherwise.
+///
+/// See [`import_xes_slice_parallel`] for details.
+pub fn import_xes_file_parallel<P: AsRef<std::path::Path>>(
+    path: P,
+    options: XESImportOptions,
+) -> Result<EventLog, LocatedXESParseError> {
+    let is_gz = is_gz_path(&path);
+    let file = File::open(path)?;
+    if is_gz {
+        let mut data = Vec::new();
+        GzDecoder::new(BufReader::new(file)).read_to_end(&mut data)?;
+        return import_xes_slice_parallel(&data, options);
+    }
+    #[cfg(feature = "memmap")]
+    {
+        // SAFETY: The file is only read; modifying it concurrently (e.g., from another process) while parsing is not supported
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        import_xes_slice_parallel(&mmap, options)
+    }
+    #[cfg(not(feature = "memmap"))]
+    {
+        let mut data = Vec::new();
+        BufReader::new(file).read_to_end(&mut data)?;
+        import_xes_slice_parallel(&data, options)
+    }
+}
+
+fn import_xes_slice_parallel_with_chunk_size(
+    xes_data: &[u8],
+    options: XESImportOptions,
+    min_chunk_bytes: usize,
+) -> Result<(EventLog, Vec<LocatedXESParseError>), LocatedXESParseError> {
+    let trace_ranges = match find_trace_ranges(xes_data) {
+        Some(r) => r,
+        None => return import_xes_with_warnings(xes_data, options),
+    };
+    let header = &xes_data[..trace_ranges[0].start];
+    let chunk_bytes = min_chunk_bytes.max(xes_data.len() / (rayon::current_num_threads() * 4));
+    // Chunks (as byte ranges) and the number of traces before each chunk
+    let mut chunks: Vec<(Range<usize>, usize)> = Vec::new();
+    for (i, r) in trace_ranges.into_iter().enumerate() {
+        match chunks.last_mut() {
+            Some((c, _)) if c.end - c.start < chunk_bytes => c.end = r.end,
+            _ => chunks.push((r, i)),
+        }
+    }
+    let parse = |body: &[u8]| {
+        import_xes_with_warnings(header.chain(body).chain(&b"</log>"[..]), options.clone())
+    };
+    // Log data and warnings of the header (which are then ignored for the chunks)
+    let (mut log, mut warnings) = parse(&[])?;
+    let header_position = ChunkPosition::new(xes_data, header.len(), 0);
+    // XESParseError is not Send (IO errors are wrapped in an Rc), so errors are converted at the chunk boundary
+    type ChunkResult = Result<(Vec<Trace>, Vec<ChunkError>), ChunkError>;
+    let chunk_results: Vec<ChunkResult> = chunks
+        .par_iter()
+        .map(|(c, _)| {
+            parse(&xes_data[c.clone()])
+                .map(|(log, warnings)| {
+                    let warnings = warnings
+                        .into_iter()
+                        .filter(|w| w.location.byte_offset >= header_position.start)
+                        .map(ChunkError::from)
+                        .collect();
+                    (log.traces, warnings)
+                })
+                .map_err(ChunkError::from)
+        })
+        .collect();
+    let mut trace_chunks = Vec::with_capacity(chunk_results.len());
+    for ((c, num_traces), res) in chunks.iter().zip(chunk_results) {
+        let position = ChunkPosition::new(xes_data, c.start, *num_traces);
+        let relocate = |e: ChunkError| {
+            let mut e = LocatedXESParseError::from(e);
+            position.relocate(&mut e.location, &header_position);
+            e
+        };
+        let (traces, chunk_warnings) = res.map_err(relocate)?;
+        warnings.extend(chunk_warnings.into_iter().map(relocate));
+        trace_chunks.push(traces);
+    }
+    log.traces = trace_chunks.into_iter().flatten().collect();
+    Ok((log, warnings))
+}
+
+///
+/// Position of a chunk in the XES data
+///
+/// Used to translate locations in the parsed chunk (i.e., header, chunk and closing `</log>` tag) to the whole XES data
+struct ChunkPosition {
+    /// Byte offset of the chunk start
+    start: u64,
+    /// Number of lines before the chunk start
+    lines: u64,
+    /// Number of bytes between the start of the line and the chunk start
+    column: u64,
+    /// Number of traces before the chunk
+    traces: usize,
+}
+
+impl ChunkPosition {
+    fn new(data: &[u8], start: usize, traces: usize) -> Self {
+        let before = &data[..start];
+        let line_start = before
+            .iter()
+            .rposition(|b| *b == b'\n')
+            .map_or(0, |i| i + 1);
+        Self {
+            start: start as u64,
+            lines: before.iter().filter(|b| **b == b'\n').count() as u64,
+            column: (start - line_start) as u64,
+            traces,
+        }
+    }
+
+    /// Translate a location in the parsed chunk (where the chunk starts at the end of the `header`) to the whole XES data
+    fn relocate(&self, location: &mut XESParseLocation, header: &ChunkPosition) {
+        if location.byte_offset < header.start {
+            // Location in the header
+            return;
+        }
+        if location.line == header.lines + 1 {
+            location.column = location.column - header.column + self.column;
+        }
+        location.line = location.line - header.lines + self.lines;
+        location.byte_offset = location.byte_offset - header.start + self.start;
+        // Indices of traces start at 0 in every chunk
+        if let Some((index, rest)) = location
+            .path
+            .strip_prefix("log/trace[")
+            .and_then(|p| p.split_once(']'))
+        {
+            if let Ok(index) = index.parse::<usize>() {
+                location.path = format!("log/trace[{}]{}", index + self.traces, rest);
+            }
+        }
+    }
+}
+
+///
+/// Error (or warning) encountered while parsing a chunk, which can be sent between threads
+///
+/// As [`XESParseError`](super::import_xes::XESParseError) is not `Send` (IO errors are wrapped in an `Rc`), only its message is sent.
+#[derive(Debug)]
+struct ChunkError {
+    message: String,
+    location: XESParseLocation,
+    detail: Option<Box<str>>,
+}
+
+impl From<LocatedXESParseError> for ChunkError {
+    fn from(e: LocatedXESParseError) -> Self {
+        Self {
+            message: e.error.to_string(),
+            location: e.location,
+            detail: e.detail,
+        }
+    }
+}
+
+impl From<ChunkError> for LocatedXESParseError {
+    fn from(e: ChunkError) -> Self {
+        Self {
+            error: std::io::Error::other(e.message).into(),
+            location: e.location,
+            detail: e.detail,
+        }
+    }
+}
+
+///
+/// Find the byte ranges of all `<trace>` elements in the XES data
+///
+/// Returns `None` if no traces are found or if there is any content besides whitespace and comments
+/// between or after the traces (except for the closing `</log>` tag)
+fn find_trace_ranges(data: &[u8]) -> Option<Vec<Range<usize>>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut trace_start: Option<usize> = None;
+    let mut log_closed = false;
+    let mut pos = 0;
+    while let Some(offset) = data[pos..].iter().position(|b| *b == b'<') {
+        let i = pos + offset;
+        let outside_traces = trace_start.is_none() && (!ranges.is_empty() || log_closed);
+        if outside_traces && !data[pos..i].iter().all(u8::is_ascii_whitespace) {
+            return None;
+        }
+        let rest = &data[i..];
+        if rest.starts_with(b"<!--") {
+            pos = i + 4 + find(&rest[4..], b"-->")? + 3;
+            continue;
+        }
+        if rest.starts_with(b"<![CDATA[") {
+            pos = i + 9 + find(&rest[9..], b"]]>")? + 3;
+            continue;
+        }
+        if rest.starts_with(b"<?") {
+            pos = i + 2 + find(&rest[2..], b"?>")? + 2;
+            continue;
+        }
+        let end = find_tag_end(data, i)?;
+        let is_closing = rest.get(1) == Some(&b'/');
+        let name_start = if is_closing { 2 } else { 1 };
+        let name_len = rest[name_start..]
+            .iter()
+            .position(|b| b.is_ascii_whitespace() || *b == b'/' || *b == b'>')?;
+        let name = &rest[name_start..name_start + name_len];
+        match (name, is_closing) {
+            (b"trace", false) => {
+                if trace_start.is_some() || log_closed {
+                    return None;
+                }
+                if data[end - 1] == b'/' {
+                    ranges.push(i..end + 1);
+                } else {
+                    trace_start = Some(i);
+                }
+            }
+            (b"trace", true) => {
+                ranges.push(trace_start.take()?..end + 1);
+            }
+            (b"log", true) if trace_start.is_none() && !log_closed => {
+                log_closed = true;
+            }
+            _ => {
+                if outside_traces {
+                    return None;
+                }
+            }
+        }
+        pos = end + 1;
+    }
+    if ranges.is_empty() || trace_start.is_some() || !log_closed {
+        return None;
+    }
+    Some(ranges)
+}
+
+/// Find the first occurrence of `needle` in `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Find the position of the `>` closing the tag starting at `start` (ignoring `>` in quoted attribute values)
+fn find_tag_end(data: &[u8], start: usize) -> Option<usize> {
+    let mut quote: Option<u8> = None;
+    for (i, b) in data.iter().enumerate().skip(start) {
+        match quote {
+            Some(q) if *b == q => quote = None,
+            Some(_) => {}
+            None if *b == b'"' || *b == b'\'' => quote = Some(*b),
+            None if *b == b'>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_log::import_xes::{build_ignore_attributes, import_xes_slice, XESParseError};
+
+    const XES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- A comment mentioning <trace> -->
+<log xes.version="2.0" xmlns="http://www.xes-standard.org/">
+    <extension name="Concept" prefix="concept" uri="http://www.xes-standard.org/concept.xesext"/>
+    <global scope="event">
+        <date key="time:timestamp" value="1970-01-01T00:00:00.000+00:00"/>
+    </global>
+    <string key="concept:name" value="Test log"/>
+    <trace>
+        <string key="concept:name" value="1"/>
+        <event>
+            <string key="concept:name" value="b"/>
+            <date key="time:timestamp" value="2024-01-02T00:00:00.000+00:00"/>
+        </event>
+        <event>
+            <string key="concept:name" value="a &lt;trace&gt;"/>
+            <string key="org:resource" value="x"/>
+            <date key="time:timestamp" value="2024-01-01T00:00:00.000+00:00"/>
+        </event>
+    </trace>
+    <!-- <trace><event></event></trace> -->
+    <trace>
+        <string key="concept:name" value="2"/>
+        <list key="items">
+            <string key="x" value="y>z"/>
+        </list>
+        <event>
+            <string key="concept:name" value="c"/>
+        </event>
+    </trace>
+    <trace>
+        <string key="concept:name" value="3"/>
+        <event>
+            <string key="concept:name" value="d"/>
+            <date key="time:timestamp" value="2024-01-03T00:00:00.000+00:00"/>
+        </event>
+    </trace>
+</log>
+"#;
+
+    #[test]
+    fn parallel_equals_sequential() {
+        let options = [
+            XESImportOptions::default(),
+            XESImportOptions {
+                sort_events_with_timestamp_key: Some("time:timestamp".to_string()),
+                ignore_event_attributes_except: Some(build_ignore_attributes([
+                    "concept:name",
+                    "time:timestamp",
+                ])),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(find_trace_ranges(XES.as_bytes()).unwrap().len(), 3);
+        for o in options {
+            let sequential = import_xes_slice(XES.as_bytes(), false, o.clone()).unwrap();
+            assert_eq!(sequential.traces.len(), 3);
+            // Chunk size of 1 byte: Every trace is parsed separately
+            let (parallel, _) =
+                import_xes_slice_parallel_with_chunk_size(XES.as_bytes(), o.clone(), 1).unwrap();
+            assert!(parallel == sequential);
+            let parallel = import_xes_slice_parallel(XES.as_bytes(), o).unwrap();
+            assert!(parallel == sequential);
+        }
+    }
+
+    #[test]
+    fn errors_and_warnings_equal_sequential() {
+        // Invalid dates in the header and in the second trace, unclosed event in the third trace
+        let xes = XES
+            .replace(
+                r#"<string key="concept:name" value="Test log"/>"#,
+                r#"<date key="created" value="never"/>"#,
+            )
+            .replace(
+                r#"<string key="concept:name" value="2"/>"#,
+                r#"<date key="checked" value="yesterday"/>"#,
+            )
+            .replace(r#"<string key="concept:name" value="d"/>"#, "<event>");
+        // Errors of chunks are returned as IO errors with the message of the original error
+        let describe = |warnings: &[LocatedXESParseError]| {
+            warnings
+                .iter()
+                .map(|w| {
+                    let message = match &w.error {
+                        XESParseError::IOError(e) => e.to_string(),
+                        e => e.to_string(),
+                    };
+                    (message, w.location.clone(), w.detail.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+        let sequential = import_xes_with_warnings(xes.as_bytes(), Default::default()).unwrap_err();
+        let parallel =
+            import_xes_slice_parallel_with_chunk_size(xes.as_bytes(), Default::default(), 1)
+                .unwrap_err();
+        assert!(matches!(parallel.error, XESParseError::IOError(_)));
+        // Locations (including trace indices) refer to the whole XES data
+        assert_eq!(parallel.location.path, "log/trace[2]/event[0]");
+        assert_eq!(describe(&[parallel]), describe(&[sequential]));
+
+        let options = XESImportOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let (sequential, sequential_warnings) =
+            import_xes_with_warnings(xes.as_bytes(), options.clone()).unwrap();
+        let (parallel, parallel_warnings) =
+            import_xes_slice_parallel_with_chunk_size(xes.as_bytes(), options, 1).unwrap();
+        assert_eq!(parallel.traces.len(), 2);
+        assert!(parallel == sequential);
+        assert_eq!(parallel_warnings.len(), 3);
+        assert_eq!(describe(&parallel_warnings), describe(&sequential_warnings));
+    }
+
+    #[test]
+    fn fallback_to_sequential() {
+        // Log attribute after traces
+        let xes = XES.replace("</log>", r#"<string key="late" value="attribute"/></log>"#);
+        assert!(find_trace_ranges(xes.as_bytes()).is_none());
+        let sequential = import_xes_slice(xes.as_bytes(), false, Default::default()).unwrap();
+        let parallel = import_xes_slice_parallel(xes.as_bytes(), Default::default()).unwrap();
+        assert!(parallel == sequential);
+        // Missing closing log tag
+        assert!(find_trace_ranges(XES.replace("</log>", "").as_bytes()).is_none());
+    }
+}
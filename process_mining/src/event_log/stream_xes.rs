@@ -1,14 +1,16 @@
 use std::{
+    cell::Cell,
     fmt::Debug,
     fs::File,
     io::{BufRead, BufReader, Read},
     iter::FusedIterator,
+    rc::Rc,
     str::FromStr,
 };
 
 use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use flate2::read::GzDecoder;
-use quick_xml::{escape::unescape, events::BytesStart, Reader};
+use quick_xml::{escape::unescape, events::BytesStart, name::QName, Reader};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -16,7 +18,8 @@ use crate::XESImportOptions;
 
 use super::{
     event_log_struct::{EventLogClassifier, EventLogExtension},
-    import_xes::XESParseError,
+    import_xes::{LocatedXESParseError, XESParseError, XESParseLocation},
+    stream_xes_filter::XESStreamFilter,
     Attribute, AttributeValue, Attributes, Event, Trace, XESEditableAttribute,
 };
 
@@ -91,6 +94,16 @@ pub struct StreamingXESParser<'a> {
     log_data_emitted: bool,
     /// Whether the parsing was terminated (either by encountering an error or reaching the Eof)
     finished: bool,
+    /// Filter applied while parsing (see [`XESStreamFilter`])
+    filter: Option<XESStreamFilter>,
+    /// Number of traces emitted so far
+    emitted_traces: usize,
+    /// Whether the remainder of the currently open trace should be skipped (i.e., it was rejected by the filter)
+    skip_current_trace: bool,
+    /// Whether the parser is recovering from an error in lenient mode (i.e., everything until the next trace is ignored)
+    recovering: bool,
+    /// Current location and collected warnings (see [`ParseDiagnostics`])
+    diagnostics: ParseDiagnostics,
 }
 
 impl Debug for StreamingXESParser<'_> {
@@ -107,6 +120,11 @@ impl Debug for StreamingXESParser<'_> {
             .field("log_data", &self.log_data)
             .field("log_data_emitted", &self.log_data_emitted)
             .field("finished", &self.finished)
+            .field("filter", &self.filter)
+            .field("emitted_traces", &self.emitted_traces)
+            .field("skip_current_trace", &self.skip_current_trace)
+            .field("recovering", &self.recovering)
+            .field("diagnostics", &self.diagnostics)
             .finish()
     }
 }
@@ -123,6 +141,199 @@ pub enum XESNextStreamElement {
     Error(XESParseError),
 }
 
+/// Position in the (uncompressed) XES data
+#[derive(Debug, Clone, Copy, Default)]
+struct ReadPosition {
+    /// Byte offset
+    offset: u64,
+    /// Number of complete lines before the position
+    lines: u64,
+    /// Byte offset at which the current line starts
+    line_start: u64,
+}
+
+/// Positions tracked by [`PositionTrackingReader`]
+#[derive(Debug, Clone, Copy, Default)]
+struct ReaderPositions {
+    /// Position up to which the data was consumed
+    current: ReadPosition,
+    /// Position of the last consumed `<` (i.e., the start of the last XML tag)
+    tag_start: ReadPosition,
+}
+
+///
+/// [`BufRead`] wrapper keeping track of the position (bytes and lines) up to which the inner reader was consumed
+///
+struct PositionTrackingReader<'a> {
+    inner: Box<dyn BufRead + 'a>,
+    positions: Rc<Cell<ReaderPositions>>,
+}
+
+impl ReaderPositions {
+    /// Advance the positions by the consumed `bytes`
+    fn advance(&mut self, bytes: &[u8]) {
+        let start = self.current.offset;
+        for (i, b) in bytes.iter().enumerate() {
+            match b {
+                b'\n' => {
+                    self.current.lines += 1;
+                    self.current.line_start = start + i as u64 + 1;
+                }
+                b'<' => {
+                    self.tag_start = ReadPosition {
+                        offset: start + i as u64,
+                        ..self.current
+                    };
+                }
+                _ => {}
+            }
+        }
+        self.current.offset = start + bytes.len() as u64;
+    }
+}
+
+impl PositionTrackingReader<'_> {
+    fn advance(&self, bytes: &[u8]) {
+        let mut positions = self.positions.get();
+        positions.advance(bytes);
+        self.positions.set(positions);
+    }
+}
+
+impl Read for PositionTrackingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.advance(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl BufRead for PositionTrackingReader<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The data to consume is still buffered, so this does not read from the underlying source
+        if let Ok(buf) = self.inner.fill_buf() {
+            let mut positions = self.positions.get();
+            positions.advance(&buf[..amt.min(buf.len())]);
+            self.positions.set(positions);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+/// Segment of the path of an XML element (see [`XESParseLocation::path`])
+#[derive(Debug, Clone)]
+enum PathSegment {
+    Log,
+    Trace(usize),
+    Event(usize),
+    Other(String),
+}
+
+///
+/// Keeps track of the current location while parsing and collects warnings
+///
+#[derive(Debug, Default)]
+struct ParseDiagnostics {
+    /// Positions in the XES data (updated by the [`PositionTrackingReader`])
+    positions: Rc<Cell<ReaderPositions>>,
+    /// Path of currently open XML elements
+    path: Vec<PathSegment>,
+    /// Number of traces started so far
+    num_traces: usize,
+    /// Number of events started in the current trace
+    num_events: usize,
+    /// Warnings encountered so far
+    warnings: Vec<LocatedXESParseError>,
+    /// Location of the error which terminated parsing (if any)
+    error_location: Option<XESParseLocation>,
+    /// Byte offset up to which the data was consumed when the last error was recovered from
+    last_recovered_offset: Option<u64>,
+}
+
+impl ParseDiagnostics {
+    fn start_element(&mut self, name: &[u8]) {
+        let segment = match name {
+            b"log" => PathSegment::Log,
+            b"trace" => {
+                self.num_traces += 1;
+                self.num_events = 0;
+                PathSegment::Trace(self.num_traces - 1)
+            }
+            b"event" => {
+                self.num_events += 1;
+                PathSegment::Event(self.num_events - 1)
+            }
+            _ => PathSegment::Other(String::from_utf8_lossy(name).to_string()),
+        };
+        self.path.push(segment);
+    }
+
+    fn end_element(&mut self) {
+        self.path.pop();
+    }
+
+    fn in_trace(&self) -> bool {
+        self.path.iter().any(|s| matches!(s, PathSegment::Trace(_)))
+    }
+
+    /// Close the current trace (and all elements inside of it)
+    fn leave_trace(&mut self) {
+        if let Some(i) = self
+            .path
+            .iter()
+            .position(|s| matches!(s, PathSegment::Trace(_)))
+        {
+            self.path.truncate(i);
+        }
+    }
+
+    /// Current location, optionally with the name of an empty element (which is not part of the current path)
+    fn location(&self, empty_element: Option<&[u8]>) -> XESParseLocation {
+        let tag_start = self.positions.get().tag_start;
+        let mut path: Vec<String> = self
+            .path
+            .iter()
+            .map(|s| match s {
+                PathSegment::Log => "log".to_string(),
+                PathSegment::Trace(i) => format!("trace[{i}]"),
+                PathSegment::Event(i) => format!("event[{i}]"),
+                PathSegment::Other(name) => name.clone(),
+            })
+            .collect();
+        if let Some(name) = empty_element {
+            path.push(String::from_utf8_lossy(name).to_string());
+        }
+        XESParseLocation {
+            byte_offset: tag_start.offset,
+            line: tag_start.lines + 1,
+            column: tag_start.offset - tag_start.line_start + 1,
+            path: path.join("/"),
+        }
+    }
+
+    fn warn(&mut self, error: XESParseError, empty_element: Option<&[u8]>) {
+        self.warn_with_detail(error, None, empty_element);
+    }
+
+    fn warn_with_detail(
+        &mut self,
+        error: XESParseError,
+        detail: Option<Box<str>>,
+        empty_element: Option<&[u8]>,
+    ) {
+        let location = self.location(empty_element);
+        self.warnings.push(LocatedXESParseError {
+            error,
+            location,
+            detail,
+        });
+    }
+}
+
 impl StreamingXESParser<'_> {
     /// Try to parse a next [`XESNextStreamElement`] from the current position
     ///
@@ -140,9 +351,23 @@ impl StreamingXESParser<'_> {
             error: XESParseError,
         ) -> Option<XESNextStreamElement> {
             myself.finished = true;
+            myself.diagnostics.error_location = Some(myself.diagnostics.location(None));
             Some(XESNextStreamElement::Error(error))
         }
 
+        // Record the error as warning and continue parsing if possible (i.e., in lenient mode), otherwise terminate parsing
+        macro_rules! fail {
+            ($error:expr) => {
+                match self.recover_from_error($error) {
+                    Ok(()) => {
+                        self.buf.clear();
+                        continue;
+                    }
+                    Err(e) => return terminate_with_error(self, e),
+                }
+            };
+        }
+
         fn emit_log_data(myself: &mut StreamingXESParser<'_>) -> Option<XESNextStreamElement> {
             myself.log_data_emitted = true;
 
@@ -177,6 +402,15 @@ impl StreamingXESParser<'_> {
                     .events
                     .iter_mut()
                     .for_each(|e| e.attributes.shrink_to_fit());
+                myself.emitted_traces += 1;
+                if myself
+                    .filter
+                    .as_ref()
+                    .is_some_and(|f| f.reached_max_traces(myself.emitted_traces))
+                {
+                    // Stop parsing once the trace limit is reached
+                    myself.finished = true;
+                }
                 return Some(XESNextStreamElement::Trace(trace));
             }
             terminate_with_error(myself, XESParseError::MissingLastTrace)
@@ -207,30 +441,94 @@ impl StreamingXESParser<'_> {
         }
 
         loop {
-            match self.reader.read_event_into(&mut self.buf) {
+            if self.skip_current_trace {
+                // Skip the remainder of a trace rejected by the filter without parsing it
+                self.skip_current_trace = false;
+                self.current_trace = None;
+                self.current_nested_attributes.clear();
+                self.current_mode = Mode::Log;
+                if let Err(e) = self.reader.read_to_end_into(QName(b"trace"), &mut self.buf) {
+                    fail!(XESParseError::XMLParsingError(e));
+                }
+                self.diagnostics.leave_trace();
+                self.buf.clear();
+            }
+            let event = self.reader.read_event_into(&mut self.buf);
+            if self.recovering {
+                // Ignore everything until the malformed trace ends (or the next trace starts)
+                let (stop_recovering, ignore_event) = match &event {
+                    Ok(quick_xml::events::Event::End(t)) => match t.as_ref() {
+                        b"trace" => (true, true),
+                        b"log" => (true, false),
+                        _ => (false, true),
+                    },
+                    Ok(quick_xml::events::Event::Start(t) | quick_xml::events::Event::Empty(t)) => {
+                        (t.name().as_ref() == b"trace", t.name().as_ref() != b"trace")
+                    }
+                    Ok(quick_xml::events::Event::Eof) => (true, false),
+                    Ok(_) => (false, true),
+                    Err(_) => (false, false),
+                };
+                if stop_recovering {
+                    self.recovering = false;
+                    self.diagnostics.leave_trace();
+                    self.current_mode = Mode::Log;
+                }
+                if ignore_event {
+                    self.buf.clear();
+                    continue;
+                }
+            }
+            if let Ok(quick_xml::events::Event::Start(t)) = &event {
+                self.diagnostics.start_element(t.name().as_ref());
+            }
+            match event {
                 Ok(r) => {
                     match r {
                         quick_xml::events::Event::Start(t) => match t.name().as_ref() {
                             b"trace" => {
+                                if self
+                                    .filter
+                                    .as_ref()
+                                    .is_some_and(|f| f.reached_max_traces(self.emitted_traces))
+                                {
+                                    self.finished = true;
+                                    if !self.log_data_emitted {
+                                        return emit_log_data(self);
+                                    }
+                                    return None;
+                                }
                                 self.current_mode = Mode::Trace;
                                 self.current_trace = Some(Trace {
                                     attributes: Attributes::with_capacity(10),
                                     events: Vec::with_capacity(10),
                                 });
+                                self.skip_current_trace =
+                                    self.filter.as_mut().is_some_and(|f| !f.start_trace());
                                 if !self.log_data_emitted {
                                     return emit_log_data(self);
                                 }
                             }
                             b"event" => {
-                                self.current_mode = Mode::Event;
-                                match &mut self.current_trace {
-                                    Some(t) => {
-                                        t.events.push(Event {
-                                            attributes: Attributes::with_capacity(10),
-                                        });
-                                    }
-                                    None => {
-                                        eprintln!("Invalid XES format: Event without trace")
+                                let rejected = match (&mut self.filter, &self.current_trace) {
+                                    (Some(f), Some(trace)) => !f.accept_trace_start(trace),
+                                    _ => false,
+                                };
+                                if rejected {
+                                    self.skip_current_trace = true;
+                                } else {
+                                    self.current_mode = Mode::Event;
+                                    match &mut self.current_trace {
+                                        Some(t) => {
+                                            t.events.push(Event {
+                                                attributes: Attributes::with_capacity(10),
+                                            });
+                                        }
+                                        None => {
+                                            // Invalid XES format: Event without trace
+                                            self.diagnostics
+                                                .warn(XESParseError::MissingLastTrace, None);
+                                        }
                                     }
                                 }
                             }
@@ -238,25 +536,10 @@ impl StreamingXESParser<'_> {
                                 Ok(Some(a)) => match a.value.as_ref() {
                                     b"trace" => self.current_mode = Mode::GlobalTraceAttributes,
                                     b"event" => self.current_mode = Mode::GlobalEventAttributes,
-                                    _ => {
-                                        return terminate_with_error(
-                                            self,
-                                            XESParseError::InvalidKeyValue("scope"),
-                                        )
-                                    }
+                                    _ => fail!(XESParseError::InvalidKeyValue("scope")),
                                 },
-                                Ok(None) => {
-                                    return terminate_with_error(
-                                        self,
-                                        XESParseError::MissingKey("scope"),
-                                    );
-                                }
-                                Err(e) => {
-                                    return terminate_with_error(
-                                        self,
-                                        XESParseError::XMLParsingError(e),
-                                    );
-                                }
+                                Ok(None) => fail!(XESParseError::MissingKey("scope")),
+                                Err(e) => fail!(XESParseError::XMLParsingError(e)),
                             },
                             b"log" => {
                                 if self.encountered_log {
@@ -275,10 +558,7 @@ impl StreamingXESParser<'_> {
                             }
                             _x => {
                                 if !self.encountered_log {
-                                    return terminate_with_error(
-                                        self,
-                                        XESParseError::NoTopLevelLog,
-                                    );
+                                    fail!(XESParseError::NoTopLevelLog);
                                 }
                                 {
                                     // Nested attribute!
@@ -292,7 +572,17 @@ impl StreamingXESParser<'_> {
                                             &t,
                                             &self.current_mode,
                                             &self.options,
-                                        );
+                                        )
+                                        .unwrap_or_else(|(e, detail)| {
+                                            // Nested attributes are not skipped in lenient mode, as their child attributes
+                                            // would otherwise be added to the parent element
+                                            self.diagnostics.warn_with_detail(
+                                                e,
+                                                Some(detail.into()),
+                                                None,
+                                            );
+                                            fallback_attribute_value(&t)
+                                        });
                                         if !(key.is_empty()
                                             && matches!(value, AttributeValue::None()))
                                         {
@@ -337,10 +627,7 @@ impl StreamingXESParser<'_> {
                             }
                             _ => {
                                 if !self.encountered_log {
-                                    return terminate_with_error(
-                                        self,
-                                        XESParseError::NoTopLevelLog,
-                                    );
+                                    fail!(XESParseError::NoTopLevelLog);
                                 }
                                 if !StreamingXESParser::add_attribute_from_tag(
                                     &self.current_mode,
@@ -348,21 +635,38 @@ impl StreamingXESParser<'_> {
                                     &mut self.log_data,
                                     &mut self.current_nested_attributes,
                                     &self.options,
+                                    &mut self.diagnostics,
                                     &t,
                                 ) {
-                                    return terminate_with_error(
-                                        self,
-                                        XESParseError::AttributeOutsideLog,
-                                    );
+                                    fail!(XESParseError::AttributeOutsideLog);
                                 }
                             }
                         },
                         quick_xml::events::Event::End(t) => {
                             match t.as_ref() {
-                                b"event" => self.current_mode = Mode::Trace,
+                                b"event" => {
+                                    self.current_mode = Mode::Trace;
+                                    if let (Some(f), Some(trace)) =
+                                        (&mut self.filter, &mut self.current_trace)
+                                    {
+                                        if let Some(e) =
+                                            trace.events.pop().and_then(|e| f.process_event(e))
+                                        {
+                                            trace.events.push(e);
+                                        }
+                                    }
+                                }
                                 b"trace" => {
                                     self.current_mode = Mode::Log;
-                                    return emit_trace_data(self);
+                                    let accepted = match (&mut self.filter, &self.current_trace) {
+                                        (Some(f), Some(trace)) => f.accept_complete_trace(trace),
+                                        _ => true,
+                                    };
+                                    if accepted {
+                                        self.diagnostics.end_element();
+                                        return emit_trace_data(self);
+                                    }
+                                    self.current_trace = None;
                                 }
                                 b"log" => self.current_mode = Mode::None,
                                 b"global" => self.current_mode = Mode::Log,
@@ -402,9 +706,8 @@ impl StreamingXESParser<'_> {
                                                                     .attributes
                                                                     .add_attribute(attr);
                                                             } else {
-                                                                return terminate_with_error(
-                                                                    self,
-                                                                    XESParseError::MissingLastTrace,
+                                                                fail!(
+                                                                    XESParseError::MissingLastTrace
                                                                 );
                                                             }
                                                         }
@@ -419,12 +722,11 @@ impl StreamingXESParser<'_> {
                                                                         .attributes
                                                                         .add_attribute(attr);
                                                                 } else {
-                                                                    return terminate_with_error(self,XESParseError::MissingLastEvent);
+                                                                    fail!(XESParseError::MissingLastEvent);
                                                                 }
                                                             } else {
-                                                                return terminate_with_error(
-                                                                    self,
-                                                                    XESParseError::MissingLastTrace,
+                                                                fail!(
+                                                                    XESParseError::MissingLastTrace
                                                                 );
                                                             }
                                                         }
@@ -444,10 +746,7 @@ impl StreamingXESParser<'_> {
                                                                 .add_attribute(attr);
                                                         }
                                                         _x => {
-                                                            return terminate_with_error(
-                                                                self,
-                                                                XESParseError::InvalidMode,
-                                                            );
+                                                            fail!(XESParseError::InvalidMode);
                                                         }
                                                     }
                                                     self.current_mode = self.last_mode_before_attr;
@@ -455,7 +754,8 @@ impl StreamingXESParser<'_> {
                                             } else {
                                                 // This means there was no current nested attribute but the mode indicated otherwise
                                                 // Should thus not happen, but execution can continue.
-                                                eprintln!("[Rust] Warning: Attribute mode but no open nested attributes!");
+                                                self.diagnostics
+                                                    .warn(XESParseError::InvalidMode, None);
                                                 self.current_mode = self.last_mode_before_attr;
                                             }
                                         }
@@ -466,12 +766,13 @@ impl StreamingXESParser<'_> {
                                     }
                                 }
                             }
+                            self.diagnostics.end_element();
                         }
                         quick_xml::events::Event::Eof => {
                             // Finished!
                             if !self.encountered_log {
                                 // If there was no (top-level) log tag, this was not a valid XES file!
-                                return terminate_with_error(self, XESParseError::NoTopLevelLog);
+                                fail!(XESParseError::NoTopLevelLog);
                             }
                             if !self.log_data_emitted {
                                 return emit_log_data(self);
@@ -483,9 +784,7 @@ impl StreamingXESParser<'_> {
                         _ => {}
                     }
                 }
-                Err(e) => {
-                    return terminate_with_error(self, XESParseError::XMLParsingError(e));
-                }
+                Err(e) => fail!(XESParseError::XMLParsingError(e)),
             }
             self.buf.clear();
         }
@@ -690,6 +989,49 @@ fn test_classifier_parse() {
     );
 }
 impl StreamingXESParser<'_> {
+    ///
+    /// Record an error as warning if parsing can continue (i.e., in lenient mode, see [`XESImportOptions::lenient`])
+    ///
+    /// If the error occured inside a trace, this trace is dropped and everything until the next trace is ignored.
+    ///
+    /// Returns the error if parsing cannot continue.
+    ///
+    fn recover_from_error(&mut self, error: XESParseError) -> Result<(), XESParseError> {
+        let offset = self.diagnostics.positions.get().current.offset;
+        let recoverable = self.options.lenient
+            // Errors in the log header are not recovered from
+            && self.log_data_emitted
+            // Prevent endless loops if the reader does not make progress after an error
+            && self.diagnostics.last_recovered_offset != Some(offset)
+            && !matches!(
+                error,
+                XESParseError::NoTopLevelLog
+                    | XESParseError::IOError(_)
+                    | XESParseError::XMLParsingError(quick_xml::Error::Io(_))
+            );
+        if !recoverable {
+            return Err(error);
+        }
+        if matches!(error, XESParseError::XMLParsingError(_)) {
+            // The XML reader does not continue after an error: Continue parsing the remaining data with a new reader
+            // (which does not check end tag names, as it does not know about the previously opened elements)
+            let inner = std::mem::replace(self.reader.get_mut(), Box::new(std::io::empty()));
+            *self.reader = Reader::from_reader(inner);
+            self.reader.check_end_names(false);
+        }
+        self.diagnostics.last_recovered_offset = Some(offset);
+        self.diagnostics.warn(error, None);
+        self.current_nested_attributes.clear();
+        self.skip_current_trace = false;
+        if self.diagnostics.in_trace() {
+            self.current_trace = None;
+            self.recovering = true;
+        } else if matches!(self.current_mode, Mode::Attribute) {
+            self.current_mode = self.last_mode_before_attr;
+        }
+        Ok(())
+    }
+
     ///
     /// Add XES attribute from tag to the currently active element (indicated by `current_mode`)
     ///
@@ -699,6 +1041,7 @@ impl StreamingXESParser<'_> {
         log_data: &mut XESOuterLogData,
         current_nested_attributes: &mut [Attribute],
         options: &XESImportOptions,
+        diagnostics: &mut ParseDiagnostics,
         t: &BytesStart<'_>,
     ) -> bool {
         let key = get_attribute_string(t, "key").unwrap_or_default();
@@ -706,36 +1049,36 @@ impl StreamingXESParser<'_> {
             return true;
         }
 
-        let val = parse_attribute_value_from_tag(t, current_mode, options);
+        let val = match parse_attribute_value_from_tag(t, current_mode, options) {
+            Ok(val) => val,
+            Err((e, detail)) => {
+                diagnostics.warn_with_detail(e, Some(detail.into()), Some(t.name().as_ref()));
+                if options.lenient {
+                    return true;
+                }
+                fallback_attribute_value(t)
+            }
+        };
         match current_mode {
             Mode::Trace => match current_trace {
                 Some(t) => {
                     t.attributes.add_to_attributes(key, val);
                 }
                 None => {
-                    eprintln!(
-                        "No current trace when parsing trace attribute: Key {:?}, Value {:?}",
-                        key, val
-                    );
+                    diagnostics.warn(XESParseError::MissingLastTrace, Some(t.name().as_ref()));
                 }
             },
             Mode::Event => match current_trace {
-                Some(t) => match t.events.last_mut() {
+                Some(trace) => match trace.events.last_mut() {
                     Some(e) => {
                         e.attributes.add_to_attributes(key, val);
                     }
                     None => {
-                        eprintln!(
-                            "No current event when parsing event attribute: Key {:?}, Value {:?}",
-                            key, val
-                        )
+                        diagnostics.warn(XESParseError::MissingLastEvent, Some(t.name().as_ref()));
                     }
                 },
                 None => {
-                    eprintln!(
-                        "No current trace when parsing event attribute: Key {:?}, Value {:?}",
-                        key, val
-                    );
+                    diagnostics.warn(XESParseError::MissingLastTrace, Some(t.name().as_ref()));
                 }
             },
 
@@ -822,6 +1165,29 @@ impl<'a> XESParsingTraceStream<'a> {
         self.error.clone()
     }
 
+    ///
+    /// Get all warnings encountered while parsing so far
+    ///
+    /// Warnings include attributes with invalid values and, in lenient mode (see [`XESImportOptions::lenient`]), errors
+    /// which were recovered from (e.g., by skipping a malformed trace).
+    /// Each warning includes its location in the XES data.
+    ///
+    pub fn warnings(&self) -> &[LocatedXESParseError] {
+        &self.inner.diagnostics.warnings
+    }
+
+    /// Take all warnings encountered while parsing so far (see [`XESParsingTraceStream::warnings`])
+    pub fn take_warnings(&mut self) -> Vec<LocatedXESParseError> {
+        std::mem::take(&mut self.inner.diagnostics.warnings)
+    }
+
+    /// Get the location in the XES data at which the error which terminated parsing was encountered (if any)
+    ///
+    /// See also [`XESParsingTraceStream::error`].
+    pub fn last_error_location(&self) -> Option<&XESParseLocation> {
+        self.inner.diagnostics.error_location.as_ref()
+    }
+
     /// Set or update parsing options
     pub fn set_options(&mut self, options: XESImportOptions) {
         self.inner.options = options;
@@ -832,6 +1198,25 @@ impl<'a> XESParsingTraceStream<'a> {
         &self.inner.options
     }
 
+    ///
+    /// Set a filter, which is applied to all (remaining) traces while parsing (see [`XESStreamFilter`])
+    ///
+    /// Should be set before iterating over the traces.
+    ///
+    pub fn set_filter(&mut self, filter: XESStreamFilter) {
+        self.inner.filter = Some(filter);
+        // The first trace was already started when parsing the log data: Apply the trace limit and sampling to it
+        if let Some(f) = &mut self.inner.filter {
+            if self.inner.current_trace.is_some() {
+                if f.reached_max_traces(self.inner.emitted_traces) {
+                    self.inner.finished = true;
+                } else if !f.start_trace() {
+                    self.inner.skip_current_trace = true;
+                }
+            }
+        }
+    }
+
     ///
     /// Try to construct a new [`XESParsingTraceStream`] and directly try to parse until the first trace
     ///
@@ -841,6 +1226,23 @@ impl<'a> XESParsingTraceStream<'a> {
         reader: Box<Reader<Box<dyn BufRead + 'a>>>,
         options: XESImportOptions,
     ) -> Result<(Self, XESOuterLogData), XESParseError> {
+        Self::try_new_located(reader, options).map_err(|e| e.error)
+    }
+
+    ///
+    /// Same as [`XESParsingTraceStream::try_new`], but errors include their location in the XES data
+    ///
+    pub(crate) fn try_new_located(
+        mut reader: Box<Reader<Box<dyn BufRead + 'a>>>,
+        options: XESImportOptions,
+    ) -> Result<(Self, XESOuterLogData), LocatedXESParseError> {
+        // Keep track of the position in the XES data for error locations (keeping the configuration of the passed reader)
+        let diagnostics = ParseDiagnostics::default();
+        let inner = std::mem::replace(reader.get_mut(), Box::new(std::io::empty()));
+        *reader.get_mut() = Box::new(PositionTrackingReader {
+            inner,
+            positions: Rc::clone(&diagnostics.positions),
+        });
         let log_data = XESOuterLogData::default();
         let mut s = StreamingXESParser {
             reader,
@@ -854,14 +1256,28 @@ impl<'a> XESParsingTraceStream<'a> {
             log_data_emitted: false,
             buf: Vec::new(),
             finished: false,
+            filter: None,
+            emitted_traces: 0,
+            skip_current_trace: false,
+            recovering: false,
+            diagnostics,
         };
         let next = s.next_trace();
+        let located = |s: &StreamingXESParser<'_>, error| LocatedXESParseError {
+            error,
+            location: s
+                .diagnostics
+                .error_location
+                .clone()
+                .unwrap_or_else(|| s.diagnostics.location(None)),
+            detail: None,
+        };
         match next {
             Some(el) => match el {
-                XESNextStreamElement::Error(e) => Err(e),
+                XESNextStreamElement::Error(e) => Err(located(&s, e)),
                 XESNextStreamElement::Trace(_) => {
                     eprintln!("Encountered trace before LogData; This should not happen!");
-                    Err(XESParseError::ExpectedLogData)
+                    Err(located(&s, XESParseError::ExpectedLogData))
                 }
                 XESNextStreamElement::LogData(d) => Ok((
                     (Self {
@@ -876,7 +1292,7 @@ impl<'a> XESParsingTraceStream<'a> {
                 eprintln!(
                     "Iterator initially empty. Expected log data or error; This should not happen!"
                 );
-                Err(XESParseError::ExpectedLogData)
+                Err(located(&s, XESParseError::ExpectedLogData))
             }
         }
     }
@@ -975,97 +1391,85 @@ fn get_attribute_string(t: &BytesStart<'_>, key: &'static str) -> Option<String>
     None
 }
 
+///
+/// Parse the value of an XES attribute from its XML tag
+///
+/// Returns an error (with a description as detail) if the value is invalid (e.g., a date which cannot be parsed) or the attribute type is unknown.
+///
 fn parse_attribute_value_from_tag(
     t: &BytesStart<'_>,
     mode: &Mode,
     options: &XESImportOptions,
-) -> AttributeValue {
-    let attribute_val: Option<AttributeValue> = match t.name().as_ref() {
-        b"container" => Some(AttributeValue::Container(Attributes::new())),
-        b"list" => Some(AttributeValue::List(Vec::new())),
-        _ => {
-            let value = get_attribute_string(t, "value");
-            if let Some(value) = value {
-                match t.name().as_ref() {
-                    b"string" => Some(AttributeValue::String(
-                        unescape(value.as_str())
-                            .unwrap_or(value.as_str().into())
-                            .into(),
-                    )),
-                    b"date" => match parse_date_from_str(&value, &options.date_format) {
-                        Some(dt) => Some(AttributeValue::Date(dt)),
-                        None => {
-                            eprintln!("Failed to parse data from {:?}", value);
-                            None
-                        }
-                    },
-                    b"int" => {
-                        let parsed_val = match value.parse::<i64>() {
-                            Ok(n) => n,
-                            Err(e) => {
-                                eprintln!("Could not parse integer {:?}: Error {}", value, e);
-                                i64::default()
-                            }
-                        };
-                        Some(AttributeValue::Int(parsed_val))
-                    }
-                    b"float" => {
-                        let parsed_val = match value.parse::<f64>() {
-                            Ok(n) => n,
-                            Err(e) => {
-                                eprintln!("Could not parse float {:?}: Error {}", value, e);
-                                f64::default()
-                            }
-                        };
-                        Some(AttributeValue::Float(parsed_val))
-                    }
-                    b"boolean" => {
-                        let parsed_val = match value.parse::<bool>() {
-                            Ok(n) => n,
-                            Err(e) => {
-                                eprintln!("Could not parse boolean {:?}: Error {}", value, e);
-                                bool::default()
-                            }
-                        };
-                        Some(AttributeValue::Boolean(parsed_val))
-                    }
-                    b"id" => {
-                        let parsed_val = match Uuid::from_str(&value) {
-                            Ok(n) => n,
-                            Err(e) => {
-                                eprintln!("Could not parse UUID {:?}: Error {}", value, e);
-                                Uuid::default()
-                            }
-                        };
-
-                        Some(AttributeValue::ID(parsed_val))
-                    }
-
-                    _ => match mode {
-                        Mode::Log => None,
-                        m => {
-                            let mut name_str = String::new();
-                            t.name()
-                                .as_ref()
-                                .read_to_string(&mut name_str)
-                                .unwrap_or_default();
-                            eprintln!(
-                                "Attribute type not implemented '{}' in mode {:?}",
-                                name_str, m
-                            );
-                            None
-                        }
-                    },
-                }
-            } else {
-                None
-            }
-        }
+) -> Result<AttributeValue, (XESParseError, String)> {
+    let value = match t.name().as_ref() {
+        b"container" => return Ok(AttributeValue::Container(Attributes::new())),
+        b"list" => return Ok(AttributeValue::List(Vec::new())),
+        _ => match get_attribute_string(t, "value") {
+            Some(value) => value,
+            None => return Ok(AttributeValue::None()),
+        },
     };
-    attribute_val.unwrap_or(AttributeValue::None())
+    let invalid_value = |value: &str| {
+        (
+            XESParseError::InvalidKeyValue("value"),
+            format!(
+                "invalid value {:?} of attribute {:?}",
+                value,
+                get_attribute_string(t, "key").unwrap_or_default()
+            ),
+        )
+    };
+    match t.name().as_ref() {
+        b"string" => Ok(AttributeValue::String(
+            unescape(value.as_str())
+                .unwrap_or(value.as_str().into())
+                .into(),
+        )),
+        b"date" => parse_date_from_str(&value, &options.date_format)
+            .map(AttributeValue::Date)
+            .ok_or_else(|| invalid_value(&value)),
+        b"int" => value
+            .parse::<i64>()
+            .map(AttributeValue::Int)
+            .map_err(|_| invalid_value(&value)),
+        b"float" => value
+            .parse::<f64>()
+            .map(AttributeValue::Float)
+            .map_err(|_| invalid_value(&value)),
+        b"boolean" => value
+            .parse::<bool>()
+            .map(AttributeValue::Boolean)
+            .map_err(|_| invalid_value(&value)),
+        b"id" => Uuid::from_str(&value)
+            .map(AttributeValue::ID)
+            .map_err(|_| invalid_value(&value)),
+        name => match mode {
+            Mode::Log => Ok(AttributeValue::None()),
+            _ => Err((
+                XESParseError::InvalidMode,
+                format!("unknown attribute type {:?}", String::from_utf8_lossy(name)),
+            )),
+        },
+    }
 }
 
-fn parse_date_from_str(value: &str, date_format: &Option<String>) -> Option<DateTime<FixedOffset>> {
+///
+/// Value used for attributes with invalid values (see [`parse_attribute_value_from_tag`]), if not parsing leniently
+///
+fn fallback_attribute_value(t: &BytesStart<'_>) -> AttributeValue {
+    match t.name().as_ref() {
+        b"int" => AttributeValue::Int(i64::default()),
+        b"float" => AttributeValue::Float(f64::default()),
+        b"boolean" => AttributeValue::Boolean(bool::default()),
+        b"id" => AttributeValue::ID(Uuid::default()),
+        _ => AttributeValue::None(),
+    }
+}
+
+pub(crate) fn parse_date_from_str(
+    value: &str,
+    date_format: &Option<String>,
+) -> Option<DateTime<FixedOffset>> {
     // Is a date_format string provided?
     if let Some(date_format) = &date_format {
         if let Ok(dt) = DateTime::parse_from_str(value, date_format) {
@@ -1099,10 +1503,108 @@ mod stream_test {
     use std::{collections::HashSet, time::Instant};
 
     use crate::{
-        event_log::import_xes::build_ignore_attributes, stream_xes_from_path,
-        utils::test_utils::get_test_data_path, XESImportOptions,
+        event_log::import_xes::{
+            build_ignore_attributes, import_xes_str, import_xes_with_warnings, XESParseError,
+        },
+        stream_xes_from_path, stream_xes_slice,
+        utils::test_utils::get_test_data_path,
+        XESImportOptions,
     };
 
+    const MALFORMED_XES: &str = r#"<log>
+<string key="concept:name" value="Log"/>
+<trace>
+  <string key="concept:name" value="0"/>
+  <event><string key="concept:name" value="a"/></event>
+  <event><string key="concept:name" value="b"/><date key="time:timestamp" value="yesterday"/></event>
+</trace>
+<trace>
+  <string key="concept:name" value="1"/>
+  <event><string key="concept:name" value="c"/>
+</trace>
+<trace><string key="concept:name" value="2"/></trace>
+</log>"#;
+
+    #[test]
+    fn test_error_locations() {
+        let err = import_xes_str(MALFORMED_XES, XESImportOptions::default()).unwrap_err();
+        assert!(matches!(
+            err.error,
+            XESParseError::XMLParsingError(quick_xml::Error::EndEventMismatch { .. })
+        ));
+        let location = err.location;
+        assert_eq!(location.path, "log/trace[1]/event[0]");
+        assert_eq!((location.line, location.column), (11, 1));
+        assert_eq!(
+            location.byte_offset as usize,
+            MALFORMED_XES.find("</trace>\n<trace><string").unwrap()
+        );
+
+        // The same location is available from trace streams
+        let (mut stream, _log_data) =
+            stream_xes_slice(MALFORMED_XES.as_bytes(), XESImportOptions::default()).unwrap();
+        assert_eq!(stream.count(), 1);
+        assert!(stream.error.is_some());
+        assert_eq!(stream.last_error_location(), Some(&location));
+        // ... and from imports returning warnings
+        let err = import_xes_with_warnings(MALFORMED_XES.as_bytes(), XESImportOptions::default())
+            .unwrap_err();
+        assert_eq!(err.location, location);
+
+        let header_err = import_xes_with_warnings(
+            "<log>\n  <global scope=\"log\"></global></log>".as_bytes(),
+            XESImportOptions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            header_err.error,
+            XESParseError::InvalidKeyValue("scope")
+        ));
+        assert_eq!(header_err.location.path, "log/global");
+        assert_eq!(header_err.location.line, 2);
+        assert_eq!(header_err.location.column, 3);
+    }
+
+    #[test]
+    fn test_lenient_parsing() {
+        // Without the malformed trace, invalid values are reported as warnings (with a fallback value)
+        let xes = MALFORMED_XES.replace("<event><string key=\"concept:name\" value=\"c\"/>\n", "");
+        let (log, warnings) =
+            import_xes_with_warnings(xes.as_bytes(), XESImportOptions::default()).unwrap();
+        assert_eq!(log.traces.len(), 3);
+        assert_eq!(log.traces[0].events[1].attributes.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].error,
+            XESParseError::InvalidKeyValue("value")
+        ));
+        assert_eq!(
+            warnings[0].detail.as_deref(),
+            Some("invalid value \"yesterday\" of attribute \"time:timestamp\"")
+        );
+        assert_eq!(warnings[0].location.path, "log/trace[0]/event[1]/date");
+        assert_eq!(warnings[0].location.line, 6);
+
+        // In lenient mode, the invalid attribute and the malformed trace are skipped
+        let options = XESImportOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let (log, warnings) =
+            import_xes_with_warnings(MALFORMED_XES.as_bytes(), options.clone()).unwrap();
+        assert_eq!(log.traces.len(), 2);
+        assert_eq!(log.traces[0].events[1].attributes.len(), 1);
+        assert_eq!(
+            log.traces[1].attributes[0].value.try_as_string().unwrap(),
+            "2"
+        );
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[1].location.path, "log/trace[1]/event[0]");
+
+        // Errors before the first trace are not recovered from
+        assert!(import_xes_str("<log><global></global></log>", options).is_err());
+    }
+
     #[test]
     fn test_xes_stream() {
         let path = get_test_data_path().join("xes").join("RepairExample.xes");
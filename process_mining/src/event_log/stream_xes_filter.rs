@@ -0,0 +1,391 @@
+use std::fmt::Debug;
+
+use super::{AttributeValue, Attributes, Event, Trace, XESEditableAttribute};
+
+/// Predicate on the attributes of a [`Trace`]
+pub type TraceAttributePredicate = Box<dyn FnMut(&Attributes) -> bool>;
+/// Predicate on complete [`Trace`]s
+pub type TracePredicate = Box<dyn FnMut(&Trace) -> bool>;
+/// Predicate on [`Event`]s
+pub type EventPredicate = Box<dyn FnMut(&Event) -> bool>;
+/// Mapping of [`Event`]s
+pub type EventMapping = Box<dyn FnMut(Event) -> Event>;
+
+///
+/// Filter and transformation stage applied by the streaming XES parser while parsing
+///
+/// Traces that are filtered out are skipped as early as possible: Sampled out traces and traces beyond the trace limit are
+/// not parsed at all, and traces rejected by their attributes or maximal length are skipped as soon as this is known.
+/// Thus, rejected traces are never fully held in memory.
+///
+/// The filter is applied to a trace stream using [`super::stream_xes::XESParsingTraceStream::set_filter`]:
+///
+/// ```rust
+/// use process_mining::{event_log::stream_xes_filter::XESStreamFilter, stream_xes_slice};
+/// let xes = r#"<log><trace><event><string key="concept:name" value="a"/></event></trace>
+/// <trace><event><string key="concept:name" value="b"/></event><event></event></trace></log>"#;
+/// let (mut stream, _log_data) = stream_xes_slice(xes.as_bytes(), Default::default()).unwrap();
+/// stream.set_filter(
+///     XESStreamFilter::new()
+///         .with_event_predicate(|e| !e.attributes.is_empty())
+///         .with_trace_predicate(|t| t.events.len() < 2)
+///         .with_max_traces(10),
+/// );
+/// assert_eq!((&mut stream).count(), 2);
+/// ```
+///
+/// Filters are applied in the following order:
+///
+/// 1. The trace limit ([`XESStreamFilter::with_max_traces`]), counting only emitted traces
+/// 2. Random sampling ([`XESStreamFilter::with_sample_ratio`])
+/// 3. The trace attribute predicate ([`XESStreamFilter::with_trace_attribute_predicate`]), once when the first event of a trace starts
+///    (or when the trace ends, if it has no events)
+/// 4. The event predicate and mapping ([`XESStreamFilter::with_event_predicate`] and [`XESStreamFilter::with_event_mapping`]), when an event is complete
+/// 5. The trace length bounds ([`XESStreamFilter::with_min_events`] and [`XESStreamFilter::with_max_events`]), counting only events that were not filtered out
+/// 6. The trace predicate ([`XESStreamFilter::with_trace_predicate`]), once the trace is complete
+///
+#[derive(Default)]
+pub struct XESStreamFilter {
+    trace_attribute_predicate: Option<TraceAttributePredicate>,
+    /// Whether the trace attribute predicate was already evaluated for the current trace
+    trace_attributes_checked: bool,
+    trace_predicate: Option<TracePredicate>,
+    event_predicate: Option<EventPredicate>,
+    event_mapping: Option<EventMapping>,
+    min_events: Option<usize>,
+    max_events: Option<usize>,
+    max_traces: Option<usize>,
+    sample: Option<(f64, SplitMix64)>,
+}
+
+impl Debug for XESStreamFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XESStreamFilter")
+            .field(
+                "trace_attribute_predicate",
+                &self.trace_attribute_predicate.is_some(),
+            )
+            .field("trace_attributes_checked", &self.trace_attributes_checked)
+            .field("trace_predicate", &self.trace_predicate.is_some())
+            .field("event_predicate", &self.event_predicate.is_some())
+            .field("event_mapping", &self.event_mapping.is_some())
+            .field("min_events", &self.min_events)
+            .field("max_events", &self.max_events)
+            .field("max_traces", &self.max_traces)
+            .field("sample", &self.sample)
+            .finish()
+    }
+}
+
+impl XESStreamFilter {
+    /// Create a new filter, which does not filter out anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Only keep traces whose attributes satisfy the predicate
+    ///
+    /// The predicate is evaluated once per trace, when the first event of the trace starts (or when the trace ends, if it has no events).
+    /// Thus, the events of rejected traces are skipped without parsing them.
+    ///
+    pub fn with_trace_attribute_predicate<F: FnMut(&Attributes) -> bool + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.trace_attribute_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    ///
+    /// Only keep traces satisfying the predicate
+    ///
+    /// The predicate is evaluated on the complete trace (incl. all events which were not filtered out),
+    /// e.g., for filtering traces by their length or the events they contain.
+    /// For filtering by trace attributes, prefer [`XESStreamFilter::with_trace_attribute_predicate`], which skips rejected traces early.
+    ///
+    pub fn with_trace_predicate<F: FnMut(&Trace) -> bool + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.trace_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    ///
+    /// Only keep traces which have a trace attribute with the given key and value
+    ///
+    pub fn with_trace_attribute(self, key: impl Into<String>, value: AttributeValue) -> Self {
+        let key = key.into();
+        self.with_trace_attribute_predicate(move |attributes| {
+            attributes
+                .get_by_key(&key)
+                .is_some_and(|a| a.value == value)
+        })
+    }
+
+    ///
+    /// Only keep events satisfying the predicate (evaluated once an event is completely parsed)
+    ///
+    pub fn with_event_predicate<F: FnMut(&Event) -> bool + 'static>(
+        mut self,
+        predicate: F,
+    ) -> Self {
+        self.event_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    ///
+    /// Map all (kept) events before they are added to their trace
+    ///
+    pub fn with_event_mapping<F: FnMut(Event) -> Event + 'static>(mut self, mapping: F) -> Self {
+        self.event_mapping = Some(Box::new(mapping));
+        self
+    }
+
+    /// Only keep traces with at least `min_events` (kept) events
+    pub fn with_min_events(mut self, min_events: usize) -> Self {
+        self.min_events = Some(min_events);
+        self
+    }
+
+    /// Only keep traces with at most `max_events` (kept) events
+    ///
+    /// If no event predicate is set, traces are skipped as soon as they exceed this length.
+    pub fn with_max_events(mut self, max_events: usize) -> Self {
+        self.max_events = Some(max_events);
+        self
+    }
+
+    /// Stop parsing after `max_traces` traces (which passed all other filters) were emitted
+    pub fn with_max_traces(mut self, max_traces: usize) -> Self {
+        self.max_traces = Some(max_traces);
+        self
+    }
+
+    ///
+    /// Randomly sample traces: Each trace is kept with probability `ratio` (between `0.0` and `1.0`)
+    ///
+    /// The sampling is deterministic for a given `seed`, i.e., the same traces are sampled when parsing the same log again.
+    /// Sampled out traces are skipped without parsing them.
+    ///
+    pub fn with_sample_ratio(mut self, ratio: f64, seed: u64) -> Self {
+        self.sample = Some((ratio, SplitMix64(seed)));
+        self
+    }
+
+    /// Whether the trace limit is reached after emitting `emitted_traces` traces
+    pub(crate) fn reached_max_traces(&self, emitted_traces: usize) -> bool {
+        self.max_traces.is_some_and(|max| emitted_traces >= max)
+    }
+
+    /// Start a new trace and decide whether it is included in the sample
+    pub(crate) fn start_trace(&mut self) -> bool {
+        self.trace_attributes_checked = false;
+        match &mut self.sample {
+            Some((ratio, rng)) => rng.next_f64() < *ratio,
+            None => true,
+        }
+    }
+
+    /// Check if a trace, which will have at least one more event, can still be accepted
+    pub(crate) fn accept_trace_start(&mut self, trace: &Trace) -> bool {
+        // Without event predicate, the new event will be kept (so the trace would exceed the maximum length)
+        if self.event_predicate.is_none()
+            && self.max_events.is_some_and(|max| trace.events.len() >= max)
+        {
+            return false;
+        }
+        self.accept_trace_attributes(trace)
+    }
+
+    /// Check if the attributes of a trace are accepted (evaluating the trace attribute predicate only once per trace)
+    fn accept_trace_attributes(&mut self, trace: &Trace) -> bool {
+        if self.trace_attributes_checked {
+            return true;
+        }
+        self.trace_attributes_checked = true;
+        match &mut self.trace_attribute_predicate {
+            Some(predicate) => predicate(&trace.attributes),
+            None => true,
+        }
+    }
+
+    /// Check if a complete trace is accepted
+    pub(crate) fn accept_complete_trace(&mut self, trace: &Trace) -> bool {
+        let len = trace.events.len();
+        if self.min_events.is_some_and(|min| len < min)
+            || self.max_events.is_some_and(|max| len > max)
+            || !self.accept_trace_attributes(trace)
+        {
+            return false;
+        }
+        match &mut self.trace_predicate {
+            Some(predicate) => predicate(trace),
+            None => true,
+        }
+    }
+
+    /// Apply the event predicate and mapping to a complete event
+    pub(crate) fn process_event(&mut self, event: Event) -> Option<Event> {
+        if let Some(predicate) = &mut self.event_predicate {
+            if !predicate(&event) {
+                return None;
+            }
+        }
+        match &mut self.event_mapping {
+            Some(mapping) => Some(mapping(event)),
+            None => Some(event),
+        }
+    }
+}
+
+/// Minimal deterministic pseudo-random number generator (`SplitMix64`), used for sampling traces
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed float in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{event_log::constants::ACTIVITY_NAME, stream_xes_slice};
+
+    use super::*;
+
+    fn xes(num_traces: usize) -> String {
+        let mut xes = String::from(r#"<?xml version="1.0" encoding="UTF-8"?><log>"#);
+        for i in 0..num_traces {
+            xes.push_str(&format!(
+                r#"<trace><string key="concept:name" value="{i}"/><string key="group" value="{}"/>"#,
+                i % 3
+            ));
+            for j in 0..(i % 4) {
+                xes.push_str(&format!(
+                    r#"<event><string key="concept:name" value="e{j}"/><int key="n" value="{j}"/></event>"#
+                ));
+            }
+            xes.push_str("</trace>");
+        }
+        xes.push_str("</log>");
+        xes
+    }
+
+    fn stream_with_filter(xes: &str, filter: XESStreamFilter) -> Vec<Trace> {
+        let (mut stream, _) = stream_xes_slice(xes.as_bytes(), Default::default()).unwrap();
+        stream.set_filter(filter);
+        let traces: Vec<Trace> = (&mut stream).collect();
+        assert!(stream.check_for_errors().is_none());
+        traces
+    }
+
+    fn names(traces: &[Trace]) -> Vec<String> {
+        traces
+            .iter()
+            .map(|t| {
+                t.attributes
+                    .get_by_key(ACTIVITY_NAME)
+                    .and_then(|a| a.value.try_as_string())
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn filter_traces() {
+        let xes = xes(12);
+        let traces = stream_with_filter(
+            &xes,
+            XESStreamFilter::new()
+                .with_trace_attribute("group", AttributeValue::String("0".to_string())),
+        );
+        assert_eq!(names(&traces), vec!["0", "3", "6", "9"]);
+
+        let traces = stream_with_filter(
+            &xes,
+            XESStreamFilter::new().with_min_events(1).with_max_events(2),
+        );
+        assert_eq!(names(&traces), vec!["1", "2", "5", "6", "9", "10"]);
+
+        let traces = stream_with_filter(
+            &xes,
+            XESStreamFilter::new().with_min_events(3).with_max_traces(2),
+        );
+        assert_eq!(names(&traces), vec!["3", "7"]);
+
+        assert!(stream_with_filter(&xes, XESStreamFilter::new().with_max_traces(0)).is_empty());
+    }
+
+    #[test]
+    fn filter_traces_by_predicates() {
+        let xes = xes(12);
+        let traces = stream_with_filter(
+            &xes,
+            XESStreamFilter::new().with_trace_predicate(|t| t.events.len() >= 3),
+        );
+        assert_eq!(names(&traces), vec!["3", "7", "11"]);
+
+        let traces = stream_with_filter(
+            &xes,
+            XESStreamFilter::new()
+                .with_trace_attribute_predicate(|attributes| {
+                    attributes
+                        .get_by_key("group")
+                        .is_some_and(|a| a.value != AttributeValue::String("0".to_string()))
+                })
+                .with_trace_predicate(|t| t.events.len() >= 2),
+        );
+        assert_eq!(names(&traces), vec!["2", "7", "10", "11"]);
+    }
+
+    #[test]
+    fn filter_and_map_events() {
+        let traces = stream_with_filter(
+            &xes(4),
+            XESStreamFilter::new()
+                .with_event_predicate(|e| {
+                    e.attributes
+                        .get_by_key("n")
+                        .is_some_and(|a| a.value != AttributeValue::Int(1))
+                })
+                .with_event_mapping(|mut e| {
+                    e.attributes.remove_with_key("n");
+                    e
+                })
+                .with_min_events(1),
+        );
+        assert_eq!(names(&traces), vec!["1", "2", "3"]);
+        assert_eq!(traces[2].events.len(), 2);
+        assert!(traces
+            .iter()
+            .flat_map(|t| t.events.iter())
+            .all(|e| e.attributes.len() == 1));
+    }
+
+    #[test]
+    fn sample_traces() {
+        let xes = xes(1000);
+        let sample = stream_with_filter(&xes, XESStreamFilter::new().with_sample_ratio(0.1, 42));
+        assert!(sample.len() > 50 && sample.len() < 150);
+        // Deterministic for the same seed
+        let sample_again =
+            stream_with_filter(&xes, XESStreamFilter::new().with_sample_ratio(0.1, 42));
+        assert_eq!(names(&sample), names(&sample_again));
+        assert!(
+            stream_with_filter(&xes, XESStreamFilter::new().with_sample_ratio(0.0, 1)).is_empty()
+        );
+    }
+}
@@ -6,7 +6,7 @@ use quick_xml::Writer;
 use crate::{
     event_log::{
         export_xes::export_xes_event_log,
-        import_xes::{import_xes_slice, XESImportOptions, XESParseError},
+        import_xes::{import_xes_slice, LocatedXESParseError, XESImportOptions, XESParseError},
         AttributeValue, Trace, XESEditableAttribute,
     },
     import_xes_file,
@@ -115,12 +115,24 @@ pub fn test_invalid_xes_non_existing_file_gz() {
         "this-file-does-not-exist.xes.gz",
         XESImportOptions::default(),
     );
-    assert!(matches!(res_gz, Err(XESParseError::IOError(_))));
+    assert!(matches!(
+        res_gz,
+        Err(LocatedXESParseError {
+            error: XESParseError::IOError(_),
+            ..
+        })
+    ));
 }
 #[test]
 pub fn test_invalid_xes_non_existing_file() {
     let res_gz = import_xes_file("this-file-does-not-exist.xes", XESImportOptions::default());
-    assert!(matches!(res_gz, Err(XESParseError::IOError(_))));
+    assert!(matches!(
+        res_gz,
+        Err(LocatedXESParseError {
+            error: XESParseError::IOError(_),
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -132,7 +144,13 @@ pub fn test_invalid_xes_file_gz_expected() {
     let mut bytes = Vec::new();
     File::open(&path).unwrap().read_to_end(&mut bytes).unwrap();
     let res_gz = import_xes_slice(&bytes, true, XESImportOptions::default());
-    assert!(matches!(res_gz, Err(XESParseError::XMLParsingError(_))));
+    assert!(matches!(
+        res_gz,
+        Err(LocatedXESParseError {
+            error: XESParseError::XMLParsingError(_),
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -146,7 +164,10 @@ pub fn test_normal_xes_file_gz_expected() {
     let res_gz = import_xes_slice(&bytes, true, XESImportOptions::default());
     assert!(matches!(
         res_gz,
-        Err(XESParseError::XMLParsingError(quick_xml::Error::Io(_)))
+        Err(LocatedXESParseError {
+            error: XESParseError::XMLParsingError(quick_xml::Error::Io(_)),
+            ..
+        })
     ));
 }
 
@@ -158,7 +179,13 @@ pub fn test_invalid_xes_file_gz_unexpected() {
     let mut bytes = Vec::new();
     File::open(&path).unwrap().read_to_end(&mut bytes).unwrap();
     let res = import_xes_slice(&bytes, false, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::NoTopLevelLog)));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::NoTopLevelLog,
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -168,7 +195,13 @@ pub fn test_invalid_xes_file_zero() {
     let mut bytes = Vec::new();
     File::open(&path).unwrap().read_to_end(&mut bytes).unwrap();
     let res = import_xes_slice(&bytes, false, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::NoTopLevelLog)));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::NoTopLevelLog,
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -178,7 +211,13 @@ pub fn test_invalid_xes_file_zero_gz() {
     let mut bytes = Vec::new();
     File::open(&path).unwrap().read_to_end(&mut bytes).unwrap();
     let res = import_xes_slice(&bytes, true, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::XMLParsingError(_))));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::XMLParsingError(_),
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -187,7 +226,13 @@ pub fn test_invalid_xes_file_pnml() {
         .join("petri-net")
         .join("BPI_Challenge_2019_sampled_3000cases_model_alphappp.pnml");
     let res = import_xes_file(&path, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::NoTopLevelLog)));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::NoTopLevelLog,
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -196,14 +241,26 @@ pub fn test_invalid_xes_file_json() {
         .join("ocel")
         .join("order-management.json");
     let res = import_xes_file(&path, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::NoTopLevelLog)));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::NoTopLevelLog,
+            ..
+        })
+    ));
 }
 
 #[test]
 pub fn test_invalid_xes_file_empty() {
     let x: &'static [u8] = &[];
     let res = import_xes_slice(x, false, XESImportOptions::default());
-    assert!(matches!(res, Err(XESParseError::NoTopLevelLog)));
+    assert!(matches!(
+        res,
+        Err(LocatedXESParseError {
+            error: XESParseError::NoTopLevelLog,
+            ..
+        })
+    ));
 }
 
 #[test]
@@ -0,0 +1,987 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use chrono::{DateTime, FixedOffset};
+use quick_xml::{events::BytesStart, Error as QuickXMLError, Reader};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    event_log_struct::EventLogExtension, Attribute, AttributeValue, Attributes, Event, EventLog,
+    Trace, XESEditableAttribute,
+};
+
+/// URI of the XES concept extension
+pub const CONCEPT_EXTENSION_URI: &str = "http://www.xes-standard.org/concept.xesext";
+/// URI of the XES time extension
+pub const TIME_EXTENSION_URI: &str = "http://www.xes-standard.org/time.xesext";
+/// URI of the XES lifecycle extension
+pub const LIFECYCLE_EXTENSION_URI: &str = "http://www.xes-standard.org/lifecycle.xesext";
+/// URI of the XES organizational extension
+pub const ORG_EXTENSION_URI: &str = "http://www.xes-standard.org/org.xesext";
+/// URI of the XES cost extension
+pub const COST_EXTENSION_URI: &str = "http://www.xes-standard.org/cost.xesext";
+/// URI of the XES identity extension
+pub const IDENTITY_EXTENSION_URI: &str = "http://www.xes-standard.org/identity.xesext";
+/// URI of the XES semantic extension
+pub const SEMANTIC_EXTENSION_URI: &str = "http://www.xes-standard.org/semantic.xesext";
+/// URI of the XES micro extension
+pub const MICRO_EXTENSION_URI: &str = "http://www.xes-standard.org/micro.xesext";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Type of an XES attribute
+///
+pub enum XESAttributeType {
+    /// String values
+    String,
+    /// `DateTime` values
+    Date,
+    /// Integer values
+    Int,
+    /// Float values
+    Float,
+    /// Boolean values
+    Boolean,
+    /// IDs (UUIDs)
+    ID,
+    /// Lists of attributes
+    List,
+    /// Containers of attributes
+    Container,
+}
+
+impl XESAttributeType {
+    ///
+    /// Get the type of an [`AttributeValue`]
+    ///
+    /// Returns `None` for [`AttributeValue::None`] (i.e., invalid values)
+    ///
+    pub fn of(value: &AttributeValue) -> Option<Self> {
+        match value {
+            AttributeValue::String(_) => Some(Self::String),
+            AttributeValue::Date(_) => Some(Self::Date),
+            AttributeValue::Int(_) => Some(Self::Int),
+            AttributeValue::Float(_) => Some(Self::Float),
+            AttributeValue::Boolean(_) => Some(Self::Boolean),
+            AttributeValue::ID(_) => Some(Self::ID),
+            AttributeValue::List(_) => Some(Self::List),
+            AttributeValue::Container(_) => Some(Self::Container),
+            AttributeValue::None() => None,
+        }
+    }
+
+    /// Parse the type from the name of the corresponding XES (or XESEXT) tag (e.g., `string` or `date`)
+    pub fn from_tag_name(name: &[u8]) -> Option<Self> {
+        match name {
+            b"string" => Some(Self::String),
+            b"date" => Some(Self::Date),
+            b"int" => Some(Self::Int),
+            b"float" => Some(Self::Float),
+            b"boolean" => Some(Self::Boolean),
+            b"id" => Some(Self::ID),
+            b"list" => Some(Self::List),
+            b"container" => Some(Self::Container),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Scope of an attribute defined by an XES extension
+///
+pub enum XESExtensionScope {
+    /// Log-level attributes
+    Log,
+    /// Trace-level attributes
+    Trace,
+    /// Event-level attributes
+    Event,
+    /// Meta attributes (i.e., attributes nested in other attributes)
+    Meta,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Attribute defined by an XES extension
+///
+pub struct XESExtensionAttributeDefinition {
+    /// Key of the attribute (_without_ the prefix of the extension, e.g., `timestamp`)
+    pub key: String,
+    /// Scope in which the attribute is defined
+    pub scope: XESExtensionScope,
+    /// Type of the attribute
+    pub attribute_type: XESAttributeType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Definition of an XES extension (i.e., the contents of an XESEXT file)
+///
+pub struct XESExtensionDefinition {
+    /// Extension name
+    pub name: String,
+    /// Default prefix of attributes defined by the extension
+    pub prefix: String,
+    /// URI of the extension
+    pub uri: String,
+    /// Attributes defined by the extension
+    pub attributes: Vec<XESExtensionAttributeDefinition>,
+}
+
+impl XESExtensionDefinition {
+    ///
+    /// Get the definition of the attribute with the given key (without prefix) in the given scope
+    ///
+    pub fn get_attribute(
+        &self,
+        scope: XESExtensionScope,
+        key: &str,
+    ) -> Option<&XESExtensionAttributeDefinition> {
+        self.attributes
+            .iter()
+            .find(|a| a.scope == scope && a.key == key)
+    }
+}
+
+fn definition(
+    name: &str,
+    prefix: &str,
+    uri: &str,
+    attributes: &[(XESExtensionScope, &str, XESAttributeType)],
+) -> XESExtensionDefinition {
+    XESExtensionDefinition {
+        name: name.to_string(),
+        prefix: prefix.to_string(),
+        uri: uri.to_string(),
+        attributes: attributes
+            .iter()
+            .map(
+                |(scope, key, attribute_type)| XESExtensionAttributeDefinition {
+                    key: key.to_string(),
+                    scope: *scope,
+                    attribute_type: *attribute_type,
+                },
+            )
+            .collect(),
+    }
+}
+
+///
+/// Built-in definitions of the standard XES extensions
+///
+/// Includes the concept, time, lifecycle, org, cost, identity, semantic and micro extensions (as defined in IEEE 1849-2016)
+///
+pub fn standard_extension_definitions() -> Vec<XESExtensionDefinition> {
+    use XESAttributeType::*;
+    use XESExtensionScope::{Event, Log, Meta, Trace};
+    vec![
+        definition(
+            "Concept",
+            "concept",
+            CONCEPT_EXTENSION_URI,
+            &[
+                (Log, "name", String),
+                (Trace, "name", String),
+                (Event, "name", String),
+                (Event, "instance", String),
+            ],
+        ),
+        definition(
+            "Time",
+            "time",
+            TIME_EXTENSION_URI,
+            &[(Event, "timestamp", Date)],
+        ),
+        definition(
+            "Lifecycle",
+            "lifecycle",
+            LIFECYCLE_EXTENSION_URI,
+            &[
+                (Log, "model", String),
+                (Event, "transition", String),
+                (Event, "state", String),
+            ],
+        ),
+        definition(
+            "Organizational",
+            "org",
+            ORG_EXTENSION_URI,
+            &[
+                (Event, "resource", String),
+                (Event, "role", String),
+                (Event, "group", String),
+            ],
+        ),
+        definition(
+            "Cost",
+            "cost",
+            COST_EXTENSION_URI,
+            &[
+                (Trace, "total", Float),
+                (Trace, "currency", String),
+                (Trace, "drivers", List),
+                (Event, "total", Float),
+                (Event, "currency", String),
+                (Event, "drivers", List),
+                (Meta, "amount", Float),
+                (Meta, "driver", String),
+                (Meta, "type", String),
+            ],
+        ),
+        definition(
+            "Identity",
+            "identity",
+            IDENTITY_EXTENSION_URI,
+            &[
+                (Log, "id", ID),
+                (Trace, "id", ID),
+                (Event, "id", ID),
+                (Meta, "id", ID),
+            ],
+        ),
+        definition(
+            "Semantic",
+            "semantic",
+            SEMANTIC_EXTENSION_URI,
+            &[
+                (Log, "modelReference", String),
+                (Trace, "modelReference", String),
+                (Event, "modelReference", String),
+                (Meta, "modelReference", String),
+            ],
+        ),
+        definition(
+            "Micro",
+            "micro",
+            MICRO_EXTENSION_URI,
+            &[
+                (Event, "level", Int),
+                (Event, "parentId", ID),
+                (Event, "length", Int),
+            ],
+        ),
+    ]
+}
+
+///
+/// [`EventLogExtension`]s of the standard XES extensions with the given URIs (in the given order), with their default prefixes
+///
+/// See [`standard_extension_definitions`] for the available extensions.
+///
+pub(crate) fn standard_log_extensions(uris: &[&str]) -> Vec<EventLogExtension> {
+    let definitions = standard_extension_definitions();
+    uris.iter()
+        .filter_map(|uri| definitions.iter().find(|d| d.uri == *uri))
+        .map(|d| EventLogExtension {
+            name: d.name.clone(),
+            prefix: d.prefix.clone(),
+            uri: d.uri.clone(),
+        })
+        .collect()
+}
+
+///
+/// Error encountered while parsing an XESEXT extension definition
+///
+#[derive(Debug, Clone)]
+pub enum XESExtParseError {
+    /// There is no top-level `<xesextension>`
+    NoTopLevelExtension,
+    /// A required XML attribute (e.g., `prefix` or `key`) is missing
+    MissingAttribute(String),
+    /// An attribute definition was encountered outside of a scope (i.e., `<log>`, `<trace>`, `<event>` or `<meta>`)
+    AttributeOutsideScope(String),
+    /// IO errror
+    IOError(std::rc::Rc<std::io::Error>),
+    /// XML error (e.g., incorrect XML format )
+    XMLParsingError(QuickXMLError),
+}
+
+impl std::fmt::Display for XESExtParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse XESEXT: {:?}", self)
+    }
+}
+
+impl std::error::Error for XESExtParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            XESExtParseError::IOError(e) => Some(e.as_ref()),
+            XESExtParseError::XMLParsingError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for XESExtParseError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IOError(std::rc::Rc::new(e))
+    }
+}
+
+impl From<QuickXMLError> for XESExtParseError {
+    fn from(e: QuickXMLError) -> Self {
+        Self::XMLParsingError(e)
+    }
+}
+
+fn get_required_attribute(t: &BytesStart<'_>, key: &str) -> Result<String, XESExtParseError> {
+    match t.try_get_attribute(key)? {
+        Some(a) => Ok(a.unescape_value()?.to_string()),
+        None => Err(XESExtParseError::MissingAttribute(key.to_string())),
+    }
+}
+
+///
+/// Parse an [`XESExtensionDefinition`] from an XESEXT file (given as reader)
+///
+/// Aliases of attributes are ignored.
+///
+pub fn import_xesext<T: BufRead>(reader: T) -> Result<XESExtensionDefinition, XESExtParseError> {
+    let mut reader = Reader::from_reader(reader);
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    let mut ret: Option<XESExtensionDefinition> = None;
+    let mut scope: Option<XESExtensionScope> = None;
+    loop {
+        let (t, is_empty) = match reader.read_event_into(&mut buf)? {
+            quick_xml::events::Event::Start(t) => (t, false),
+            quick_xml::events::Event::Empty(t) => (t, true),
+            quick_xml::events::Event::End(t) => {
+                if matches!(
+                    t.local_name().as_ref(),
+                    b"log" | b"trace" | b"event" | b"meta"
+                ) {
+                    scope = None;
+                }
+                buf.clear();
+                continue;
+            }
+            quick_xml::events::Event::Eof => break,
+            _ => {
+                buf.clear();
+                continue;
+            }
+        };
+        let name = t.local_name();
+        match (&mut ret, name.as_ref()) {
+            (None, b"xesextension") => {
+                ret = Some(XESExtensionDefinition {
+                    name: get_required_attribute(&t, "name")?,
+                    prefix: get_required_attribute(&t, "prefix")?,
+                    uri: get_required_attribute(&t, "uri")?,
+                    attributes: Vec::new(),
+                });
+            }
+            (None, _) => return Err(XESExtParseError::NoTopLevelExtension),
+            (Some(_), b"log") if !is_empty => scope = Some(XESExtensionScope::Log),
+            (Some(_), b"trace") if !is_empty => scope = Some(XESExtensionScope::Trace),
+            (Some(_), b"event") if !is_empty => scope = Some(XESExtensionScope::Event),
+            (Some(_), b"meta") if !is_empty => scope = Some(XESExtensionScope::Meta),
+            (Some(ext), tag) => {
+                if let Some(attribute_type) = XESAttributeType::from_tag_name(tag) {
+                    let key = get_required_attribute(&t, "key")?;
+                    match scope {
+                        Some(scope) => ext.attributes.push(XESExtensionAttributeDefinition {
+                            key,
+                            scope,
+                            attribute_type,
+                        }),
+                        None => return Err(XESExtParseError::AttributeOutsideScope(key)),
+                    }
+                }
+            }
+        }
+        buf.clear();
+    }
+    ret.ok_or(XESExtParseError::NoTopLevelExtension)
+}
+
+///
+/// Parse an [`XESExtensionDefinition`] from an XESEXT file path
+///
+pub fn import_xesext_file<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<XESExtensionDefinition, XESExtParseError> {
+    let file = File::open(path)?;
+    import_xesext(BufReader::new(file))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Collection of known [`XESExtensionDefinition`]s, used to resolve the [`EventLogExtension`]s declared in an [`EventLog`]
+///
+/// The [`Default`] registry contains all [`standard_extension_definitions`].
+///
+pub struct XESExtensionRegistry {
+    /// Known extension definitions
+    pub definitions: Vec<XESExtensionDefinition>,
+}
+
+impl Default for XESExtensionRegistry {
+    fn default() -> Self {
+        Self {
+            definitions: standard_extension_definitions(),
+        }
+    }
+}
+
+impl XESExtensionRegistry {
+    ///
+    /// Add an extension definition
+    ///
+    /// A previously known definition with the same URI is replaced.
+    ///
+    pub fn add_definition(&mut self, definition: XESExtensionDefinition) {
+        self.definitions.retain(|d| d.uri != definition.uri);
+        self.definitions.push(definition);
+    }
+
+    ///
+    /// Load a custom extension definition from an XESEXT file and add it to the registry
+    ///
+    pub fn load_xesext_file<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), XESExtParseError> {
+        self.add_definition(import_xesext_file(path)?);
+        Ok(())
+    }
+
+    ///
+    /// Get the definition of an [`EventLogExtension`]
+    ///
+    /// Definitions are matched by URI (ignoring the URI scheme, i.e., `http` or `https`) and, as fallback, by their (case-insensitive) name.
+    ///
+    pub fn resolve(&self, extension: &EventLogExtension) -> Option<&XESExtensionDefinition> {
+        let normalize_uri = |uri: &str| {
+            uri.trim()
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .to_lowercase()
+        };
+        let uri = normalize_uri(&extension.uri);
+        self.definitions
+            .iter()
+            .find(|d| normalize_uri(&d.uri) == uri)
+            .or_else(|| {
+                self.definitions
+                    .iter()
+                    .find(|d| d.name.eq_ignore_ascii_case(&extension.name))
+            })
+    }
+}
+
+/// Prefixed keys of the attributes used by the typed accessors
+#[derive(Debug, Clone, Default)]
+struct SemanticKeys {
+    name: Option<String>,
+    timestamp: Option<String>,
+    transition: Option<String>,
+    resource: Option<String>,
+    role: Option<String>,
+    group: Option<String>,
+    cost_total: Option<String>,
+    cost_currency: Option<String>,
+    identity: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+///
+/// Extension context of an [`EventLog`]: The resolved extensions (with their declared prefixes) and the global attributes
+///
+/// Used by the typed accessors on [`Event`] and [`Trace`] (e.g., [`Event::timestamp`]) and for validating attributes
+/// (see [`validate_extension_attributes`]).
+///
+/// Standard extensions that are not declared in the log are included with their default prefix.
+/// The [`Default`] context contains all standard extensions (with their default prefixes) and no global attributes.
+///
+/// ```rust
+/// use process_mining::event_log::{Event, xes_extensions::XESExtensionContext};
+/// let event = Event::new("a".to_string());
+/// let ctx = XESExtensionContext::default();
+/// assert_eq!(event.activity(&ctx).unwrap(), "a");
+/// assert!(event.timestamp(&ctx).is_none());
+/// ```
+pub struct XESExtensionContext<'a> {
+    /// Resolved extensions with their prefix used in the log
+    pub extensions: Vec<(String, XESExtensionDefinition)>,
+    /// Global trace attributes of the log
+    pub global_trace_attrs: Option<&'a Attributes>,
+    /// Global event attributes of the log
+    pub global_event_attrs: Option<&'a Attributes>,
+    keys: SemanticKeys,
+}
+
+impl Default for XESExtensionContext<'_> {
+    fn default() -> Self {
+        Self::from_extensions(&[], &XESExtensionRegistry::default(), None, None)
+    }
+}
+
+impl<'a> XESExtensionContext<'a> {
+    ///
+    /// Create the extension context of an [`EventLog`], resolving its declared extensions using the given [`XESExtensionRegistry`]
+    ///
+    pub fn new(log: &'a EventLog, registry: &XESExtensionRegistry) -> Self {
+        Self::from_extensions(
+            log.extensions.as_deref().unwrap_or_default(),
+            registry,
+            log.global_trace_attrs.as_ref(),
+            log.global_event_attrs.as_ref(),
+        )
+    }
+
+    ///
+    /// Create an extension context from declared extensions and global attributes
+    ///
+    /// Declared extensions which are not known to the `registry` are ignored.
+    ///
+    pub fn from_extensions(
+        declared_extensions: &[EventLogExtension],
+        registry: &XESExtensionRegistry,
+        global_trace_attrs: Option<&'a Attributes>,
+        global_event_attrs: Option<&'a Attributes>,
+    ) -> Self {
+        let mut extensions: Vec<(String, XESExtensionDefinition)> = declared_extensions
+            .iter()
+            .filter_map(|e| Some((e.prefix.clone(), registry.resolve(e)?.clone())))
+            .collect();
+        for d in standard_extension_definitions() {
+            let already_included = extensions
+                .iter()
+                .any(|(prefix, e)| e.uri == d.uri || *prefix == d.prefix);
+            if !already_included {
+                extensions.push((d.prefix.clone(), d));
+            }
+        }
+        let mut ret = Self {
+            extensions,
+            global_trace_attrs,
+            global_event_attrs,
+            keys: SemanticKeys::default(),
+        };
+        ret.keys = SemanticKeys {
+            name: ret.prefixed_key(CONCEPT_EXTENSION_URI, "name"),
+            timestamp: ret.prefixed_key(TIME_EXTENSION_URI, "timestamp"),
+            transition: ret.prefixed_key(LIFECYCLE_EXTENSION_URI, "transition"),
+            resource: ret.prefixed_key(ORG_EXTENSION_URI, "resource"),
+            role: ret.prefixed_key(ORG_EXTENSION_URI, "role"),
+            group: ret.prefixed_key(ORG_EXTENSION_URI, "group"),
+            cost_total: ret.prefixed_key(COST_EXTENSION_URI, "total"),
+            cost_currency: ret.prefixed_key(COST_EXTENSION_URI, "currency"),
+            identity: ret.prefixed_key(IDENTITY_EXTENSION_URI, "id"),
+        };
+        ret
+    }
+
+    ///
+    /// Get the full attribute key (i.e., including the prefix used in the log) of an attribute defined by the extension with the given URI
+    ///
+    /// For example, `prefixed_key(TIME_EXTENSION_URI, "timestamp")` returns `Some("time:timestamp")` for most logs.
+    ///
+    pub fn prefixed_key(&self, extension_uri: &str, key: &str) -> Option<String> {
+        self.extensions
+            .iter()
+            .find(|(_, e)| e.uri == extension_uri)
+            .map(|(prefix, _)| format!("{prefix}:{key}"))
+    }
+
+    ///
+    /// Get the extension definition and the attribute definition (in the given scope) of a full attribute key (e.g., `time:timestamp`)
+    ///
+    pub fn get_attribute_definition(
+        &self,
+        scope: XESExtensionScope,
+        full_key: &str,
+    ) -> Option<(&XESExtensionDefinition, &XESExtensionAttributeDefinition)> {
+        let (prefix, key) = full_key.split_once(':')?;
+        self.extensions
+            .iter()
+            .filter(|(p, _)| p == prefix)
+            .find_map(|(_, e)| Some((e, e.get_attribute(scope, key)?)))
+    }
+
+    fn event_attribute<'b>(&self, event: &'b Event, key: &Option<String>) -> Option<&'b Attribute>
+    where
+        'a: 'b,
+    {
+        get_with_globals(&event.attributes, key.as_deref()?, self.global_event_attrs)
+    }
+
+    fn trace_attribute<'b>(&self, trace: &'b Trace, key: &Option<String>) -> Option<&'b Attribute>
+    where
+        'a: 'b,
+    {
+        get_with_globals(&trace.attributes, key.as_deref()?, self.global_trace_attrs)
+    }
+}
+
+fn get_with_globals<'b>(
+    attributes: &'b Attributes,
+    key: &str,
+    globals: Option<&'b Attributes>,
+) -> Option<&'b Attribute> {
+    attributes
+        .get_by_key(key)
+        .or_else(|| globals.and_then(|g| g.get_by_key(key)))
+}
+
+impl EventLog {
+    ///
+    /// Get the [`XESExtensionContext`] of this log, resolving the declared extensions using the standard extension definitions
+    ///
+    /// See [`XESExtensionContext::new`] for using a custom [`XESExtensionRegistry`].
+    ///
+    pub fn extension_context(&self) -> XESExtensionContext<'_> {
+        XESExtensionContext::new(self, &XESExtensionRegistry::default())
+    }
+}
+
+impl Event {
+    /// Activity (i.e., `concept:name`) of the event, if present and of type string
+    pub fn activity<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.name)?
+            .value
+            .try_as_string()
+    }
+    /// Timestamp (i.e., `time:timestamp`) of the event, if present and of type date
+    pub fn timestamp<'a>(
+        &'a self,
+        ctx: &XESExtensionContext<'a>,
+    ) -> Option<&'a DateTime<FixedOffset>> {
+        ctx.event_attribute(self, &ctx.keys.timestamp)?
+            .value
+            .try_as_date()
+    }
+    /// Lifecycle transition (i.e., `lifecycle:transition`) of the event, if present and of type string
+    pub fn lifecycle<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.transition)?
+            .value
+            .try_as_string()
+    }
+    /// Resource (i.e., `org:resource`) of the event, if present and of type string
+    pub fn resource<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.resource)?
+            .value
+            .try_as_string()
+    }
+    /// Role (i.e., `org:role`) of the event, if present and of type string
+    pub fn role<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.role)?
+            .value
+            .try_as_string()
+    }
+    /// Group (i.e., `org:group`) of the event, if present and of type string
+    pub fn group<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.group)?
+            .value
+            .try_as_string()
+    }
+    /// Total cost (i.e., `cost:total`) of the event, if present and of type float
+    pub fn cost_total(&self, ctx: &XESExtensionContext<'_>) -> Option<f64> {
+        ctx.event_attribute(self, &ctx.keys.cost_total)?
+            .value
+            .try_as_float()
+            .copied()
+    }
+    /// Currency of the costs (i.e., `cost:currency`) of the event, if present and of type string
+    pub fn cost_currency<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.event_attribute(self, &ctx.keys.cost_currency)?
+            .value
+            .try_as_string()
+    }
+    /// Identity (i.e., `identity:id`) of the event, if present and of type ID
+    pub fn identity<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a Uuid> {
+        ctx.event_attribute(self, &ctx.keys.identity)?
+            .value
+            .try_as_uuid()
+    }
+}
+
+impl Trace {
+    /// Name (i.e., `concept:name`) of the trace, if present and of type string
+    pub fn name<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.trace_attribute(self, &ctx.keys.name)?
+            .value
+            .try_as_string()
+    }
+    /// Total cost (i.e., `cost:total`) of the trace, if present and of type float
+    pub fn cost_total(&self, ctx: &XESExtensionContext<'_>) -> Option<f64> {
+        ctx.trace_attribute(self, &ctx.keys.cost_total)?
+            .value
+            .try_as_float()
+            .copied()
+    }
+    /// Currency of the costs (i.e., `cost:currency`) of the trace, if present and of type string
+    pub fn cost_currency<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a String> {
+        ctx.trace_attribute(self, &ctx.keys.cost_currency)?
+            .value
+            .try_as_string()
+    }
+    /// Identity (i.e., `identity:id`) of the trace, if present and of type ID
+    pub fn identity<'a>(&'a self, ctx: &XESExtensionContext<'a>) -> Option<&'a Uuid> {
+        ctx.trace_attribute(self, &ctx.keys.identity)?
+            .value
+            .try_as_uuid()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Location of an attribute in an [`EventLog`]
+///
+pub enum AttributeLocation {
+    /// Log-level attribute
+    Log,
+    /// Global trace attribute
+    GlobalTrace,
+    /// Global event attribute
+    GlobalEvent,
+    /// Attribute of the trace with the given index
+    Trace(usize),
+    /// Attribute of the event with the given index in the trace with the given index
+    Event(usize, usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Attribute whose type contradicts the definition of its extension
+///
+pub struct ExtensionAttributeViolation {
+    /// Location of the attribute (nested attributes are reported at the location of their top-level attribute)
+    pub location: AttributeLocation,
+    /// Full key of the attribute (e.g., `time:timestamp`)
+    pub key: String,
+    /// Name of the extension defining the attribute
+    pub extension: String,
+    /// Type defined by the extension
+    pub expected: XESAttributeType,
+    /// Actual type of the attribute
+    pub found: XESAttributeType,
+}
+
+///
+/// Validate the attributes of an [`EventLog`] against the definitions of their extensions
+///
+/// Reports all attributes (incl. nested attributes, which are checked against the meta scope) whose type contradicts
+/// the type defined by their extension.
+/// Attributes of unknown extensions, attributes not defined by their extension and invalid values (i.e., [`AttributeValue::None`]) are not reported.
+///
+pub fn validate_extension_attributes(
+    log: &EventLog,
+    ctx: &XESExtensionContext<'_>,
+) -> Vec<ExtensionAttributeViolation> {
+    let mut ret = Vec::new();
+    let mut check = |attributes: &[Attribute], scope, location| {
+        for a in attributes {
+            validate_attribute(a, scope, location, ctx, &mut ret);
+        }
+    };
+    check(
+        &log.attributes,
+        XESExtensionScope::Log,
+        AttributeLocation::Log,
+    );
+    if let Some(g) = &log.global_trace_attrs {
+        check(g, XESExtensionScope::Trace, AttributeLocation::GlobalTrace);
+    }
+    if let Some(g) = &log.global_event_attrs {
+        check(g, XESExtensionScope::Event, AttributeLocation::GlobalEvent);
+    }
+    for (i, t) in log.traces.iter().enumerate() {
+        check(
+            &t.attributes,
+            XESExtensionScope::Trace,
+            AttributeLocation::Trace(i),
+        );
+        for (j, e) in t.events.iter().enumerate() {
+            check(
+                &e.attributes,
+                XESExtensionScope::Event,
+                AttributeLocation::Event(i, j),
+            );
+        }
+    }
+    ret
+}
+
+fn validate_attribute(
+    attribute: &Attribute,
+    scope: XESExtensionScope,
+    location: AttributeLocation,
+    ctx: &XESExtensionContext<'_>,
+    ret: &mut Vec<ExtensionAttributeViolation>,
+) {
+    if let (Some((extension, definition)), Some(found)) = (
+        ctx.get_attribute_definition(scope, &attribute.key),
+        XESAttributeType::of(&attribute.value),
+    ) {
+        if definition.attribute_type != found {
+            ret.push(ExtensionAttributeViolation {
+                location,
+                key: attribute.key.clone(),
+                extension: extension.name.clone(),
+                expected: definition.attribute_type,
+                found,
+            });
+        }
+    }
+    let nested = match &attribute.value {
+        AttributeValue::List(children) | AttributeValue::Container(children) => Some(children),
+        _ => attribute.own_attributes.as_ref(),
+    };
+    for child in nested.into_iter().flatten() {
+        validate_attribute(child, XESExtensionScope::Meta, location, ctx, ret);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        event_log::{event_log_struct::EventLogExtension, Attribute, AttributeValue},
+        utils::test_utils::{event_log_from_events, event_with_attributes},
+    };
+
+    use super::*;
+
+    #[test]
+    fn parse_xesext() {
+        let xesext = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<xesextension name="Cost" prefix="cost" uri="http://www.xes-standard.org/cost.xesext">
+    <trace>
+        <float key="total">
+            <alias mapping="EN" name="Total Cost"/>
+        </float>
+    </trace>
+    <event>
+        <string key="currency"/>
+    </event>
+    <meta>
+        <float key="amount"/>
+    </meta>
+</xesextension>"#;
+        let ext = import_xesext(xesext.as_bytes()).unwrap();
+        assert_eq!(ext.prefix, "cost");
+        assert_eq!(ext.attributes.len(), 3);
+        assert_eq!(
+            ext.get_attribute(XESExtensionScope::Trace, "total")
+                .unwrap()
+                .attribute_type,
+            XESAttributeType::Float
+        );
+        assert!(ext
+            .get_attribute(XESExtensionScope::Event, "total")
+            .is_none());
+        assert!(matches!(
+            import_xesext("<log/>".as_bytes()),
+            Err(XESExtParseError::NoTopLevelExtension)
+        ));
+    }
+
+    fn log_with_custom_prefixes() -> EventLog {
+        let mut log = event_log_from_events(vec![vec![
+            event_with_attributes(
+                "a",
+                &[
+                    (
+                        "t:timestamp",
+                        AttributeValue::Date(
+                            DateTime::parse_from_rfc3339("2024-01-01T00:00:00+00:00").unwrap(),
+                        ),
+                    ),
+                    (
+                        "lifecycle:transition",
+                        AttributeValue::String("start".to_string()),
+                    ),
+                ],
+            ),
+            event_with_attributes(
+                "b",
+                &[
+                    (
+                        "t:timestamp",
+                        AttributeValue::String("yesterday".to_string()),
+                    ),
+                    (
+                        "c:drivers",
+                        AttributeValue::List(vec![Attribute::new(
+                            "c:amount".to_string(),
+                            AttributeValue::Int(1),
+                        )]),
+                    ),
+                ],
+            ),
+        ]]);
+        log.traces[0]
+            .attributes
+            .add_to_attributes("c:total".to_string(), AttributeValue::Float(3.5));
+        log.extensions = Some(vec![
+            EventLogExtension {
+                name: "Time".to_string(),
+                prefix: "t".to_string(),
+                uri: "https://www.xes-standard.org/time.xesext".to_string(),
+            },
+            EventLogExtension {
+                name: "Cost".to_string(),
+                prefix: "c".to_string(),
+                uri: "http://www.xes-standard.org/cost.xesext".to_string(),
+            },
+        ]);
+        log.global_event_attrs = Some(vec![Attribute::new(
+            "lifecycle:transition".to_string(),
+            AttributeValue::String("complete".to_string()),
+        )]);
+        log
+    }
+
+    #[test]
+    fn typed_accessors() {
+        let log = log_with_custom_prefixes();
+        let ctx = log.extension_context();
+        let trace = &log.traces[0];
+        assert_eq!(trace.cost_total(&ctx), Some(3.5));
+        assert!(trace.name(&ctx).is_none());
+        let (e1, e2) = (&trace.events[0], &trace.events[1]);
+        assert_eq!(e1.activity(&ctx).unwrap(), "a");
+        assert!(e1.timestamp(&ctx).is_some());
+        // Wrong type
+        assert!(e2.timestamp(&ctx).is_none());
+        assert_eq!(e1.lifecycle(&ctx).unwrap(), "start");
+        // Global default
+        assert_eq!(e2.lifecycle(&ctx).unwrap(), "complete");
+        // The undeclared prefix `time` is not used, as the time extension is declared with prefix `t`
+        assert!(e1.timestamp(&XESExtensionContext::default()).is_none());
+    }
+
+    #[test]
+    fn validate_types() {
+        let log = log_with_custom_prefixes();
+        let violations = validate_extension_attributes(&log, &log.extension_context());
+        assert_eq!(
+            violations,
+            vec![
+                ExtensionAttributeViolation {
+                    location: AttributeLocation::Event(0, 1),
+                    key: "t:timestamp".to_string(),
+                    extension: "Time".to_string(),
+                    expected: XESAttributeType::Date,
+                    found: XESAttributeType::String,
+                },
+                ExtensionAttributeViolation {
+                    location: AttributeLocation::Event(0, 1),
+                    key: "c:amount".to_string(),
+                    extension: "Cost".to_string(),
+                    expected: XESAttributeType::Float,
+                    found: XESAttributeType::Int,
+                },
+            ]
+        );
+    }
+}
@@ -15,6 +15,8 @@
 pub mod event_log {
     /// Activity projection of event logs
     pub mod activity_projection;
+    /// Columnar event log with interned attribute keys and values
+    pub mod columnar_log;
     /// Constants
     pub mod constants;
     /// Conversion of XES event data from/to polars `DataFrame`
@@ -25,12 +27,22 @@ pub mod event_log {
     pub mod event_log_splitter;
     /// [`EventLog`] struct and sub-structs
     pub mod event_log_struct;
+    /// MXML Export (legacy `ProM` event log format)
+    pub mod export_mxml;
     /// XES Export
     pub mod export_xes;
+    /// MXML Import (legacy `ProM` event log format)
+    pub mod import_mxml;
     /// XES Import
     pub mod import_xes;
+    /// Parallel XES Import (parsing chunks of traces on multiple threads)
+    pub mod import_xes_parallel;
     /// Streaming XES Import
     pub mod stream_xes;
+    /// Filtering and transforming traces and events while streaming XES
+    pub mod stream_xes_filter;
+    /// XES extension definitions (XESEXT), typed accessors for extension attributes and validation of attribute types
+    pub mod xes_extensions;
     ///
     /// OCEL2.0 (Object-Centric Event Logs)
     ///
@@ -62,6 +74,10 @@ pub mod utils;
 /// Petri nets
 ///
 pub mod petri_net {
+    /// Structural classification of [`PetriNet`]s (e.g., free-choice or workflow nets), S-/T-components, siphons and traps
+    pub mod classification;
+    /// Composition of [`PetriNet`]s (parallel composition, synchronous product, disjoint union), label hiding and relabeling
+    pub mod composition;
     /// Export [`PetriNet`] to `.pnml`
     pub mod export_pnml;
     #[cfg(feature = "graphviz-export")]
@@ -72,15 +88,64 @@ pub mod petri_net {
     /// Also requires an active graphviz installation in the PATH.
     /// See also <https://github.com/besok/graphviz-rust?tab=readme-ov-file#caveats> and <https://graphviz.org/download/>
     pub mod image_export;
+    #[cfg(feature = "graphviz-export")]
+    /// Import [`PetriNet`] from DOT (e.g., as exported by [`image_export`])
+    ///
+    /// __Requires the `graphviz-export` feature to be enabled__ (but no graphviz installation)
+    pub mod import_dot;
     /// Import [`PetriNet`] from `.pnml`
     pub mod import_pnml;
+    /// Place and transition invariants (semi-flows) of [`PetriNet`]s
+    pub mod invariants;
     /// [`PetriNet`] struct
     pub mod petri_net_struct;
+    #[cfg(feature = "simulation")]
+    /// Simulate event logs by playing out [`PetriNet`]s, incl. discrete-event simulation of stochastic Petri nets
+    ///
+    /// __Requires the `simulation` feature to be enabled__
+    pub mod playout;
+    /// Reduction rules for [`PetriNet`]s (preserving their visible language) and implicit place detection
+    pub mod reduction;
+    /// Stochastic and timed Petri nets (GSPN) with delay distributions, incl. fitting them to event logs
+    pub mod stochastic;
 
     #[doc(inline)]
     pub use petri_net_struct::PetriNet;
 }
 
+///
+/// BPMN 2.0 process models
+///
+pub mod bpmn {
+    /// [`BPMNModel`] struct
+    pub mod bpmn_struct;
+    /// Conversion of [`BPMNModel`]s from/to [`PetriNet`](crate::PetriNet)s
+    pub mod conversion;
+    /// Export [`BPMNModel`] to BPMN 2.0 XML (incl. a generated diagram layout)
+    pub mod export_bpmn;
+    /// Import [`BPMNModel`] from BPMN 2.0 XML
+    pub mod import_bpmn;
+
+    #[doc(inline)]
+    pub use crate::bpmn::bpmn_struct::BPMNModel;
+}
+
+///
+/// Layout and rendering of graphs (Petri nets, directly-follows graphs, ...) without external tools
+///
+pub mod visualization {
+    /// Layered (Sugiyama-style) layout of directed graphs
+    pub mod layout;
+    #[cfg(feature = "svg-export")]
+    /// Export [`PetriNet`](crate::PetriNet)s, [`DirectlyFollowsGraph`](crate::dfg::DirectlyFollowsGraph)s
+    /// and [`PartialOrderTrace`](crate::partial_orders::PartialOrderTrace)s as SVG images
+    ///
+    /// __Requires the `svg-export` feature to be enabled__
+    ///
+    /// In contrast to the `graphviz-export` feature, no Graphviz installation is required (e.g., for usage in WebAssembly).
+    pub mod svg_export;
+}
+
 ///
 /// Directly-follows graph
 ///
@@ -95,6 +160,11 @@ pub mod dfg {
     /// Also requires an active graphviz installation in the PATH.
     /// See also <https://github.com/besok/graphviz-rust?tab=readme-ov-file#caveats> and <https://graphviz.org/download/>
     pub mod image_export;
+    #[cfg(feature = "graphviz-export")]
+    /// Import [`DirectlyFollowsGraph`] from DOT (e.g., as exported by [`image_export`])
+    ///
+    /// __Requires the `graphviz-export` feature to be enabled__ (but no graphviz installation)
+    pub mod import_dot;
 
     #[doc(inline)]
     pub use crate::dfg::dfg_struct::DirectlyFollowsGraph;
@@ -122,6 +192,87 @@ pub mod partial_orders {
     pub use crate::partial_orders::partial_event_log_struct::PartialOrderEventLog;
 }
 
+///
+/// Organizational mining (social networks and roles of resources)
+///
+pub mod organizational {
+    #[cfg(feature = "graphviz-export")]
+    /// Export [`ResourceNetwork`]s to images (SVG, PNG, ...)
+    ///
+    /// __Requires the `graphviz-export` feature to be enabled__
+    ///
+    /// Also requires an active graphviz installation in the PATH.
+    /// See also <https://github.com/besok/graphviz-rust?tab=readme-ov-file#caveats> and <https://graphviz.org/download/>
+    pub mod image_export;
+    /// Activity profiles of resources
+    pub mod resource_profile;
+    /// Discovery of organizational roles
+    pub mod role_discovery;
+    /// Social networks between resources (handover of work, subcontracting, working together, similar activities)
+    pub mod social_network;
+
+    #[doc(inline)]
+    pub use crate::organizational::resource_profile::OrganizationalMiningOptions;
+
+    #[doc(inline)]
+    pub use crate::organizational::social_network::ResourceNetwork;
+}
+
+///
+/// Declare (declarative process models)
+///
+pub mod declare {
+    /// Conformance checking of [`DeclareModel`]s (activations, fulfilments and violations)
+    pub mod checking;
+    /// [`DeclareModel`] struct and Declare templates
+    pub mod declare_model;
+    /// Discovery of [`DeclareModel`]s based on support and confidence
+    pub mod discovery;
+    /// Export [`DeclareModel`]s to the `.decl` format
+    pub mod export_decl;
+    /// Import [`DeclareModel`]s from the `.decl` format
+    pub mod import_decl;
+
+    #[doc(inline)]
+    pub use crate::declare::declare_model::DeclareModel;
+}
+
+///
+/// Linear Temporal Logic on finite traces (`LTLf`)
+///
+pub mod ltlf {
+    /// Compilation of [`LTLfFormula`]s to (lazily constructed) deterministic automata
+    pub mod automaton;
+    /// Checking [`LTLfFormula`]s on traces and event logs
+    pub mod checking;
+    /// [`LTLfFormula`] struct (abstract syntax tree)
+    pub mod formula;
+    /// Parser for the textual syntax of [`LTLfFormula`]s
+    pub mod parser;
+
+    #[doc(inline)]
+    pub use crate::ltlf::formula::LTLfFormula;
+
+    #[doc(inline)]
+    pub use crate::ltlf::parser::parse_ltlf;
+}
+
+///
+/// Log Skeleton (discovery and conformance checking)
+///
+pub mod log_skeleton {
+    /// Conformance checking of traces against a [`LogSkeleton`]
+    pub mod conformance;
+    /// [`LogSkeleton`] struct and discovery
+    pub mod log_skeleton_struct;
+
+    #[doc(inline)]
+    pub use crate::log_skeleton::log_skeleton_struct::LogSkeleton;
+
+    #[doc(inline)]
+    pub use crate::log_skeleton::log_skeleton_struct::discover_log_skeleton;
+}
+
 use std::fs::File;
 use std::io::BufReader;
 use std::io::BufWriter;
@@ -139,6 +290,12 @@ pub use event_log::import_xes::import_xes_file;
 #[doc(inline)]
 pub use event_log::import_xes::import_xes_slice;
 
+#[doc(inline)]
+pub use event_log::import_xes_parallel::import_xes_file_parallel;
+
+#[doc(inline)]
+pub use event_log::import_xes_parallel::import_xes_slice_parallel;
+
 #[doc(inline)]
 pub use event_log::stream_xes::stream_xes_from_path;
 
@@ -160,12 +317,24 @@ pub use event_log::export_xes::export_xes_trace_stream_to_file;
 #[doc(inline)]
 pub use event_log::export_xes::export_xes_event_log_to_file_path;
 
+#[doc(inline)]
+pub use event_log::export_xes::export_xes_trace_stream_to_file_path;
+
+#[doc(inline)]
+pub use event_log::import_mxml::import_mxml_file;
+
+#[doc(inline)]
+pub use event_log::export_mxml::export_mxml_to_file_path;
+
 #[doc(inline)]
 pub use event_log::stream_xes::StreamingXESParser;
 
 #[doc(inline)]
 pub use event_log::import_xes::XESImportOptions;
 
+#[doc(inline)]
+pub use event_log::export_xes::XESExportOptions;
+
 #[doc(inline)]
 pub use event_log::event_log_struct::EventLog;
 
@@ -218,6 +387,26 @@ pub use event_log::dataframe::convert_log_to_dataframe;
 #[doc(inline)]
 pub use event_log::dataframe::convert_dataframe_to_log;
 
+#[cfg(feature = "dataframes")]
+#[doc(inline)]
+pub use event_log::dataframe::parquet_ipc::export_log_to_parquet_path;
+
+#[cfg(feature = "dataframes")]
+#[doc(inline)]
+pub use event_log::dataframe::parquet_ipc::import_log_from_parquet_path;
+
+#[cfg(feature = "dataframes")]
+#[doc(inline)]
+pub use event_log::dataframe::parquet_ipc::export_log_to_ipc_path;
+
+#[cfg(feature = "dataframes")]
+#[doc(inline)]
+pub use event_log::dataframe::parquet_ipc::import_log_from_ipc_path;
+
+#[cfg(feature = "dataframes")]
+#[doc(inline)]
+pub use event_log::dataframe::parquet_ipc::export_trace_stream_to_parquet_path;
+
 #[doc(inline)]
 pub use petri_net::petri_net_struct::PetriNet;
 
@@ -235,9 +424,41 @@ pub use petri_net::export_pnml::export_petri_net_to_pnml;
 #[doc(inline)]
 pub use petri_net::import_pnml::import_pnml;
 
+#[doc(inline)]
+pub use bpmn::BPMNModel;
+
+#[doc(inline)]
+pub use bpmn::export_bpmn::export_bpmn_to_path;
+
+#[doc(inline)]
+pub use bpmn::import_bpmn::import_bpmn_from_path;
+
+#[cfg(feature = "svg-export")]
+#[doc(inline)]
+pub use visualization::svg_export::SVGExportOptions;
+
 #[doc(inline)]
 pub use event_log::activity_projection::EventLogActivityProjection;
 
+///
+/// Conformance checking and quality metrics (fitness, precision, generalization, simplicity) of [`PetriNet`]s
+///
+pub mod conformance {
+    /// Replay-based generalization
+    pub mod generalization;
+    /// Escaping-edges precision (`ETConformance`)
+    pub mod precision;
+    /// Combined [`ModelQuality`] of a Petri net w.r.t. an event log
+    pub mod quality;
+    /// Simplicity metrics (arc degree, number of nodes, extended Cardoso metric)
+    pub mod simplicity;
+    /// Token-based replay of event logs on Petri nets
+    pub mod token_replay;
+
+    #[doc(inline)]
+    pub use crate::conformance::quality::ModelQuality;
+}
+
 ///
 /// Module for the Alpha+++ Process Discovery algorithm
 ///
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_log::{
+        activity_projection::EventLogActivityProjection, event_log_struct::EventLogClassifier,
+    },
+    EventLog,
+};
+
+use super::log_skeleton_struct::LogSkeleton;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// A violated constraint of a [`LogSkeleton`]
+///
+pub enum LogSkeletonViolation {
+    /// Equivalence constraint `(a, b)` is violated (i.e., `a` occurs, but not as often as `b`)
+    Equivalence(String, String),
+    /// Always-after constraint `(a, b)` is violated (i.e., some `a` is not followed by a `b`)
+    AlwaysAfter(String, String),
+    /// Always-before constraint `(a, b)` is violated (i.e., some `a` is not preceded by a `b`)
+    AlwaysBefore(String, String),
+    /// Never-together constraint `(a, b)` is violated (i.e., both `a` and `b` occur)
+    NeverTogether(String, String),
+    /// The directly-follows pair `(a, b)` occurs, but is not allowed
+    DirectlyFollows(String, String),
+    /// The activity occurs in the trace with a number of occurrences that is not allowed
+    ActivityFrequency(String, u64),
+}
+
+impl LogSkeleton {
+    ///
+    /// Check a trace (given as a sequence of activities) against the [`LogSkeleton`]
+    ///
+    /// Returns all violated constraints (empty if the trace conforms to the model).
+    ///
+    pub fn check_trace<S: AsRef<str>>(&self, trace: &[S]) -> Vec<LogSkeletonViolation> {
+        let trace: Vec<&str> = trace.iter().map(|a| a.as_ref()).collect();
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        let mut first: HashMap<&str, usize> = HashMap::new();
+        let mut last: HashMap<&str, usize> = HashMap::new();
+        for (i, a) in trace.iter().enumerate() {
+            *counts.entry(a).or_default() += 1;
+            first.entry(a).or_insert(i);
+            last.insert(a, i);
+        }
+        let count = |a: &str| *counts.get(a).unwrap_or(&0);
+        let mut ret = Vec::new();
+        for (a, b) in &self.equivalence {
+            if count(a) > 0 && count(a) != count(b) {
+                ret.push(LogSkeletonViolation::Equivalence(a.clone(), b.clone()));
+            }
+        }
+        for (a, b) in &self.always_after {
+            if let Some(last_a) = last.get(a.as_str()) {
+                if last.get(b.as_str()).map_or(true, |last_b| last_b < last_a) {
+                    ret.push(LogSkeletonViolation::AlwaysAfter(a.clone(), b.clone()));
+                }
+            }
+        }
+        for (a, b) in &self.always_before {
+            if let Some(first_a) = first.get(a.as_str()) {
+                if first
+                    .get(b.as_str())
+                    .map_or(true, |first_b| first_b > first_a)
+                {
+                    ret.push(LogSkeletonViolation::AlwaysBefore(a.clone(), b.clone()));
+                }
+            }
+        }
+        for (a, b) in &self.never_together {
+            if count(a) > 0 && count(b) > 0 {
+                ret.push(LogSkeletonViolation::NeverTogether(a.clone(), b.clone()));
+            }
+        }
+        let trace_dfs: BTreeSet<(&str, &str)> = trace.windows(2).map(|w| (w[0], w[1])).collect();
+        for (a, b) in trace_dfs {
+            if !self
+                .directly_follows
+                .contains(&(a.to_string(), b.to_string()))
+            {
+                ret.push(LogSkeletonViolation::DirectlyFollows(
+                    a.to_string(),
+                    b.to_string(),
+                ));
+            }
+        }
+        let mut trace_acts: BTreeMap<&str, u64> = counts.iter().map(|(a, c)| (*a, *c)).collect();
+        for a in self.activity_frequencies.keys() {
+            trace_acts.entry(a).or_insert(0);
+        }
+        for (a, c) in trace_acts {
+            let allowed = self
+                .activity_frequencies
+                .get(a)
+                .map_or(c == 0, |freqs| freqs.contains(&c));
+            if !allowed {
+                ret.push(LogSkeletonViolation::ActivityFrequency(a.to_string(), c));
+            }
+        }
+        ret
+    }
+}
+
+///
+/// Check all traces of an [`EventLog`] against a [`LogSkeleton`]
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`].
+/// Returns the violated constraints for each trace (in the same order as the traces of the log).
+pub fn check_log_skeleton(
+    log: &EventLog,
+    skeleton: &LogSkeleton,
+    classifier: &EventLogClassifier,
+) -> Vec<Vec<LogSkeletonViolation>> {
+    log.traces
+        .par_iter()
+        .map(|t| {
+            let trace: Vec<String> = t
+                .events
+                .iter()
+                .map(|e| classifier.get_class_identity_with_globals(e, &log.global_event_attrs))
+                .collect();
+            skeleton.check_trace(&trace)
+        })
+        .collect()
+}
+
+///
+/// Check all trace variants of an [`EventLogActivityProjection`] against a [`LogSkeleton`]
+///
+/// Returns the violated constraints for each variant (in the same order as [`EventLogActivityProjection::traces`]).
+pub fn check_log_skeleton_projection(
+    log: &EventLogActivityProjection,
+    skeleton: &LogSkeleton,
+) -> Vec<Vec<LogSkeletonViolation>> {
+    log.traces
+        .par_iter()
+        .map(|(t, _)| {
+            let trace: Vec<&str> = t.iter().map(|a| log.activities[*a].as_str()).collect();
+            skeleton.check_trace(&trace)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        log_skeleton::log_skeleton_struct::discover_log_skeleton,
+        utils::test_utils::event_log_from_activities,
+    };
+
+    use super::*;
+
+    #[test]
+    fn check_traces() {
+        let log = event_log_from_activities(&[
+            &["register", "check", "pay"],
+            &["register", "check", "pay"],
+            &["register", "check", "check", "pay"],
+        ]);
+        let proj: EventLogActivityProjection = (&log).into();
+        let skeleton = discover_log_skeleton(&proj, 0.0);
+        assert!(check_log_skeleton_projection(&proj, &skeleton)
+            .iter()
+            .all(|v| v.is_empty()));
+        assert!(
+            check_log_skeleton(&log, &skeleton, &EventLogClassifier::default())
+                .iter()
+                .all(|v| v.is_empty())
+        );
+
+        let violations = skeleton.check_trace(&["check", "register", "pay", "pay", "archive"]);
+        let s = |x: &str| x.to_string();
+        assert!(violations.contains(&LogSkeletonViolation::AlwaysBefore(
+            s("check"),
+            s("register")
+        )));
+        assert!(violations.contains(&LogSkeletonViolation::AlwaysAfter(
+            s("register"),
+            s("check")
+        )));
+        assert!(violations.contains(&LogSkeletonViolation::Equivalence(s("register"), s("pay"))));
+        assert!(violations.contains(&LogSkeletonViolation::DirectlyFollows(s("pay"), s("pay"))));
+        assert!(violations.contains(&LogSkeletonViolation::ActivityFrequency(s("pay"), 2)));
+        assert!(violations.contains(&LogSkeletonViolation::ActivityFrequency(s("archive"), 1)));
+        assert!(!violations.contains(&LogSkeletonViolation::ActivityFrequency(s("check"), 1)));
+    }
+}
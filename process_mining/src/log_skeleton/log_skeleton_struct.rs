@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::event_log::activity_projection::EventLogActivityProjection;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Log Skeleton model
+///
+/// A Log Skeleton consists of the following constraints between activities, which all traces are expected to satisfy:
+///
+/// - __Equivalence__ `(a, b)`: If `a` occurs in a trace, `a` and `b` occur equally often
+/// - __Always after__ `(a, b)`: Every occurrence of `a` is followed by some `b` (later in the trace)
+/// - __Always before__ `(a, b)`: Every occurrence of `a` is preceded by some `b` (earlier in the trace)
+/// - __Never together__ `(a, b)`: `a` and `b` do not occur in the same trace
+/// - __Directly-follows__: Only the listed pairs `(a, b)` may directly follow each other
+/// - __Activity frequencies__: Each activity occurs only with one of the listed frequencies per trace (including `0`)
+///
+/// See also [`discover_log_skeleton`] and [`super::conformance`].
+pub struct LogSkeleton {
+    /// All activities (sorted)
+    pub activities: Vec<String>,
+    /// Equivalence relation
+    pub equivalence: BTreeSet<(String, String)>,
+    /// Always-after relation
+    pub always_after: BTreeSet<(String, String)>,
+    /// Always-before relation
+    pub always_before: BTreeSet<(String, String)>,
+    /// Never-together relation
+    pub never_together: BTreeSet<(String, String)>,
+    /// Allowed directly-follows pairs
+    pub directly_follows: BTreeSet<(String, String)>,
+    /// Allowed number of occurrences per trace of each activity
+    pub activity_frequencies: BTreeMap<String, BTreeSet<u64>>,
+}
+
+///
+/// Discover a [`LogSkeleton`] from an [`EventLogActivityProjection`]
+///
+/// The `noise_threshold` (between `0.0` and `1.0`) allows constraints to be violated by a fraction of the log:
+///
+/// - Equivalence and never-together constraints `(a, b)` are kept if they hold for at least `1 - noise_threshold` of the traces containing `a`
+/// - Always-after and always-before constraints `(a, b)` are kept if they hold for at least `1 - noise_threshold` of the occurrences of `a`
+/// - Directly-follows pairs `(a, b)` are kept if they make up at least `noise_threshold` of the occurrences of `a`
+/// - Activity frequencies are kept (from most to least common) until they cover at least `1 - noise_threshold` of the traces
+///
+/// With a `noise_threshold` of `0.0`, all traces of the log conform to the discovered model.
+///
+/// An [`EventLogActivityProjection`] can be constructed from an [`crate::EventLog`] using `(&log).into()`.
+pub fn discover_log_skeleton(
+    log: &EventLogActivityProjection,
+    noise_threshold: f64,
+) -> LogSkeleton {
+    let n = log.activities.len();
+    let mut traces_with = vec![0u64; n];
+    let mut occurrences = vec![0u64; n];
+    let mut equivalence = vec![vec![0u64; n]; n];
+    let mut always_after = vec![vec![0u64; n]; n];
+    let mut always_before = vec![vec![0u64; n]; n];
+    let mut never_together = vec![vec![0u64; n]; n];
+    let mut directly_follows: HashMap<(usize, usize), u64> = HashMap::new();
+    let mut frequencies: Vec<HashMap<u64, u64>> = vec![HashMap::new(); n];
+    let mut total_traces = 0;
+
+    for (trace, freq) in &log.traces {
+        let freq = *freq;
+        total_traces += freq;
+        let mut counts = vec![0u64; n];
+        for a in trace {
+            counts[*a] += 1;
+        }
+        for a in 0..n {
+            *frequencies[a].entry(counts[a]).or_default() += freq;
+            if counts[a] == 0 {
+                continue;
+            }
+            traces_with[a] += freq;
+            occurrences[a] += counts[a] * freq;
+            for b in 0..n {
+                if counts[a] == counts[b] {
+                    equivalence[a][b] += freq;
+                }
+                if counts[b] == 0 {
+                    never_together[a][b] += freq;
+                }
+            }
+        }
+        // Activities seen before (forward pass) and after (backward pass) each position
+        let mut seen = vec![false; n];
+        let mut seen_list = Vec::new();
+        for a in trace {
+            for b in &seen_list {
+                always_before[*a][*b] += freq;
+            }
+            if !seen[*a] {
+                seen[*a] = true;
+                seen_list.push(*a);
+            }
+        }
+        let mut seen = vec![false; n];
+        let mut seen_list = Vec::new();
+        for a in trace.iter().rev() {
+            for b in &seen_list {
+                always_after[*a][*b] += freq;
+            }
+            if !seen[*a] {
+                seen[*a] = true;
+                seen_list.push(*a);
+            }
+        }
+        for w in trace.windows(2) {
+            *directly_follows.entry((w[0], w[1])).or_default() += freq;
+        }
+    }
+
+    let keep = |count: u64, total: u64| count as f64 >= (1.0 - noise_threshold) * total as f64;
+    let pair = |a: usize, b: usize| (log.activities[a].clone(), log.activities[b].clone());
+    let mut ret = LogSkeleton {
+        activities: log.activities.clone(),
+        ..Default::default()
+    };
+    ret.activities.sort();
+    for a in 0..n {
+        if traces_with[a] == 0 {
+            continue;
+        }
+        for b in (0..n).filter(|b| *b != a) {
+            if keep(equivalence[a][b], traces_with[a]) {
+                ret.equivalence.insert(pair(a, b));
+            }
+            if keep(never_together[a][b], traces_with[a]) {
+                ret.never_together.insert(pair(a, b));
+            }
+            if keep(always_after[a][b], occurrences[a]) {
+                ret.always_after.insert(pair(a, b));
+            }
+            if keep(always_before[a][b], occurrences[a]) {
+                ret.always_before.insert(pair(a, b));
+            }
+        }
+    }
+    for ((a, b), count) in directly_follows {
+        if count as f64 >= noise_threshold * occurrences[a] as f64 {
+            ret.directly_follows.insert(pair(a, b));
+        }
+    }
+    for (a, freqs) in frequencies.into_iter().enumerate() {
+        let mut freqs: Vec<(u64, u64)> = freqs.into_iter().collect();
+        // Most common frequencies first (ties broken by the frequency value for determinism)
+        freqs.sort_by(|(x, x_count), (y, y_count)| y_count.cmp(x_count).then(x.cmp(y)));
+        let mut covered = 0;
+        let mut allowed = BTreeSet::new();
+        for (f, count) in freqs {
+            if allowed.is_empty() || !keep(covered, total_traces) {
+                allowed.insert(f);
+                covered += count;
+            }
+        }
+        ret.activity_frequencies
+            .insert(log.activities[a].clone(), allowed);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_projection() -> EventLogActivityProjection {
+        let activities: Vec<String> = ["register", "check", "pay", "reject"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        EventLogActivityProjection {
+            act_to_index: activities
+                .iter()
+                .enumerate()
+                .map(|(i, a)| (a.clone(), i))
+                .collect(),
+            activities,
+            traces: vec![
+                (vec![0, 1, 2], 6),
+                (vec![0, 1, 1, 2], 3),
+                (vec![0, 1, 3], 1),
+            ],
+        }
+    }
+
+    fn p(a: &str, b: &str) -> (String, String) {
+        (a.to_string(), b.to_string())
+    }
+
+    #[test]
+    fn discover_without_noise() {
+        let skeleton = discover_log_skeleton(&sample_projection(), 0.0);
+        assert_eq!(
+            skeleton.activities,
+            vec!["check", "pay", "register", "reject"]
+        );
+        assert!(skeleton.always_after.contains(&p("register", "check")));
+        assert!(!skeleton.always_after.contains(&p("register", "pay")));
+        assert!(skeleton.always_before.contains(&p("pay", "register")));
+        assert!(skeleton.always_before.contains(&p("check", "register")));
+        assert!(skeleton.never_together.contains(&p("pay", "reject")));
+        assert!(skeleton.never_together.contains(&p("reject", "pay")));
+        assert!(skeleton.equivalence.contains(&p("pay", "register")));
+        assert!(!skeleton.equivalence.contains(&p("register", "check")));
+        assert!(skeleton.directly_follows.contains(&p("check", "check")));
+        assert_eq!(skeleton.directly_follows.len(), 4);
+        assert_eq!(
+            skeleton.activity_frequencies["check"],
+            BTreeSet::from_iter([1, 2])
+        );
+        assert_eq!(
+            skeleton.activity_frequencies["reject"],
+            BTreeSet::from_iter([0, 1])
+        );
+    }
+
+    #[test]
+    fn discover_with_noise() {
+        let skeleton = discover_log_skeleton(&sample_projection(), 0.15);
+        // 9 of 10 traces end with pay
+        assert!(skeleton.always_after.contains(&p("register", "pay")));
+        assert!(!skeleton.directly_follows.contains(&p("check", "reject")));
+        assert_eq!(
+            skeleton.activity_frequencies["reject"],
+            BTreeSet::from_iter([0])
+        );
+        assert_eq!(
+            skeleton.activity_frequencies["check"],
+            BTreeSet::from_iter([1, 2])
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::event_log::{Attributes, Event};
+
+use super::formula::{LTLfAtom, LTLfFormula};
+
+///
+/// Internal representation of `LTLf` formulas in negation normal form
+///
+/// Conjunctions and disjunctions are represented as sets, so that equivalent formulas
+/// (up to associativity, commutativity and idempotence) are represented identically.
+/// Atoms are referenced by their index in [`LTLfAutomaton::atoms`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Nnf {
+    True,
+    False,
+    Atom(usize),
+    NotAtom(usize),
+    And(BTreeSet<Nnf>),
+    Or(BTreeSet<Nnf>),
+    Next(Box<Nnf>),
+    WeakNext(Box<Nnf>),
+    Eventually(Box<Nnf>),
+    Globally(Box<Nnf>),
+    Until(Box<Nnf>, Box<Nnf>),
+    Release(Box<Nnf>, Box<Nnf>),
+}
+
+impl Nnf {
+    fn and<I: IntoIterator<Item = Nnf>>(parts: I) -> Nnf {
+        let mut set = BTreeSet::new();
+        for p in parts {
+            match p {
+                Nnf::True => {}
+                Nnf::False => return Nnf::False,
+                Nnf::And(inner) => set.extend(inner),
+                p => {
+                    set.insert(p);
+                }
+            }
+        }
+        let contradiction = set.iter().any(|p| match p {
+            Nnf::Atom(i) => set.contains(&Nnf::NotAtom(*i)),
+            _ => false,
+        });
+        if contradiction {
+            return Nnf::False;
+        }
+        match set.len() {
+            0 => Nnf::True,
+            1 => set.into_iter().next().unwrap(),
+            _ => Nnf::And(set),
+        }
+    }
+
+    fn or<I: IntoIterator<Item = Nnf>>(parts: I) -> Nnf {
+        let mut set = BTreeSet::new();
+        for p in parts {
+            match p {
+                Nnf::False => {}
+                Nnf::True => return Nnf::True,
+                Nnf::Or(inner) => set.extend(inner),
+                p => {
+                    set.insert(p);
+                }
+            }
+        }
+        let tautology = set.iter().any(|p| match p {
+            Nnf::Atom(i) => set.contains(&Nnf::NotAtom(*i)),
+            _ => false,
+        });
+        if tautology {
+            return Nnf::True;
+        }
+        match set.len() {
+            0 => Nnf::False,
+            1 => set.into_iter().next().unwrap(),
+            _ => Nnf::Or(set),
+        }
+    }
+
+    /// Convert a formula to negation normal form (negating it if `negated` is set)
+    fn from_formula(f: &LTLfFormula, atoms: &[&LTLfAtom], negated: bool) -> Nnf {
+        let nnf = |f: &LTLfFormula, neg: bool| Box::new(Nnf::from_formula(f, atoms, neg));
+        match f {
+            LTLfFormula::True if negated => Nnf::False,
+            LTLfFormula::True => Nnf::True,
+            LTLfFormula::False if negated => Nnf::True,
+            LTLfFormula::False => Nnf::False,
+            LTLfFormula::Atom(a) => {
+                let index = atoms.iter().position(|x| *x == a).unwrap();
+                if negated {
+                    Nnf::NotAtom(index)
+                } else {
+                    Nnf::Atom(index)
+                }
+            }
+            LTLfFormula::Not(g) => Nnf::from_formula(g, atoms, !negated),
+            LTLfFormula::And(g, h) if negated => Nnf::or([*nnf(g, true), *nnf(h, true)]),
+            LTLfFormula::And(g, h) => Nnf::and([*nnf(g, false), *nnf(h, false)]),
+            LTLfFormula::Or(g, h) if negated => Nnf::and([*nnf(g, true), *nnf(h, true)]),
+            LTLfFormula::Or(g, h) => Nnf::or([*nnf(g, false), *nnf(h, false)]),
+            LTLfFormula::Implies(g, h) if negated => Nnf::and([*nnf(g, false), *nnf(h, true)]),
+            LTLfFormula::Implies(g, h) => Nnf::or([*nnf(g, true), *nnf(h, false)]),
+            LTLfFormula::Equivalent(g, h) => Nnf::or([
+                Nnf::and([*nnf(g, false), *nnf(h, negated)]),
+                Nnf::and([*nnf(g, true), *nnf(h, !negated)]),
+            ]),
+            LTLfFormula::Next(g) if negated => Nnf::WeakNext(nnf(g, true)),
+            LTLfFormula::Next(g) => Nnf::Next(nnf(g, false)),
+            LTLfFormula::WeakNext(g) if negated => Nnf::Next(nnf(g, true)),
+            LTLfFormula::WeakNext(g) => Nnf::WeakNext(nnf(g, false)),
+            LTLfFormula::Eventually(g) if negated => Nnf::Globally(nnf(g, true)),
+            LTLfFormula::Eventually(g) => Nnf::Eventually(nnf(g, false)),
+            LTLfFormula::Globally(g) if negated => Nnf::Eventually(nnf(g, true)),
+            LTLfFormula::Globally(g) => Nnf::Globally(nnf(g, false)),
+            LTLfFormula::Until(g, h) if negated => Nnf::Release(nnf(g, true), nnf(h, true)),
+            LTLfFormula::Until(g, h) => Nnf::Until(nnf(g, false), nnf(h, false)),
+            LTLfFormula::Release(g, h) if negated => Nnf::Until(nnf(g, true), nnf(h, true)),
+            LTLfFormula::Release(g, h) => Nnf::Release(nnf(g, false), nnf(h, false)),
+            // g W h = h R (g | h)
+            LTLfFormula::WeakUntil(g, h) if negated => Nnf::Until(
+                nnf(h, true),
+                Box::new(Nnf::and([*nnf(g, true), *nnf(h, true)])),
+            ),
+            LTLfFormula::WeakUntil(g, h) => Nnf::Release(
+                nnf(h, false),
+                Box::new(Nnf::or([*nnf(g, false), *nnf(h, false)])),
+            ),
+        }
+    }
+
+    ///
+    /// Progress the formula over one event (with the given atom valuation)
+    ///
+    /// The result is the formula that has to hold on the remaining suffix of the trace.
+    /// Strong/weak next obligations are encoded using `F(true)` (there is another event) and `G(false)` (there is no other event).
+    fn progress(&self, valuation: &[bool]) -> Nnf {
+        match self {
+            Nnf::True => Nnf::True,
+            Nnf::False => Nnf::False,
+            Nnf::Atom(i) if valuation[*i] => Nnf::True,
+            Nnf::Atom(_) => Nnf::False,
+            Nnf::NotAtom(i) if valuation[*i] => Nnf::False,
+            Nnf::NotAtom(_) => Nnf::True,
+            Nnf::And(parts) => Nnf::and(parts.iter().map(|p| p.progress(valuation))),
+            Nnf::Or(parts) => Nnf::or(parts.iter().map(|p| p.progress(valuation))),
+            Nnf::Next(f) => Nnf::and([*f.clone(), Nnf::Eventually(Box::new(Nnf::True))]),
+            Nnf::WeakNext(f) => Nnf::or([*f.clone(), Nnf::Globally(Box::new(Nnf::False))]),
+            Nnf::Eventually(f) => Nnf::or([f.progress(valuation), self.clone()]),
+            Nnf::Globally(f) => Nnf::and([f.progress(valuation), self.clone()]),
+            Nnf::Until(f, g) => Nnf::or([
+                g.progress(valuation),
+                Nnf::and([f.progress(valuation), self.clone()]),
+            ]),
+            Nnf::Release(f, g) => Nnf::and([
+                g.progress(valuation),
+                Nnf::or([f.progress(valuation), self.clone()]),
+            ]),
+        }
+    }
+
+    /// Whether the formula is satisfied by the empty suffix (i.e., at the end of a trace)
+    ///
+    /// Atoms do not hold at a non-existent position, so negated atoms do.
+    /// Thus, a formula and its negation are never both (un)satisfied by the empty suffix.
+    fn accepts_empty(&self) -> bool {
+        match self {
+            Nnf::True => true,
+            Nnf::False => false,
+            Nnf::Atom(_) => false,
+            Nnf::NotAtom(_) => true,
+            Nnf::And(parts) => parts.iter().all(|p| p.accepts_empty()),
+            Nnf::Or(parts) => parts.iter().any(|p| p.accepts_empty()),
+            Nnf::Next(_) | Nnf::Eventually(_) | Nnf::Until(_, _) => false,
+            Nnf::WeakNext(_) | Nnf::Globally(_) | Nnf::Release(_, _) => true,
+        }
+    }
+}
+
+///
+/// Deterministic finite automaton for an [`LTLfFormula`]
+///
+/// The automaton is constructed lazily using formula progression: States correspond to (normalized) formulas
+/// which remain to be satisfied by the rest of the trace and transitions are computed (and cached) on demand
+/// for each encountered valuation of the atoms of the formula.
+/// Thus, only the part of the automaton relevant for the checked traces is constructed.
+///
+#[derive(Debug, Clone)]
+pub struct LTLfAutomaton {
+    atoms: Vec<LTLfAtom>,
+    states: Vec<Nnf>,
+    state_indices: HashMap<Nnf, usize>,
+    accepting: Vec<bool>,
+    transitions: HashMap<(usize, Vec<bool>), usize>,
+}
+
+impl LTLfAutomaton {
+    /// Index of the initial state
+    pub const INITIAL_STATE: usize = 0;
+
+    /// Construct the (lazy) automaton of a formula
+    pub fn new(formula: &LTLfFormula) -> Self {
+        let atoms = formula.atoms();
+        let initial = Nnf::from_formula(formula, &atoms, false);
+        let mut ret = Self {
+            atoms: atoms.into_iter().cloned().collect(),
+            states: Vec::new(),
+            state_indices: HashMap::new(),
+            accepting: Vec::new(),
+            transitions: HashMap::new(),
+        };
+        ret.get_or_add_state(initial);
+        ret
+    }
+
+    fn get_or_add_state(&mut self, state: Nnf) -> usize {
+        if let Some(i) = self.state_indices.get(&state) {
+            return *i;
+        }
+        let i = self.states.len();
+        self.accepting.push(state.accepts_empty());
+        self.states.push(state.clone());
+        self.state_indices.insert(state, i);
+        i
+    }
+
+    /// Atoms of the formula; Valuations are given in the same order
+    pub fn atoms(&self) -> &[LTLfAtom] {
+        &self.atoms
+    }
+
+    /// Number of states constructed so far
+    pub fn num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Whether the state is accepting (i.e., the formula is satisfied if the trace ends in this state)
+    pub fn is_accepting(&self, state: usize) -> bool {
+        self.accepting[state]
+    }
+
+    /// Whether the state can not be left anymore (i.e., the result is already determined)
+    pub fn is_sink(&self, state: usize) -> bool {
+        matches!(self.states[state], Nnf::True | Nnf::False)
+    }
+
+    /// Evaluate all atoms of the formula on an event
+    pub fn valuation(
+        &self,
+        event: &Event,
+        activity: &str,
+        global_event_attrs: &Option<Attributes>,
+    ) -> Vec<bool> {
+        self.atoms
+            .iter()
+            .map(|a| a.holds(event, activity, global_event_attrs))
+            .collect()
+    }
+
+    /// Perform a transition from the given state for an event with the given atom valuation
+    pub fn step(&mut self, state: usize, valuation: &[bool]) -> usize {
+        if let Some(next) = self.transitions.get(&(state, valuation.to_vec())) {
+            return *next;
+        }
+        let next = self.states[state].progress(valuation);
+        let next = self.get_or_add_state(next);
+        self.transitions.insert((state, valuation.to_vec()), next);
+        next
+    }
+
+    /// Check if the sequence of atom valuations (one per event) satisfies the formula
+    pub fn accepts<'a, I: IntoIterator<Item = &'a [bool]>>(&mut self, valuations: I) -> bool {
+        let mut state = Self::INITIAL_STATE;
+        for v in valuations {
+            if self.is_sink(state) {
+                break;
+            }
+            state = self.step(state, v);
+        }
+        self.is_accepting(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ltlf::parser::parse_ltlf;
+
+    use super::*;
+
+    /// Check a formula on a trace of single activities (only supports activity atoms)
+    fn accepts(formula: &str, trace: &[&str]) -> bool {
+        let f = parse_ltlf(formula).unwrap();
+        let mut automaton = LTLfAutomaton::new(&f);
+        let valuations: Vec<Vec<bool>> = trace
+            .iter()
+            .map(|act| {
+                automaton
+                    .atoms()
+                    .iter()
+                    .map(|a| *a == LTLfAtom::Activity(act.to_string()))
+                    .collect()
+            })
+            .collect();
+        automaton.accepts(valuations.iter().map(|v| v.as_slice()))
+    }
+
+    #[test]
+    fn ltlf_semantics() {
+        assert!(accepts("a", &["a", "b"]));
+        assert!(!accepts("a", &[]));
+        assert!(accepts("!a", &[]));
+        assert!(accepts("!(a & b)", &[]));
+        assert!(accepts("a | !a", &[]));
+        assert!(!accepts("a & !a", &[]));
+        assert!(accepts("X !b", &["a"]) != accepts("!X !b", &["a"]));
+        assert!(accepts("F b", &["a", "b"]));
+        assert!(!accepts("F c", &["a", "b"]));
+        assert!(accepts("G !c", &["a", "b"]));
+        assert!(accepts("G !c", &[]));
+        assert!(accepts("X b", &["a", "b"]));
+        assert!(!accepts("X X b", &["a", "b"]));
+        assert!(!accepts("WX X b", &["a", "b"]));
+        assert!(accepts("WX X b", &["a"]));
+        assert!(accepts("X WX c", &["a", "b"]));
+        assert!(accepts("a U b", &["a", "a", "b"]));
+        assert!(!accepts("a U b", &["a", "a"]));
+        assert!(accepts("a W b", &["a", "a"]));
+        assert!(!accepts("a W b", &["a", "c", "b"]));
+        assert!(accepts("b R !c", &["a", "b", "c"]));
+        assert!(!accepts("b R !c", &["a", "c", "b"]));
+        assert!(accepts("F(a & X(b))", &["c", "a", "b"]));
+        assert!(!accepts("F(a & X(b))", &["c", "a"]));
+        assert!(accepts("(a <-> X b) & !(c -> false)", &["c"]));
+        assert!(accepts("(a <-> X b)", &["c", "a"]));
+
+        let rule = "G(approve -> F(pay | cancel))";
+        assert!(accepts(rule, &["approve", "check", "pay"]));
+        assert!(accepts(rule, &["approve", "cancel"]));
+        assert!(accepts(rule, &["check"]));
+        assert!(!accepts(rule, &["approve", "pay", "approve"]));
+    }
+
+    #[test]
+    fn automaton_is_reused() {
+        let f = parse_ltlf("G(a -> F b)").unwrap();
+        let mut automaton = LTLfAutomaton::new(&f);
+        for _ in 0..10 {
+            automaton.accepts(
+                [[true, false], [false, true], [true, false]]
+                    .iter()
+                    .map(|v| v.as_slice()),
+            );
+        }
+        assert!(automaton.num_states() <= 3);
+    }
+}
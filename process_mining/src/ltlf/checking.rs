@@ -0,0 +1,173 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    event_log::{event_log_struct::EventLogClassifier, Attributes, Trace},
+    EventLog,
+};
+
+use super::{automaton::LTLfAutomaton, formula::LTLfFormula};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Result of checking an [`LTLfFormula`] on an [`EventLog`]
+///
+pub struct LTLfCheckingResult {
+    /// Indices of all traces satisfying the formula
+    pub satisfying_traces: Vec<usize>,
+    /// Indices of all traces violating the formula
+    pub violating_traces: Vec<usize>,
+}
+
+impl LTLfCheckingResult {
+    /// Fraction of traces satisfying the formula (`1.0` for empty logs)
+    pub fn satisfaction_ratio(&self) -> f64 {
+        let total = self.satisfying_traces.len() + self.violating_traces.len();
+        if total == 0 {
+            return 1.0;
+        }
+        self.satisfying_traces.len() as f64 / total as f64
+    }
+}
+
+///
+/// Check whether a single [`Trace`] satisfies an [`LTLfFormula`]
+///
+/// The activity of events is determined using the passed [`EventLogClassifier`], `global_event_attrs` are used as default attribute values.
+///
+/// For checking multiple traces, prefer [`check_ltlf_formula`] or [`check_ltlf_trace_with_automaton`], which reuse the constructed automaton.
+pub fn check_ltlf_trace(
+    trace: &Trace,
+    formula: &LTLfFormula,
+    classifier: &EventLogClassifier,
+    global_event_attrs: &Option<Attributes>,
+) -> bool {
+    let mut automaton = LTLfAutomaton::new(formula);
+    check_ltlf_trace_with_automaton(trace, &mut automaton, classifier, global_event_attrs)
+}
+
+///
+/// Check whether a single [`Trace`] is accepted by an [`LTLfAutomaton`]
+///
+/// See also [`check_ltlf_trace`].
+pub fn check_ltlf_trace_with_automaton(
+    trace: &Trace,
+    automaton: &mut LTLfAutomaton,
+    classifier: &EventLogClassifier,
+    global_event_attrs: &Option<Attributes>,
+) -> bool {
+    let mut state = LTLfAutomaton::INITIAL_STATE;
+    for e in &trace.events {
+        if automaton.is_sink(state) {
+            break;
+        }
+        let activity = classifier.get_class_identity_with_globals(e, global_event_attrs);
+        let valuation = automaton.valuation(e, &activity, global_event_attrs);
+        state = automaton.step(state, &valuation);
+    }
+    automaton.is_accepting(state)
+}
+
+///
+/// Check an [`LTLfFormula`] on all traces of an [`EventLog`]
+///
+/// See [`check_ltlf_formulas`] for checking multiple formulas at once.
+pub fn check_ltlf_formula(
+    log: &EventLog,
+    formula: &LTLfFormula,
+    classifier: &EventLogClassifier,
+) -> LTLfCheckingResult {
+    check_ltlf_formulas(log, std::slice::from_ref(formula), classifier)
+        .pop()
+        .unwrap()
+}
+
+///
+/// Check multiple [`LTLfFormula`]s on all traces of an [`EventLog`]
+///
+/// Returns one [`LTLfCheckingResult`] per formula (in the same order).
+/// Each formula is compiled into an [`LTLfAutomaton`] once, which is then reused for all traces.
+/// Event activities are computed once using the passed [`EventLogClassifier`] and shared between all formulas.
+pub fn check_ltlf_formulas(
+    log: &EventLog,
+    formulas: &[LTLfFormula],
+    classifier: &EventLogClassifier,
+) -> Vec<LTLfCheckingResult> {
+    let activities: Vec<Vec<String>> = log
+        .traces
+        .par_iter()
+        .map(|t| {
+            t.events
+                .iter()
+                .map(|e| classifier.get_class_identity_with_globals(e, &log.global_event_attrs))
+                .collect()
+        })
+        .collect();
+    formulas
+        .par_iter()
+        .map(|f| {
+            let mut automaton = LTLfAutomaton::new(f);
+            let mut res = LTLfCheckingResult::default();
+            for (i, (trace, trace_acts)) in log.traces.iter().zip(&activities).enumerate() {
+                let mut state = LTLfAutomaton::INITIAL_STATE;
+                for (e, act) in trace.events.iter().zip(trace_acts) {
+                    if automaton.is_sink(state) {
+                        break;
+                    }
+                    let valuation = automaton.valuation(e, act, &log.global_event_attrs);
+                    state = automaton.step(state, &valuation);
+                }
+                if automaton.is_accepting(state) {
+                    res.satisfying_traces.push(i);
+                } else {
+                    res.violating_traces.push(i);
+                }
+            }
+            res
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event_log::AttributeValue,
+        ltlf::parser::parse_ltlf,
+        utils::test_utils::{event_log_from_events, event_with_attributes},
+    };
+
+    use super::*;
+
+    #[test]
+    fn check_log() {
+        let event = |activity: &str, amount: i64| {
+            event_with_attributes(activity, &[("amount", AttributeValue::Int(amount))])
+        };
+        let log = event_log_from_events(vec![
+            vec![event("approve", 50), event("pay", 50)],
+            vec![event("approve", 500), event("cancel", 500)],
+            vec![event("approve", 200), event("check", 200)],
+            vec![event("check", 1000)],
+        ]);
+        let formulas = [
+            parse_ltlf("G(approve -> F(pay | cancel))").unwrap(),
+            parse_ltlf("G([amount > 100] -> !pay)").unwrap(),
+            parse_ltlf("F check").unwrap(),
+        ];
+        let res = check_ltlf_formulas(&log, &formulas, &EventLogClassifier::default());
+        assert_eq!(res[0].satisfying_traces, vec![0, 1, 3]);
+        assert_eq!(res[0].violating_traces, vec![2]);
+        assert_eq!(res[1].violating_traces, Vec::<usize>::new());
+        assert_eq!(res[2].satisfying_traces, vec![2, 3]);
+        assert_eq!(res[2].satisfaction_ratio(), 0.5);
+
+        assert!(!check_ltlf_trace(
+            &log.traces[2],
+            &formulas[0],
+            &EventLogClassifier::default(),
+            &None
+        ));
+        let single = check_ltlf_formula(&log, &formulas[0], &EventLogClassifier::default());
+        assert_eq!(single, res[0]);
+    }
+}
@@ -0,0 +1,267 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::event_log::{AttributeValue, Attributes, Event, XESEditableAttribute};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Comparison operators for attribute predicates
+pub enum ComparisonOperator {
+    /// `=` (or `==`)
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+}
+
+impl ComparisonOperator {
+    fn holds(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::*;
+        match self {
+            ComparisonOperator::Equal => ordering == Equal,
+            ComparisonOperator::NotEqual => ordering != Equal,
+            ComparisonOperator::Less => ordering == Less,
+            ComparisonOperator::LessOrEqual => ordering != Greater,
+            ComparisonOperator::Greater => ordering == Greater,
+            ComparisonOperator::GreaterOrEqual => ordering != Less,
+        }
+    }
+}
+
+impl Display for ComparisonOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::NotEqual => "!=",
+            ComparisonOperator::Less => "<",
+            ComparisonOperator::LessOrEqual => "<=",
+            ComparisonOperator::Greater => ">",
+            ComparisonOperator::GreaterOrEqual => ">=",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Literal values used in attribute predicates
+pub enum AttributeLiteral {
+    /// String value
+    String(String),
+    /// Numeric value (compared with [`AttributeValue::Int`] and [`AttributeValue::Float`] attributes)
+    Number(f64),
+    /// Boolean value
+    Boolean(bool),
+}
+
+impl Display for AttributeLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeLiteral::String(s) => write!(f, "{s:?}"),
+            AttributeLiteral::Number(n) => write!(f, "{n}"),
+            AttributeLiteral::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Atomic propositions of `LTLf` formulas, evaluated on a single event
+///
+pub enum LTLfAtom {
+    /// The activity (i.e., class identity of the used classifier) of the event equals the given value
+    Activity(String),
+    /// The value of the event attribute with the given key satisfies the comparison
+    ///
+    /// Predicates on missing attributes or attributes of incompatible types do not hold
+    Attribute {
+        /// Attribute key
+        key: String,
+        /// Comparison operator
+        operator: ComparisonOperator,
+        /// Value to compare the attribute value with
+        value: AttributeLiteral,
+    },
+}
+
+impl LTLfAtom {
+    ///
+    /// Evaluate the atom on an event
+    ///
+    /// `activity` is the (already computed) class identity of the event, `global_event_attrs` are used as default attribute values.
+    ///
+    pub fn holds(
+        &self,
+        event: &Event,
+        activity: &str,
+        global_event_attrs: &Option<Attributes>,
+    ) -> bool {
+        match self {
+            LTLfAtom::Activity(a) => a == activity,
+            LTLfAtom::Attribute {
+                key,
+                operator,
+                value,
+            } => {
+                let attr = match event
+                    .attributes
+                    .get_by_key_or_global(key, global_event_attrs)
+                {
+                    Some(attr) => &attr.value,
+                    None => return false,
+                };
+                let ordering = match (attr, value) {
+                    (AttributeValue::String(s), AttributeLiteral::String(v)) => {
+                        Some(s.as_str().cmp(v.as_str()))
+                    }
+                    (AttributeValue::Int(i), AttributeLiteral::Number(n)) => {
+                        (*i as f64).partial_cmp(n)
+                    }
+                    (AttributeValue::Float(x), AttributeLiteral::Number(n)) => x.partial_cmp(n),
+                    (AttributeValue::Boolean(b), AttributeLiteral::Boolean(v)) => Some(b.cmp(v)),
+                    _ => None,
+                };
+                ordering.is_some_and(|o| operator.holds(o))
+            }
+        }
+    }
+}
+
+impl Display for LTLfAtom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LTLfAtom::Activity(a) => {
+                if !a.is_empty()
+                    && a.chars().all(super::parser::is_identifier_char)
+                    && !super::parser::is_keyword(a)
+                {
+                    write!(f, "{a}")
+                } else {
+                    write!(f, "{a:?}")
+                }
+            }
+            LTLfAtom::Attribute {
+                key,
+                operator,
+                value,
+            } => write!(f, "[{key} {operator} {value}]"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Linear Temporal Logic formula on finite traces (`LTLf`)
+///
+/// Formulas can be parsed from their textual representation (see [`super::parser::parse_ltlf`]),
+/// for example `G(approve -> F(pay | cancel))`.
+///
+pub enum LTLfFormula {
+    /// Always true
+    True,
+    /// Always false
+    False,
+    /// Atomic proposition on the current event
+    Atom(LTLfAtom),
+    /// Negation
+    Not(Box<LTLfFormula>),
+    /// Conjunction
+    And(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// Disjunction
+    Or(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// Implication
+    Implies(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// Equivalence
+    Equivalent(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// (Strong) next: There is a next event and the formula holds there
+    Next(Box<LTLfFormula>),
+    /// Weak next: If there is a next event, the formula holds there
+    WeakNext(Box<LTLfFormula>),
+    /// Eventually: The formula holds now or at some later event
+    Eventually(Box<LTLfFormula>),
+    /// Globally: The formula holds now and at all later events
+    Globally(Box<LTLfFormula>),
+    /// (Strong) until: The second formula eventually holds and the first one holds until then
+    Until(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// Weak until: The first formula holds until the second one holds (which is not required to happen)
+    WeakUntil(Box<LTLfFormula>, Box<LTLfFormula>),
+    /// Release: The second formula holds until (and including) the first one holds (which is not required to happen)
+    Release(Box<LTLfFormula>, Box<LTLfFormula>),
+}
+
+impl LTLfFormula {
+    /// Atomic proposition that holds for events with the given activity
+    pub fn activity<S: Into<String>>(activity: S) -> Self {
+        LTLfFormula::Atom(LTLfAtom::Activity(activity.into()))
+    }
+
+    /// Collect all distinct atoms of this formula (in order of first occurrence)
+    pub fn atoms(&self) -> Vec<&LTLfAtom> {
+        let mut ret = Vec::new();
+        self.collect_atoms(&mut ret);
+        ret
+    }
+
+    fn collect_atoms<'a>(&'a self, atoms: &mut Vec<&'a LTLfAtom>) {
+        match self {
+            LTLfFormula::True | LTLfFormula::False => {}
+            LTLfFormula::Atom(a) => {
+                if !atoms.contains(&a) {
+                    atoms.push(a);
+                }
+            }
+            LTLfFormula::Not(f)
+            | LTLfFormula::Next(f)
+            | LTLfFormula::WeakNext(f)
+            | LTLfFormula::Eventually(f)
+            | LTLfFormula::Globally(f) => f.collect_atoms(atoms),
+            LTLfFormula::And(f, g)
+            | LTLfFormula::Or(f, g)
+            | LTLfFormula::Implies(f, g)
+            | LTLfFormula::Equivalent(f, g)
+            | LTLfFormula::Until(f, g)
+            | LTLfFormula::WeakUntil(f, g)
+            | LTLfFormula::Release(f, g) => {
+                f.collect_atoms(atoms);
+                g.collect_atoms(atoms);
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for LTLfFormula {
+    type Err = super::parser::LTLfParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        super::parser::parse_ltlf(s)
+    }
+}
+
+impl Display for LTLfFormula {
+    /// Fully parenthesized textual representation, which can be parsed again
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LTLfFormula::True => write!(f, "true"),
+            LTLfFormula::False => write!(f, "false"),
+            LTLfFormula::Atom(a) => write!(f, "{a}"),
+            LTLfFormula::Not(g) => write!(f, "!({g})"),
+            LTLfFormula::Next(g) => write!(f, "X({g})"),
+            LTLfFormula::WeakNext(g) => write!(f, "WX({g})"),
+            LTLfFormula::Eventually(g) => write!(f, "F({g})"),
+            LTLfFormula::Globally(g) => write!(f, "G({g})"),
+            LTLfFormula::And(g, h) => write!(f, "({g}) & ({h})"),
+            LTLfFormula::Or(g, h) => write!(f, "({g}) | ({h})"),
+            LTLfFormula::Implies(g, h) => write!(f, "({g}) -> ({h})"),
+            LTLfFormula::Equivalent(g, h) => write!(f, "({g}) <-> ({h})"),
+            LTLfFormula::Until(g, h) => write!(f, "({g}) U ({h})"),
+            LTLfFormula::WeakUntil(g, h) => write!(f, "({g}) W ({h})"),
+            LTLfFormula::Release(g, h) => write!(f, "({g}) R ({h})"),
+        }
+    }
+}
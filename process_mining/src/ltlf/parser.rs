@@ -0,0 +1,450 @@
+use super::formula::{AttributeLiteral, ComparisonOperator, LTLfAtom, LTLfFormula};
+
+#[derive(Debug, Clone, PartialEq)]
+///
+/// Errors that can occur when parsing an `LTLf` formula
+///
+/// Positions are character offsets in the parsed input.
+///
+pub enum LTLfParseError {
+    /// Encountered an unexpected token (position and token included)
+    UnexpectedToken(usize, String),
+    /// Encountered an unexpected character (position and character included)
+    UnexpectedCharacter(usize, char),
+    /// Input ended unexpectedly (e.g., missing closing parenthesis)
+    UnexpectedEnd,
+    /// Quoted string was not terminated (starting position included)
+    UnterminatedString(usize),
+    /// Invalid attribute predicate (position of the opening bracket included)
+    InvalidAttributePredicate(usize),
+}
+
+impl std::fmt::Display for LTLfParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse LTLf formula: {:?}", self)
+    }
+}
+
+impl std::error::Error for LTLfParseError {}
+
+const KEYWORDS: [&str; 12] = [
+    "X", "WX", "F", "G", "U", "W", "R", "true", "false", "not", "and", "or",
+];
+
+/// Characters allowed in unquoted activity names
+pub(crate) fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':' || c == '.'
+}
+
+/// Whether the identifier is reserved as a keyword (and thus needs to be quoted to be used as an activity)
+pub(crate) fn is_keyword(s: &str) -> bool {
+    KEYWORDS.contains(&s)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+    Implies,
+    Equivalent,
+    Next,
+    WeakNext,
+    Eventually,
+    Globally,
+    Until,
+    WeakUntil,
+    Release,
+    True,
+    False,
+    Atom(LTLfAtom),
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, LTLfParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '!' | '~' => Token::Not,
+            '&' => {
+                if next == Some('&') {
+                    i += 1;
+                }
+                Token::And
+            }
+            '|' => {
+                if next == Some('|') {
+                    i += 1;
+                }
+                Token::Or
+            }
+            '-' if next == Some('>') => {
+                i += 1;
+                Token::Implies
+            }
+            '<' if next == Some('-') && chars.get(i + 2) == Some(&'>') => {
+                i += 2;
+                Token::Equivalent
+            }
+            '"' | '\'' => {
+                let (s, end) = read_quoted(&chars, i)?;
+                i = end;
+                Token::Atom(LTLfAtom::Activity(s))
+            }
+            '[' => {
+                let end = find_predicate_end(&chars, i)?;
+                let content: String = chars[i + 1..end].iter().collect();
+                let atom = parse_attribute_predicate(&content)
+                    .ok_or(LTLfParseError::InvalidAttributePredicate(i))?;
+                i = end;
+                Token::Atom(atom)
+            }
+            c if is_identifier_char(c) => {
+                while i + 1 < chars.len() && is_identifier_char(chars[i + 1]) {
+                    i += 1;
+                }
+                let ident: String = chars[start..=i].iter().collect();
+                match ident.as_str() {
+                    "X" => Token::Next,
+                    "WX" => Token::WeakNext,
+                    "F" => Token::Eventually,
+                    "G" => Token::Globally,
+                    "U" => Token::Until,
+                    "W" => Token::WeakUntil,
+                    "R" => Token::Release,
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "not" => Token::Not,
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => Token::Atom(LTLfAtom::Activity(ident)),
+                }
+            }
+            c => return Err(LTLfParseError::UnexpectedCharacter(i, c)),
+        };
+        tokens.push((start, token));
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// Find the closing bracket of the attribute predicate starting at `start` (skipping quoted values)
+fn find_predicate_end(chars: &[char], start: usize) -> Result<usize, LTLfParseError> {
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' | '\'' => i = read_quoted(chars, i)?.1,
+            ']' => return Ok(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(LTLfParseError::InvalidAttributePredicate(start))
+}
+
+/// Read a quoted string starting at `start` (supporting backslash escapes); Returns the string and the index of the closing quote
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), LTLfParseError> {
+    let quote = chars[start];
+    let mut s = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                s.push(chars[i]);
+            }
+            c if c == quote => return Ok((s, i)),
+            c => s.push(c),
+        }
+        i += 1;
+    }
+    Err(LTLfParseError::UnterminatedString(start))
+}
+
+/// Parse the content of an attribute predicate, e.g., `amount >= 100` or `org:resource = "Pete"`
+fn parse_attribute_predicate(content: &str) -> Option<LTLfAtom> {
+    let op_start = content.find(['=', '!', '<', '>'])?;
+    let key = content[..op_start].trim();
+    let rest = &content[op_start..];
+    let (operator, op_len) = if rest.starts_with("==") {
+        (ComparisonOperator::Equal, 2)
+    } else if rest.starts_with("!=") {
+        (ComparisonOperator::NotEqual, 2)
+    } else if rest.starts_with("<=") {
+        (ComparisonOperator::LessOrEqual, 2)
+    } else if rest.starts_with(">=") {
+        (ComparisonOperator::GreaterOrEqual, 2)
+    } else if rest.starts_with('=') {
+        (ComparisonOperator::Equal, 1)
+    } else if rest.starts_with('<') {
+        (ComparisonOperator::Less, 1)
+    } else if rest.starts_with('>') {
+        (ComparisonOperator::Greater, 1)
+    } else {
+        return None;
+    };
+    let value = rest[op_len..].trim();
+    if key.is_empty() || value.is_empty() {
+        return None;
+    }
+    let value = if value.starts_with('"') || value.starts_with('\'') {
+        let chars: Vec<char> = value.chars().collect();
+        let (s, end) = read_quoted(&chars, 0).ok()?;
+        if end != chars.len() - 1 {
+            return None;
+        }
+        AttributeLiteral::String(s)
+    } else if value == "true" || value == "false" {
+        AttributeLiteral::Boolean(value == "true")
+    } else if let Ok(n) = value.parse::<f64>() {
+        AttributeLiteral::Number(n)
+    } else {
+        AttributeLiteral::String(value.to_string())
+    };
+    Some(LTLfAtom::Attribute {
+        key: key.to_string(),
+        operator,
+        value,
+    })
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), LTLfParseError> {
+        let t = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(LTLfParseError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(t)
+    }
+
+    fn unexpected(&self) -> LTLfParseError {
+        match self.tokens.get(self.pos) {
+            Some((i, t)) => LTLfParseError::UnexpectedToken(*i, format!("{:?}", t)),
+            None => LTLfParseError::UnexpectedEnd,
+        }
+    }
+
+    /// `equivalence := implication ('<->' implication)*`
+    fn parse_equivalence(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let mut f = self.parse_implication()?;
+        while self.peek() == Some(&Token::Equivalent) {
+            self.pos += 1;
+            f = LTLfFormula::Equivalent(Box::new(f), Box::new(self.parse_implication()?));
+        }
+        Ok(f)
+    }
+
+    /// `implication := disjunction ('->' implication)?`
+    fn parse_implication(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let f = self.parse_disjunction()?;
+        if self.peek() == Some(&Token::Implies) {
+            self.pos += 1;
+            return Ok(LTLfFormula::Implies(
+                Box::new(f),
+                Box::new(self.parse_implication()?),
+            ));
+        }
+        Ok(f)
+    }
+
+    /// `disjunction := conjunction ('|' conjunction)*`
+    fn parse_disjunction(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let mut f = self.parse_conjunction()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            f = LTLfFormula::Or(Box::new(f), Box::new(self.parse_conjunction()?));
+        }
+        Ok(f)
+    }
+
+    /// `conjunction := temporal ('&' temporal)*`
+    fn parse_conjunction(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let mut f = self.parse_binary_temporal()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            f = LTLfFormula::And(Box::new(f), Box::new(self.parse_binary_temporal()?));
+        }
+        Ok(f)
+    }
+
+    /// `temporal := unary (('U' | 'W' | 'R') temporal)?`
+    fn parse_binary_temporal(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let f = self.parse_unary()?;
+        let constructor = match self.peek() {
+            Some(Token::Until) => LTLfFormula::Until,
+            Some(Token::WeakUntil) => LTLfFormula::WeakUntil,
+            Some(Token::Release) => LTLfFormula::Release,
+            _ => return Ok(f),
+        };
+        self.pos += 1;
+        Ok(constructor(
+            Box::new(f),
+            Box::new(self.parse_binary_temporal()?),
+        ))
+    }
+
+    /// `unary := ('!' | 'X' | 'WX' | 'F' | 'G') unary | '(' equivalence ')' | 'true' | 'false' | atom`
+    fn parse_unary(&mut self) -> Result<LTLfFormula, LTLfParseError> {
+        let (_, token) = self.next()?;
+        Ok(match token {
+            Token::Not => LTLfFormula::Not(Box::new(self.parse_unary()?)),
+            Token::Next => LTLfFormula::Next(Box::new(self.parse_unary()?)),
+            Token::WeakNext => LTLfFormula::WeakNext(Box::new(self.parse_unary()?)),
+            Token::Eventually => LTLfFormula::Eventually(Box::new(self.parse_unary()?)),
+            Token::Globally => LTLfFormula::Globally(Box::new(self.parse_unary()?)),
+            Token::True => LTLfFormula::True,
+            Token::False => LTLfFormula::False,
+            Token::Atom(a) => LTLfFormula::Atom(a),
+            Token::LParen => {
+                let f = self.parse_equivalence()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(self.unexpected());
+                }
+                self.pos += 1;
+                f
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.unexpected());
+            }
+        })
+    }
+}
+
+///
+/// Parse an `LTLf` formula from its textual representation
+///
+/// The syntax supports (from lowest to highest precedence):
+///
+/// - Equivalence `<->` and implication `->` (right-associative)
+/// - Disjunction `|` (or `||`, `or`) and conjunction `&` (or `&&`, `and`)
+/// - Binary temporal operators `U` (until), `W` (weak until) and `R` (release), which are right-associative
+/// - Unary operators `!` (or `not`), `X` (next), `WX` (weak next), `F` (eventually) and `G` (globally)
+/// - Constants `true` and `false`, parentheses
+///
+/// Activities can be written without quotes if they only consist of alphanumeric characters, `_`, `:` and `.`
+/// (and are not a keyword); Otherwise they must be quoted (e.g., `"Approve Order"`).
+/// Attribute predicates are written in brackets, e.g., `[amount >= 100]` or `[org:resource = "Pete"]`,
+/// supporting the operators `=`, `!=`, `<`, `<=`, `>` and `>=`.
+///
+/// Example: `G(approve -> F(pay | cancel))`
+pub fn parse_ltlf(input: &str) -> Result<LTLfFormula, LTLfParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let f = parser.parse_equivalence()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parser.unexpected());
+    }
+    Ok(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_formulas() {
+        let f = parse_ltlf("G(approve -> F(pay | cancel))").unwrap();
+        assert_eq!(
+            f,
+            LTLfFormula::Globally(Box::new(LTLfFormula::Implies(
+                Box::new(LTLfFormula::activity("approve")),
+                Box::new(LTLfFormula::Eventually(Box::new(LTLfFormula::Or(
+                    Box::new(LTLfFormula::activity("pay")),
+                    Box::new(LTLfFormula::activity("cancel"))
+                ))))
+            )))
+        );
+
+        let f = parse_ltlf("!\"Send Mail\" U [amount >= 100] & X true").unwrap();
+        assert_eq!(
+            f,
+            LTLfFormula::And(
+                Box::new(LTLfFormula::Until(
+                    Box::new(LTLfFormula::Not(Box::new(LTLfFormula::activity(
+                        "Send Mail"
+                    )))),
+                    Box::new(LTLfFormula::Atom(LTLfAtom::Attribute {
+                        key: "amount".to_string(),
+                        operator: ComparisonOperator::GreaterOrEqual,
+                        value: AttributeLiteral::Number(100.0)
+                    }))
+                )),
+                Box::new(LTLfFormula::Next(Box::new(LTLfFormula::True)))
+            )
+        );
+
+        // Display output can be parsed again
+        for s in [
+            "G(a -> F(b | c))",
+            "[org:resource = \"Pete\"] W (WX X \"G\")",
+            "[note = \"a]b\"] & [note != 'c]']",
+            "a R (b <-> !c)",
+        ] {
+            let f = parse_ltlf(s).unwrap();
+            assert_eq!(parse_ltlf(&f.to_string()).unwrap(), f);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse_ltlf("G(a"), Err(LTLfParseError::UnexpectedEnd));
+        assert!(matches!(
+            parse_ltlf("a b"),
+            Err(LTLfParseError::UnexpectedToken(2, _))
+        ));
+        assert_eq!(
+            parse_ltlf("a & $"),
+            Err(LTLfParseError::UnexpectedCharacter(4, '$'))
+        );
+        assert_eq!(
+            parse_ltlf("F [amount]"),
+            Err(LTLfParseError::InvalidAttributePredicate(2))
+        );
+        assert_eq!(
+            parse_ltlf("F \"abc"),
+            Err(LTLfParseError::UnterminatedString(2))
+        );
+        assert_eq!(
+            parse_ltlf("[note = \"a]"),
+            Err(LTLfParseError::UnterminatedString(8))
+        );
+        assert_eq!(
+            parse_ltlf("[note = \"a]\""),
+            Err(LTLfParseError::InvalidAttributePredicate(0))
+        );
+        assert_eq!(
+            parse_ltlf("[note = \"a]b\"]"),
+            Ok(LTLfFormula::Atom(LTLfAtom::Attribute {
+                key: "note".to_string(),
+                operator: ComparisonOperator::Equal,
+                value: AttributeLiteral::String("a]b".to_string())
+            }))
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::{fs::File, io::Write};
+
+use graphviz_rust::{
+    cmd::Format,
+    dot_generator::{attr, edge, graph, id, node, node_id, stmt},
+    dot_structures::*,
+    printer::PrinterContext,
+};
+use petgraph::EdgeType;
+use uuid::Uuid;
+
+use super::social_network::ResourceNetwork;
+
+///
+/// Export the image of a [`ResourceNetwork`]
+///
+/// Also see [`export_resource_network_image_svg`] and [`export_resource_network_image_png`]
+///
+pub fn export_resource_network_image<Ty: EdgeType, P: AsRef<std::path::Path>>(
+    net: &ResourceNetwork<Ty>,
+    path: P,
+    format: Format,
+    dpi_factor: Option<f32>,
+) -> Result<(), std::io::Error> {
+    let g = export_resource_network_to_dot_graph(net, dpi_factor);
+
+    let out = graphviz_rust::exec(g, &mut PrinterContext::default(), vec![format.into()])?;
+
+    let mut f = File::create(path)?;
+    f.write_all(&out)?;
+    Ok(())
+}
+
+///
+/// Export a [`ResourceNetwork`] to a DOT graph (used in Graphviz)
+///
+/// Directed networks (e.g., handover of work) result in a directed graph, undirected networks (e.g., working together) in an undirected graph.
+///
+/// Also see [`export_resource_network_image`], as well as [`export_resource_network_image_svg`] and [`export_resource_network_image_png`]
+///
+pub fn export_resource_network_to_dot_graph<Ty: EdgeType>(
+    net: &ResourceNetwork<Ty>,
+    dpi_factor: Option<f32>,
+) -> Graph {
+    let mut resources: Vec<&str> = net.resources().collect();
+    resources.sort();
+    let resource_nodes: Vec<Stmt> = resources
+        .into_iter()
+        .map(|r| {
+            stmt!(node!(esc r; attr!("label", esc r), attr!("shape","ellipse"), attr!("fontsize",12), attr!("style","filled"), attr!("fillcolor","\"white\"")))
+        })
+        .collect();
+
+    let arcs: Vec<Stmt> = net
+        .weighted_edges()
+        .map(|(from, to, weight)| {
+            let label = format!("\"{}\"", format_weight(weight));
+            if net.graph.is_directed() {
+                stmt!(edge!(node_id!(esc from) => node_id!(esc to), vec![attr!("label", label)]))
+            } else {
+                stmt!(edge!(node_id!(esc from) => node_id!(esc to), vec![attr!("label", label), attr!("dir", "none")]))
+            }
+        })
+        .collect();
+
+    let mut global_graph_options = vec![stmt!(attr!("rankdir", "LR"))];
+    if let Some(dpi_fac) = dpi_factor {
+        global_graph_options.push(stmt!(attr!("dpi", (dpi_fac * 96.0))))
+    }
+
+    graph!(strict di id!(esc Uuid::new_v4()),vec![global_graph_options, resource_nodes, arcs].into_iter().flatten().collect())
+}
+
+/// Format weights as integers if possible, otherwise with limited precision
+fn format_weight(weight: f64) -> String {
+    if weight.fract() == 0.0 {
+        format!("{}", weight as i64)
+    } else {
+        format!("{:.3}", weight)
+    }
+}
+
+///
+/// Export the image of a [`ResourceNetwork`] as a SVG file
+///
+pub fn export_resource_network_image_svg<Ty: EdgeType, P: AsRef<std::path::Path>>(
+    net: &ResourceNetwork<Ty>,
+    path: P,
+) -> Result<(), std::io::Error> {
+    export_resource_network_image(net, path, Format::Svg, None)
+}
+
+///
+/// Export the image of a [`ResourceNetwork`] as a PNG file
+///
+pub fn export_resource_network_image_png<Ty: EdgeType, P: AsRef<std::path::Path>>(
+    net: &ResourceNetwork<Ty>,
+    path: P,
+) -> Result<(), std::io::Error> {
+    export_resource_network_image(net, path, Format::Png, Some(2.0))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{dfg::image_export::graph_to_dot, organizational::social_network::*};
+
+    use super::export_resource_network_to_dot_graph;
+
+    #[test]
+    fn test_resource_network_dot() {
+        let mut net: ResourceNetwork = ResourceNetwork::new();
+        net.add_weight("Pete", "Mike", 2.0);
+        net.add_weight("Mike", "Sara", 0.5);
+        let dot = graph_to_dot(&export_resource_network_to_dot_graph(&net, None));
+        assert!(dot.contains("\"Pete\" -> \"Mike\""));
+        assert!(dot.contains("label=\"0.500\""));
+
+        let mut undirected: UndirectedResourceNetwork = ResourceNetwork::new();
+        undirected.add_weight("Pete", "Mike", 1.0);
+        let dot = graph_to_dot(&export_resource_network_to_dot_graph(&undirected, None));
+        assert!(dot.contains("dir=none"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::event_log::{
+    constants::RESOURCE_NAME, event_log_struct::EventLogClassifier, Attributes, Event,
+    XESEditableAttribute,
+};
+use crate::EventLog;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+///
+/// Options for organizational mining (i.e., social networks and role discovery)
+///
+pub struct OrganizationalMiningOptions {
+    /// Event attribute key identifying the resource executing an event
+    ///
+    /// Defaults to [`RESOURCE_NAME`] (i.e., `org:resource`)
+    pub resource_key: String,
+    /// Classifier used to derive the activity of an event
+    pub classifier: EventLogClassifier,
+    /// If `true`, network weights are normalized to relative frequencies instead of absolute counts
+    pub normalize: bool,
+}
+
+impl Default for OrganizationalMiningOptions {
+    fn default() -> Self {
+        Self {
+            resource_key: RESOURCE_NAME.to_string(),
+            classifier: EventLogClassifier::default(),
+            normalize: false,
+        }
+    }
+}
+
+impl OrganizationalMiningOptions {
+    ///
+    /// Get the resource of an event (using the global event attributes as fallback)
+    ///
+    /// Returns `None` if the event has no resource or the resource is not a string value
+    ///
+    pub fn get_resource<'a>(
+        &self,
+        event: &'a Event,
+        global_event_attrs: &'a Option<Attributes>,
+    ) -> Option<&'a str> {
+        event
+            .attributes
+            .get_by_key_or_global(&self.resource_key, global_event_attrs)
+            .and_then(|a| a.value.try_as_string())
+            .map(|s| s.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+///
+/// Activity profiles of resources
+///
+/// Counts how often each resource executed each activity. Both resources and activities are sorted alphabetically.
+///
+pub struct ResourceActivityProfile {
+    /// All resources (sorted)
+    pub resources: Vec<String>,
+    /// All activities (sorted)
+    pub activities: Vec<String>,
+    /// Number of executions per resource and activity
+    ///
+    /// `counts[r][a]` is the number of events of activity `activities[a]` executed by resource `resources[r]`
+    pub counts: Vec<Vec<u64>>,
+}
+
+impl ResourceActivityProfile {
+    ///
+    /// Construct the [`ResourceActivityProfile`] of an [`EventLog`]
+    ///
+    /// Events without a resource are ignored.
+    ///
+    pub fn from_log(log: &EventLog, options: &OrganizationalMiningOptions) -> Self {
+        let mut counts: BTreeMap<(&str, String), u64> = BTreeMap::new();
+        let mut activities: BTreeSet<String> = BTreeSet::new();
+        for t in &log.traces {
+            for e in &t.events {
+                if let Some(res) = options.get_resource(e, &log.global_event_attrs) {
+                    let act = options
+                        .classifier
+                        .get_class_identity_with_globals(e, &log.global_event_attrs);
+                    activities.insert(act.clone());
+                    *counts.entry((res, act)).or_default() += 1;
+                }
+            }
+        }
+        let resources: Vec<String> = counts
+            .keys()
+            .map(|(r, _)| r.to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let activities: Vec<String> = activities.into_iter().collect();
+        let mut matrix = vec![vec![0; activities.len()]; resources.len()];
+        for ((r, a), c) in counts {
+            let r_index = resources.binary_search_by(|x| x.as_str().cmp(r)).unwrap();
+            let a_index = activities.binary_search(&a).unwrap();
+            matrix[r_index][a_index] = c;
+        }
+        Self {
+            resources,
+            activities,
+            counts: matrix,
+        }
+    }
+
+    /// Get the index of a resource
+    pub fn resource_index(&self, resource: &str) -> Option<usize> {
+        self.resources
+            .binary_search_by(|r| r.as_str().cmp(resource))
+            .ok()
+    }
+
+    /// Get the activity counts of a resource
+    pub fn profile_of(&self, resource: &str) -> Option<&[u64]> {
+        self.resource_index(resource)
+            .map(|i| self.counts[i].as_slice())
+    }
+
+    ///
+    /// Pearson correlation between the activity profiles of the resources with the given indices
+    ///
+    /// Returns `0.0` if one of the profiles has no variance (e.g., if there is only one activity)
+    ///
+    pub fn correlation(&self, r1: usize, r2: usize) -> f64 {
+        pearson_correlation(&self.counts[r1], &self.counts[r2])
+    }
+}
+
+///
+/// Pearson correlation coefficient of two equally-sized vectors
+///
+/// Returns `0.0` if one of the vectors has no variance
+///
+pub(crate) fn pearson_correlation(a: &[u64], b: &[u64]) -> f64 {
+    let n = a.len().min(b.len());
+    if n == 0 {
+        return 0.0;
+    }
+    let mean_a = a.iter().take(n).sum::<u64>() as f64 / n as f64;
+    let mean_b = b.iter().take(n).sum::<u64>() as f64 / n as f64;
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        let dx = *x as f64 - mean_a;
+        let dy = *y as f64 - mean_b;
+        cov += dx * dy;
+        var_a += dx * dx;
+        var_b += dy * dy;
+    }
+    if var_a == 0.0 || var_b == 0.0 {
+        return 0.0;
+    }
+    cov / (var_a.sqrt() * var_b.sqrt())
+}
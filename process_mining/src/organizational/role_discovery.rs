@@ -0,0 +1,123 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::EventLog;
+
+use super::resource_profile::{
+    pearson_correlation, OrganizationalMiningOptions, ResourceActivityProfile,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+///
+/// An organizational role, i.e., a group of resources executing similar activities
+///
+pub struct Role {
+    /// Activities executed by the resources of this role (sorted)
+    pub activities: Vec<String>,
+    /// Resources belonging to this role and the number of events they executed
+    pub resources: HashMap<String, u64>,
+}
+
+///
+/// Discover organizational roles from an [`EventLog`]
+///
+/// See [`discover_roles_from_profile`] for details.
+pub fn discover_roles(
+    log: &EventLog,
+    options: &OrganizationalMiningOptions,
+    similarity_threshold: f64,
+) -> Vec<Role> {
+    let profile = ResourceActivityProfile::from_log(log, options);
+    discover_roles_from_profile(&profile, similarity_threshold)
+}
+
+///
+/// Cluster resources into roles based on their activity profiles
+///
+/// Uses agglomerative clustering with average linkage on the Pearson correlation of the activity profiles:
+/// The two most similar clusters are merged as long as their average similarity is at least `similarity_threshold`.
+///
+/// The resulting roles are sorted by their (alphabetically) first resource.
+pub fn discover_roles_from_profile(
+    profile: &ResourceActivityProfile,
+    similarity_threshold: f64,
+) -> Vec<Role> {
+    let n = profile.resources.len();
+    let sim: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| pearson_correlation(&profile.counts[i], &profile.counts[j]))
+                .collect()
+        })
+        .collect();
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for i in 0..clusters.len() {
+            for j in (i + 1)..clusters.len() {
+                let total: f64 = clusters[i]
+                    .iter()
+                    .flat_map(|a| clusters[j].iter().map(|b| sim[*a][*b]))
+                    .sum();
+                let avg = total / (clusters[i].len() * clusters[j].len()) as f64;
+                if avg >= similarity_threshold && best.map_or(true, |(_, _, s)| avg > s) {
+                    best = Some((i, j, avg));
+                }
+            }
+        }
+        match best {
+            Some((i, j, _)) => {
+                let merged = clusters.remove(j);
+                clusters[i].extend(merged);
+            }
+            None => break,
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|members| {
+            let mut activities = BTreeSet::new();
+            let mut resources = HashMap::new();
+            for r in members {
+                let counts = &profile.counts[r];
+                for (a, c) in counts.iter().enumerate() {
+                    if *c > 0 {
+                        activities.insert(profile.activities[a].clone());
+                    }
+                }
+                resources.insert(profile.resources[r].clone(), counts.iter().sum());
+            }
+            Role {
+                activities: activities.into_iter().collect(),
+                resources,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_from_profile() {
+        let profile = ResourceActivityProfile {
+            resources: vec!["Ellen".into(), "Mike".into(), "Pete".into(), "Sara".into()],
+            activities: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+            counts: vec![
+                vec![0, 3, 0, 0],
+                vec![2, 0, 2, 0],
+                vec![3, 0, 4, 0],
+                vec![0, 0, 0, 5],
+            ],
+        };
+        let roles = discover_roles_from_profile(&profile, 0.8);
+        assert_eq!(roles.len(), 3);
+        let joint = roles.iter().find(|r| r.resources.len() == 2).unwrap();
+        assert_eq!(joint.activities, vec!["a".to_string(), "c".to_string()]);
+        assert_eq!(joint.resources.get("Pete"), Some(&7));
+        assert_eq!(joint.resources.get("Mike"), Some(&4));
+    }
+}
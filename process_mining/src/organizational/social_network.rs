@@ -0,0 +1,355 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::EventLog;
+use petgraph::{graph::NodeIndex, Directed, EdgeType, Graph, Undirected};
+
+use super::resource_profile::{OrganizationalMiningOptions, ResourceActivityProfile};
+
+///
+/// Weighted social network between resources
+///
+/// Nodes are resource names and edges are weighted by the network-specific metric (e.g., the number of handovers).
+///
+/// See also [`UndirectedResourceNetwork`] for symmetric metrics (e.g., working together).
+#[derive(Debug, Clone)]
+pub struct ResourceNetwork<Ty: EdgeType = Directed> {
+    /// Underlying weighted [`petgraph`] graph
+    pub graph: Graph<String, f64, Ty>,
+    /// Mapping of resource names to their node in `graph`
+    pub resource_nodes: HashMap<String, NodeIndex>,
+}
+
+/// A [`ResourceNetwork`] with undirected edges (used for symmetric metrics)
+pub type UndirectedResourceNetwork = ResourceNetwork<Undirected>;
+
+impl<Ty: EdgeType> Default for ResourceNetwork<Ty> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ty: EdgeType> ResourceNetwork<Ty> {
+    /// Create a new [`ResourceNetwork`] without any resources
+    pub fn new() -> Self {
+        Self {
+            graph: Graph::default(),
+            resource_nodes: HashMap::new(),
+        }
+    }
+
+    /// Add a resource (if not already present) and return its node
+    pub fn add_resource(&mut self, resource: &str) -> NodeIndex {
+        if let Some(n) = self.resource_nodes.get(resource) {
+            return *n;
+        }
+        let n = self.graph.add_node(resource.to_string());
+        self.resource_nodes.insert(resource.to_string(), n);
+        n
+    }
+
+    /// Add `weight` to the edge between `from` and `to` (adding the resources and the edge if not yet present)
+    pub fn add_weight(&mut self, from: &str, to: &str, weight: f64) {
+        let a = self.add_resource(from);
+        let b = self.add_resource(to);
+        match self.graph.find_edge(a, b) {
+            Some(e) => self.graph[e] += weight,
+            None => {
+                self.graph.add_edge(a, b, weight);
+            }
+        }
+    }
+
+    /// Get the edge weight between two resources (if there is an edge)
+    pub fn weight_between(&self, from: &str, to: &str) -> Option<f64> {
+        let a = self.resource_nodes.get(from)?;
+        let b = self.resource_nodes.get(to)?;
+        self.graph.find_edge(*a, *b).map(|e| self.graph[e])
+    }
+
+    /// Iterate over all resources of the network
+    pub fn resources(&self) -> impl Iterator<Item = &str> {
+        self.graph.node_weights().map(|r| r.as_str())
+    }
+
+    /// Iterate over all edges as (from, to, weight)
+    pub fn weighted_edges(&self) -> impl Iterator<Item = (&str, &str, f64)> {
+        self.graph.edge_indices().map(|e| {
+            let (a, b) = self.graph.edge_endpoints(e).unwrap();
+            (
+                self.graph[a].as_str(),
+                self.graph[b].as_str(),
+                self.graph[e],
+            )
+        })
+    }
+
+    /// Number of resources in the network
+    pub fn num_resources(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    #[cfg(feature = "graphviz-export")]
+    /// Export the resource network as a PNG image
+    ///
+    /// The PNG file is written to the specified filepath
+    ///
+    /// _Note_: This is an export method for __visualizing__ the resource network.
+    ///
+    /// Only available with the `graphviz-export` feature.
+    pub fn export_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        super::image_export::export_resource_network_image_png(self, path)
+    }
+
+    #[cfg(feature = "graphviz-export")]
+    /// Export the resource network as an SVG image.
+    ///
+    /// The SVG file is written to the specified filepath.
+    ///
+    /// _Note_: This is an export method for __visualizing__ the resource network.
+    ///
+    /// Only available with the `graphviz-export` feature.
+    pub fn export_svg<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+        super::image_export::export_resource_network_image_svg(self, path)
+    }
+
+    /// Build a network from (deterministically ordered) resources and weighted edges
+    fn from_weights(resources: BTreeSet<&str>, weights: BTreeMap<(&str, &str), f64>) -> Self {
+        let mut net = Self::new();
+        for r in resources {
+            net.add_resource(r);
+        }
+        for ((a, b), w) in weights {
+            net.add_weight(a, b, w);
+        }
+        net
+    }
+}
+
+/// Resources of all events in a trace (`None` for events without resource)
+fn resources_per_trace<'a>(
+    log: &'a EventLog,
+    options: &OrganizationalMiningOptions,
+) -> Vec<Vec<Option<&'a str>>> {
+    log.traces
+        .iter()
+        .map(|t| {
+            t.events
+                .iter()
+                .map(|e| options.get_resource(e, &log.global_event_attrs))
+                .collect()
+        })
+        .collect()
+}
+
+fn normalize_weights(weights: &mut BTreeMap<(&str, &str), f64>, total: f64) {
+    if total > 0.0 {
+        weights.values_mut().for_each(|w| *w /= total);
+    }
+}
+
+///
+/// Discover the _handover of work_ network of an [`EventLog`]
+///
+/// An edge from resource `a` to resource `b` counts how often an event executed by `a` is directly followed by an event executed by `b` in a trace.
+/// Handovers of a resource to itself are not counted.
+///
+/// If [`OrganizationalMiningOptions::normalize`] is set, weights are divided by the total number of handovers.
+pub fn discover_handover_of_work_network(
+    log: &EventLog,
+    options: &OrganizationalMiningOptions,
+) -> ResourceNetwork {
+    let mut resources = BTreeSet::new();
+    let mut weights: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    let mut total = 0.0;
+    for trace_res in resources_per_trace(log, options) {
+        resources.extend(trace_res.iter().flatten());
+        for w in trace_res.windows(2) {
+            if let (Some(a), Some(b)) = (w[0], w[1]) {
+                if a != b {
+                    *weights.entry((a, b)).or_default() += 1.0;
+                    total += 1.0;
+                }
+            }
+        }
+    }
+    if options.normalize {
+        normalize_weights(&mut weights, total);
+    }
+    ResourceNetwork::from_weights(resources, weights)
+}
+
+///
+/// Discover the _subcontracting_ network of an [`EventLog`]
+///
+/// An edge from resource `a` to resource `b` counts how often `b` executed an event in between two events of `a`
+/// (i.e., the pattern `a, b, a` of directly-following events, with `a != b`).
+///
+/// If [`OrganizationalMiningOptions::normalize`] is set, weights are divided by the total number of subcontracting occurrences.
+pub fn discover_subcontracting_network(
+    log: &EventLog,
+    options: &OrganizationalMiningOptions,
+) -> ResourceNetwork {
+    let mut resources = BTreeSet::new();
+    let mut weights: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    let mut total = 0.0;
+    for trace_res in resources_per_trace(log, options) {
+        resources.extend(trace_res.iter().flatten());
+        for w in trace_res.windows(3) {
+            if let (Some(a), Some(b), Some(c)) = (w[0], w[1], w[2]) {
+                if a == c && a != b {
+                    *weights.entry((a, b)).or_default() += 1.0;
+                    total += 1.0;
+                }
+            }
+        }
+    }
+    if options.normalize {
+        normalize_weights(&mut weights, total);
+    }
+    ResourceNetwork::from_weights(resources, weights)
+}
+
+///
+/// Discover the _working together_ network of an [`EventLog`]
+///
+/// An (undirected) edge between two resources counts the number of traces in which both resources executed at least one event.
+///
+/// If [`OrganizationalMiningOptions::normalize`] is set, weights are divided by the number of traces.
+pub fn discover_working_together_network(
+    log: &EventLog,
+    options: &OrganizationalMiningOptions,
+) -> UndirectedResourceNetwork {
+    let mut resources = BTreeSet::new();
+    let mut weights: BTreeMap<(&str, &str), f64> = BTreeMap::new();
+    for trace_res in resources_per_trace(log, options) {
+        let trace_res: Vec<&str> = trace_res
+            .into_iter()
+            .flatten()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        for (i, a) in trace_res.iter().enumerate() {
+            for b in trace_res.iter().skip(i + 1) {
+                *weights.entry((a, b)).or_default() += 1.0;
+            }
+        }
+        resources.extend(trace_res);
+    }
+    if options.normalize {
+        normalize_weights(&mut weights, log.traces.len() as f64);
+    }
+    ResourceNetwork::from_weights(resources, weights)
+}
+
+///
+/// Discover the _similar activities_ network of an [`EventLog`]
+///
+/// An (undirected) edge between two resources is weighted by the Pearson correlation of their activity profiles (see [`ResourceActivityProfile`]).
+/// Only pairs of resources with a correlation of at least `min_similarity` are connected.
+///
+/// The weights are correlation coefficients, so [`OrganizationalMiningOptions::normalize`] has no effect.
+pub fn discover_similar_activities_network(
+    log: &EventLog,
+    options: &OrganizationalMiningOptions,
+    min_similarity: f64,
+) -> UndirectedResourceNetwork {
+    let profile = ResourceActivityProfile::from_log(log, options);
+    similar_activities_network_from_profile(&profile, min_similarity)
+}
+
+///
+/// Construct the _similar activities_ network from a [`ResourceActivityProfile`]
+///
+/// See also [`discover_similar_activities_network`].
+pub fn similar_activities_network_from_profile(
+    profile: &ResourceActivityProfile,
+    min_similarity: f64,
+) -> UndirectedResourceNetwork {
+    let mut net = ResourceNetwork::new();
+    for r in &profile.resources {
+        net.add_resource(r);
+    }
+    for i in 0..profile.resources.len() {
+        for j in (i + 1)..profile.resources.len() {
+            let corr = profile.correlation(i, j);
+            if corr >= min_similarity {
+                net.add_weight(&profile.resources[i], &profile.resources[j], corr);
+            }
+        }
+    }
+    net
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event_log::{constants::RESOURCE_NAME, AttributeValue},
+        utils::test_utils::{event_log_from_events, event_with_attributes},
+    };
+
+    use super::*;
+
+    fn sample_log() -> EventLog {
+        let event = |activity: &str, resource: &str| {
+            event_with_attributes(
+                activity,
+                &[(RESOURCE_NAME, AttributeValue::String(resource.to_string()))],
+            )
+        };
+        event_log_from_events(vec![
+            vec![
+                event("a", "Pete"),
+                event("b", "Mike"),
+                event("c", "Pete"),
+                event("d", "Sara"),
+            ],
+            vec![event("a", "Pete"), event("b", "Ellen"), event("d", "Sara")],
+            vec![event("a", "Mike"), event("c", "Mike"), event("d", "Sara")],
+        ])
+    }
+
+    #[test]
+    fn handover_of_work() {
+        let log = sample_log();
+        let net = discover_handover_of_work_network(&log, &Default::default());
+        assert_eq!(net.num_resources(), 4);
+        assert_eq!(net.weight_between("Pete", "Mike"), Some(1.0));
+        assert_eq!(net.weight_between("Pete", "Sara"), Some(1.0));
+        assert_eq!(net.weight_between("Mike", "Sara"), Some(1.0));
+        assert_eq!(net.weight_between("Mike", "Mike"), None);
+        assert_eq!(net.weight_between("Sara", "Pete"), None);
+
+        let normalized = discover_handover_of_work_network(
+            &log,
+            &OrganizationalMiningOptions {
+                normalize: true,
+                ..Default::default()
+            },
+        );
+        let sum: f64 = normalized.weighted_edges().map(|(_, _, w)| w).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn subcontracting_and_working_together() {
+        let log = sample_log();
+        let sub = discover_subcontracting_network(&log, &Default::default());
+        assert_eq!(sub.weight_between("Pete", "Mike"), Some(1.0));
+        assert_eq!(sub.graph.edge_count(), 1);
+
+        let wt = discover_working_together_network(&log, &Default::default());
+        assert_eq!(wt.weight_between("Pete", "Sara"), Some(2.0));
+        // Undirected: both directions refer to the same edge
+        assert_eq!(wt.weight_between("Sara", "Pete"), Some(2.0));
+        assert_eq!(wt.weight_between("Ellen", "Mike"), None);
+    }
+
+    #[test]
+    fn similar_activities() {
+        let log = sample_log();
+        let net = discover_similar_activities_network(&log, &Default::default(), 0.5);
+        // Pete and Mike both execute a and c, Ellen only b
+        assert!(net.weight_between("Pete", "Mike").unwrap() > 0.5);
+        assert_eq!(net.weight_between("Ellen", "Sara"), None);
+    }
+}
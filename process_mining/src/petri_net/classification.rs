@@ -0,0 +1,505 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    invariants::{compute_place_invariants, compute_transition_invariants},
+    petri_net_struct::{ArcType, PetriNet, PlaceID, TransitionID},
+};
+
+/// No preset or postset
+static NO_NODES: BTreeSet<Uuid> = BTreeSet::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Structural classes a [`PetriNet`] belongs to (see [`PetriNet::classify`])
+///
+pub struct NetClassification {
+    /// All arcs have weight `1`
+    pub ordinary: bool,
+    /// Every transition has exactly one input and one output place
+    pub state_machine: bool,
+    /// Every place has exactly one input and one output transition
+    pub marked_graph: bool,
+    /// Every place with multiple output transitions is the only input place of these transitions
+    pub free_choice: bool,
+    /// Places with a common output transition have the same output transitions
+    pub extended_free_choice: bool,
+    /// For places with a common output transition, the output transitions of one place include the ones of the other
+    pub asymmetric_choice: bool,
+    /// There is a single source and a single sink place and every node is on a path from the source to the sink
+    pub workflow_net: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Strongly connected subnet of a [`PetriNet`], i.e., an S-component or a T-component
+///
+/// See [`PetriNet::s_components`] and [`PetriNet::t_components`]
+///
+pub struct NetComponent {
+    /// Places of the component (sorted)
+    pub places: Vec<PlaceID>,
+    /// Transitions of the component (sorted)
+    pub transitions: Vec<TransitionID>,
+}
+
+/// Presets and postsets of all places and transitions
+struct Adjacency {
+    pre: HashMap<Uuid, BTreeSet<Uuid>>,
+    post: HashMap<Uuid, BTreeSet<Uuid>>,
+}
+
+impl Adjacency {
+    fn new(net: &PetriNet) -> Self {
+        let mut pre: HashMap<Uuid, BTreeSet<Uuid>> = HashMap::new();
+        let mut post: HashMap<Uuid, BTreeSet<Uuid>> = HashMap::new();
+        for arc in &net.arcs {
+            let (from, to) = match arc.from_to {
+                ArcType::PlaceTransition(from, to) | ArcType::TransitionPlace(from, to) => {
+                    (from, to)
+                }
+            };
+            post.entry(from).or_default().insert(to);
+            pre.entry(to).or_default().insert(from);
+        }
+        Self { pre, post }
+    }
+
+    fn pre(&self, id: &Uuid) -> &BTreeSet<Uuid> {
+        self.pre.get(id).unwrap_or(&NO_NODES)
+    }
+
+    fn post(&self, id: &Uuid) -> &BTreeSet<Uuid> {
+        self.post.get(id).unwrap_or(&NO_NODES)
+    }
+
+    /// Preset (for siphons) or postset (for traps)
+    fn neighbors(&self, id: &Uuid, siphon: bool) -> &BTreeSet<Uuid> {
+        if siphon {
+            self.pre(id)
+        } else {
+            self.post(id)
+        }
+    }
+
+    /// Nodes reachable from `start` (forwards or backwards), only using the passed nodes
+    fn reachable(&self, start: Uuid, nodes: &BTreeSet<Uuid>, forwards: bool) -> HashSet<Uuid> {
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let next = if forwards {
+                self.post(&node)
+            } else {
+                self.pre(&node)
+            };
+            for n in next.intersection(nodes) {
+                if visited.insert(*n) {
+                    queue.push_back(*n);
+                }
+            }
+        }
+        visited
+    }
+
+    fn is_strongly_connected(&self, nodes: &BTreeSet<Uuid>) -> bool {
+        nodes.first().is_some_and(|start| {
+            self.reachable(*start, nodes, true).len() == nodes.len()
+                && self.reachable(*start, nodes, false).len() == nodes.len()
+        })
+    }
+}
+
+impl PetriNet {
+    /// Check if all arcs have weight `1`
+    pub fn is_ordinary(&self) -> bool {
+        self.arcs.iter().all(|arc| arc.weight == 1)
+    }
+
+    /// Check if every transition has exactly one input and one output place
+    pub fn is_state_machine(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        self.transitions
+            .keys()
+            .all(|t| adjacency.pre(t).len() == 1 && adjacency.post(t).len() == 1)
+    }
+
+    /// Check if every place has exactly one input and one output transition
+    pub fn is_marked_graph(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        self.places
+            .keys()
+            .all(|p| adjacency.pre(p).len() == 1 && adjacency.post(p).len() == 1)
+    }
+
+    ///
+    /// Check if the net is free-choice
+    ///
+    /// I.e., every place with multiple output transitions is the only input place of all these transitions.
+    /// Thus, if a transition is in conflict with another transition, either both or none of them are enabled.
+    ///
+    pub fn is_free_choice(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        self.places.keys().all(|p| {
+            let post = adjacency.post(p);
+            post.len() <= 1 || post.iter().all(|t| adjacency.pre(t).len() == 1)
+        })
+    }
+
+    /// Check if the net is extended free-choice, i.e., places with a common output transition have the same output transitions
+    pub fn is_extended_free_choice(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        self.transitions.keys().all(|t| {
+            let postsets: Vec<&BTreeSet<Uuid>> =
+                adjacency.pre(t).iter().map(|p| adjacency.post(p)).collect();
+            postsets.windows(2).all(|w| w[0] == w[1])
+        })
+    }
+
+    ///
+    /// Check if the net is asymmetric choice
+    ///
+    /// I.e., for all places with a common output transition, the output transitions of one of them include
+    /// the output transitions of the other.
+    ///
+    pub fn is_asymmetric_choice(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        self.transitions.keys().all(|t| {
+            let postsets: Vec<&BTreeSet<Uuid>> =
+                adjacency.pre(t).iter().map(|p| adjacency.post(p)).collect();
+            postsets.iter().enumerate().all(|(i, a)| {
+                postsets[i + 1..]
+                    .iter()
+                    .all(|b| a.is_subset(b) || b.is_subset(a))
+            })
+        })
+    }
+
+    ///
+    /// Check if the net is a workflow net
+    ///
+    /// I.e., there is exactly one source place (without input transitions) and exactly one sink place (without output transitions),
+    /// and every place and transition is on a path from the source to the sink place.
+    /// Markings are not considered.
+    ///
+    pub fn is_workflow_net(&self) -> bool {
+        let adjacency = Adjacency::new(self);
+        let sources: Vec<&Uuid> = self
+            .places
+            .keys()
+            .filter(|p| adjacency.pre(p).is_empty())
+            .collect();
+        let sinks: Vec<&Uuid> = self
+            .places
+            .keys()
+            .filter(|p| adjacency.post(p).is_empty())
+            .collect();
+        let (&[source], &[sink]) = (sources.as_slice(), sinks.as_slice()) else {
+            return false;
+        };
+        let nodes: BTreeSet<Uuid> = self
+            .places
+            .keys()
+            .chain(self.transitions.keys())
+            .copied()
+            .collect();
+        adjacency.reachable(*source, &nodes, true).len() == nodes.len()
+            && adjacency.reachable(*sink, &nodes, false).len() == nodes.len()
+    }
+
+    /// Determine all structural classes the net belongs to
+    pub fn classify(&self) -> NetClassification {
+        NetClassification {
+            ordinary: self.is_ordinary(),
+            state_machine: self.is_state_machine(),
+            marked_graph: self.is_marked_graph(),
+            free_choice: self.is_free_choice(),
+            extended_free_choice: self.is_extended_free_choice(),
+            asymmetric_choice: self.is_asymmetric_choice(),
+            workflow_net: self.is_workflow_net(),
+        }
+    }
+
+    ///
+    /// Get the S-components of the net
+    ///
+    /// An S-component is a strongly connected subnet, which consists of a set of places and all their input and output transitions,
+    /// such that each of these transitions has exactly one input and one output place in the subnet.
+    /// Thus, the number of tokens in an S-component never changes.
+    ///
+    /// The S-components are determined from the minimal place invariants (see [`compute_place_invariants`]).
+    ///
+    pub fn s_components(&self) -> Vec<NetComponent> {
+        let adjacency = Adjacency::new(self);
+        compute_place_invariants(self)
+            .into_iter()
+            .filter(|inv| inv.weights.values().all(|w| *w == 1))
+            .filter_map(|inv| {
+                let places: BTreeSet<Uuid> = inv.weights.keys().map(|p| p.get_uuid()).collect();
+                let transitions: BTreeSet<Uuid> = places
+                    .iter()
+                    .flat_map(|p| adjacency.pre(p).union(adjacency.post(p)))
+                    .copied()
+                    .collect();
+                let is_state_machine = transitions.iter().all(|t| {
+                    adjacency.pre(t).intersection(&places).count() == 1
+                        && adjacency.post(t).intersection(&places).count() == 1
+                });
+                to_component(&adjacency, places, transitions, is_state_machine)
+            })
+            .collect()
+    }
+
+    ///
+    /// Get the T-components of the net
+    ///
+    /// A T-component is a strongly connected subnet, which consists of a set of transitions and all their input and output places,
+    /// such that each of these places has exactly one input and one output transition in the subnet.
+    ///
+    /// The T-components are determined from the minimal transition invariants (see [`compute_transition_invariants`]).
+    ///
+    pub fn t_components(&self) -> Vec<NetComponent> {
+        let adjacency = Adjacency::new(self);
+        compute_transition_invariants(self)
+            .into_iter()
+            .filter(|inv| inv.weights.values().all(|w| *w == 1))
+            .filter_map(|inv| {
+                let transitions: BTreeSet<Uuid> =
+                    inv.weights.keys().map(|t| t.get_uuid()).collect();
+                let places: BTreeSet<Uuid> = transitions
+                    .iter()
+                    .flat_map(|t| adjacency.pre(t).union(adjacency.post(t)))
+                    .copied()
+                    .collect();
+                let is_marked_graph = places.iter().all(|p| {
+                    adjacency.pre(p).intersection(&transitions).count() == 1
+                        && adjacency.post(p).intersection(&transitions).count() == 1
+                });
+                to_component(&adjacency, places, transitions, is_marked_graph)
+            })
+            .collect()
+    }
+
+    /// Check if every place is part of an S-component (see [`PetriNet::s_components`])
+    pub fn is_s_coverable(&self) -> bool {
+        let covered: HashSet<PlaceID> = self
+            .s_components()
+            .into_iter()
+            .flat_map(|c| c.places)
+            .collect();
+        self.places.keys().all(|p| covered.contains(&PlaceID(*p)))
+    }
+
+    /// Check if every transition is part of a T-component (see [`PetriNet::t_components`])
+    pub fn is_t_coverable(&self) -> bool {
+        let covered: HashSet<TransitionID> = self
+            .t_components()
+            .into_iter()
+            .flat_map(|c| c.transitions)
+            .collect();
+        self.transitions
+            .keys()
+            .all(|t| covered.contains(&TransitionID(*t)))
+    }
+
+    ///
+    /// Get all minimal siphons of the net
+    ///
+    /// A siphon is a non-empty set of places `S`, such that every input transition of `S` is also an output transition of `S`.
+    /// Thus, once a siphon is unmarked, it remains unmarked.
+    ///
+    /// _Note_: The number of minimal siphons can grow exponentially with the size of the net.
+    ///
+    pub fn minimal_siphons(&self) -> Vec<Vec<PlaceID>> {
+        minimal_place_sets(self, &Adjacency::new(self), true)
+    }
+
+    ///
+    /// Get all minimal traps of the net
+    ///
+    /// A trap is a non-empty set of places `Q`, such that every output transition of `Q` is also an input transition of `Q`.
+    /// Thus, once a trap is marked, it remains marked.
+    ///
+    /// _Note_: The number of minimal traps can grow exponentially with the size of the net.
+    ///
+    pub fn minimal_traps(&self) -> Vec<Vec<PlaceID>> {
+        minimal_place_sets(self, &Adjacency::new(self), false)
+    }
+
+    /// Get the maximal trap contained in the passed set of places (which is empty if it contains no trap)
+    pub fn maximal_trap(&self, places: &[PlaceID]) -> Vec<PlaceID> {
+        let adjacency = Adjacency::new(self);
+        let mut trap: BTreeSet<Uuid> = places.iter().map(|p| p.get_uuid()).collect();
+        // Remove places with an output transition which does not put a token back into the set
+        while let Some(p) = trap
+            .iter()
+            .find(|p| {
+                adjacency
+                    .post(p)
+                    .iter()
+                    .any(|t| adjacency.post(t).is_disjoint(&trap))
+            })
+            .copied()
+        {
+            trap.remove(&p);
+        }
+        trap.into_iter().map(PlaceID).collect()
+    }
+
+    ///
+    /// Check the Commoner condition: Every siphon contains a trap marked in the initial marking
+    ///
+    /// For free-choice nets (see [`PetriNet::is_free_choice`]), this is equivalent to the liveness of the net
+    /// (Commoner's theorem). For asymmetric choice nets, it is a sufficient condition for liveness.
+    /// Without an initial marking, no place is marked.
+    ///
+    pub fn satisfies_commoner_condition(&self) -> bool {
+        let is_marked = |p: &PlaceID| {
+            self.initial_marking
+                .as_ref()
+                .and_then(|m| m.get(p))
+                .is_some_and(|tokens| *tokens > 0)
+        };
+        // Every siphon contains a minimal siphon, so it suffices to check these
+        self.minimal_siphons()
+            .iter()
+            .all(|siphon| self.maximal_trap(siphon).iter().any(is_marked))
+    }
+}
+
+fn to_component(
+    adjacency: &Adjacency,
+    places: BTreeSet<Uuid>,
+    transitions: BTreeSet<Uuid>,
+    has_component_structure: bool,
+) -> Option<NetComponent> {
+    let nodes: BTreeSet<Uuid> = places.union(&transitions).copied().collect();
+    (has_component_structure && adjacency.is_strongly_connected(&nodes)).then(|| NetComponent {
+        places: places.into_iter().map(PlaceID).collect(),
+        transitions: transitions.into_iter().map(TransitionID).collect(),
+    })
+}
+
+///
+/// Minimal siphons (or traps) by a depth-first search starting from every single place
+///
+/// As long as the current set is no siphon, there is an input transition of the set without input place in the set.
+/// Every siphon including the current set also includes one of the input places of this transition,
+/// so the search branches on adding one of them.
+/// For traps, the same applies to output transitions and output places.
+///
+fn minimal_place_sets(net: &PetriNet, adjacency: &Adjacency, siphon: bool) -> Vec<Vec<PlaceID>> {
+    let mut places: Vec<Uuid> = net.places.keys().copied().collect();
+    places.sort();
+    let mut found: Vec<BTreeSet<Uuid>> = Vec::new();
+    let mut visited: HashSet<BTreeSet<Uuid>> = HashSet::new();
+    for p in places {
+        let mut stack = vec![BTreeSet::from([p])];
+        while let Some(set) = stack.pop() {
+            if found.iter().any(|f| f.is_subset(&set)) || !visited.insert(set.clone()) {
+                continue;
+            }
+            let violating_transition = set
+                .iter()
+                .flat_map(|p| adjacency.neighbors(p, siphon))
+                .find(|t| adjacency.neighbors(t, siphon).is_disjoint(&set));
+            match violating_transition {
+                None => found.push(set),
+                Some(t) => {
+                    for q in adjacency.neighbors(t, siphon) {
+                        let mut next = set.clone();
+                        next.insert(*q);
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+    }
+    let mut minimal: Vec<Vec<PlaceID>> = found
+        .iter()
+        .filter(|set| !found.iter().any(|f| f != *set && f.is_subset(set)))
+        .map(|set| set.iter().copied().map(PlaceID).collect())
+        .collect();
+    minimal.sort();
+    minimal
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn classify_nets() {
+        let net = choice_net();
+        assert_eq!(
+            net.classify(),
+            NetClassification {
+                ordinary: true,
+                state_machine: true,
+                marked_graph: false,
+                free_choice: true,
+                extended_free_choice: true,
+                asymmetric_choice: true,
+                workflow_net: true,
+            }
+        );
+
+        // `p` and `q` synchronize in `t`, but `q` can also be consumed by `u` alone
+        let mut net = PetriNet::new();
+        let p = net.add_place(None);
+        let q = net.add_place(None);
+        let t = net.add_transition(Some("t".to_string()), None);
+        let u = net.add_transition(Some("u".to_string()), None);
+        net.add_arc(ArcType::place_to_transition(p, t), None);
+        net.add_arc(ArcType::place_to_transition(q, t), None);
+        net.add_arc(ArcType::place_to_transition(q, u), Some(2));
+        let classification = net.classify();
+        assert!(!classification.ordinary);
+        assert!(!classification.free_choice);
+        assert!(!classification.extended_free_choice);
+        assert!(classification.asymmetric_choice);
+        assert!(!classification.workflow_net);
+    }
+
+    #[test]
+    fn components_siphons_and_traps() {
+        let mut net = choice_net();
+        let initial = *net.initial_marking.as_ref().unwrap().keys().next().unwrap();
+        let last = *net.final_markings.as_ref().unwrap()[0]
+            .keys()
+            .next()
+            .unwrap();
+        // The workflow net is not live, as the initial place is a siphon without a marked trap
+        assert_eq!(net.minimal_siphons(), vec![vec![initial]]);
+        assert!(net.maximal_trap(&[initial]).is_empty());
+        assert!(!net.satisfies_commoner_condition());
+        assert!(net.s_components().is_empty());
+
+        // Short-circuiting the net makes it live and covered by S- and T-components
+        let reset = net.add_transition(None, None);
+        net.add_arc(ArcType::place_to_transition(last, reset), None);
+        net.add_arc(ArcType::transition_to_place(reset, initial), None);
+        assert!(!net.is_workflow_net());
+        let all_places: Vec<PlaceID> = net.minimal_siphons().concat();
+        assert_eq!(all_places.len(), 4);
+        assert_eq!(net.minimal_traps(), net.minimal_siphons());
+        assert_eq!(net.maximal_trap(&all_places), all_places);
+        assert!(net.satisfies_commoner_condition());
+        net.initial_marking = None;
+        assert!(!net.satisfies_commoner_condition());
+
+        let s_components = net.s_components();
+        assert_eq!(s_components.len(), 1);
+        assert_eq!(s_components[0].transitions.len(), 6);
+        assert!(net.is_s_coverable());
+        let t_components = net.t_components();
+        assert_eq!(t_components.len(), 4);
+        assert!(t_components
+            .iter()
+            .all(|c| c.places.len() == 4 && c.transitions.contains(&reset)));
+        assert!(net.is_t_coverable());
+    }
+}
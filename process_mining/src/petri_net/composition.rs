@@ -0,0 +1,469 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::petri_net_struct::{Arc, ArcType, Marking, PetriNet, PlaceID, TransitionID};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Place of one of the two composed nets from which a place of a [`ComposedPetriNet`] originates
+///
+pub enum PlaceOrigin {
+    /// Place of the first net
+    First(PlaceID),
+    /// Place of the second net
+    Second(PlaceID),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Transitions of the two composed nets from which a transition of a [`ComposedPetriNet`] originates
+///
+/// Synchronized transitions originate from a transition of both nets,
+/// all other transitions from a transition of exactly one of the nets.
+///
+pub struct TransitionOrigin {
+    /// Transition of the first net
+    pub first: Option<TransitionID>,
+    /// Transition of the second net
+    pub second: Option<TransitionID>,
+}
+
+impl TransitionOrigin {
+    /// Check if the transition synchronizes a transition of the first and of the second net
+    pub fn is_synchronized(&self) -> bool {
+        self.first.is_some() && self.second.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Result of composing two [`PetriNet`]s (e.g., using [`PetriNet::parallel_composition`])
+///
+/// All places and transitions of the composed net have new IDs.
+/// The origins map them to the places and transitions of the composed nets.
+///
+pub struct ComposedPetriNet {
+    /// Composed net
+    pub net: PetriNet,
+    /// Original place of each place of the composed net
+    pub place_origins: HashMap<PlaceID, PlaceOrigin>,
+    /// Original transition(s) of each transition of the composed net
+    pub transition_origins: HashMap<TransitionID, TransitionOrigin>,
+}
+
+impl ComposedPetriNet {
+    /// Transitions of the composed net originating from the given transition of the first net
+    pub fn transitions_of_first(&self, transition: &TransitionID) -> Vec<TransitionID> {
+        self.transitions_with_origin(|origin| origin.first.as_ref() == Some(transition))
+    }
+
+    /// Transitions of the composed net originating from the given transition of the second net
+    pub fn transitions_of_second(&self, transition: &TransitionID) -> Vec<TransitionID> {
+        self.transitions_with_origin(|origin| origin.second.as_ref() == Some(transition))
+    }
+
+    fn transitions_with_origin<F: Fn(&TransitionOrigin) -> bool>(
+        &self,
+        filter: F,
+    ) -> Vec<TransitionID> {
+        let mut ret: Vec<TransitionID> = self
+            .transition_origins
+            .iter()
+            .filter(|(_, origin)| filter(origin))
+            .map(|(t, _)| *t)
+            .collect();
+        ret.sort();
+        ret
+    }
+}
+
+/// How transitions with a label shared by both nets are handled during composition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Synchronization {
+    /// No synchronization (all transitions are copied)
+    None,
+    /// Transitions with shared labels are replaced by synchronized transitions
+    Replace,
+    /// Synchronized transitions are added, but the original transitions are kept as well
+    Add,
+}
+
+/// Input and output arcs of all transitions
+fn arcs_by_transition(net: &PetriNet) -> HashMap<Uuid, Vec<&Arc>> {
+    let mut ret: HashMap<Uuid, Vec<&Arc>> = HashMap::new();
+    for arc in &net.arcs {
+        let t = match arc.from_to {
+            ArcType::PlaceTransition(_, t) | ArcType::TransitionPlace(t, _) => t,
+        };
+        ret.entry(t).or_default().push(arc);
+    }
+    ret
+}
+
+/// Visible transitions with the given label (sorted)
+fn transitions_with_label<'a>(net: &'a PetriNet, label: &str) -> Vec<&'a Uuid> {
+    let mut ret: Vec<&Uuid> = net
+        .transitions
+        .iter()
+        .filter(|(_, t)| t.label.as_deref() == Some(label))
+        .map(|(id, _)| id)
+        .collect();
+    ret.sort();
+    ret
+}
+
+/// Labels of all visible transitions
+fn visible_labels(net: &PetriNet) -> HashSet<&str> {
+    net.transitions
+        .values()
+        .filter_map(|t| t.label.as_deref())
+        .collect()
+}
+
+/// Map a marking of one of the composed nets to the composed net
+fn map_marking(marking: &Marking, place_map: &HashMap<Uuid, PlaceID>) -> Marking {
+    marking
+        .iter()
+        .filter_map(|(p, tokens)| place_map.get(&p.0).map(|p| (*p, *tokens)))
+        .collect()
+}
+
+fn compose(first: &PetriNet, second: &PetriNet, sync: Synchronization) -> ComposedPetriNet {
+    let mut ret = ComposedPetriNet {
+        net: PetriNet::new(),
+        place_origins: HashMap::new(),
+        transition_origins: HashMap::new(),
+    };
+    let nets = [first, second];
+    let arcs = nets.map(arcs_by_transition);
+    let place_maps: Vec<HashMap<Uuid, PlaceID>> = nets
+        .iter()
+        .enumerate()
+        .map(|(i, net)| {
+            let mut places: Vec<_> = net.places.iter().collect();
+            places.sort_by_key(|(id, _)| **id);
+            places
+                .into_iter()
+                .map(|(id, place)| {
+                    let new_place = ret.net.add_place(None);
+                    ret.net.places.get_mut(&new_place.0).unwrap().name = place.name.clone();
+                    let origin = if i == 0 {
+                        PlaceOrigin::First(PlaceID(*id))
+                    } else {
+                        PlaceOrigin::Second(PlaceID(*id))
+                    };
+                    ret.place_origins.insert(new_place, origin);
+                    (*id, new_place)
+                })
+                .collect()
+        })
+        .collect();
+
+    // Add a transition with the arcs of the passed transitions of the first and second net
+    let mut add_transition = |label: Option<String>, origin: [Option<&Uuid>; 2]| {
+        let new_transition = ret.net.add_transition(label, None);
+        for (i, t) in origin.iter().enumerate() {
+            let Some(t) = t else { continue };
+            for arc in arcs[i].get(*t).into_iter().flatten() {
+                let from_to = match arc.from_to {
+                    ArcType::PlaceTransition(p, _) => {
+                        ArcType::place_to_transition(place_maps[i][&p], new_transition)
+                    }
+                    ArcType::TransitionPlace(_, p) => {
+                        ArcType::transition_to_place(new_transition, place_maps[i][&p])
+                    }
+                };
+                ret.net.add_arc(from_to, Some(arc.weight));
+            }
+        }
+        ret.transition_origins.insert(
+            new_transition,
+            TransitionOrigin {
+                first: origin[0].map(|t| TransitionID(*t)),
+                second: origin[1].map(|t| TransitionID(*t)),
+            },
+        );
+    };
+
+    let mut shared_labels: Vec<&str> = match sync {
+        Synchronization::None => Vec::new(),
+        Synchronization::Replace | Synchronization::Add => visible_labels(first)
+            .intersection(&visible_labels(second))
+            .copied()
+            .collect(),
+    };
+    shared_labels.sort();
+    for (i, net) in nets.iter().enumerate() {
+        let mut transitions: Vec<_> = net.transitions.iter().collect();
+        transitions.sort_by_key(|(id, _)| **id);
+        for (id, transition) in transitions {
+            if sync == Synchronization::Replace
+                && transition
+                    .label
+                    .as_deref()
+                    .is_some_and(|l| shared_labels.contains(&l))
+            {
+                continue;
+            }
+            let mut origin = [None, None];
+            origin[i] = Some(id);
+            add_transition(transition.label.clone(), origin);
+        }
+    }
+    for label in shared_labels {
+        for t1 in transitions_with_label(first, label) {
+            for t2 in transitions_with_label(second, label) {
+                add_transition(Some(label.to_string()), [Some(t1), Some(t2)]);
+            }
+        }
+    }
+
+    if first.initial_marking.is_some() || second.initial_marking.is_some() {
+        let mut initial_marking = Marking::new();
+        for (i, net) in nets.iter().enumerate() {
+            if let Some(marking) = &net.initial_marking {
+                initial_marking.extend(map_marking(marking, &place_maps[i]));
+            }
+        }
+        ret.net.initial_marking = Some(initial_marking);
+    }
+    if first.final_markings.is_some() || second.final_markings.is_some() {
+        let final_markings = |i: usize| -> Vec<Marking> {
+            match &nets[i].final_markings {
+                Some(markings) => markings
+                    .iter()
+                    .map(|m| map_marking(m, &place_maps[i]))
+                    .collect(),
+                None => vec![Marking::new()],
+            }
+        };
+        let second_final_markings = final_markings(1);
+        ret.net.final_markings = Some(
+            final_markings(0)
+                .into_iter()
+                .flat_map(|m1| {
+                    second_final_markings.iter().map(move |m2| {
+                        let mut m = m1.clone();
+                        m.extend(m2.iter().map(|(p, tokens)| (*p, *tokens)));
+                        m
+                    })
+                })
+                .collect(),
+        );
+    }
+    ret
+}
+
+impl PetriNet {
+    ///
+    /// Create a trace net, i.e., a sequential net with one transition per activity of the trace
+    ///
+    /// The initial marking is a token in the first place and the final marking a token in the last place.
+    ///
+    pub fn trace_net<S: AsRef<str>>(trace: &[S]) -> PetriNet {
+        let mut net = PetriNet::new();
+        let mut place = net.add_place(None);
+        net.initial_marking = Some(Marking::from([(place, 1)]));
+        for activity in trace {
+            let transition = net.add_transition(Some(activity.as_ref().to_string()), None);
+            let next_place = net.add_place(None);
+            net.add_arc(ArcType::place_to_transition(place, transition), None);
+            net.add_arc(ArcType::transition_to_place(transition, next_place), None);
+            place = next_place;
+        }
+        net.final_markings = Some(vec![Marking::from([(place, 1)])]);
+        net
+    }
+
+    ///
+    /// Parallel composition of two nets, synchronizing on shared labels
+    ///
+    /// Visible transitions with a label occurring in both nets are replaced by synchronized transitions:
+    /// one for every pair of a transition of this net and a transition of the other net with the label.
+    /// All other transitions (incl. silent ones) are copied and can fire independently.
+    ///
+    /// The initial marking is the union of both initial markings
+    /// and the final markings are all combinations of a final marking of both nets.
+    /// A missing initial marking or missing final markings of one of the nets are treated as the empty marking.
+    ///
+    pub fn parallel_composition(&self, other: &PetriNet) -> ComposedPetriNet {
+        compose(self, other, Synchronization::Replace)
+    }
+
+    ///
+    /// Synchronous product of this net (e.g., a process model) and another net (e.g., a trace net, see [`PetriNet::trace_net`])
+    ///
+    /// In contrast to the [`PetriNet::parallel_composition`], all transitions of both nets are kept
+    /// and synchronized transitions are added for all pairs of visible transitions with the same label.
+    /// In terms of alignments, transitions only originating from this net are model moves,
+    /// those only originating from the other net are log moves,
+    /// and synchronized transitions are synchronous moves (see [`TransitionOrigin`]).
+    ///
+    /// Markings are merged as in the [`PetriNet::parallel_composition`].
+    ///
+    pub fn synchronous_product(&self, other: &PetriNet) -> ComposedPetriNet {
+        compose(self, other, Synchronization::Add)
+    }
+
+    ///
+    /// Disjoint union of two nets, i.e., both nets side by side without any synchronization
+    ///
+    /// Markings are merged as in the [`PetriNet::parallel_composition`].
+    ///
+    pub fn disjoint_union(&self, other: &PetriNet) -> ComposedPetriNet {
+        compose(self, other, Synchronization::None)
+    }
+
+    ///
+    /// Hide all transitions with one of the given labels (i.e., turn them into silent transitions)
+    ///
+    pub fn hide_labels<S: AsRef<str>>(&mut self, labels: &[S]) {
+        let labels: HashSet<&str> = labels.iter().map(AsRef::as_ref).collect();
+        for transition in self.transitions.values_mut() {
+            if transition
+                .label
+                .as_deref()
+                .is_some_and(|l| labels.contains(l))
+            {
+                transition.label = None;
+            }
+        }
+    }
+
+    ///
+    /// Project the net on the given labels, i.e., hide all transitions with a label not included in `labels`
+    ///
+    pub fn project_on_labels<S: AsRef<str>>(&mut self, labels: &[S]) {
+        let labels: HashSet<&str> = labels.iter().map(AsRef::as_ref).collect();
+        for transition in self.transitions.values_mut() {
+            if transition
+                .label
+                .as_deref()
+                .is_some_and(|l| !labels.contains(l))
+            {
+                transition.label = None;
+            }
+        }
+    }
+
+    ///
+    /// Rename the labels of transitions according to the passed mapping (from old to new label)
+    ///
+    /// Labels without an entry in the mapping are kept.
+    ///
+    pub fn relabel(&mut self, mapping: &HashMap<String, String>) {
+        for transition in self.transitions.values_mut() {
+            if let Some(new_label) = transition.label.as_ref().and_then(|l| mapping.get(l)) {
+                transition.label = Some(new_label.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bpmn::conversion::tests::accepts, conformance::token_replay::tests::choice_net};
+
+    use super::*;
+
+    #[test]
+    fn parallel_composition_and_disjoint_union() {
+        let first = PetriNet::trace_net(&["a", "s"]);
+        let second = PetriNet::trace_net(&["s", "b"]);
+        let composed = first.parallel_composition(&second);
+        assert_eq!(composed.net.places.len(), 6);
+        assert_eq!(composed.net.transitions.len(), 3);
+        assert_eq!(
+            composed
+                .transition_origins
+                .values()
+                .filter(|o| o.is_synchronized())
+                .count(),
+            1
+        );
+        assert!(composed.place_origins.values().all(|origin| match origin {
+            PlaceOrigin::First(p) => first.places.contains_key(&p.0),
+            PlaceOrigin::Second(p) => second.places.contains_key(&p.0),
+        }));
+        assert_eq!(composed.net.initial_marking.as_ref().unwrap().len(), 2);
+        assert_eq!(composed.net.final_markings.as_ref().unwrap().len(), 1);
+        assert!(accepts(&composed.net, &["a", "s", "b"]));
+        assert!(!accepts(&composed.net, &["s", "a", "b"]));
+        assert!(!accepts(&composed.net, &["a", "s", "s", "b"]));
+
+        let union = first.disjoint_union(&second);
+        assert_eq!(union.net.transitions.len(), 4);
+        assert!(union
+            .transition_origins
+            .values()
+            .all(|o| !o.is_synchronized()));
+        assert!(accepts(&union.net, &["a", "s", "s", "b"]));
+        assert!(accepts(&union.net, &["s", "b", "a", "s"]));
+        assert!(!accepts(&union.net, &["a", "s", "b"]));
+    }
+
+    #[test]
+    fn synchronous_product_with_trace_net() {
+        let model = choice_net();
+        let trace = PetriNet::trace_net(&["a", "x", "b"]);
+        let product = model.synchronous_product(&trace);
+        // 5 model moves, 3 log moves and the synchronous moves of `a` and `b`
+        assert_eq!(product.net.transitions.len(), 10);
+        assert_eq!(product.net.places.len(), 8);
+        let a = model
+            .transitions
+            .values()
+            .find(|t| t.label.as_deref() == Some("a"))
+            .map(TransitionID::from)
+            .unwrap();
+        let moves_of_a = product.transitions_of_first(&a);
+        assert_eq!(moves_of_a.len(), 2);
+        assert_eq!(
+            moves_of_a
+                .iter()
+                .filter(|t| product.transition_origins[t].is_synchronized())
+                .count(),
+            1
+        );
+        let silent = model
+            .transitions
+            .values()
+            .find(|t| t.label.is_none())
+            .map(TransitionID::from)
+            .unwrap();
+        assert_eq!(product.transitions_of_first(&silent).len(), 1);
+        // Synchronous moves for `a` and `b`, a log move for `x` and the silent skip of `d`
+        assert!(accepts(&product.net, &["a", "x", "b"]));
+        assert!(accepts(&product.net, &["a", "a", "x", "b", "b"]));
+        assert!(!accepts(&product.net, &["a", "b"]));
+    }
+
+    #[test]
+    fn hide_project_and_relabel() {
+        let mut net = choice_net();
+        net.hide_labels(&["b"]);
+        assert!(accepts(&net, &["a"]));
+        assert!(accepts(&net, &["a", "c", "d"]));
+        assert!(!accepts(&net, &["a", "b"]));
+
+        let mut net = choice_net();
+        net.project_on_labels(&["a", "d"]);
+        assert!(accepts(&net, &["a", "d"]));
+        assert!(accepts(&net, &["a"]));
+        assert!(!accepts(&net, &["a", "c"]));
+
+        let mut net = choice_net();
+        net.relabel(&HashMap::from([("c".to_string(), "b".to_string())]));
+        assert_eq!(
+            net.transitions
+                .values()
+                .filter(|t| t.label.as_deref() == Some("b"))
+                .count(),
+            2
+        );
+        assert!(accepts(&net, &["a", "b", "d"]));
+        assert!(!accepts(&net, &["a", "c"]));
+    }
+}
@@ -1,16 +1,86 @@
 use std::{fs::File, io::Write};
 
-use quick_xml::{events::BytesText, Writer};
-use uuid::Uuid;
+use quick_xml::{
+    events::{BytesText, Event},
+    Reader, Writer,
+};
 
 use crate::utils::xml_utils::XMLWriterWrapper;
 
-use super::petri_net_struct::PetriNet;
+use super::petri_net_struct::{ArcType, NodePosition, PetriNet, PetriNetElement};
 const OK: Result<(), quick_xml::Error> = Ok::<(), quick_xml::Error>(());
 
+/// Write an element with a single `<text>` child (e.g., `<name><text>...</text></name>`)
+fn write_text_element<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &str,
+    text: &str,
+) -> Result<(), quick_xml::Error> {
+    writer.create_element(tag).write_inner_content(|writer| {
+        writer
+            .create_element("text")
+            .write_text_content(BytesText::new(text))?;
+        OK
+    })?;
+    Ok(())
+}
+
+/// Write the `<graphics>` element of a place or transition
+fn write_graphics<W: Write>(
+    writer: &mut Writer<W>,
+    position: &NodePosition,
+) -> Result<(), quick_xml::Error> {
+    writer
+        .create_element("graphics")
+        .write_inner_content(|writer| {
+            writer
+                .create_element("position")
+                .with_attributes([
+                    ("x", position.x.to_string().as_str()),
+                    ("y", position.y.to_string().as_str()),
+                ])
+                .write_empty()?;
+            if let (Some(width), Some(height)) = (position.width, position.height) {
+                writer
+                    .create_element("dimension")
+                    .with_attributes([
+                        ("x", width.to_string().as_str()),
+                        ("y", height.to_string().as_str()),
+                    ])
+                    .write_empty()?;
+            }
+            OK
+        })?;
+    Ok(())
+}
+
+/// Write all tool-specific data (raw XML) of an element of the Petri net
+fn write_tool_specific<W: Write>(
+    writer: &mut Writer<W>,
+    pn: &PetriNet,
+    element: &PetriNetElement,
+) -> Result<(), quick_xml::Error> {
+    for xml in pn.tool_specific.get(element).into_iter().flatten() {
+        // Re-emit the events, so that the output is indented consistently
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        loop {
+            match reader.read_event()? {
+                Event::Eof => break,
+                event => writer.write_event(event)?,
+            }
+        }
+    }
+    Ok(())
+}
+
 ///
 /// Export a [`PetriNet`] to the PNML format and write the result to the provided writer which implements into [`quick_xml::Writer`] / [`std::io::Write`]
 ///
+/// Places and transitions are written in the order of their IDs, together with their names, positions
+/// ([`PetriNet::positions`]) and tool-specific data ([`PetriNet::tool_specific`]).
+/// Places without a name are named by their ID.
+///
 pub fn export_petri_net_to_pnml<'a, W>(
     pn: &PetriNet,
     into_writer: impl Into<XMLWriterWrapper<'a, W>>,
@@ -20,6 +90,10 @@ where
 {
     let mut xml_writer: XMLWriterWrapper<'_, W> = into_writer.into();
     let writer = xml_writer.to_xml_writer();
+    let mut places: Vec<_> = pn.places.iter().collect();
+    places.sort_by_key(|(id, _)| **id);
+    let mut transitions: Vec<_> = pn.transitions.iter().collect();
+    transitions.sort_by_key(|(id, _)| **id);
     writer
         .create_element("pnml")
         .write_inner_content(|writer| {
@@ -33,99 +107,78 @@ where
                     ),
                 ])
                 .write_inner_content(|writer| {
+                    write_tool_specific(writer, pn, &PetriNetElement::Net)?;
                     writer
                         .create_element("page")
                         .with_attribute(("id", "n0"))
                         .write_inner_content(|writer| {
-                            pn.places.iter().for_each(|(id, place)| {
+                            for (id, place) in &places {
                                 writer
                                     .create_element("place")
                                     .with_attribute(("id", id.to_string().as_str()))
                                     .write_inner_content(|writer| {
-                                        writer
-                                            .create_element("name")
-                                            .write_inner_content(|writer| {
-                                                writer
-                                                    .create_element("text")
-                                                    .write_text_content(BytesText::new(
-                                                        id.to_string().as_str(),
-                                                    ))
-                                                    .unwrap();
-                                                OK
-                                            })
-                                            .unwrap();
-                                        if let Some(initial_marking) = pn.initial_marking.clone() {
-                                            if initial_marking.contains_key(&place.into()) {
-                                                let tokens =
-                                                    initial_marking.get(&place.into()).unwrap();
-                                                writer
-                                                    .create_element("initialMarking")
-                                                    .write_inner_content(|writer| {
-                                                        writer
-                                                            .create_element("text")
-                                                            .write_text_content(BytesText::new(
-                                                                tokens.to_string().as_str(),
-                                                            ))
-                                                            .unwrap();
-                                                        OK
-                                                    })
-                                                    .unwrap();
-                                            }
+                                        write_text_element(
+                                            writer,
+                                            "name",
+                                            &place.name.clone().unwrap_or(id.to_string()),
+                                        )?;
+                                        if let Some(position) = pn.positions.get(id) {
+                                            write_graphics(writer, position)?;
                                         }
-
-                                        OK
-                                    })
-                                    .unwrap();
-                            });
-                            pn.transitions.iter().for_each(|(id, transition)| {
+                                        if let Some(tokens) = pn
+                                            .initial_marking
+                                            .as_ref()
+                                            .and_then(|m| m.get(&(*place).into()))
+                                        {
+                                            write_text_element(
+                                                writer,
+                                                "initialMarking",
+                                                &tokens.to_string(),
+                                            )?;
+                                        }
+                                        write_tool_specific(
+                                            writer,
+                                            pn,
+                                            &PetriNetElement::Node(**id),
+                                        )
+                                    })?;
+                            }
+                            for (id, transition) in &transitions {
                                 writer
                                     .create_element("transition")
                                     .with_attribute(("id", id.to_string().as_str()))
                                     .write_inner_content(|writer| {
-                                        writer
-                                            .create_element("name")
-                                            .write_inner_content(|writer| {
-                                                writer
-                                                    .create_element("text")
-                                                    .write_text_content(BytesText::new(
-                                                        transition
-                                                            .label
-                                                            .clone()
-                                                            .unwrap_or("Tau".to_string())
-                                                            .as_str(),
-                                                    ))
-                                                    .unwrap();
-                                                OK
-                                            })
-                                            .unwrap();
+                                        write_text_element(
+                                            writer,
+                                            "name",
+                                            transition.label.as_deref().unwrap_or("Tau"),
+                                        )?;
+                                        if let Some(position) = pn.positions.get(id) {
+                                            write_graphics(writer, position)?;
+                                        }
                                         if transition.label.is_none() {
-                                            // TODO: Add  something like <toolspecific tool="ProM" version="6.4" activity="$invisible$" localNodeID="..."/>
                                             writer
                                                 .create_element("toolspecific")
                                                 .with_attributes(vec![
                                                     ("tool", "ProM"),
                                                     ("version", "6.4"),
                                                     ("activity", "$invisible$"),
-                                                    (
-                                                        "localNodeID",
-                                                        Uuid::new_v4().to_string().as_str(),
-                                                    ),
+                                                    // Derived from the transition ID, such that repeated exports are identical
+                                                    ("localNodeID", id.to_string().as_str()),
                                                 ])
-                                                .write_empty()
-                                                .unwrap();
+                                                .write_empty()?;
                                         }
-                                        OK
-                                    })
-                                    .unwrap();
-                            });
-                            pn.arcs.iter().for_each(|arc| {
+                                        write_tool_specific(
+                                            writer,
+                                            pn,
+                                            &PetriNetElement::Node(**id),
+                                        )
+                                    })?;
+                            }
+                            for arc in &pn.arcs {
                                 let (source_id, target_id) = match arc.from_to {
-                                    super::petri_net_struct::ArcType::PlaceTransition(from, to) => {
-                                        (from, to)
-                                    }
-                                    super::petri_net_struct::ArcType::TransitionPlace(from, to) => {
-                                        (from, to)
-                                    }
+                                    ArcType::PlaceTransition(from, to) => (from, to),
+                                    ArcType::TransitionPlace(from, to) => (from, to),
                                 };
                                 writer
                                     .create_element("arc")
@@ -136,34 +189,30 @@ where
                                     ))
                                     .with_attribute(("source", source_id.to_string().as_str()))
                                     .with_attribute(("target", target_id.to_string().as_str()))
-                                    .write_inner_content(|w| {
-                                        w.create_element("inscription")
-                                            .write_inner_content(|w| {
-                                                w.create_element("text")
-                                                    .write_text_content(BytesText::new(
-                                                        arc.weight.to_string().as_str(),
-                                                    ))
-                                                    .unwrap();
-                                                OK
-                                            })
-                                            .unwrap();
-                                        OK
-                                    })
-                                    .unwrap();
-                            });
+                                    .write_inner_content(|writer| {
+                                        write_text_element(
+                                            writer,
+                                            "inscription",
+                                            &arc.weight.to_string(),
+                                        )?;
+                                        write_tool_specific(
+                                            writer,
+                                            pn,
+                                            &PetriNetElement::Arc(arc.from_to.clone()),
+                                        )
+                                    })?;
+                            }
                             OK
-                        })
-                        .unwrap();
+                        })?;
 
-                    if let Some(final_markings) = pn.final_markings.clone() {
+                    if let Some(final_markings) = &pn.final_markings {
                         writer
                             .create_element("finalmarkings")
                             .write_inner_content(|writer| {
-                                final_markings.iter().for_each(|marking| {
-                                    writer
-                                        .create_element("marking")
-                                        .write_inner_content(|writer| {
-                                            marking.iter().for_each(|(place_id, tokens)| {
+                                for marking in final_markings {
+                                    writer.create_element("marking").write_inner_content(
+                                        |writer| {
+                                            for (place_id, tokens) in marking {
                                                 writer
                                                     .create_element("place")
                                                     .with_attribute((
@@ -175,25 +224,21 @@ where
                                                             .create_element("text")
                                                             .write_text_content(BytesText::new(
                                                                 tokens.to_string().as_str(),
-                                                            ))
-                                                            .unwrap();
+                                                            ))?;
                                                         OK
-                                                    })
-                                                    .unwrap();
-                                            });
+                                                    })?;
+                                            }
                                             OK
-                                        })
-                                        .unwrap();
-                                });
+                                        },
+                                    )?;
+                                }
                                 OK
-                            })
-                            .unwrap();
+                            })?;
                     }
 
                     // </net>
                     OK
-                })
-                .unwrap();
+                })?;
             OK
         })?;
     Ok(())
@@ -216,8 +261,9 @@ mod test {
     use std::{fs::File, io::BufWriter};
 
     use crate::{
-        import_xes_file, petri_net::export_pnml::export_petri_net_to_pnml,
-        utils::test_utils::get_test_data_path, XESImportOptions,
+        conformance::token_replay::tests::choice_net, import_xes_file,
+        petri_net::export_pnml::export_petri_net_to_pnml, utils::test_utils::get_test_data_path,
+        XESImportOptions,
     };
 
     use super::export_petri_net_to_pnml_path;
@@ -235,6 +281,20 @@ mod test {
         println!("file:///{}", export_path.to_string_lossy());
     }
 
+    #[test]
+    fn test_export_pnml_deterministic() {
+        let mut pn = choice_net();
+        pn.add_transition(None, None);
+        let export = || {
+            let mut out = Vec::new();
+            export_petri_net_to_pnml(&pn, &mut out).unwrap();
+            out
+        };
+        let first = export();
+        assert!(String::from_utf8_lossy(&first).contains("$invisible$"));
+        assert_eq!(first, export());
+    }
+
     #[test]
     fn test_export_pnml_to_writer() -> Result<(), quick_xml::Error> {
         let path = get_test_data_path().join("xes").join("AN1-example.xes");
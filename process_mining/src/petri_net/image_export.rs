@@ -41,14 +41,28 @@ pub fn export_petri_net_to_dot_graph(net: &PetriNet, dpi_factor: Option<f32>) ->
         .places
         .iter()
         .map(|(p_id, p)| {
-            let symbol = if net.is_in_initial_marking(&p.into()) {
-                "●"
-            } else {
-                ""
+            let tokens = net
+                .initial_marking
+                .as_ref()
+                .and_then(|m| m.get(&p.into()))
+                .copied()
+                .unwrap_or_default();
+            let symbol = match tokens {
+                0 => String::new(),
+                1..=3 => "●".repeat(tokens as usize),
+                _ => tokens.to_string(),
             };
             let shape = if net.is_in_a_final_marking(&p.into()) {"doublecircle"} else {"circle"};
             let size = if net.is_in_a_final_marking(&p.into()) {0.4} else {0.5};
-            stmt!(node!(esc p_id; attr!("label", esc symbol), attr!("shape",shape), attr!("fixedsize",true), attr!("width",size), attr!("height",size)))
+            let mut attributes = vec![attr!("label", esc symbol), attr!("shape",shape), attr!("fixedsize",true), attr!("width",size), attr!("height",size)];
+            // Tokens in the final markings (as `index:tokens` pairs), such that they can be imported again
+            let final_tokens = net.final_markings.iter().flatten().enumerate().filter_map(|(i, m)| {
+                m.get(&p.into()).map(|tokens| format!("{i}:{tokens}"))
+            }).collect::<Vec<_>>();
+            if !final_tokens.is_empty() {
+                attributes.push(attr!("final_markings", esc final_tokens.join(",")));
+            }
+            stmt!(node!(esc p_id, attributes))
         }).collect();
 
     let transition_nodes: Vec<_> = net
@@ -83,6 +97,9 @@ pub fn export_petri_net_to_dot_graph(net: &PetriNet, dpi_factor: Option<f32>) ->
         .collect();
 
     let mut global_graph_options = vec![stmt!(attr!("rankdir", "LR"))];
+    if let Some(final_markings) = &net.final_markings {
+        global_graph_options.push(stmt!(attr!("final_markings", (final_markings.len()))))
+    }
     if let Some(dpi_fac) = dpi_factor {
         global_graph_options.push(stmt!(attr!("dpi", (dpi_fac * 96.0))))
     }
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::utils::dot_utils::{parse_number, DOTNode, DOTParseError, FlatDOTGraph};
+
+use super::petri_net_struct::{ArcType, Marking, PetriNet, PlaceID, TransitionID};
+
+/// Token symbols used in place labels (the first one is used by [`export_petri_net_to_dot_graph`](super::image_export::export_petri_net_to_dot_graph))
+const TOKEN_SYMBOLS: &[char] = &['●', '•'];
+
+/// Number of final markings supported in addition to the number of places
+///
+/// Limits the memory allocated for the final markings (which are given by numbers in the DOT source)
+const ADDITIONAL_FINAL_MARKINGS: usize = 1024;
+
+/// Get the shape of a node (`ellipse` if not set, as in Graphviz)
+fn node_shape(node: &DOTNode) -> &str {
+    node.attributes
+        .get("shape")
+        .map(String::as_str)
+        .unwrap_or("ellipse")
+}
+
+/// Parse the number of tokens from the label of a place
+///
+/// The label can either be empty, consist of token symbols (e.g., `●●`) or be a number.
+fn parse_tokens(label: &str) -> Result<u64, DOTParseError> {
+    let label = label.trim();
+    if label.chars().all(|c| TOKEN_SYMBOLS.contains(&c)) {
+        Ok(label.chars().count() as u64)
+    } else {
+        parse_number(label)
+    }
+}
+
+/// Parse the tokens of a place in the final markings (comma-separated `index:tokens` pairs)
+fn parse_final_tokens(value: &str) -> Result<Vec<(usize, u64)>, DOTParseError> {
+    value
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| match pair.split_once(':') {
+            Some((i, tokens)) => Ok((parse_number(i)?, parse_number(tokens)?)),
+            None => Err(DOTParseError::InvalidNumber(pair.to_string())),
+        })
+        .collect()
+}
+
+///
+/// Import a [`PetriNet`] from DOT source
+///
+/// Follows the conventions of [`export_petri_net_to_dot_graph`](super::image_export::export_petri_net_to_dot_graph),
+/// such that exported Petri nets can be imported again:
+///
+/// - Nodes with shape `circle` or `doublecircle` are places.
+///   The label of a place holds its tokens in the initial marking (either as token symbols like `●●` or as a number).
+/// - The final markings are given by the `final_markings` attribute of the graph (number of final markings)
+///   and of the places (comma-separated `index:tokens` pairs, e.g., `0:1,1:2`).
+///   If neither is set, places with shape `doublecircle` form the final marking (with one token each).
+///   At most 1024 final markings more than there are places are supported.
+/// - Nodes with shape `box`, `rect`, `rectangle` or `square` are transitions.
+///   Transitions with an empty label are silent. If no label is set, the node ID is used as label (as in Graphviz).
+/// - Edges are arcs, with an optional numeric label as arc weight.
+///
+/// Node IDs which are UUIDs are used as IDs of the places and transitions; otherwise, new UUIDs are generated.
+/// Default node and edge attributes (e.g., `node [shape=box]`) and subgraphs are supported.
+///
+/// __Requires the `graphviz-export` feature to be enabled__ (but no Graphviz installation)
+///
+pub fn import_petri_net_from_dot(dot: &str) -> Result<PetriNet, DOTParseError> {
+    let graph = FlatDOTGraph::parse(dot)?;
+    let mut net = PetriNet::new();
+    let mut places: HashMap<&str, PlaceID> = HashMap::new();
+    let mut transitions: HashMap<&str, TransitionID> = HashMap::new();
+    let mut initial_marking = Marking::new();
+    let mut final_marking = Marking::new();
+    let max_final_markings = graph
+        .nodes
+        .iter()
+        .filter(|n| matches!(node_shape(n), "circle" | "doublecircle"))
+        .count()
+        + ADDITIONAL_FINAL_MARKINGS;
+    let check_final_markings = |n: usize| {
+        if n > max_final_markings {
+            Err(DOTParseError::TooManyFinalMarkings(n, max_final_markings))
+        } else {
+            Ok(n)
+        }
+    };
+    let mut final_markings: Vec<Marking> = match graph.attributes.get("final_markings") {
+        Some(count) => vec![Marking::new(); check_final_markings(parse_number(count)?)?],
+        None => Vec::new(),
+    };
+    let explicit_final_markings = graph.attributes.contains_key("final_markings")
+        || graph
+            .nodes
+            .iter()
+            .any(|n| n.attributes.contains_key("final_markings"));
+    for node in &graph.nodes {
+        let uuid = Uuid::parse_str(&node.id).ok();
+        let shape = node_shape(node);
+        let label = node.attributes.get("label").map(|l| {
+            if l == "\\N" {
+                node.id.as_str()
+            } else {
+                l.as_str()
+            }
+        });
+        match shape {
+            "circle" | "doublecircle" => {
+                let place = net.add_place(uuid);
+                let tokens = parse_tokens(label.unwrap_or_default())?;
+                if tokens > 0 {
+                    initial_marking.insert(place, tokens);
+                }
+                if let Some(final_tokens) = node.attributes.get("final_markings") {
+                    for (i, tokens) in parse_final_tokens(final_tokens)? {
+                        if final_markings.len() <= i {
+                            final_markings.resize(check_final_markings(i + 1)?, Marking::new());
+                        }
+                        final_markings[i].insert(place, tokens);
+                    }
+                } else if shape == "doublecircle" {
+                    final_marking.insert(place, 1);
+                }
+                places.insert(&node.id, place);
+            }
+            "box" | "rect" | "rectangle" | "square" => {
+                let label = match label {
+                    Some("") => None,
+                    Some(label) => Some(label.to_string()),
+                    None => Some(node.id.clone()),
+                };
+                transitions.insert(&node.id, net.add_transition(label, uuid));
+            }
+            _ => {
+                return Err(DOTParseError::UnsupportedShape(
+                    node.id.clone(),
+                    shape.to_string(),
+                ))
+            }
+        }
+    }
+    for edge in &graph.edges {
+        let from_to = match (
+            places.get(edge.from.as_str()),
+            transitions.get(edge.from.as_str()),
+            places.get(edge.to.as_str()),
+            transitions.get(edge.to.as_str()),
+        ) {
+            (Some(p), _, _, Some(t)) => ArcType::place_to_transition(*p, *t),
+            (_, Some(t), Some(p), _) => ArcType::transition_to_place(*t, *p),
+            _ => {
+                return Err(DOTParseError::InvalidEdge(
+                    edge.from.clone(),
+                    edge.to.clone(),
+                ))
+            }
+        };
+        let weight = edge
+            .attributes
+            .get("label")
+            .map(|l| parse_number(l))
+            .transpose()?;
+        net.add_arc(from_to, weight);
+    }
+    if !initial_marking.is_empty() {
+        net.initial_marking = Some(initial_marking);
+    }
+    if explicit_final_markings {
+        net.final_markings = Some(final_markings);
+    } else if !final_marking.is_empty() {
+        net.final_markings = Some(vec![final_marking]);
+    }
+    Ok(net)
+}
+
+///
+/// Import a [`PetriNet`] from a DOT file at the given filepath
+///
+/// See [`import_petri_net_from_dot`] for the supported DOT conventions.
+///
+pub fn import_petri_net_from_dot_path<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<PetriNet, DOTParseError> {
+    import_petri_net_from_dot(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conformance::token_replay::tests::choice_net,
+        petri_net::image_export::{export_petri_net_to_dot_graph, graph_to_dot},
+    };
+
+    use super::*;
+
+    #[test]
+    fn dot_round_trip() {
+        let mut net = choice_net();
+        let t = net.transitions.keys().next().copied().unwrap();
+        let p = net.places.keys().next().copied().unwrap();
+        net.add_arc(
+            ArcType::transition_to_place(TransitionID(t), PlaceID(p)),
+            Some(3),
+        );
+        let dot = graph_to_dot(&export_petri_net_to_dot_graph(&net, None));
+        let imported = import_petri_net_from_dot(&dot).unwrap();
+        assert_eq!(imported.places, net.places);
+        assert_eq!(imported.transitions, net.transitions);
+        assert_eq!(imported.initial_marking, net.initial_marking);
+        assert_eq!(imported.final_markings, net.final_markings);
+        let mut arcs = net.arcs.clone();
+        arcs.sort();
+        let mut imported_arcs = imported.arcs.clone();
+        imported_arcs.sort();
+        assert_eq!(imported_arcs, arcs);
+    }
+
+    #[test]
+    fn dot_round_trip_with_multiple_final_markings() {
+        let mut net = choice_net();
+        let mut place_ids: Vec<PlaceID> = net.places.keys().map(|p| PlaceID(*p)).collect();
+        place_ids.sort();
+        net.final_markings = Some(vec![
+            Marking::from([(place_ids[0], 1), (place_ids[1], 2)]),
+            Marking::from([(place_ids[1], 1)]),
+        ]);
+        let dot = graph_to_dot(&export_petri_net_to_dot_graph(&net, None));
+        let imported = import_petri_net_from_dot(&dot).unwrap();
+        assert_eq!(imported.final_markings, net.final_markings);
+
+        net.final_markings = Some(Vec::new());
+        let dot = graph_to_dot(&export_petri_net_to_dot_graph(&net, None));
+        let imported = import_petri_net_from_dot(&dot).unwrap();
+        assert_eq!(imported.final_markings, Some(Vec::new()));
+    }
+
+    #[test]
+    fn hand_written_dot() {
+        let net = import_petri_net_from_dot(
+            r#"digraph {
+                node [shape=circle];
+                source [label="2"]; sink [shape=doublecircle, label=""];
+                a [shape=box]; tau [shape=box, label=""];
+                source -> a -> sink;
+                source -> tau -> sink [label=2];
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(net.places.len(), 2);
+        let mut labels: Vec<_> = net.transitions.values().map(|t| t.label.clone()).collect();
+        labels.sort();
+        assert_eq!(labels, vec![None, Some("a".to_string())]);
+        assert_eq!(
+            net.initial_marking.unwrap().values().collect::<Vec<_>>(),
+            vec![&2]
+        );
+        assert_eq!(net.final_markings.unwrap().len(), 1);
+        assert_eq!(net.arcs.iter().filter(|a| a.weight == 2).count(), 2);
+
+        assert!(matches!(
+            import_petri_net_from_dot("digraph { p [shape=circle]; q [shape=circle]; p -> q }"),
+            Err(DOTParseError::InvalidEdge(from, to)) if from == "p" && to == "q"
+        ));
+        assert!(matches!(
+            import_petri_net_from_dot("digraph { a -> b }"),
+            Err(DOTParseError::UnsupportedShape(node, shape)) if node == "a" && shape == "ellipse"
+        ));
+        assert!(matches!(
+            import_petri_net_from_dot("digraph { final_markings=1000000000000; p [shape=circle] }"),
+            Err(DOTParseError::TooManyFinalMarkings(1000000000000, 1025))
+        ));
+        assert!(matches!(
+            import_petri_net_from_dot(
+                "digraph { p [shape=circle, final_markings=\"1026:1\"]; q [shape=circle] }"
+            ),
+            Err(DOTParseError::TooManyFinalMarkings(1027, 1026))
+        ));
+    }
+}
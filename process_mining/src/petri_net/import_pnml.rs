@@ -1,11 +1,14 @@
 use std::{collections::HashMap, io::BufRead};
 
-use quick_xml::{Error as QuickXMLError, Reader};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Error as QuickXMLError, Reader, Writer,
+};
 use uuid::Uuid;
 
 use crate::PetriNet;
 
-use super::petri_net_struct::{ArcType, Marking, PlaceID};
+use super::petri_net_struct::{ArcType, Marking, PetriNetElement, PlaceID};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Mode {
@@ -22,12 +25,47 @@ enum Mode {
     FinalMarkingMarkingPlace,
     Arc,
     ArcInscription,
+    /// Inside of a `referencePlace` or `referenceTransition` element
+    Reference,
+}
+
+/// Element to which a `<toolspecific>` element belongs
+#[derive(Debug, Clone, Copy)]
+enum ToolSpecificOwner {
+    Net,
+    Node(Uuid),
+    /// Arc (with index in the list of parsed arcs)
+    Arc(usize),
+    /// Element whose tool-specific data is not kept (e.g., reference nodes)
+    Ignored,
 }
 
 fn read_to_string(x: &mut &[u8]) -> String {
     String::from_utf8_lossy(x).to_string()
 }
 
+fn get_f64_attribute(b: &BytesStart<'_>, key: &str) -> Option<f64> {
+    let attr = b.try_get_attribute(key).ok()??;
+    read_to_string(&mut attr.value.as_ref()).trim().parse().ok()
+}
+
+/// Resolve the ID of a (reference) node to the UUID of the referenced place or transition
+fn resolve_id(
+    id_map: &HashMap<String, Uuid>,
+    references: &HashMap<String, String>,
+    id: &str,
+) -> Option<Uuid> {
+    let mut id = id;
+    // References might be chained, but should never be cyclic
+    for _ in 0..=references.len() {
+        if let Some(uuid) = id_map.get(id) {
+            return Some(*uuid);
+        }
+        id = references.get(id)?;
+    }
+    None
+}
+
 ///
 /// Error encountered while parsing PNML
 ///
@@ -91,13 +129,18 @@ impl From<QuickXMLError> for PNMLParseError {
 /// Note, that this implementation (at least currently) is a best-effort parser for the basic types of Petri nets encountered commonly in Process Mining.
 /// In particular, the following Petri net features are implemented:
 ///
-/// - Places, Transitions
+/// - Places (incl. names), Transitions
 /// - Silent transitions (toolspecific tag)
 /// - Basics arcs (i.e., not inhibitor arcs, etc.)
 /// - Arc weights
 /// - A single initial marking
 /// - Multiple final markings
+/// - Positions and sizes of places and transitions (`graphics` tag, see [`PetriNet::positions`])
+/// - Other `toolspecific` tags of the net, places, transitions and arcs, which are kept as raw XML (see [`PetriNet::tool_specific`])
 ///
+/// Nets with multiple (possibly nested) pages are flattened into a single [`PetriNet`],
+/// resolving reference places and transitions (`referencePlace`/`referenceTransition`) to the nodes they refer to.
+/// IDs of places and transitions which are UUIDs are kept; for all other IDs, new UUIDs are generated.
 ///
 /// Also see [`import_pnml_reader`] for an alternative version of this function, which takes a (standard) buffered reader implementing [`std::io::BufRead`] instead
 pub fn import_pnml<T>(reader: &mut Reader<T>) -> Result<PetriNet, PNMLParseError>
@@ -119,9 +162,44 @@ where
     let mut current_id: Option<Uuid> = None;
 
     let mut arcs: Vec<(String, String, u32)> = Vec::new();
+    // Reference nodes (ID of reference -> ID of referenced node)
+    let mut references: HashMap<String, String> = HashMap::new();
+    // Currently captured `<toolspecific>` element (writer, nesting depth and owner)
+    let mut tool_specific_capture: Option<(Writer<Vec<u8>>, usize, ToolSpecificOwner)> = None;
+    let mut arc_tool_specific: Vec<(usize, String)> = Vec::new();
 
     loop {
-        match reader.read_event_into(&mut buf)? {
+        let event = reader.read_event_into(&mut buf)?;
+        if let Some((writer, depth, owner)) = tool_specific_capture.as_mut() {
+            match &event {
+                Event::Start(_) => *depth += 1,
+                Event::End(_) => *depth -= 1,
+                Event::Eof => break,
+                _ => {}
+            }
+            writer.write_event(event.borrow())?;
+            if *depth == 0 {
+                let owner = *owner;
+                let (writer, _, _) = tool_specific_capture.take().unwrap();
+                let xml = String::from_utf8_lossy(&writer.into_inner()).to_string();
+                match owner {
+                    ToolSpecificOwner::Net => pn
+                        .tool_specific
+                        .entry(PetriNetElement::Net)
+                        .or_default()
+                        .push(xml),
+                    ToolSpecificOwner::Node(id) => pn
+                        .tool_specific
+                        .entry(PetriNetElement::Node(id))
+                        .or_default()
+                        .push(xml),
+                    ToolSpecificOwner::Arc(index) => arc_tool_specific.push((index, xml)),
+                    ToolSpecificOwner::Ignored => {}
+                }
+            }
+            continue;
+        }
+        match event {
             quick_xml::events::Event::Start(b) => match b.name().as_ref() {
                 b"pnml" => {
                     current_mode = Mode::Pnml;
@@ -152,7 +230,7 @@ where
                         );
                         current_mode = Mode::FinalMarkingMarkingPlace;
                         // Save idref as current_id (and use it when parsing the corresponding <text>1</text>)
-                        current_id = id_map.get(&id_ref).cloned();
+                        current_id = resolve_id(&id_map, &references, &id_ref);
                     } else {
                         // Add place
                         current_mode = Mode::Place;
@@ -161,7 +239,7 @@ where
                             .unwrap_or_default()
                             .ok_or(PNMLParseError::MissingKey("id"))?;
                         let place_id_str = read_to_string(&mut place_id.value.as_ref());
-                        let uuid = Uuid::new_v4();
+                        let uuid = new_node_uuid(&pn, &place_id_str);
                         current_id = Some(uuid);
                         id_map.insert(place_id_str, uuid);
                         pn.add_place(Some(uuid));
@@ -174,7 +252,7 @@ where
                         .unwrap_or_default()
                         .ok_or(PNMLParseError::MissingKey("id"))?;
                     let trans_id_str = read_to_string(&mut trans_id.value.as_ref());
-                    let uuid = Uuid::new_v4();
+                    let uuid = new_node_uuid(&pn, &trans_id_str);
                     current_id = Some(uuid);
                     id_map.insert(trans_id_str, uuid);
                     pn.add_transition(Some(String::new()), Some(uuid));
@@ -202,27 +280,80 @@ where
                     current_mode = Mode::Arc;
                 }
                 // Handle weighted arcs
-                b"inscription" => {
-                    if current_mode == Mode::Arc {
-                        current_mode = Mode::ArcInscription;
+                b"inscription" if current_mode == Mode::Arc => {
+                    current_mode = Mode::ArcInscription;
+                }
+                b"referencePlace" | b"referenceTransition" => {
+                    let id = read_to_string(
+                        &mut b
+                            .try_get_attribute("id")
+                            .unwrap_or_default()
+                            .ok_or(PNMLParseError::MissingKey("id"))?
+                            .value
+                            .as_ref(),
+                    );
+                    let referenced_id = read_to_string(
+                        &mut b
+                            .try_get_attribute("ref")
+                            .unwrap_or_default()
+                            .ok_or(PNMLParseError::MissingKey("ref"))?
+                            .value
+                            .as_ref(),
+                    );
+                    references.insert(id, referenced_id);
+                    current_mode = Mode::Reference;
+                }
+                // Positions and sizes of places and transitions (not of their names)
+                b"position" | b"dimension"
+                    if matches!(current_mode, Mode::Place | Mode::Transition) =>
+                {
+                    if let (Some(id), Some(x), Some(y)) = (
+                        current_id,
+                        get_f64_attribute(&b, "x"),
+                        get_f64_attribute(&b, "y"),
+                    ) {
+                        let position = pn.positions.entry(id).or_default();
+                        if b.name().as_ref() == b"position" {
+                            position.x = x;
+                            position.y = y;
+                        } else {
+                            position.width = Some(x);
+                            position.height = Some(y);
+                        }
                     }
                 }
                 // For handling silent transitions
                 b"toolspecific" => {
-                    if let Some(attr) = b.try_get_attribute("activity").unwrap_or_default().as_ref()
-                    {
-                        if attr.value.as_ref() == b"$invisible$" {
-                            if let Some(trans) =
-                                current_id.and_then(|id| pn.transitions.get_mut(&id))
-                            {
-                                // Set label to None (silent)
-                                trans.label = None;
-                            } else {
-                                eprintln!(
-                                    "Can't find current transition when adding toolspecific!"
-                                );
-                            }
+                    let is_invisible = b
+                        .try_get_attribute("activity")
+                        .unwrap_or_default()
+                        .is_some_and(|attr| attr.value.as_ref() == b"$invisible$");
+                    if is_invisible {
+                        if let Some(trans) = current_id.and_then(|id| pn.transitions.get_mut(&id)) {
+                            // Set label to None (silent)
+                            trans.label = None;
+                        } else {
+                            eprintln!("Can't find current transition when adding toolspecific!");
                         }
+                    } else {
+                        // Keep all other tool-specific data as raw XML
+                        let owner = match current_mode {
+                            Mode::Place
+                            | Mode::PlaceName
+                            | Mode::InitialMarking
+                            | Mode::Transition
+                            | Mode::TransitionName => current_id
+                                .map(ToolSpecificOwner::Node)
+                                .unwrap_or(ToolSpecificOwner::Ignored),
+                            Mode::Arc | Mode::ArcInscription => {
+                                ToolSpecificOwner::Arc(arcs.len() - 1)
+                            }
+                            Mode::Pnml | Mode::Net => ToolSpecificOwner::Net,
+                            _ => ToolSpecificOwner::Ignored,
+                        };
+                        let mut writer = Writer::new(Vec::new());
+                        writer.write_event(Event::Start(b.borrow()))?;
+                        tool_specific_capture = Some((writer, 1, owner));
                     }
                 }
                 // For handling initial markings
@@ -230,12 +361,10 @@ where
                     current_mode = Mode::InitialMarking;
                 }
                 b"finalmarkings" => current_mode = Mode::FinalMarkings,
-                b"marking" => {
-                    if current_mode == Mode::FinalMarkings {
-                        current_mode = Mode::FinalMarkingsMarking;
-                        // Add new final marking
-                        final_markings.push(HashMap::new());
-                    }
+                b"marking" if current_mode == Mode::FinalMarkings => {
+                    current_mode = Mode::FinalMarkingsMarking;
+                    // Add new final marking
+                    final_markings.push(HashMap::new());
                 }
                 b"name" => match current_mode {
                     Mode::Place => current_mode = Mode::PlaceName,
@@ -263,14 +392,13 @@ where
                 }
                 b"finalmarkings" => current_mode = Mode::Net,
                 b"marking" => current_mode = Mode::FinalMarkings,
-                b"inscription" => {
-                    if current_mode == Mode::ArcInscription {
-                        current_mode = Mode::Arc
-                    }
-                }
+                b"inscription" if current_mode == Mode::ArcInscription => current_mode = Mode::Arc,
                 b"arc" => {
                     current_mode = Mode::Net;
                 }
+                b"referencePlace" | b"referenceTransition" => {
+                    current_mode = Mode::Net;
+                }
                 b"name" => match current_mode {
                     Mode::PlaceName => current_mode = Mode::Place,
                     Mode::TransitionName => current_mode = Mode::Transition,
@@ -281,6 +409,15 @@ where
             quick_xml::events::Event::Text(t) => {
                 let text = read_to_string(&mut t.as_ref());
                 match current_mode {
+                    Mode::PlaceName => {
+                        if let Some(place) = current_id.and_then(|id| pn.places.get_mut(&id)) {
+                            // Places without a name are exported with their ID as name
+                            if Some(text.as_str()) != current_id.map(|id| id.to_string()).as_deref()
+                            {
+                                place.name = Some(text);
+                            }
+                        }
+                    }
                     Mode::TransitionName => {
                         if let Some(trans) = current_id.and_then(|id| pn.transitions.get_mut(&id)) {
                             // Only overwrite label if it is set to Some(...)
@@ -326,11 +463,18 @@ where
         return Err(PNMLParseError::NoPNMLTag);
     }
 
-    for (from, to, weight) in arcs {
-        let from_uuid = id_map.get(&from);
-        let to_uuid = id_map.get(&to);
-        if let Some(from_uuid) = from_uuid {
-            if let Some(to_uuid) = to_uuid {
+    let mut arc_tool_specific_by_index: HashMap<usize, Vec<String>> = HashMap::new();
+    for (index, xml) in arc_tool_specific {
+        arc_tool_specific_by_index
+            .entry(index)
+            .or_default()
+            .push(xml);
+    }
+    for (index, (from, to, weight)) in arcs.into_iter().enumerate() {
+        let from_uuid = resolve_id(&id_map, &references, &from);
+        let to_uuid = resolve_id(&id_map, &references, &to);
+        if let Some(from_uuid) = from_uuid.as_ref() {
+            if let Some(to_uuid) = to_uuid.as_ref() {
                 let mut from_to = None;
                 // Option 1: Place -> Transition
                 if let Some(place) = pn.places.get(from_uuid) {
@@ -344,6 +488,12 @@ where
                     }
                 }
                 if let Some(from_to) = from_to {
+                    if let Some(tool_specific) = arc_tool_specific_by_index.remove(&index) {
+                        pn.tool_specific
+                            .entry(PetriNetElement::Arc(from_to.clone()))
+                            .or_default()
+                            .extend(tool_specific);
+                    }
                     pn.add_arc(from_to, Some(weight))
                 }
             }
@@ -358,6 +508,14 @@ where
     Ok(pn)
 }
 
+/// Use the ID of a place or transition as UUID, if it is a (not yet used) UUID, or generate a new one
+fn new_node_uuid(pn: &PetriNet, id: &str) -> Uuid {
+    Uuid::parse_str(id)
+        .ok()
+        .filter(|uuid| !pn.places.contains_key(uuid) && !pn.transitions.contains_key(uuid))
+        .unwrap_or_else(Uuid::new_v4)
+}
+
 ///
 /// Import a PNML file from the given standard buffered reader (implementing [`std::io::BufRead`])
 ///
@@ -386,10 +544,94 @@ pub fn import_pnml_from_path<P: AsRef<std::path::Path>>(
 mod test {
     use quick_xml::Reader;
 
-    use crate::utils::test_utils::get_test_data_path;
+    use crate::{
+        petri_net::{
+            export_pnml::export_petri_net_to_pnml,
+            petri_net_struct::{NodePosition, PetriNetElement},
+        },
+        utils::test_utils::get_test_data_path,
+    };
 
     use super::import_pnml;
 
+    const MULTI_PAGE_PNML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<pnml>
+  <net id="net1" type="http://www.pnml.org/version-2009/grammar/ptnet">
+    <toolspecific tool="WoPeD" version="1.0"><bounds><position x="2" y="25"/></bounds></toolspecific>
+    <page id="page1">
+      <place id="p1">
+        <name><text>start</text><graphics><offset x="0" y="40"/></graphics></name>
+        <graphics><position x="70" y="170"/><dimension x="40" y="40"/></graphics>
+        <initialMarking><text>1</text></initialMarking>
+      </place>
+      <transition id="t1">
+        <name><text>register</text></name>
+        <graphics><position x="150" y="170"/></graphics>
+        <toolspecific tool="WoPeD" version="1.0"><time>0</time></toolspecific>
+      </transition>
+      <arc id="a1" source="p1" target="t1">
+        <inscription><text>1</text></inscription>
+        <toolspecific tool="WoPeD" version="1.0"><probability>1.0</probability></toolspecific>
+      </arc>
+      <referencePlace id="rp2" ref="p2"/>
+      <arc id="a2" source="t1" target="rp2"><inscription><text>2</text></inscription></arc>
+    </page>
+    <page id="page2">
+      <page id="page3">
+        <place id="p2"><name><text>end</text></name></place>
+      </page>
+    </page>
+  </net>
+</pnml>"#;
+
+    #[test]
+    fn test_pnml_multi_page_graphics_tool_specific() {
+        let pn = import_pnml(&mut Reader::from_reader(MULTI_PAGE_PNML.as_bytes())).unwrap();
+        assert_eq!(pn.places.len(), 2);
+        assert_eq!(pn.transitions.len(), 1);
+        assert_eq!(pn.arcs.len(), 2);
+        assert!(pn.arcs.iter().any(|arc| arc.weight == 2));
+        let mut names: Vec<_> = pn.places.values().map(|p| p.name.clone()).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![Some("end".to_string()), Some("start".to_string())]
+        );
+        let start = pn
+            .places
+            .iter()
+            .find(|(_, p)| p.name.as_deref() == Some("start"))
+            .map(|(id, _)| *id)
+            .unwrap();
+        assert_eq!(
+            pn.positions[&start],
+            NodePosition {
+                x: 70.0,
+                y: 170.0,
+                width: Some(40.0),
+                height: Some(40.0)
+            }
+        );
+        assert_eq!(pn.positions.len(), 2);
+        assert_eq!(pn.tool_specific.len(), 3);
+        assert!(pn.tool_specific[&PetriNetElement::Net][0].contains("<bounds>"));
+
+        // Export and import again
+        let mut pnml = Vec::new();
+        export_petri_net_to_pnml(&pn, &mut pnml).unwrap();
+        let re_imported = import_pnml(&mut Reader::from_reader(pnml.as_slice())).unwrap();
+        assert_eq!(re_imported.places, pn.places);
+        assert_eq!(re_imported.transitions, pn.transitions);
+        assert_eq!(re_imported.positions, pn.positions);
+        assert_eq!(re_imported.tool_specific, pn.tool_specific);
+        assert_eq!(re_imported.initial_marking, pn.initial_marking);
+        let mut arcs = pn.arcs.clone();
+        arcs.sort();
+        let mut re_imported_arcs = re_imported.arcs.clone();
+        re_imported_arcs.sort();
+        assert_eq!(re_imported_arcs, arcs);
+    }
+
     #[test]
     fn test_pnml_import() {
         let path = get_test_data_path().join("petri-net").join("pn.pnml");
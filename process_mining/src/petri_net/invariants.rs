@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::petri_net_struct::{ArcType, Marking, PetriNet, PlaceID, TransitionID};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Place invariant (P-semi-flow) of a [`PetriNet`]
+///
+/// Non-negative place weights `y` with `y · C = 0` for the incidence matrix `C`.
+/// Thus, the weighted sum of tokens is the same in all reachable markings.
+///
+pub struct PlaceInvariant {
+    /// Weights of the places in the support of the invariant (places with weight `0` are omitted)
+    pub weights: HashMap<PlaceID, u64>,
+}
+
+impl PlaceInvariant {
+    /// Places with a non-zero weight (sorted)
+    pub fn support(&self) -> Vec<PlaceID> {
+        let mut support: Vec<PlaceID> = self.weights.keys().copied().collect();
+        support.sort();
+        support
+    }
+
+    /// Weighted sum of tokens in the marking, which is constant for all markings reachable from it
+    pub fn weighted_token_sum(&self, marking: &Marking) -> u64 {
+        self.weights
+            .iter()
+            .map(|(p, weight)| weight * marking.get(p).copied().unwrap_or_default())
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Transition invariant (T-semi-flow) of a [`PetriNet`]
+///
+/// Non-negative firing counts `x` with `C · x = 0` for the incidence matrix `C`.
+/// Thus, firing every transition as often as given by the invariant (if possible) reproduces the marking.
+///
+pub struct TransitionInvariant {
+    /// Firing counts of the transitions in the support of the invariant (transitions with count `0` are omitted)
+    pub weights: HashMap<TransitionID, u64>,
+}
+
+impl TransitionInvariant {
+    /// Transitions with a non-zero firing count (sorted)
+    pub fn support(&self) -> Vec<TransitionID> {
+        let mut support: Vec<TransitionID> = self.weights.keys().copied().collect();
+        support.sort();
+        support
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Minimal place and transition invariants of a [`PetriNet`]
+///
+pub struct PetriNetInvariants {
+    /// Minimal place invariants
+    pub place_invariants: Vec<PlaceInvariant>,
+    /// Minimal transition invariants
+    pub transition_invariants: Vec<TransitionInvariant>,
+}
+
+impl PetriNetInvariants {
+    /// Compute all minimal place and transition invariants of the net
+    ///
+    /// See [`compute_place_invariants`] and [`compute_transition_invariants`]
+    pub fn compute(net: &PetriNet) -> Self {
+        Self {
+            place_invariants: compute_place_invariants(net),
+            transition_invariants: compute_transition_invariants(net),
+        }
+    }
+
+    /// Check if every place of the net is in the support of a place invariant
+    ///
+    /// Nets covered by place invariants are (structurally) bounded, i.e., bounded for every initial marking.
+    pub fn covers_all_places(&self, net: &PetriNet) -> bool {
+        net.places.keys().all(|p| {
+            self.place_invariants
+                .iter()
+                .any(|inv| inv.weights.contains_key(&PlaceID(*p)))
+        })
+    }
+
+    /// Check if every transition of the net is in the support of a transition invariant
+    ///
+    /// Covering by transition invariants is necessary for a bounded net to be live.
+    pub fn covers_all_transitions(&self, net: &PetriNet) -> bool {
+        net.transitions.keys().all(|t| {
+            self.transition_invariants
+                .iter()
+                .any(|inv| inv.weights.contains_key(&TransitionID(*t)))
+        })
+    }
+}
+
+///
+/// Compute all minimal place invariants (P-semi-flows) of a [`PetriNet`] using the Farkas algorithm
+///
+/// Minimal invariants have a minimal support (i.e., no other invariant has a support which is a strict subset)
+/// and their weights have no common divisor.
+/// Every place invariant is a non-negative linear combination of the minimal ones.
+///
+/// The invariants are sorted by their support.
+/// _Note_: The number of minimal invariants can grow exponentially with the size of the net.
+///
+pub fn compute_place_invariants(net: &PetriNet) -> Vec<PlaceInvariant> {
+    let (places, _, incidence) = incidence_matrix(net);
+    minimal_semi_flows(&incidence)
+        .into_iter()
+        .map(|flow| PlaceInvariant {
+            weights: to_weights(&places, &flow, PlaceID),
+        })
+        .collect()
+}
+
+///
+/// Compute all minimal transition invariants (T-semi-flows) of a [`PetriNet`] using the Farkas algorithm
+///
+/// See [`compute_place_invariants`] for details.
+///
+pub fn compute_transition_invariants(net: &PetriNet) -> Vec<TransitionInvariant> {
+    let (places, transitions, incidence) = incidence_matrix(net);
+    let transition_rows: Vec<Vec<i64>> = (0..transitions.len())
+        .map(|t| (0..places.len()).map(|p| incidence[p][t]).collect())
+        .collect();
+    minimal_semi_flows(&transition_rows)
+        .into_iter()
+        .map(|flow| TransitionInvariant {
+            weights: to_weights(&transitions, &flow, TransitionID),
+        })
+        .collect()
+}
+
+/// Check if every place of the net is in the support of a place invariant (which implies structural boundedness)
+pub fn is_covered_by_place_invariants(net: &PetriNet) -> bool {
+    PetriNetInvariants {
+        place_invariants: compute_place_invariants(net),
+        transition_invariants: Vec::new(),
+    }
+    .covers_all_places(net)
+}
+
+/// Check if every transition of the net is in the support of a transition invariant
+pub fn is_covered_by_transition_invariants(net: &PetriNet) -> bool {
+    PetriNetInvariants {
+        place_invariants: Vec::new(),
+        transition_invariants: compute_transition_invariants(net),
+    }
+    .covers_all_transitions(net)
+}
+
+/// Sorted place IDs, sorted transition IDs and the incidence matrix (rows: places, columns: transitions)
+fn incidence_matrix(net: &PetriNet) -> (Vec<Uuid>, Vec<Uuid>, Vec<Vec<i64>>) {
+    let mut places: Vec<Uuid> = net.places.keys().copied().collect();
+    places.sort();
+    let mut transitions: Vec<Uuid> = net.transitions.keys().copied().collect();
+    transitions.sort();
+    let place_index: HashMap<Uuid, usize> =
+        places.iter().enumerate().map(|(i, p)| (*p, i)).collect();
+    let transition_index: HashMap<Uuid, usize> = transitions
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (*t, i))
+        .collect();
+    let mut incidence = vec![vec![0; transitions.len()]; places.len()];
+    for arc in &net.arcs {
+        let (p, t, sign) = match arc.from_to {
+            ArcType::PlaceTransition(p, t) => (p, t, -1),
+            ArcType::TransitionPlace(t, p) => (p, t, 1),
+        };
+        if let (Some(p), Some(t)) = (place_index.get(&p), transition_index.get(&t)) {
+            incidence[*p][*t] += sign * arc.weight as i64;
+        }
+    }
+    (places, transitions, incidence)
+}
+
+fn to_weights<T: std::hash::Hash + Eq>(
+    ids: &[Uuid],
+    flow: &[u64],
+    to_id: impl Fn(Uuid) -> T,
+) -> HashMap<T, u64> {
+    ids.iter()
+        .zip(flow)
+        .filter(|(_, weight)| **weight > 0)
+        .map(|(id, weight)| (to_id(*id), *weight))
+        .collect()
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Row of the Farkas algorithm: remaining (not yet eliminated) matrix entries and the combination of original rows
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FarkasRow {
+    matrix: Vec<i64>,
+    combination: Vec<i64>,
+}
+
+impl FarkasRow {
+    fn support(&self) -> Vec<usize> {
+        (0..self.combination.len())
+            .filter(|i| self.combination[*i] != 0)
+            .collect()
+    }
+
+    /// Divide all entries by their greatest common divisor
+    fn normalize(mut self) -> Self {
+        let divisor = self
+            .matrix
+            .iter()
+            .chain(&self.combination)
+            .fold(0, |acc, v| gcd(acc, *v));
+        if divisor > 1 {
+            self.matrix.iter_mut().for_each(|v| *v /= divisor);
+            self.combination.iter_mut().for_each(|v| *v /= divisor);
+        }
+        self
+    }
+}
+
+///
+/// Minimal-support non-negative integer solutions `y` of `y · matrix = 0` (with one matrix row per variable)
+///
+/// Farkas algorithm: Columns are eliminated one after another by combining all pairs of rows with opposite signs
+/// in that column. Rows whose support (in terms of the original rows) strictly contains the support of another row
+/// can not lead to minimal solutions and are removed after every step.
+///
+fn minimal_semi_flows(matrix: &[Vec<i64>]) -> Vec<Vec<u64>> {
+    let num_columns = matrix.first().map(Vec::len).unwrap_or_default();
+    let mut rows: Vec<FarkasRow> = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut combination = vec![0; matrix.len()];
+            combination[i] = 1;
+            FarkasRow {
+                matrix: row.clone(),
+                combination,
+            }
+        })
+        .collect();
+    for column in 0..num_columns {
+        let (mut next, non_zero): (Vec<FarkasRow>, Vec<FarkasRow>) =
+            rows.into_iter().partition(|row| row.matrix[column] == 0);
+        for (i, a) in non_zero.iter().enumerate() {
+            for b in &non_zero[i + 1..] {
+                if a.matrix[column].signum() == b.matrix[column].signum() {
+                    continue;
+                }
+                let (factor_a, factor_b) = (b.matrix[column].abs(), a.matrix[column].abs());
+                let combine = |x: &[i64], y: &[i64]| -> Vec<i64> {
+                    x.iter()
+                        .zip(y)
+                        .map(|(x, y)| factor_a * x + factor_b * y)
+                        .collect()
+                };
+                next.push(
+                    FarkasRow {
+                        matrix: combine(&a.matrix, &b.matrix),
+                        combination: combine(&a.combination, &b.combination),
+                    }
+                    .normalize(),
+                );
+            }
+        }
+        rows = remove_non_minimal(next);
+    }
+    let mut flows: Vec<Vec<u64>> = rows
+        .into_iter()
+        .map(|row| row.combination.into_iter().map(|v| v as u64).collect())
+        .collect();
+    flows.sort_by_key(|flow| {
+        (0..flow.len())
+            .filter(|i| flow[*i] > 0)
+            .collect::<Vec<usize>>()
+    });
+    flows
+}
+
+/// Remove duplicate rows and rows whose support strictly contains the support of another row
+fn remove_non_minimal(rows: Vec<FarkasRow>) -> Vec<FarkasRow> {
+    let supports: Vec<Vec<usize>> = rows.iter().map(FarkasRow::support).collect();
+    let is_strict_subset = |a: &[usize], b: &[usize]| {
+        a.len() < b.len() && a.iter().all(|i| b.binary_search(i).is_ok())
+    };
+    rows.iter()
+        .enumerate()
+        .filter(|(i, row)| {
+            !rows[..*i].contains(row)
+                && !supports
+                    .iter()
+                    .any(|other| is_strict_subset(other, &supports[*i]))
+        })
+        .map(|(_, row)| row.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance::token_replay::tests::choice_net;
+
+    use super::*;
+
+    #[test]
+    fn invariants_of_choice_net() {
+        let mut net = choice_net();
+        let invariants = PetriNetInvariants::compute(&net);
+        // All places form a single state machine component
+        assert_eq!(invariants.place_invariants.len(), 1);
+        assert!(invariants.place_invariants[0]
+            .weights
+            .values()
+            .all(|w| *w == 1));
+        assert!(invariants.covers_all_places(&net));
+        assert!(invariants.transition_invariants.is_empty());
+        assert!(!invariants.covers_all_transitions(&net));
+
+        // Connecting the final to the initial place allows to repeat every path
+        let initial = *net.initial_marking.as_ref().unwrap().keys().next().unwrap();
+        let last = *net.final_markings.as_ref().unwrap()[0]
+            .keys()
+            .next()
+            .unwrap();
+        let reset = net.add_transition(None, None);
+        net.add_arc(ArcType::place_to_transition(last, reset), None);
+        net.add_arc(ArcType::transition_to_place(reset, initial), None);
+        let transition_invariants = compute_transition_invariants(&net);
+        // Choice between `b` and `c`, and between `d` and its silent skip
+        assert_eq!(transition_invariants.len(), 4);
+        assert!(transition_invariants
+            .iter()
+            .all(|inv| inv.weights.len() == 4 && inv.weights.contains_key(&reset)));
+        assert!(is_covered_by_transition_invariants(&net));
+        assert!(is_covered_by_place_invariants(&net));
+
+        let json = serde_json::to_string(&PetriNetInvariants::compute(&net)).unwrap();
+        let parsed: PetriNetInvariants = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, PetriNetInvariants::compute(&net));
+    }
+
+    #[test]
+    fn weighted_invariants() {
+        let mut net = PetriNet::new();
+        let p = net.add_place(None);
+        let q = net.add_place(None);
+        let unbounded = net.add_place(None);
+        let split = net.add_transition(Some("split".to_string()), None);
+        let join = net.add_transition(Some("join".to_string()), None);
+        let produce = net.add_transition(Some("produce".to_string()), None);
+        net.add_arc(ArcType::place_to_transition(p, split), None);
+        net.add_arc(ArcType::transition_to_place(split, q), Some(2));
+        net.add_arc(ArcType::place_to_transition(q, join), Some(2));
+        net.add_arc(ArcType::transition_to_place(join, p), None);
+        net.add_arc(ArcType::place_to_transition(p, produce), None);
+        net.add_arc(ArcType::transition_to_place(produce, p), None);
+        net.add_arc(ArcType::transition_to_place(produce, unbounded), None);
+
+        let place_invariants = compute_place_invariants(&net);
+        assert_eq!(place_invariants.len(), 1);
+        assert_eq!(place_invariants[0].weights, HashMap::from([(p, 2), (q, 1)]));
+        let mut support = vec![p, q];
+        support.sort();
+        assert_eq!(place_invariants[0].support(), support);
+        assert_eq!(
+            place_invariants[0].weighted_token_sum(&Marking::from([(p, 1), (unbounded, 5)])),
+            2
+        );
+        assert!(!is_covered_by_place_invariants(&net));
+
+        let transition_invariants = compute_transition_invariants(&net);
+        assert_eq!(transition_invariants.len(), 1);
+        assert_eq!(
+            transition_invariants[0].weights,
+            HashMap::from([(split, 1), (join, 1)])
+        );
+        assert!(!is_covered_by_transition_invariants(&net));
+    }
+}
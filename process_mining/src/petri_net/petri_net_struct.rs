@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -7,6 +8,9 @@ use super::import_pnml::PNMLParseError;
 /// Place in a Petri net
 pub struct Place {
     id: Uuid,
+    /// Place name (e.g., as imported from PNML)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Hash, Eq, PartialOrd, Ord)]
@@ -102,6 +106,33 @@ impl TransitionID {
 /// Marking of a Petri net: Assigning [`PlaceID`]s to a number of tokens
 pub type Marking = HashMap<PlaceID, u64>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+/// Position (and optional size) of a place or transition in a graphical representation of a Petri net
+pub struct NodePosition {
+    /// X-coordinate of the center of the node
+    pub x: f64,
+    /// Y-coordinate of the center of the node
+    pub y: f64,
+    /// Width of the node (if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    /// Height of the node (if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+/// Element of a [`PetriNet`] to which tool-specific data can be attached
+pub enum PetriNetElement {
+    /// The Petri net itself
+    Net,
+    /// Place or transition (with ID)
+    Node(Uuid),
+    /// Arc (with source and target)
+    Arc(ArcType),
+}
+
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone)]
 ///
 /// A Petri net of [`Place`]s and [`Transition`]s
@@ -118,6 +149,13 @@ pub struct PetriNet {
     pub initial_marking: Option<Marking>,
     /// Final markings (any of them are accepted as a final marking)
     pub final_markings: Option<Vec<Marking>>,
+    /// Positions of places and transitions (by their ID), e.g., as imported from PNML
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub positions: HashMap<Uuid, NodePosition>,
+    /// Opaque tool-specific data (e.g., raw XML of PNML `<toolspecific>` elements), which is preserved but not interpreted
+    #[serde_as(as = "Vec<(_, _)>")]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_specific: HashMap<PetriNetElement, Vec<String>>,
 }
 
 impl Default for PetriNet {
@@ -134,6 +172,8 @@ impl PetriNet {
             arcs: Vec::new(),
             initial_marking: None,
             final_markings: None,
+            positions: HashMap::new(),
+            tool_specific: HashMap::new(),
         }
     }
     /// Serialize to JSON string
@@ -145,7 +185,10 @@ impl PetriNet {
     /// If no ID is passed, a new UUID will be generated
     pub fn add_place(&mut self, place_id: Option<Uuid>) -> PlaceID {
         let place_id = place_id.unwrap_or(Uuid::new_v4());
-        let place = Place { id: place_id };
+        let place = Place {
+            id: place_id,
+            name: None,
+        };
         self.places.insert(place_id, place);
         PlaceID(place_id)
     }
@@ -188,6 +231,12 @@ impl PetriNet {
         }
         self.transitions.remove(id);
         self.arcs.retain(|arc| !arc.from_to.contains(id));
+        self.positions.remove(id);
+        self.tool_specific.retain(|element, _| match element {
+            PetriNetElement::Net => true,
+            PetriNetElement::Node(node) => node != id,
+            PetriNetElement::Arc(arc) => !arc.contains(id),
+        });
     }
 
     /// Remove a Place from the Petri net
@@ -316,6 +365,16 @@ impl PetriNet {
         super::image_export::export_petri_net_image_svg(self, path)
     }
 
+    #[cfg(feature = "svg-export")]
+    /// Render Petri net as an SVG image (without requiring Graphviz)
+    ///
+    /// _Note_: This is an export method for __visualizing__ the Petri net.
+    ///
+    /// Only available with the `svg-export` feature.
+    pub fn to_svg(&self, options: &crate::SVGExportOptions) -> String {
+        crate::visualization::svg_export::petri_net_to_svg(self, options)
+    }
+
     /// Export Petri net to a PNML file
     ///
     /// The PNML file is written to the specified filepath
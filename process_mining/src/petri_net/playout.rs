@@ -0,0 +1,871 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::event_log::{
+    constants::{TIMESTAMP_NAME, TRACE_ID_NAME},
+    xes_extensions::{standard_log_extensions, CONCEPT_EXTENSION_URI, TIME_EXTENSION_URI},
+    AttributeValue, Event, EventLog, Trace, XESEditableAttribute,
+};
+
+use super::{
+    petri_net_struct::{ArcType, Marking, PetriNet, PlaceID, TransitionID},
+    stochastic::{DelayDistribution, StochasticPetriNet, StochasticTransition},
+};
+
+#[derive(Debug, Clone)]
+///
+/// Options for [`playout_petri_net`]
+///
+pub struct PlayoutOptions {
+    /// Number of simulated traces
+    pub num_traces: usize,
+    /// Maximal number of transition firings per trace (including silent transitions)
+    pub max_trace_length: usize,
+    /// Relative weights for randomly choosing between enabled transitions
+    ///
+    /// Transitions without a weight have weight `1.0`. Transitions with weight `0.0` never fire.
+    pub transition_weights: HashMap<TransitionID, f64>,
+    /// Mean delay (in seconds) of a transition, i.e., the mean time between the previous event and its event
+    ///
+    /// Delays are exponentially distributed. Transitions without a mean delay use [`PlayoutOptions::default_mean_delay`].
+    pub mean_delays: HashMap<TransitionID, f64>,
+    /// Mean delay (in seconds) of transitions without an entry in [`PlayoutOptions::mean_delays`]
+    pub default_mean_delay: f64,
+    /// Mean time (in seconds) between the start of two consecutive traces (exponentially distributed)
+    pub mean_case_arrival_time: f64,
+    /// Start time of the first trace
+    pub start_time: DateTime<FixedOffset>,
+    /// Seed of the random number generator (for reproducible event logs)
+    ///
+    /// If `None`, the random number generator is seeded by the operating system.
+    pub seed: Option<u64>,
+    /// Also include traces which did not reach a final marking (because of a deadlock or the maximal trace length)
+    pub keep_incomplete_traces: bool,
+}
+
+impl Default for PlayoutOptions {
+    fn default() -> Self {
+        Self {
+            num_traces: 1000,
+            max_trace_length: 100,
+            transition_weights: HashMap::new(),
+            mean_delays: HashMap::new(),
+            default_mean_delay: 3600.0,
+            mean_case_arrival_time: 3600.0,
+            start_time: Utc
+                .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+                .unwrap()
+                .fixed_offset(),
+            seed: None,
+            keep_incomplete_traces: false,
+        }
+    }
+}
+
+/// Transition with its input and output places (and weights) and simulation parameters
+struct PlayoutTransition {
+    label: Option<String>,
+    pre: Vec<(PlaceID, u64)>,
+    post: Vec<(PlaceID, u64)>,
+    weight: f64,
+    /// Priority of immediate transitions (`None` for timed transitions)
+    priority: Option<u32>,
+    delay: DelayDistribution,
+}
+
+impl PlayoutTransition {
+    fn new(
+        net: &PetriNet,
+        id: TransitionID,
+        weight: f64,
+        priority: Option<u32>,
+        delay: DelayDistribution,
+    ) -> Self {
+        let arcs_with = |from_place: bool| -> Vec<(PlaceID, u64)> {
+            net.arcs
+                .iter()
+                .filter_map(|arc| match arc.from_to {
+                    ArcType::PlaceTransition(p, to) if from_place && to == id.0 => {
+                        Some((PlaceID(p), arc.weight as u64))
+                    }
+                    ArcType::TransitionPlace(from, p) if !from_place && from == id.0 => {
+                        Some((PlaceID(p), arc.weight as u64))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        Self {
+            label: net.transitions[&id.0].label.clone(),
+            pre: arcs_with(true),
+            post: arcs_with(false),
+            weight,
+            priority,
+            delay,
+        }
+    }
+
+    fn is_enabled(&self, marking: &Marking) -> bool {
+        self.weight > 0.0
+            && self
+                .pre
+                .iter()
+                .all(|(p, weight)| marking.get(p).is_some_and(|tokens| tokens >= weight))
+    }
+
+    fn fire(&self, marking: &mut Marking) {
+        for (p, weight) in &self.pre {
+            if let Some(tokens) = marking.get_mut(p) {
+                *tokens -= weight;
+                if *tokens == 0 {
+                    marking.remove(p);
+                }
+            }
+        }
+        for (p, weight) in &self.post {
+            *marking.entry(*p).or_default() += weight;
+        }
+    }
+}
+
+/// Sample a duration from the given delay distribution (in seconds)
+fn sample_duration(rng: &mut StdRng, distribution: &DelayDistribution) -> Duration {
+    Duration::milliseconds((distribution.sample(rng) * 1000.0).round() as i64)
+}
+
+/// Duration in seconds
+fn seconds(duration: Duration) -> f64 {
+    duration.num_milliseconds() as f64 / 1000.0
+}
+
+/// Choose one of the candidate transitions (indices) with a probability proportional to its weight
+fn choose_weighted(
+    rng: &mut StdRng,
+    transitions: &[PlayoutTransition],
+    candidates: &[usize],
+) -> usize {
+    let total_weight: f64 = candidates.iter().map(|t| transitions[*t].weight).sum();
+    let mut remaining = rng.random::<f64>() * total_weight;
+    candidates
+        .iter()
+        .copied()
+        .find(|t| {
+            remaining -= transitions[*t].weight;
+            remaining < 0.0
+        })
+        .unwrap_or(candidates[candidates.len() - 1])
+}
+
+/// Marking without places with zero tokens (for comparing markings)
+fn without_empty_places(marking: &Marking) -> Marking {
+    marking
+        .iter()
+        .filter(|(_, tokens)| **tokens > 0)
+        .map(|(p, tokens)| (*p, *tokens))
+        .collect()
+}
+
+/// Initial marking, final markings (without empty places) and random number generator for simulating a net
+fn simulation_setup(net: &PetriNet, seed: Option<u64>) -> (Marking, Option<Vec<Marking>>, StdRng) {
+    let initial_marking = net
+        .initial_marking
+        .as_ref()
+        .map(without_empty_places)
+        .unwrap_or_default();
+    let final_markings: Option<Vec<Marking>> = net
+        .final_markings
+        .as_ref()
+        .map(|markings| markings.iter().map(without_empty_places).collect());
+    let rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    (initial_marking, final_markings, rng)
+}
+
+/// Empty event log using the Concept and Time extensions
+fn simulated_event_log() -> EventLog {
+    let mut log = EventLog::new();
+    log.extensions = Some(standard_log_extensions(&[
+        CONCEPT_EXTENSION_URI,
+        TIME_EXTENSION_URI,
+    ]));
+    log
+}
+
+/// Empty trace named by its index
+fn simulated_trace(trace_index: usize) -> Trace {
+    let mut trace = Trace::new();
+    trace.attributes.add_to_attributes(
+        TRACE_ID_NAME.to_string(),
+        AttributeValue::String(trace_index.to_string()),
+    );
+    trace
+}
+
+/// Add an event for the fired transition (if it is labeled) at the given time
+fn add_event(trace: &mut Trace, transition: &PlayoutTransition, time: DateTime<FixedOffset>) {
+    if let Some(label) = &transition.label {
+        let mut event = Event::new(label.clone());
+        event
+            .attributes
+            .add_to_attributes(TIMESTAMP_NAME.to_string(), AttributeValue::Date(time));
+        trace.events.push(event);
+    }
+}
+
+///
+/// Simulate an [`EventLog`] by randomly playing out a [`PetriNet`]
+///
+/// Every trace starts in the initial marking (or the empty marking, if the net has none).
+/// Enabled transitions are chosen randomly (according to the [`PlayoutOptions::transition_weights`]) and fired,
+/// until one of the final markings is reached.
+/// If the net has no final markings, transitions are fired until no transition is enabled anymore.
+///
+/// Every fired labeled transition results in an event with the transition label as `concept:name`
+/// and a synthetic `time:timestamp` (based on exponentially distributed delays).
+/// Silent transitions fire without delay and are not included in the event log.
+/// Traces are named by their index (`concept:name`).
+///
+/// Traces which do not reach a final marking are only included if [`PlayoutOptions::keep_incomplete_traces`] is set.
+/// Thus, the resulting event log can contain fewer than [`PlayoutOptions::num_traces`] traces.
+///
+/// For simulating nets with other delay distributions, immediate transitions or priorities,
+/// see [`simulate_stochastic_petri_net`].
+///
+/// __Requires the `simulation` feature to be enabled__
+///
+pub fn playout_petri_net(net: &PetriNet, options: &PlayoutOptions) -> EventLog {
+    let mut transition_ids: Vec<TransitionID> = net.transitions.values().map(Into::into).collect();
+    transition_ids.sort();
+    let transitions: Vec<PlayoutTransition> = transition_ids
+        .iter()
+        .map(|t| {
+            let mean_delay = options
+                .mean_delays
+                .get(t)
+                .copied()
+                .unwrap_or(options.default_mean_delay);
+            PlayoutTransition::new(
+                net,
+                *t,
+                options.transition_weights.get(t).copied().unwrap_or(1.0),
+                None,
+                DelayDistribution::Exponential { mean: mean_delay },
+            )
+        })
+        .collect();
+    let (initial_marking, final_markings, mut rng) = simulation_setup(net, options.seed);
+    let case_arrival = DelayDistribution::Exponential {
+        mean: options.mean_case_arrival_time,
+    };
+
+    let mut log = simulated_event_log();
+    let mut case_start = options.start_time;
+    for trace_index in 0..options.num_traces {
+        if trace_index > 0 {
+            case_start += sample_duration(&mut rng, &case_arrival);
+        }
+        let mut time = case_start;
+        let mut marking = initial_marking.clone();
+        let mut trace = simulated_trace(trace_index);
+        let mut fired = 0;
+        let is_complete = loop {
+            if final_markings
+                .as_ref()
+                .is_some_and(|markings| markings.contains(&marking))
+            {
+                break true;
+            }
+            let enabled: Vec<usize> = (0..transitions.len())
+                .filter(|t| transitions[*t].is_enabled(&marking))
+                .collect();
+            if enabled.is_empty() {
+                break final_markings.is_none();
+            }
+            if fired == options.max_trace_length {
+                break false;
+            }
+            let transition = &transitions[choose_weighted(&mut rng, &transitions, &enabled)];
+            transition.fire(&mut marking);
+            fired += 1;
+            if transition.label.is_some() {
+                time += sample_duration(&mut rng, &transition.delay);
+                add_event(&mut trace, transition, time);
+            }
+        };
+        if is_complete || options.keep_incomplete_traces {
+            log.traces.push(trace);
+        }
+    }
+    log
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// Policy for choosing which enabled timed transition of a [`StochasticPetriNet`] fires next
+///
+pub enum ExecutionPolicy {
+    ///
+    /// Choose an enabled timed transition randomly (proportional to its weight) and then sample its delay
+    ///
+    /// This is the policy the nets fitted by [`fit_stochastic_petri_net`](super::stochastic::fit_stochastic_petri_net) are intended for.
+    #[default]
+    GlobalPreselection,
+    ///
+    /// All enabled timed transitions race against each other: the transition with the earliest sampled firing time fires
+    ///
+    /// The firing time of a transition is sampled when it becomes enabled and kept as long as it stays enabled (enabling memory).
+    RaceEnablingMemory,
+}
+
+#[derive(Debug, Clone)]
+///
+/// Options for [`simulate_stochastic_petri_net`]
+///
+pub struct StochasticSimulationOptions {
+    /// Number of simulated cases
+    pub num_cases: usize,
+    /// Maximal number of transition firings per case (including silent and immediate transitions)
+    pub max_trace_length: usize,
+    /// Distribution of the time (in seconds) between the start of two consecutive cases
+    pub case_arrival: DelayDistribution,
+    /// Start time of the first case
+    pub start_time: DateTime<FixedOffset>,
+    /// Policy for choosing the next timed transition
+    pub execution_policy: ExecutionPolicy,
+    /// Seed of the random number generator (for reproducible simulations)
+    ///
+    /// If `None`, the random number generator is seeded by the operating system.
+    pub seed: Option<u64>,
+    /// Also include traces which did not reach a final marking in the event log
+    pub keep_incomplete_traces: bool,
+}
+
+impl Default for StochasticSimulationOptions {
+    fn default() -> Self {
+        let playout_defaults = PlayoutOptions::default();
+        Self {
+            num_cases: playout_defaults.num_traces,
+            max_trace_length: playout_defaults.max_trace_length,
+            case_arrival: DelayDistribution::Exponential {
+                mean: playout_defaults.mean_case_arrival_time,
+            },
+            start_time: playout_defaults.start_time,
+            execution_policy: ExecutionPolicy::default(),
+            seed: None,
+            keep_incomplete_traces: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Performance KPIs of a simulation of a [`StochasticPetriNet`]
+///
+/// All durations are in seconds.
+///
+pub struct SimulationStatistics {
+    /// Number of simulated cases
+    pub num_cases: usize,
+    /// Number of cases which reached a final marking
+    pub num_completed_cases: usize,
+    /// Cycle times (i.e., time between the start of a case and its last transition firing) of all completed cases
+    pub cycle_times: Vec<f64>,
+    /// Number of times each transition was fired (in all cases)
+    pub transition_firings: HashMap<TransitionID, u64>,
+    /// Average number of tokens in each place while a case is running (weighted by time)
+    pub mean_tokens: HashMap<PlaceID, f64>,
+    /// Mean delay between enabling and firing of each transition (in all cases)
+    pub mean_transition_delays: HashMap<TransitionID, f64>,
+    /// Completed cases per hour (between the start of the first case and the completion of the last completed case)
+    ///
+    /// `None` if no case was completed (or all cases completed immediately).
+    pub throughput: Option<f64>,
+}
+
+impl SimulationStatistics {
+    /// Mean cycle time of all completed cases (`None` if no case was completed)
+    pub fn mean_cycle_time(&self) -> Option<f64> {
+        if self.cycle_times.is_empty() {
+            return None;
+        }
+        Some(self.cycle_times.iter().sum::<f64>() / self.cycle_times.len() as f64)
+    }
+
+    /// Median cycle time of all completed cases (`None` if no case was completed)
+    pub fn median_cycle_time(&self) -> Option<f64> {
+        if self.cycle_times.is_empty() {
+            return None;
+        }
+        let mut sorted = self.cycle_times.clone();
+        sorted.sort_by(f64::total_cmp);
+        let mid = sorted.len() / 2;
+        Some(if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+///
+/// Result of [`simulate_stochastic_petri_net`]: the simulated event log and performance KPIs
+///
+pub struct StochasticSimulationResult {
+    /// Simulated event log
+    pub log: EventLog,
+    /// Performance KPIs of all simulated cases
+    pub statistics: SimulationStatistics,
+}
+
+///
+/// Discrete-event simulation of a [`StochasticPetriNet`] (GSPN), producing a timestamped [`EventLog`] and performance KPIs
+///
+/// Cases arrive according to [`StochasticSimulationOptions::case_arrival`] and start in the initial marking
+/// (or the empty marking, if the net has none).
+/// In every step, enabled immediate transitions fire first (without delay): among those with the highest priority,
+/// one is chosen randomly with a probability proportional to its weight.
+/// If no immediate transition is enabled, a timed transition fires after its delay,
+/// chosen according to the [`StochasticSimulationOptions::execution_policy`].
+/// A case ends as soon as a final marking is reached (or, if the net has no final markings, no transition is enabled anymore).
+///
+/// As in [`playout_petri_net`], fired labeled transitions result in events with a `concept:name` and `time:timestamp`
+/// and traces are named by their index.
+///
+/// __Requires the `simulation` feature to be enabled__
+///
+pub fn simulate_stochastic_petri_net(
+    net: &StochasticPetriNet,
+    options: &StochasticSimulationOptions,
+) -> StochasticSimulationResult {
+    let mut transition_ids: Vec<TransitionID> =
+        net.net.transitions.values().map(Into::into).collect();
+    transition_ids.sort();
+    let transitions: Vec<PlayoutTransition> = transition_ids
+        .iter()
+        .map(|t| {
+            let stochastic = net.get_transition(t);
+            let weight = stochastic.weight();
+            match stochastic {
+                StochasticTransition::Immediate { priority, .. } => PlayoutTransition::new(
+                    &net.net,
+                    *t,
+                    weight,
+                    Some(priority),
+                    DelayDistribution::Deterministic { value: 0.0 },
+                ),
+                StochasticTransition::Timed { distribution, .. } => {
+                    PlayoutTransition::new(&net.net, *t, weight, None, distribution)
+                }
+            }
+        })
+        .collect();
+    let (initial_marking, final_markings, mut rng) = simulation_setup(&net.net, options.seed);
+
+    let mut log = simulated_event_log();
+    let mut firings = vec![0_u64; transitions.len()];
+    let mut delay_sums = vec![0.0; transitions.len()];
+    let mut token_seconds: HashMap<PlaceID, f64> = HashMap::new();
+    let mut total_seconds = 0.0;
+    let mut cycle_times = Vec::new();
+    let mut last_completion: Option<DateTime<FixedOffset>> = None;
+    let mut case_start = options.start_time;
+    for case_index in 0..options.num_cases {
+        if case_index > 0 {
+            case_start += sample_duration(&mut rng, &options.case_arrival);
+        }
+        let mut time = case_start;
+        let mut marking = initial_marking.clone();
+        let mut trace = simulated_trace(case_index);
+        // Time since which each transition is enabled and (for races) its sampled firing time
+        let mut enabled_since: HashMap<usize, DateTime<FixedOffset>> = HashMap::new();
+        let mut firing_times: HashMap<usize, DateTime<FixedOffset>> = HashMap::new();
+        let mut fired = 0;
+        let is_complete = loop {
+            if final_markings
+                .as_ref()
+                .is_some_and(|markings| markings.contains(&marking))
+            {
+                break true;
+            }
+            let enabled: Vec<usize> = (0..transitions.len())
+                .filter(|t| transitions[*t].is_enabled(&marking))
+                .collect();
+            enabled_since.retain(|t, _| enabled.contains(t));
+            firing_times.retain(|t, _| enabled.contains(t));
+            for t in &enabled {
+                enabled_since.entry(*t).or_insert(time);
+            }
+            if enabled.is_empty() {
+                break final_markings.is_none();
+            }
+            if fired == options.max_trace_length {
+                break false;
+            }
+            let highest_priority = enabled
+                .iter()
+                .filter_map(|t| transitions[*t].priority)
+                .max();
+            let (index, firing_time) = if let Some(priority) = highest_priority {
+                let candidates: Vec<usize> = enabled
+                    .iter()
+                    .copied()
+                    .filter(|t| transitions[*t].priority == Some(priority))
+                    .collect();
+                (choose_weighted(&mut rng, &transitions, &candidates), time)
+            } else {
+                match options.execution_policy {
+                    ExecutionPolicy::GlobalPreselection => {
+                        let t = choose_weighted(&mut rng, &transitions, &enabled);
+                        (t, time + sample_duration(&mut rng, &transitions[t].delay))
+                    }
+                    ExecutionPolicy::RaceEnablingMemory => {
+                        for t in &enabled {
+                            if !firing_times.contains_key(t) {
+                                let firing_time =
+                                    time + sample_duration(&mut rng, &transitions[*t].delay);
+                                firing_times.insert(*t, firing_time);
+                            }
+                        }
+                        enabled
+                            .iter()
+                            .map(|t| (*t, firing_times[t]))
+                            .min_by_key(|(_, firing_time)| *firing_time)
+                            .unwrap()
+                    }
+                }
+            };
+            let elapsed = seconds(firing_time - time);
+            for (p, tokens) in &marking {
+                *token_seconds.entry(*p).or_default() += *tokens as f64 * elapsed;
+            }
+            time = firing_time;
+            let transition = &transitions[index];
+            transition.fire(&mut marking);
+            fired += 1;
+            firings[index] += 1;
+            delay_sums[index] += seconds(time - enabled_since[&index]);
+            enabled_since.remove(&index);
+            firing_times.remove(&index);
+            add_event(&mut trace, transition, time);
+        };
+        total_seconds += seconds(time - case_start);
+        if is_complete {
+            cycle_times.push(seconds(time - case_start));
+            last_completion = Some(last_completion.map_or(time, |last| last.max(time)));
+        }
+        if is_complete || options.keep_incomplete_traces {
+            log.traces.push(trace);
+        }
+    }
+
+    let throughput = last_completion.and_then(|last| {
+        let hours = seconds(last - options.start_time) / 3600.0;
+        (hours > 0.0).then(|| cycle_times.len() as f64 / hours)
+    });
+    let statistics = SimulationStatistics {
+        num_cases: options.num_cases,
+        num_completed_cases: cycle_times.len(),
+        transition_firings: transition_ids
+            .iter()
+            .copied()
+            .zip(firings.clone())
+            .collect(),
+        mean_tokens: net
+            .net
+            .places
+            .keys()
+            .map(|p| {
+                let p = PlaceID(*p);
+                let tokens = token_seconds.get(&p).copied().unwrap_or(0.0);
+                (
+                    p,
+                    if total_seconds > 0.0 {
+                        tokens / total_seconds
+                    } else {
+                        0.0
+                    },
+                )
+            })
+            .collect(),
+        mean_transition_delays: transition_ids
+            .iter()
+            .zip(firings.iter().zip(delay_sums))
+            .filter(|(_, (n, _))| **n > 0)
+            .map(|(t, (n, sum))| (*t, sum / *n as f64))
+            .collect(),
+        cycle_times,
+        throughput,
+    };
+    StochasticSimulationResult { log, statistics }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        conformance::token_replay::tests::choice_net,
+        event_log::{
+            constants::ACTIVITY_NAME, export_xes::export_xes_event_log,
+            import_xes::XESImportOptions,
+        },
+        import_xes_slice,
+        petri_net::stochastic::{fit_stochastic_petri_net, FittingOptions},
+    };
+
+    use super::*;
+
+    /// Activities (i.e., `concept:name` values) of the events of a simulated trace
+    fn trace_activities(trace: &Trace) -> Vec<&str> {
+        trace
+            .events
+            .iter()
+            .filter_map(|e| e.attributes.get_by_key(ACTIVITY_NAME))
+            .filter_map(|a| a.value.try_as_string().map(String::as_str))
+            .collect()
+    }
+
+    #[test]
+    fn playout_choice_net() {
+        let net = choice_net();
+        let options = PlayoutOptions {
+            num_traces: 50,
+            seed: Some(42),
+            ..Default::default()
+        };
+        let log = playout_petri_net(&net, &options);
+        assert_eq!(log.traces.len(), 50);
+        let valid = [
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["a", "b", "d"],
+            vec!["a", "c", "d"],
+        ];
+        for trace in &log.traces {
+            assert!(valid.contains(&trace_activities(trace)));
+            let timestamps: Vec<_> = trace
+                .events
+                .iter()
+                .map(|e| {
+                    *e.attributes
+                        .get_by_key(TIMESTAMP_NAME)
+                        .unwrap()
+                        .value
+                        .try_as_date()
+                        .unwrap()
+                })
+                .collect();
+            assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+        }
+        // All variants are simulated
+        for variant in &valid {
+            assert!(log.traces.iter().any(|t| trace_activities(t) == *variant));
+        }
+        // Simulation is reproducible with the same seed
+        assert_eq!(playout_petri_net(&net, &options), log);
+
+        let mut xes = Vec::new();
+        export_xes_event_log(&mut xes, &log).unwrap();
+        let imported = import_xes_slice(&xes, false, XESImportOptions::default()).unwrap();
+        assert_eq!(imported.traces.len(), 50);
+    }
+
+    #[test]
+    fn playout_weights_and_max_length() {
+        let net = choice_net();
+        let c = net
+            .transitions
+            .values()
+            .find(|t| t.label.as_deref() == Some("c"))
+            .map(TransitionID::from)
+            .unwrap();
+        let log = playout_petri_net(
+            &net,
+            &PlayoutOptions {
+                num_traces: 20,
+                seed: Some(1),
+                transition_weights: HashMap::from([(c, 0.0)]),
+                ..Default::default()
+            },
+        );
+        assert!(log
+            .traces
+            .iter()
+            .all(|t| !trace_activities(t).contains(&"c")));
+
+        // Endless loop of `a`, as the final marking can not be reached
+        let mut net = PetriNet::new();
+        let p = net.add_place(None);
+        let end = net.add_place(None);
+        let a = net.add_transition(Some("a".to_string()), None);
+        net.add_arc(ArcType::place_to_transition(p, a), None);
+        net.add_arc(ArcType::transition_to_place(a, p), None);
+        net.initial_marking = Some(Marking::from([(p, 1)]));
+        net.final_markings = Some(vec![Marking::from([(end, 1)])]);
+        let mut options = PlayoutOptions {
+            num_traces: 3,
+            max_trace_length: 5,
+            seed: Some(7),
+            ..Default::default()
+        };
+        assert!(playout_petri_net(&net, &options).traces.is_empty());
+        options.keep_incomplete_traces = true;
+        let log = playout_petri_net(&net, &options);
+        assert_eq!(log.traces.len(), 3);
+        assert!(log.traces.iter().all(|t| t.events.len() == 5));
+    }
+
+    /// Timestamps of the events of a simulated trace in seconds since the passed start time
+    fn trace_seconds(trace: &Trace, start: DateTime<FixedOffset>) -> Vec<f64> {
+        trace
+            .events
+            .iter()
+            .filter_map(|e| e.attributes.get_by_key(TIMESTAMP_NAME))
+            .filter_map(|a| a.value.try_as_date())
+            .map(|t| seconds(*t - start))
+            .collect()
+    }
+
+    #[test]
+    fn simulate_gspn_priorities_and_distributions() {
+        // a (timed), then immediate i (priority 2) or j (priority 1), then b (timed)
+        let mut net = PetriNet::new();
+        let places: Vec<PlaceID> = (0..4).map(|_| net.add_place(None)).collect();
+        let mut add = |label: &str, from: usize, to: usize| {
+            let t = net.add_transition(Some(label.to_string()), None);
+            net.add_arc(ArcType::place_to_transition(places[from], t), None);
+            net.add_arc(ArcType::transition_to_place(t, places[to]), None);
+            t
+        };
+        let a = add("a", 0, 1);
+        let i = add("i", 1, 2);
+        let j = add("j", 1, 2);
+        let b = add("b", 2, 3);
+        net.initial_marking = Some(Marking::from([(places[0], 1)]));
+        net.final_markings = Some(vec![Marking::from([(places[3], 1)])]);
+        let mut gspn = StochasticPetriNet::new(net);
+        gspn.set_transition(
+            a,
+            StochasticTransition::Timed {
+                distribution: DelayDistribution::Deterministic { value: 60.0 },
+                weight: 1.0,
+            },
+        );
+        gspn.set_transition(
+            i,
+            StochasticTransition::Immediate {
+                weight: 1.0,
+                priority: 2,
+            },
+        );
+        gspn.set_transition(
+            j,
+            StochasticTransition::Immediate {
+                weight: 100.0,
+                priority: 1,
+            },
+        );
+        gspn.set_transition(
+            b,
+            StochasticTransition::Timed {
+                distribution: DelayDistribution::Uniform {
+                    min: 10.0,
+                    max: 20.0,
+                },
+                weight: 1.0,
+            },
+        );
+        let options = StochasticSimulationOptions {
+            num_cases: 20,
+            seed: Some(3),
+            ..Default::default()
+        };
+        let result = simulate_stochastic_petri_net(&gspn, &options);
+        assert_eq!(result.log.traces.len(), 20);
+        for trace in &result.log.traces {
+            assert_eq!(trace_activities(trace), vec!["a", "i", "b"]);
+            let start = *trace.events[0]
+                .attributes
+                .get_by_key(TIMESTAMP_NAME)
+                .unwrap()
+                .value
+                .try_as_date()
+                .unwrap();
+            let times = trace_seconds(trace, start - Duration::seconds(60));
+            assert_eq!(times[..2], [60.0, 60.0]);
+            assert!((70.0..=80.0).contains(&times[2]));
+        }
+
+        let statistics = &result.statistics;
+        assert_eq!(statistics.num_completed_cases, 20);
+        assert_eq!(statistics.transition_firings[&i], 20);
+        assert_eq!(statistics.transition_firings[&j], 0);
+        assert_eq!(statistics.mean_transition_delays[&a], 60.0);
+        assert_eq!(statistics.mean_transition_delays[&i], 0.0);
+        assert!(!statistics.mean_transition_delays.contains_key(&j));
+        let mean_cycle_time = statistics.mean_cycle_time().unwrap();
+        assert!((70.0..=80.0).contains(&mean_cycle_time));
+        assert!((70.0..=80.0).contains(&statistics.median_cycle_time().unwrap()));
+        // The token is in the first place for 60 of 70-80 seconds
+        let first_place = statistics.mean_tokens[&places[0]];
+        assert!((60.0 / 80.0..=60.0 / 70.0).contains(&first_place));
+        assert!(statistics.throughput.unwrap() > 0.0);
+        assert_eq!(
+            simulate_stochastic_petri_net(&gspn, &options).log,
+            result.log
+        );
+    }
+
+    #[test]
+    fn simulate_policies_and_fitted_net() {
+        let net = choice_net();
+        let transition = |label: &str| {
+            net.transitions
+                .values()
+                .find(|t| t.label.as_deref() == Some(label))
+                .map(TransitionID::from)
+                .unwrap()
+        };
+        let mut gspn = StochasticPetriNet::new(net.clone());
+        for (label, delay) in [("b", 10.0), ("c", 20.0)] {
+            gspn.set_transition(
+                transition(label),
+                StochasticTransition::Timed {
+                    distribution: DelayDistribution::Deterministic { value: delay },
+                    weight: 1.0,
+                },
+            );
+        }
+        let mut options = StochasticSimulationOptions {
+            num_cases: 30,
+            seed: Some(5),
+            execution_policy: ExecutionPolicy::RaceEnablingMemory,
+            ..Default::default()
+        };
+        // `b` always wins the race against `c`
+        let result = simulate_stochastic_petri_net(&gspn, &options);
+        assert_eq!(result.statistics.transition_firings[&transition("c")], 0);
+        options.execution_policy = ExecutionPolicy::GlobalPreselection;
+        let result = simulate_stochastic_petri_net(&gspn, &options);
+        assert!(result.statistics.transition_firings[&transition("c")] > 0);
+
+        // Fitted nets reproduce the behavior of the simulated log (incl. skipping `d`)
+        let fitted = fit_stochastic_petri_net(&net, &result.log, &FittingOptions::default());
+        let resimulated = simulate_stochastic_petri_net(&fitted, &options);
+        assert_eq!(resimulated.statistics.num_completed_cases, 30);
+        let variants: HashSet<Vec<&str>> = resimulated
+            .log
+            .traces
+            .iter()
+            .map(trace_activities)
+            .collect();
+        assert!(variants.contains(&vec!["a", "b", "d"]));
+        assert!(variants.contains(&vec!["a", "c"]));
+    }
+}
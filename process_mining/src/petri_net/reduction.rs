@@ -0,0 +1,760 @@
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::petri_net_struct::{ArcType, Marking, PetriNet, PlaceID, TransitionID};
+
+/// Tolerance for comparing floating point values in the simplex method
+const EPSILON: f64 = 1e-9;
+
+/// No input or output arcs
+static NO_ARCS: BTreeMap<Uuid, u32> = BTreeMap::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+///
+/// Reduction rule for [`PetriNet`]s
+///
+/// The rules follow the reduction rules of Murata, but are restricted such that the visible language
+/// (i.e., the sequences of transition labels leading from the initial marking to a final marking) is preserved.
+/// Silent transitions are transitions without a label.
+///
+pub enum ReductionRule {
+    /// Fuse two places `p` and `q` connected by a silent transition, which only consumes one token from `p` and produces one token in `q`
+    ///
+    /// The transition is removed and `q` is fused into `p`.
+    /// Requires that the transition is the only output of `p` or the only input of `q` (and `q` is not marked initially).
+    /// `p` must not be part of a final marking.
+    FusionOfSeriesPlaces,
+    /// Fuse two transitions `t1` and `t2` connected by a place, which is the only output of `t1` and the only input of `t2`
+    ///
+    /// The place and one of the transitions are removed.
+    /// Requires that the place is the only input of `t2`, is not marked in any marking,
+    /// and that either `t2` is silent or `t1` is silent and the place is its only output.
+    FusionOfSeriesTransitions,
+    /// Remove a place with the same input and output arcs (and the same tokens in all markings) as another place
+    FusionOfParallelPlaces,
+    /// Remove a transition with the same label and the same input and output arcs as another transition
+    FusionOfParallelTransitions,
+    /// Remove a place which is only connected through self-loops (i.e., every transition puts back the tokens it consumes)
+    ///
+    /// Requires that the place initially holds enough tokens for all connected transitions
+    /// and the same number of tokens in all final markings.
+    EliminationOfSelfLoopPlaces,
+    /// Remove a silent transition which puts back exactly the tokens it consumes
+    EliminationOfSelfLoopTransitions,
+    /// Remove an implicit place (see [`is_implicit_place`])
+    ImplicitPlaces,
+}
+
+impl ReductionRule {
+    /// All reduction rules (structural rules first, the more expensive implicit place detection last)
+    pub const ALL: [ReductionRule; 7] = [
+        ReductionRule::EliminationOfSelfLoopTransitions,
+        ReductionRule::FusionOfSeriesPlaces,
+        ReductionRule::FusionOfSeriesTransitions,
+        ReductionRule::FusionOfParallelPlaces,
+        ReductionRule::FusionOfParallelTransitions,
+        ReductionRule::EliminationOfSelfLoopPlaces,
+        ReductionRule::ImplicitPlaces,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Options for [`reduce_petri_net`]
+///
+pub struct ReductionOptions {
+    /// Reduction rules to apply
+    ///
+    /// The rules are tried in the given order, starting again with the first rule after every successful application.
+    pub rules: Vec<ReductionRule>,
+}
+
+impl Default for ReductionOptions {
+    fn default() -> Self {
+        Self {
+            rules: ReductionRule::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// A single application of a [`ReductionRule`]
+///
+pub struct AppliedReduction {
+    /// The applied rule
+    pub rule: ReductionRule,
+    /// Places removed by the application
+    pub removed_places: Vec<PlaceID>,
+    /// Transitions removed by the application
+    pub removed_transitions: Vec<TransitionID>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Report of [`reduce_petri_net`] about the applied rules
+///
+pub struct ReductionReport {
+    /// All rule applications (in the order they were applied)
+    pub applied: Vec<AppliedReduction>,
+}
+
+impl ReductionReport {
+    /// Number of times the given rule was applied
+    pub fn count(&self, rule: ReductionRule) -> usize {
+        self.applied.iter().filter(|a| a.rule == rule).count()
+    }
+}
+
+///
+/// Reduce a [`PetriNet`] by repeatedly applying the given [`ReductionRule`]s until none of them is applicable anymore
+///
+/// All rules preserve the visible language of the net
+/// (i.e., the sequences of transition labels leading from the initial marking to a final marking).
+/// If no final markings are set, all reachable markings are considered final.
+/// Multiple arcs between the same place and transition are merged (summing up their weights).
+///
+/// Returns a [`ReductionReport`] listing which rules fired and which places and transitions they removed.
+///
+pub fn reduce_petri_net(net: &mut PetriNet, options: &ReductionOptions) -> ReductionReport {
+    merge_duplicate_arcs(net);
+    let mut report = ReductionReport::default();
+    'reduce: loop {
+        for rule in &options.rules {
+            let arcs = WeightedArcs::new(net);
+            let applied = match rule {
+                ReductionRule::FusionOfSeriesPlaces => fuse_series_places(net, &arcs),
+                ReductionRule::FusionOfSeriesTransitions => fuse_series_transitions(net, &arcs),
+                ReductionRule::FusionOfParallelPlaces => fuse_parallel_places(net, &arcs),
+                ReductionRule::FusionOfParallelTransitions => fuse_parallel_transitions(net, &arcs),
+                ReductionRule::EliminationOfSelfLoopPlaces => {
+                    eliminate_self_loop_places(net, &arcs)
+                }
+                ReductionRule::EliminationOfSelfLoopTransitions => {
+                    eliminate_self_loop_transitions(net, &arcs)
+                }
+                ReductionRule::ImplicitPlaces => sorted_ids(&net.places)
+                    .into_iter()
+                    .find(|p| is_implicit(net, &arcs, *p))
+                    .map(|p| {
+                        net.remove_place(&p);
+                        AppliedReduction {
+                            rule: ReductionRule::ImplicitPlaces,
+                            removed_places: vec![PlaceID(p)],
+                            removed_transitions: Vec::new(),
+                        }
+                    }),
+            };
+            if let Some(applied) = applied {
+                merge_duplicate_arcs(net);
+                report.applied.push(applied);
+                continue 'reduce;
+            }
+        }
+        return report;
+    }
+}
+
+///
+/// Check if a place is implicit, i.e., if it never restricts the firing of its output transitions and
+/// its number of tokens in the final markings is determined by the other places
+///
+/// Removing an implicit place preserves the visible language of the net.
+///
+/// A sufficient structural condition is checked using linear programming:
+/// The place `p` is implicit if there are non-negative weights `y` for all other places and a constant `mu` such that
+/// - `y · C <= C[p]` (with the incidence matrix `C`; with equality if the net has final markings),
+/// - `y · m0 + mu <= m0[p]` for the initial marking `m0`,
+/// - `y · Pre[t] + mu >= Pre[p, t]` for all output transitions `t` of `p`, and
+/// - `y · (mf - m0) = mf[p] - m0[p]` for all final markings `mf`.
+///
+pub fn is_implicit_place(net: &PetriNet, place: PlaceID) -> bool {
+    net.places.contains_key(&place.0) && is_implicit(net, &WeightedArcs::new(net), place.0)
+}
+
+///
+/// Find all implicit places of a [`PetriNet`] (see [`is_implicit_place`])
+///
+/// _Note_: Every returned place is implicit on its own, but removing one of them can make others non-implicit
+/// (e.g., for two places with the same arcs).
+/// Use [`reduce_petri_net`] with [`ReductionRule::ImplicitPlaces`] to remove implicit places one after another.
+///
+pub fn find_implicit_places(net: &PetriNet) -> Vec<PlaceID> {
+    let arcs = WeightedArcs::new(net);
+    sorted_ids(&net.places)
+        .into_iter()
+        .filter(|p| is_implicit(net, &arcs, *p))
+        .map(PlaceID)
+        .collect()
+}
+
+/// Merge multiple arcs between the same nodes into one arc (summing up their weights)
+pub(crate) fn merge_duplicate_arcs(net: &mut PetriNet) {
+    let mut weights: HashMap<ArcType, u32> = HashMap::new();
+    net.arcs
+        .retain(|arc| match weights.entry(arc.from_to.clone()) {
+            Entry::Occupied(mut e) => {
+                *e.get_mut() += arc.weight;
+                false
+            }
+            Entry::Vacant(e) => {
+                e.insert(arc.weight);
+                true
+            }
+        });
+    for arc in &mut net.arcs {
+        arc.weight = weights[&arc.from_to];
+    }
+}
+
+/// Input and output nodes (with arc weights) of all places and transitions
+struct WeightedArcs {
+    inputs: HashMap<Uuid, BTreeMap<Uuid, u32>>,
+    outputs: HashMap<Uuid, BTreeMap<Uuid, u32>>,
+}
+
+impl WeightedArcs {
+    fn new(net: &PetriNet) -> Self {
+        let mut inputs: HashMap<Uuid, BTreeMap<Uuid, u32>> = HashMap::new();
+        let mut outputs: HashMap<Uuid, BTreeMap<Uuid, u32>> = HashMap::new();
+        for arc in &net.arcs {
+            let (from, to) = match arc.from_to {
+                ArcType::PlaceTransition(from, to) | ArcType::TransitionPlace(from, to) => {
+                    (from, to)
+                }
+            };
+            *outputs.entry(from).or_default().entry(to).or_default() += arc.weight;
+            *inputs.entry(to).or_default().entry(from).or_default() += arc.weight;
+        }
+        Self { inputs, outputs }
+    }
+
+    fn inputs(&self, id: &Uuid) -> &BTreeMap<Uuid, u32> {
+        self.inputs.get(id).unwrap_or(&NO_ARCS)
+    }
+
+    fn outputs(&self, id: &Uuid) -> &BTreeMap<Uuid, u32> {
+        self.outputs.get(id).unwrap_or(&NO_ARCS)
+    }
+}
+
+/// The only node (and arc weight) of the passed inputs or outputs (if there is exactly one)
+fn single(arcs: &BTreeMap<Uuid, u32>) -> Option<(Uuid, u32)> {
+    match arcs.len() {
+        1 => arcs.iter().next().map(|(id, weight)| (*id, *weight)),
+        _ => None,
+    }
+}
+
+/// IDs of places or transitions in a deterministic order
+fn sorted_ids<T>(nodes: &HashMap<Uuid, T>) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> = nodes.keys().copied().collect();
+    ids.sort();
+    ids
+}
+
+fn is_silent(net: &PetriNet, t: &Uuid) -> bool {
+    net.transitions.get(t).is_some_and(|t| t.label.is_none())
+}
+
+fn tokens(marking: Option<&Marking>, p: Uuid) -> u64 {
+    marking
+        .and_then(|m| m.get(&PlaceID(p)))
+        .copied()
+        .unwrap_or_default()
+}
+
+fn final_markings(net: &PetriNet) -> &[Marking] {
+    net.final_markings.as_deref().unwrap_or_default()
+}
+
+fn is_marked_in_final_marking(net: &PetriNet, p: Uuid) -> bool {
+    final_markings(net).iter().any(|m| tokens(Some(m), p) > 0)
+}
+
+fn fuse_series_places(net: &mut PetriNet, arcs: &WeightedArcs) -> Option<AppliedReduction> {
+    for t in sorted_ids(&net.transitions) {
+        if !is_silent(net, &t) {
+            continue;
+        }
+        let (Some((p, 1)), Some((q, 1))) = (single(arcs.inputs(&t)), single(arcs.outputs(&t)))
+        else {
+            continue;
+        };
+        if p == q || is_marked_in_final_marking(net, p) {
+            continue;
+        }
+        let only_output_of_p = arcs.outputs(&p).len() == 1;
+        let only_input_of_q =
+            arcs.inputs(&q).len() == 1 && tokens(net.initial_marking.as_ref(), q) == 0;
+        if !only_output_of_p && !only_input_of_q {
+            continue;
+        }
+        net.remove_transition(&t);
+        fuse_places(net, p, q);
+        return Some(AppliedReduction {
+            rule: ReductionRule::FusionOfSeriesPlaces,
+            removed_places: vec![PlaceID(q)],
+            removed_transitions: vec![TransitionID(t)],
+        });
+    }
+    None
+}
+
+/// Fuse place `remove` into place `keep` (moving all arcs and marking tokens)
+fn fuse_places(net: &mut PetriNet, keep: Uuid, remove: Uuid) {
+    for arc in &mut net.arcs {
+        match &mut arc.from_to {
+            ArcType::PlaceTransition(p, _) | ArcType::TransitionPlace(_, p) if *p == remove => {
+                *p = keep;
+            }
+            _ => {}
+        }
+    }
+    let markings = net
+        .initial_marking
+        .iter_mut()
+        .chain(net.final_markings.iter_mut().flatten());
+    for marking in markings {
+        if let Some(tokens) = marking.remove(&PlaceID(remove)) {
+            *marking.entry(PlaceID(keep)).or_default() += tokens;
+        }
+    }
+    net.remove_place(&remove);
+}
+
+fn fuse_series_transitions(net: &mut PetriNet, arcs: &WeightedArcs) -> Option<AppliedReduction> {
+    for p in sorted_ids(&net.places) {
+        let (Some((t1, 1)), Some((t2, 1))) = (single(arcs.inputs(&p)), single(arcs.outputs(&p)))
+        else {
+            continue;
+        };
+        if t1 == t2
+            || arcs.inputs(&t2).len() != 1
+            || tokens(net.initial_marking.as_ref(), p) > 0
+            || is_marked_in_final_marking(net, p)
+        {
+            continue;
+        }
+        // A silent `t2` can always fire right after `t1`, and a silent `t1` (with only `p` as output) can be delayed until `t2` fires
+        let (keep, remove) = if is_silent(net, &t2) {
+            (t1, t2)
+        } else if is_silent(net, &t1) && arcs.outputs(&t1).len() == 1 {
+            (t2, t1)
+        } else {
+            continue;
+        };
+        for arc in &mut net.arcs {
+            match &mut arc.from_to {
+                ArcType::PlaceTransition(_, t) | ArcType::TransitionPlace(t, _) if *t == remove => {
+                    *t = keep;
+                }
+                _ => {}
+            }
+        }
+        net.remove_place(&p);
+        net.remove_transition(&remove);
+        return Some(AppliedReduction {
+            rule: ReductionRule::FusionOfSeriesTransitions,
+            removed_places: vec![PlaceID(p)],
+            removed_transitions: vec![TransitionID(remove)],
+        });
+    }
+    None
+}
+
+fn fuse_parallel_places(net: &mut PetriNet, arcs: &WeightedArcs) -> Option<AppliedReduction> {
+    let mut seen = HashMap::new();
+    for p in sorted_ids(&net.places) {
+        let marking_tokens: Vec<u64> = net
+            .initial_marking
+            .iter()
+            .chain(final_markings(net))
+            .map(|m| tokens(Some(m), p))
+            .collect();
+        match seen.entry((arcs.inputs(&p), arcs.outputs(&p), marking_tokens)) {
+            Entry::Occupied(_) => {
+                net.remove_place(&p);
+                return Some(AppliedReduction {
+                    rule: ReductionRule::FusionOfParallelPlaces,
+                    removed_places: vec![PlaceID(p)],
+                    removed_transitions: Vec::new(),
+                });
+            }
+            Entry::Vacant(e) => {
+                e.insert(p);
+            }
+        }
+    }
+    None
+}
+
+fn fuse_parallel_transitions(net: &mut PetriNet, arcs: &WeightedArcs) -> Option<AppliedReduction> {
+    let mut seen = HashMap::new();
+    for t in sorted_ids(&net.transitions) {
+        let label = net.transitions[&t].label.clone();
+        match seen.entry((label, arcs.inputs(&t), arcs.outputs(&t))) {
+            Entry::Occupied(_) => {
+                net.remove_transition(&t);
+                return Some(AppliedReduction {
+                    rule: ReductionRule::FusionOfParallelTransitions,
+                    removed_places: Vec::new(),
+                    removed_transitions: vec![TransitionID(t)],
+                });
+            }
+            Entry::Vacant(e) => {
+                e.insert(t);
+            }
+        }
+    }
+    None
+}
+
+fn eliminate_self_loop_places(net: &mut PetriNet, arcs: &WeightedArcs) -> Option<AppliedReduction> {
+    for p in sorted_ids(&net.places) {
+        let inputs = arcs.inputs(&p);
+        if inputs.is_empty() || inputs != arcs.outputs(&p) {
+            continue;
+        }
+        let initial_tokens = tokens(net.initial_marking.as_ref(), p);
+        let max_weight = inputs.values().max().copied().unwrap_or_default();
+        if initial_tokens < max_weight as u64
+            || final_markings(net)
+                .iter()
+                .any(|m| tokens(Some(m), p) != initial_tokens)
+        {
+            continue;
+        }
+        net.remove_place(&p);
+        return Some(AppliedReduction {
+            rule: ReductionRule::EliminationOfSelfLoopPlaces,
+            removed_places: vec![PlaceID(p)],
+            removed_transitions: Vec::new(),
+        });
+    }
+    None
+}
+
+fn eliminate_self_loop_transitions(
+    net: &mut PetriNet,
+    arcs: &WeightedArcs,
+) -> Option<AppliedReduction> {
+    let t = sorted_ids(&net.transitions)
+        .into_iter()
+        .find(|t| is_silent(net, t) && arcs.inputs(t) == arcs.outputs(t))?;
+    net.remove_transition(&t);
+    Some(AppliedReduction {
+        rule: ReductionRule::EliminationOfSelfLoopTransitions,
+        removed_places: Vec::new(),
+        removed_transitions: vec![TransitionID(t)],
+    })
+}
+
+/// Check the linear program described at [`is_implicit_place`]
+fn is_implicit(net: &PetriNet, arcs: &WeightedArcs, p: Uuid) -> bool {
+    let others: Vec<Uuid> = sorted_ids(&net.places)
+        .into_iter()
+        .filter(|q| *q != p)
+        .collect();
+    let initial = net.initial_marking.as_ref();
+    let finals = final_markings(net);
+    let pre = |q: Uuid, t: Uuid| arcs.inputs(&t).get(&q).copied().unwrap_or_default() as f64;
+    let incidence =
+        |q: Uuid, t: Uuid| arcs.outputs(&t).get(&q).copied().unwrap_or_default() as f64 - pre(q, t);
+    // Variables: weights `y` of all other places, followed by `mu = mu_plus - mu_minus`
+    let row = |coefficient: &dyn Fn(Uuid) -> f64, mu: f64| -> Vec<f64> {
+        others
+            .iter()
+            .map(|q| coefficient(*q))
+            .chain([mu, -mu])
+            .collect()
+    };
+    let mut constraints = Vec::new();
+    for t in sorted_ids(&net.transitions) {
+        constraints.push(Constraint {
+            coefficients: row(&|q| incidence(q, t), 0.0),
+            relation: if finals.is_empty() {
+                Relation::LessEq
+            } else {
+                Relation::Equal
+            },
+            value: incidence(p, t),
+        });
+    }
+    constraints.push(Constraint {
+        coefficients: row(&|q| tokens(initial, q) as f64, 1.0),
+        relation: Relation::LessEq,
+        value: tokens(initial, p) as f64,
+    });
+    for (t, weight) in arcs.outputs(&p) {
+        constraints.push(Constraint {
+            coefficients: row(&|q| pre(q, *t), 1.0),
+            relation: Relation::GreaterEq,
+            value: *weight as f64,
+        });
+    }
+    for m in finals {
+        let difference = |q: Uuid| tokens(Some(m), q) as f64 - tokens(initial, q) as f64;
+        constraints.push(Constraint {
+            coefficients: row(&difference, 0.0),
+            relation: Relation::Equal,
+            value: difference(p),
+        });
+    }
+    is_feasible(&constraints, others.len() + 2)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Relation {
+    LessEq,
+    GreaterEq,
+    Equal,
+}
+
+/// Linear constraint `coefficients · x (<=|>=|=) value`
+struct Constraint {
+    coefficients: Vec<f64>,
+    relation: Relation,
+    value: f64,
+}
+
+///
+/// Check if there is a non-negative solution `x` satisfying all constraints
+///
+/// Uses the first phase of the simplex method (minimizing the sum of artificial variables) with Bland's rule.
+///
+fn is_feasible(constraints: &[Constraint], num_vars: usize) -> bool {
+    let num_slack = constraints
+        .iter()
+        .filter(|c| c.relation != Relation::Equal)
+        .count();
+    // Columns: variables, slack variables, artificial variables (one per constraint), right-hand side
+    let artificial_start = num_vars + num_slack;
+    let rhs = artificial_start + constraints.len();
+    let mut tableau: Vec<Vec<f64>> = Vec::with_capacity(constraints.len());
+    let mut slack = num_vars;
+    for (i, c) in constraints.iter().enumerate() {
+        let mut row = vec![0.0; rhs + 1];
+        row[..num_vars].copy_from_slice(&c.coefficients);
+        match c.relation {
+            Relation::LessEq => row[slack] = 1.0,
+            Relation::GreaterEq => row[slack] = -1.0,
+            Relation::Equal => {}
+        }
+        if c.relation != Relation::Equal {
+            slack += 1;
+        }
+        row[rhs] = c.value;
+        // Right-hand sides need to be non-negative for the artificial variables to form a feasible basis
+        if row[rhs] < 0.0 {
+            row.iter_mut().for_each(|v| *v = -*v);
+        }
+        row[artificial_start + i] = 1.0;
+        tableau.push(row);
+    }
+    let mut basis: Vec<usize> = (artificial_start..rhs).collect();
+    // Reduced costs of minimizing the sum of all artificial variables
+    let mut objective = vec![0.0; rhs + 1];
+    for row in &tableau {
+        for j in (0..artificial_start).chain([rhs]) {
+            objective[j] -= row[j];
+        }
+    }
+    while let Some(entering) = (0..artificial_start).find(|j| objective[*j] < -EPSILON) {
+        let leaving = (0..tableau.len())
+            .filter(|i| tableau[*i][entering] > EPSILON)
+            .min_by(|a, b| {
+                let ratio_a = tableau[*a][rhs] / tableau[*a][entering];
+                let ratio_b = tableau[*b][rhs] / tableau[*b][entering];
+                ratio_a
+                    .partial_cmp(&ratio_b)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(basis[*a].cmp(&basis[*b]))
+            });
+        let Some(leaving) = leaving else {
+            // Unbounded, which can not happen as the objective is bounded by zero
+            break;
+        };
+        let pivot = tableau[leaving][entering];
+        tableau[leaving].iter_mut().for_each(|v| *v /= pivot);
+        let pivot_row = tableau[leaving].clone();
+        for (i, row) in tableau.iter_mut().enumerate() {
+            let factor = row[entering];
+            if i != leaving && factor != 0.0 {
+                row.iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(v, p)| *v -= factor * p);
+            }
+        }
+        let factor = objective[entering];
+        objective
+            .iter_mut()
+            .zip(&pivot_row)
+            .for_each(|(v, p)| *v -= factor * p);
+        basis[leaving] = entering;
+    }
+    // The (negated) sum of artificial variables is zero iff the constraints are satisfiable
+    objective[rhs] > -1e-6
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bpmn::conversion::tests::accepts;
+
+    use super::*;
+
+    /// Assert that both nets accept the same traces (over the labels of the first net) up to the given length
+    fn assert_same_language(net: &PetriNet, reduced: &PetriNet, max_len: usize) {
+        let mut labels: Vec<&str> = net
+            .transitions
+            .values()
+            .filter_map(|t| t.label.as_deref())
+            .collect();
+        labels.sort();
+        labels.dedup();
+        let mut traces: Vec<Vec<&str>> = vec![Vec::new()];
+        let mut accepted = 0;
+        while let Some(trace) = traces.pop() {
+            assert_eq!(
+                accepts(net, &trace),
+                accepts(reduced, &trace),
+                "Trace {trace:?}"
+            );
+            accepted += accepts(net, &trace) as usize;
+            if trace.len() < max_len {
+                for label in &labels {
+                    let mut next = trace.clone();
+                    next.push(label);
+                    traces.push(next);
+                }
+            }
+        }
+        assert!(accepted > 0);
+    }
+
+    fn add_arcs(net: &mut PetriNet, pre: &[PlaceID], t: TransitionID, post: &[PlaceID]) {
+        for p in pre {
+            net.add_arc(ArcType::place_to_transition(*p, t), None);
+        }
+        for p in post {
+            net.add_arc(ArcType::transition_to_place(t, *p), None);
+        }
+    }
+
+    #[test]
+    fn series_and_self_loop_reductions() {
+        let mut net = PetriNet::new();
+        let places: Vec<PlaceID> = (0..5).map(|_| net.add_place(None)).collect();
+        let a = net.add_transition(Some("a".to_string()), None);
+        let tau_1 = net.add_transition(None, None);
+        let tau_2 = net.add_transition(None, None);
+        let b = net.add_transition(Some("b".to_string()), None);
+        let tau_loop = net.add_transition(None, None);
+        let resource = net.add_place(None);
+        add_arcs(&mut net, &[places[0], resource], a, &[places[1], resource]);
+        add_arcs(&mut net, &[places[1]], tau_1, &[places[2]]);
+        add_arcs(&mut net, &[places[2]], tau_2, &[places[3]]);
+        add_arcs(&mut net, &[places[3]], b, &[places[4]]);
+        add_arcs(&mut net, &[places[3]], tau_loop, &[places[3]]);
+        net.initial_marking = Some(Marking::from([(places[0], 1), (resource, 1)]));
+        net.final_markings = Some(vec![Marking::from([(places[4], 1), (resource, 1)])]);
+
+        let mut reduced = net.clone();
+        let report = reduce_petri_net(&mut reduced, &ReductionOptions::default());
+        assert_eq!(
+            report.count(ReductionRule::EliminationOfSelfLoopTransitions),
+            1
+        );
+        assert_eq!(report.count(ReductionRule::EliminationOfSelfLoopPlaces), 1);
+        assert_eq!(
+            report.count(ReductionRule::FusionOfSeriesPlaces)
+                + report.count(ReductionRule::FusionOfSeriesTransitions),
+            2
+        );
+        assert_eq!(reduced.transitions.len(), 2);
+        assert_eq!(reduced.places.len(), 3);
+        assert!(reduced.transitions.values().all(|t| t.label.is_some()));
+        assert_same_language(&net, &reduced, 3);
+
+        // The marked place can not be removed if its tokens differ in the final marking
+        net.final_markings = Some(vec![Marking::from([(places[4], 1)])]);
+        let report = reduce_petri_net(&mut net, &ReductionOptions::default());
+        assert_eq!(report.count(ReductionRule::EliminationOfSelfLoopPlaces), 0);
+        assert!(net.places.contains_key(&resource.0));
+    }
+
+    #[test]
+    fn parallel_reductions() {
+        let mut net = PetriNet::new();
+        let start = net.add_place(None);
+        let middle = net.add_place(None);
+        let middle_copy = net.add_place(None);
+        let end = net.add_place(None);
+        let a = net.add_transition(Some("a".to_string()), None);
+        let b = net.add_transition(Some("b".to_string()), None);
+        let b_copy = net.add_transition(Some("b".to_string()), None);
+        let c = net.add_transition(Some("c".to_string()), None);
+        add_arcs(&mut net, &[start], a, &[middle, middle_copy]);
+        add_arcs(&mut net, &[middle, middle_copy], b, &[end]);
+        add_arcs(&mut net, &[middle, middle_copy], b_copy, &[end]);
+        add_arcs(&mut net, &[middle, middle_copy], c, &[end]);
+        net.initial_marking = Some(Marking::from([(start, 1)]));
+        net.final_markings = Some(vec![Marking::from([(end, 1)])]);
+
+        let mut reduced = net.clone();
+        let options = ReductionOptions {
+            rules: vec![
+                ReductionRule::FusionOfParallelPlaces,
+                ReductionRule::FusionOfParallelTransitions,
+            ],
+        };
+        let report = reduce_petri_net(&mut reduced, &options);
+        assert_eq!(report.applied.len(), 2);
+        assert_eq!(reduced.places.len(), 3);
+        assert_eq!(reduced.transitions.len(), 3);
+        assert_eq!(
+            report.applied[1].removed_transitions[0],
+            TransitionID(b.0.max(b_copy.0))
+        );
+        assert_same_language(&net, &reduced, 3);
+    }
+
+    #[test]
+    fn implicit_places() {
+        let mut net = PetriNet::new();
+        let places: Vec<PlaceID> = (0..4).map(|_| net.add_place(None)).collect();
+        let shortcut = net.add_place(None);
+        let a = net.add_transition(Some("a".to_string()), None);
+        let b = net.add_transition(Some("b".to_string()), None);
+        let c = net.add_transition(Some("c".to_string()), None);
+        let d = net.add_transition(Some("d".to_string()), None);
+        add_arcs(&mut net, &[places[0]], a, &[places[1], shortcut]);
+        add_arcs(&mut net, &[places[1]], b, &[places[2]]);
+        add_arcs(&mut net, &[places[2], shortcut], c, &[places[3]]);
+        // `c` consumes the token of `shortcut`, so `d` could only fire after `c` without `shortcut`
+        add_arcs(&mut net, &[places[3], shortcut], d, &[places[3]]);
+        net.initial_marking = Some(Marking::from([(places[0], 1)]));
+        net.final_markings = Some(vec![Marking::from([(places[3], 1)])]);
+        assert!(find_implicit_places(&net).is_empty());
+
+        net.remove_transition(&d.0);
+        assert_eq!(find_implicit_places(&net), vec![shortcut]);
+        assert!(is_implicit_place(&net, shortcut));
+        assert!(!is_implicit_place(&net, places[3]));
+
+        let mut reduced = net.clone();
+        let options = ReductionOptions {
+            rules: vec![ReductionRule::ImplicitPlaces],
+        };
+        let report = reduce_petri_net(&mut reduced, &options);
+        assert_eq!(report.applied.len(), 1);
+        assert_eq!(report.applied[0].removed_places, vec![shortcut]);
+        assert_same_language(&net, &reduced, 4);
+
+        // Without final markings, places without output transitions are implicit
+        net.final_markings = None;
+        assert!(find_implicit_places(&net).contains(&places[3]));
+    }
+}
@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    conformance::token_replay::token_replay,
+    event_log::{
+        constants::TIMESTAMP_NAME, event_log_struct::EventLogClassifier, XESEditableAttribute,
+    },
+    EventLog,
+};
+
+use super::petri_net_struct::{PetriNet, TransitionID};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+///
+/// Distribution of the delay (in seconds) of a timed transition
+///
+pub enum DelayDistribution {
+    /// Exponential distribution with the given mean
+    Exponential {
+        /// Mean delay
+        mean: f64,
+    },
+    /// Normal distribution (negative samples are truncated to `0`)
+    Normal {
+        /// Mean delay
+        mean: f64,
+        /// Standard deviation
+        std_dev: f64,
+    },
+    /// Uniform distribution between `min` and `max`
+    Uniform {
+        /// Minimal delay
+        min: f64,
+        /// Maximal delay
+        max: f64,
+    },
+    /// Constant delay
+    Deterministic {
+        /// Delay
+        value: f64,
+    },
+    /// Empirical distribution, sampling uniformly from the observed delays
+    Empirical {
+        /// Observed delays
+        values: Vec<f64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+///
+/// Kind of [`DelayDistribution`] (e.g., to fit to observed delays)
+///
+pub enum DelayDistributionType {
+    /// [`DelayDistribution::Exponential`]
+    #[default]
+    Exponential,
+    /// [`DelayDistribution::Normal`]
+    Normal,
+    /// [`DelayDistribution::Uniform`]
+    Uniform,
+    /// [`DelayDistribution::Deterministic`]
+    Deterministic,
+    /// [`DelayDistribution::Empirical`]
+    Empirical,
+}
+
+impl DelayDistribution {
+    ///
+    /// Mean delay (in seconds) of the distribution
+    ///
+    /// For normal distributions, the truncation of negative samples is not taken into account.
+    pub fn mean(&self) -> f64 {
+        match self {
+            DelayDistribution::Exponential { mean } | DelayDistribution::Normal { mean, .. } => {
+                *mean
+            }
+            DelayDistribution::Uniform { min, max } => (min + max) / 2.0,
+            DelayDistribution::Deterministic { value } => *value,
+            DelayDistribution::Empirical { values } => mean(values),
+        }
+    }
+
+    ///
+    /// Estimate a distribution of the given type from observed delays (in seconds)
+    ///
+    /// Parameters are estimated using the sample mean and standard deviation (or minimum and maximum for uniform distributions).
+    /// Returns `None` if no delays were observed.
+    pub fn fit(distribution_type: DelayDistributionType, delays: &[f64]) -> Option<Self> {
+        if delays.is_empty() {
+            return None;
+        }
+        let min = delays.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = delays.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Some(match distribution_type {
+            DelayDistributionType::Exponential => {
+                DelayDistribution::Exponential { mean: mean(delays) }
+            }
+            DelayDistributionType::Normal => {
+                let mean = mean(delays);
+                let variance = delays.iter().map(|d| (d - mean).powi(2)).sum::<f64>()
+                    / (delays.len() - 1).max(1) as f64;
+                DelayDistribution::Normal {
+                    mean,
+                    std_dev: variance.sqrt(),
+                }
+            }
+            DelayDistributionType::Uniform => DelayDistribution::Uniform { min, max },
+            DelayDistributionType::Deterministic => DelayDistribution::Deterministic {
+                value: mean(delays),
+            },
+            DelayDistributionType::Empirical => {
+                let mut values = delays.to_vec();
+                values.sort_by(f64::total_cmp);
+                DelayDistribution::Empirical { values }
+            }
+        })
+    }
+
+    ///
+    /// Sample a delay (in seconds) from the distribution
+    ///
+    /// Normal distributions are sampled using the Box-Muller transform.
+    ///
+    /// __Requires the `simulation` feature to be enabled__
+    ///
+    #[cfg(feature = "simulation")]
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            DelayDistribution::Exponential { mean } => -mean * (1.0 - rng.random::<f64>()).ln(),
+            DelayDistribution::Normal { mean, std_dev } => {
+                let u1 = 1.0 - rng.random::<f64>();
+                let u2 = rng.random::<f64>();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (mean + std_dev * z).max(0.0)
+            }
+            DelayDistribution::Uniform { min, max } => min + rng.random::<f64>() * (max - min),
+            DelayDistribution::Deterministic { value } => *value,
+            DelayDistribution::Empirical { values } => {
+                if values.is_empty() {
+                    0.0
+                } else {
+                    values[rng.random_range(0..values.len())]
+                }
+            }
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Stochastic behavior of a transition in a [`StochasticPetriNet`] (GSPN)
+///
+pub enum StochasticTransition {
+    ///
+    /// Transition firing without delay as soon as it is enabled
+    ///
+    /// Enabled immediate transitions always fire before timed transitions.
+    /// Among the enabled immediate transitions, only those with the highest priority can fire,
+    /// and one of them is chosen randomly with a probability proportional to its weight.
+    Immediate {
+        /// Relative weight for choosing between enabled immediate transitions (`0.0` never fires)
+        weight: f64,
+        /// Priority (higher priorities fire first)
+        priority: u32,
+    },
+    ///
+    /// Transition firing after a random delay
+    ///
+    Timed {
+        /// Distribution of the delay (in seconds)
+        distribution: DelayDistribution,
+        /// Relative weight for choosing between enabled timed transitions (`0.0` never fires)
+        ///
+        /// Only used if timed transitions are preselected by their weights
+        /// (instead of racing against each other).
+        weight: f64,
+    },
+}
+
+impl Default for StochasticTransition {
+    /// Timed transition with an exponentially distributed delay with a mean of one hour (and weight `1.0`)
+    fn default() -> Self {
+        StochasticTransition::Timed {
+            distribution: DelayDistribution::Exponential { mean: 3600.0 },
+            weight: 1.0,
+        }
+    }
+}
+
+impl StochasticTransition {
+    /// Relative weight of the transition
+    pub fn weight(&self) -> f64 {
+        match self {
+            StochasticTransition::Immediate { weight, .. }
+            | StochasticTransition::Timed { weight, .. } => *weight,
+        }
+    }
+
+    /// Check if the transition is an immediate transition
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, StochasticTransition::Immediate { .. })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// [`PetriNet`] with an (optional) stochastic layer, i.e., a generalized stochastic Petri net (GSPN)
+///
+/// Transitions without an entry in [`StochasticPetriNet::transitions`]
+/// use the [`StochasticTransition::default`].
+///
+pub struct StochasticPetriNet {
+    /// Underlying Petri net
+    pub net: PetriNet,
+    /// Stochastic behavior of the transitions
+    pub transitions: HashMap<TransitionID, StochasticTransition>,
+}
+
+impl StochasticPetriNet {
+    /// Create a stochastic Petri net without stochastic information (i.e., all transitions use the default)
+    pub fn new(net: PetriNet) -> Self {
+        Self {
+            net,
+            transitions: HashMap::new(),
+        }
+    }
+
+    /// Get the stochastic behavior of a transition (or the [`StochasticTransition::default`])
+    pub fn get_transition(&self, transition: &TransitionID) -> StochasticTransition {
+        self.transitions
+            .get(transition)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Set the stochastic behavior of a transition
+    pub fn set_transition(&mut self, transition: TransitionID, stochastic: StochasticTransition) {
+        self.transitions.insert(transition, stochastic);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+///
+/// Options for [`fit_stochastic_petri_net`]
+///
+pub struct FittingOptions {
+    /// Type of the distributions fitted to the observed delays
+    pub distribution_type: DelayDistributionType,
+    /// Classifier for matching events to transition labels
+    pub classifier: EventLogClassifier,
+}
+
+///
+/// Estimate the stochastic behavior of a [`PetriNet`] from a (timestamped) [`EventLog`]
+///
+/// The event log is replayed on the net using token-based replay
+/// (see [`token_replay`]).
+/// The weight of every transition is the number of times it was fired during replay.
+///
+/// Labeled transitions become timed transitions. Their delay distribution is fitted
+/// (see [`DelayDistribution::fit`]) to the time between each event with their label and the previous event of the trace.
+/// Transitions whose label was never observed with a predecessor get a delay of `0`.
+///
+/// Silent transitions become timed transitions without delay (and not immediate transitions),
+/// so that choices between silent and labeled transitions (e.g., skipping an activity) are decided by the weights.
+/// Thus, the fitted net is intended to be simulated with a weight-based preselection of the next transition.
+///
+pub fn fit_stochastic_petri_net(
+    net: &PetriNet,
+    log: &EventLog,
+    options: &FittingOptions,
+) -> StochasticPetriNet {
+    let replay = token_replay(net, log, &options.classifier);
+    let mut delays: HashMap<String, Vec<f64>> = HashMap::new();
+    for trace in &log.traces {
+        let mut previous: Option<DateTime<FixedOffset>> = None;
+        for event in &trace.events {
+            let Some(time) = event
+                .attributes
+                .get_by_key(TIMESTAMP_NAME)
+                .and_then(|a| a.value.try_as_date())
+                .copied()
+            else {
+                continue;
+            };
+            if let Some(previous) = previous {
+                let activity = options
+                    .classifier
+                    .get_class_identity_with_globals(event, &log.global_event_attrs);
+                let seconds = (time - previous).num_milliseconds() as f64 / 1000.0;
+                delays.entry(activity).or_default().push(seconds.max(0.0));
+            }
+            previous = Some(time);
+        }
+    }
+    let transitions = net
+        .transitions
+        .values()
+        .map(|t| {
+            let id = TransitionID::from(t);
+            let weight = replay.transition_firings.get(&id).copied().unwrap_or(0) as f64;
+            let distribution = t
+                .label
+                .as_ref()
+                .and_then(|label| delays.get(label))
+                .and_then(|d| DelayDistribution::fit(options.distribution_type, d))
+                .unwrap_or(DelayDistribution::Deterministic { value: 0.0 });
+            (
+                id,
+                StochasticTransition::Timed {
+                    distribution,
+                    weight,
+                },
+            )
+        })
+        .collect();
+    StochasticPetriNet {
+        net: net.clone(),
+        transitions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use crate::{
+        conformance::token_replay::tests::choice_net,
+        event_log::AttributeValue,
+        utils::test_utils::{event_log_from_events, event_with_attributes},
+    };
+
+    use super::*;
+
+    /// Event log with the given traces of (activity, seconds since the trace start)
+    fn timed_log(traces: &[&[(&str, i64)]]) -> EventLog {
+        let start = Utc
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .unwrap()
+            .fixed_offset();
+        event_log_from_events(
+            traces
+                .iter()
+                .map(|events| {
+                    events
+                        .iter()
+                        .map(|(activity, seconds)| {
+                            event_with_attributes(
+                                activity,
+                                &[(
+                                    TIMESTAMP_NAME,
+                                    AttributeValue::Date(start + Duration::seconds(*seconds)),
+                                )],
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn transition_by_label(net: &PetriNet, label: Option<&str>) -> TransitionID {
+        net.transitions
+            .values()
+            .find(|t| t.label.as_deref() == label)
+            .map(TransitionID::from)
+            .unwrap()
+    }
+
+    #[test]
+    fn fit_distributions() {
+        let delays = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(
+            DelayDistribution::fit(DelayDistributionType::Exponential, &delays),
+            Some(DelayDistribution::Exponential { mean: 25.0 })
+        );
+        assert_eq!(
+            DelayDistribution::fit(DelayDistributionType::Uniform, &delays),
+            Some(DelayDistribution::Uniform {
+                min: 10.0,
+                max: 40.0
+            })
+        );
+        assert_eq!(
+            DelayDistribution::fit(DelayDistributionType::Empirical, &[3.0, 1.0, 2.0]),
+            Some(DelayDistribution::Empirical {
+                values: vec![1.0, 2.0, 3.0]
+            })
+        );
+        match DelayDistribution::fit(DelayDistributionType::Normal, &delays).unwrap() {
+            DelayDistribution::Normal { mean, std_dev } => {
+                assert_eq!(mean, 25.0);
+                assert!((std_dev - (500.0_f64 / 3.0).sqrt()).abs() < 1e-9);
+            }
+            d => panic!("Unexpected distribution {d:?}"),
+        }
+        assert_eq!(
+            DelayDistribution::fit(DelayDistributionType::Deterministic, &[]),
+            None
+        );
+        assert_eq!(
+            DelayDistribution::Uniform { min: 1.0, max: 3.0 }.mean(),
+            2.0
+        );
+    }
+
+    #[test]
+    fn fit_choice_net() {
+        let net = choice_net();
+        let log = timed_log(&[
+            &[("a", 0), ("b", 60), ("d", 180)],
+            &[("a", 0), ("c", 30)],
+            &[("a", 0), ("b", 120)],
+        ]);
+        let fitted = fit_stochastic_petri_net(
+            &net,
+            &log,
+            &FittingOptions {
+                distribution_type: DelayDistributionType::Deterministic,
+                ..Default::default()
+            },
+        );
+        let expect = |label: Option<&str>, delay: f64, weight: f64| {
+            assert_eq!(
+                fitted.get_transition(&transition_by_label(&net, label)),
+                StochasticTransition::Timed {
+                    distribution: DelayDistribution::Deterministic { value: delay },
+                    weight
+                },
+                "{label:?}"
+            );
+        };
+        expect(Some("a"), 0.0, 3.0);
+        expect(Some("b"), 90.0, 2.0);
+        expect(Some("c"), 30.0, 1.0);
+        expect(Some("d"), 120.0, 1.0);
+        // Silent skip of `d`
+        expect(None, 0.0, 2.0);
+
+        let json = serde_json::to_string(&fitted).unwrap();
+        let imported: StochasticPetriNet = serde_json::from_str(&json).unwrap();
+        assert_eq!(imported.transitions, fitted.transitions);
+    }
+}
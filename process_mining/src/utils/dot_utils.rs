@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use graphviz_rust::dot_structures::{Attribute, EdgeTy, Graph, GraphAttributes, Id, Stmt, Vertex};
+
+///
+/// Error encountered while parsing a DOT graph
+///
+#[derive(Debug, Clone)]
+pub enum DOTParseError {
+    /// IO error
+    IOError(std::rc::Rc<std::io::Error>),
+    /// Invalid DOT syntax (with message of the DOT parser)
+    SyntaxError(String),
+    /// Node has a shape which can not be mapped (node ID and shape included)
+    UnsupportedShape(String, String),
+    /// Edge connects two nodes which can not be connected (e.g., two places; source and target node ID included)
+    InvalidEdge(String, String),
+    /// Label or attribute could not be parsed as a number (value included)
+    InvalidNumber(String),
+    /// Edges from or to subgraphs are not supported
+    SubgraphEdge,
+    /// Number of final markings (or index of a final marking) exceeds the supported maximum (value and maximum included)
+    TooManyFinalMarkings(usize, usize),
+}
+
+impl std::fmt::Display for DOTParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse DOT: {:?}", self)
+    }
+}
+
+impl std::error::Error for DOTParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DOTParseError::IOError(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DOTParseError {
+    fn from(e: std::io::Error) -> Self {
+        Self::IOError(std::rc::Rc::new(e))
+    }
+}
+
+/// Get the value of a DOT ID (removing the quotes of quoted strings)
+pub(crate) fn id_to_string(id: &Id) -> String {
+    match id {
+        Id::Escaped(s) => s
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .unwrap_or(s)
+            .replace("\\\"", "\"")
+            .replace("\\\n", ""),
+        Id::Html(s) | Id::Plain(s) | Id::Anonymous(s) => s.clone(),
+    }
+}
+
+/// Node of a [`FlatDOTGraph`] with all its attributes (incl. default node attributes)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DOTNode {
+    pub id: String,
+    pub attributes: HashMap<String, String>,
+}
+
+/// Edge of a [`FlatDOTGraph`] with all its attributes (incl. default edge attributes)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DOTEdge {
+    pub from: String,
+    pub to: String,
+    pub attributes: HashMap<String, String>,
+}
+
+///
+/// DOT graph with all nodes and edges (also those of subgraphs) and their resolved attributes
+///
+/// Nodes are ordered by their first occurrence (in a node or edge statement).
+/// Multiple statements for the same node are merged, with later attribute values taking precedence.
+/// Edge chains (e.g., `a -> b -> c`) are split into separate edges.
+/// Only attributes of the graph itself (not of subgraphs) are collected as graph attributes.
+///
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FlatDOTGraph {
+    pub attributes: HashMap<String, String>,
+    pub nodes: Vec<DOTNode>,
+    pub edges: Vec<DOTEdge>,
+}
+
+impl FlatDOTGraph {
+    /// Parse DOT source and flatten the resulting graph
+    pub fn parse(dot: &str) -> Result<Self, DOTParseError> {
+        let graph = graphviz_rust::parse(dot).map_err(DOTParseError::SyntaxError)?;
+        let stmts = match graph {
+            Graph::Graph { stmts, .. } | Graph::DiGraph { stmts, .. } => stmts,
+        };
+        let mut ret = Self::default();
+        for stmt in &stmts {
+            match stmt {
+                Stmt::GAttribute(GraphAttributes::Graph(attributes)) => {
+                    ret.attributes.extend(attributes_to_map(attributes));
+                }
+                Stmt::Attribute(attribute) => {
+                    ret.attributes
+                        .extend(attributes_to_map(std::slice::from_ref(attribute)));
+                }
+                _ => {}
+            }
+        }
+        let mut node_index: HashMap<String, usize> = HashMap::new();
+        ret.add_stmts(&stmts, &mut node_index, &HashMap::new(), &HashMap::new())?;
+        Ok(ret)
+    }
+
+    /// Get the node with the given ID (adding it with the default node attributes if it does not exist yet)
+    fn get_or_add_node<'a>(
+        &'a mut self,
+        id: String,
+        node_index: &mut HashMap<String, usize>,
+        node_defaults: &HashMap<String, String>,
+    ) -> &'a mut DOTNode {
+        let index = *node_index.entry(id.clone()).or_insert_with(|| {
+            self.nodes.push(DOTNode {
+                id,
+                attributes: node_defaults.clone(),
+            });
+            self.nodes.len() - 1
+        });
+        &mut self.nodes[index]
+    }
+
+    fn add_stmts(
+        &mut self,
+        stmts: &[Stmt],
+        node_index: &mut HashMap<String, usize>,
+        node_defaults: &HashMap<String, String>,
+        edge_defaults: &HashMap<String, String>,
+    ) -> Result<(), DOTParseError> {
+        // Default attributes only apply to the following statements (and subgraphs)
+        let mut node_defaults = node_defaults.clone();
+        let mut edge_defaults = edge_defaults.clone();
+        for stmt in stmts {
+            match stmt {
+                Stmt::Node(node) => {
+                    let node_id = id_to_string(&node.id.0);
+                    let dot_node = self.get_or_add_node(node_id, node_index, &node_defaults);
+                    dot_node
+                        .attributes
+                        .extend(attributes_to_map(&node.attributes));
+                }
+                Stmt::Edge(edge) => {
+                    let vertices = match &edge.ty {
+                        EdgeTy::Pair(from, to) => vec![from, to],
+                        EdgeTy::Chain(vertices) => vertices.iter().collect(),
+                    };
+                    let mut node_ids = Vec::with_capacity(vertices.len());
+                    for vertex in vertices {
+                        match vertex {
+                            Vertex::N(node_id) => {
+                                let node_id = id_to_string(&node_id.0);
+                                self.get_or_add_node(node_id.clone(), node_index, &node_defaults);
+                                node_ids.push(node_id);
+                            }
+                            Vertex::S(_) => return Err(DOTParseError::SubgraphEdge),
+                        }
+                    }
+                    let mut attributes = edge_defaults.clone();
+                    attributes.extend(attributes_to_map(&edge.attributes));
+                    for pair in node_ids.windows(2) {
+                        self.edges.push(DOTEdge {
+                            from: pair[0].clone(),
+                            to: pair[1].clone(),
+                            attributes: attributes.clone(),
+                        });
+                    }
+                }
+                Stmt::GAttribute(GraphAttributes::Node(attributes)) => {
+                    node_defaults.extend(attributes_to_map(attributes));
+                }
+                Stmt::GAttribute(GraphAttributes::Edge(attributes)) => {
+                    edge_defaults.extend(attributes_to_map(attributes));
+                }
+                Stmt::Subgraph(subgraph) => {
+                    self.add_stmts(&subgraph.stmts, node_index, &node_defaults, &edge_defaults)?;
+                }
+                Stmt::GAttribute(GraphAttributes::Graph(_)) | Stmt::Attribute(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn attributes_to_map(attributes: &[Attribute]) -> HashMap<String, String> {
+    attributes
+        .iter()
+        .map(|Attribute(key, value)| (id_to_string(key), id_to_string(value)))
+        .collect()
+}
+
+/// Parse a (trimmed) numeric label or attribute value
+pub(crate) fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, DOTParseError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| DOTParseError::InvalidNumber(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_dot_graph() {
+        let graph = FlatDOTGraph::parse(
+            r#"digraph {
+                rankdir=LR;
+                node [shape=box];
+                a [label="A \"quoted\""];
+                subgraph cluster_0 { edge [label=2]; b -> c -> "a" }
+                a -> b;
+                a [fillcolor=red]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(graph.attributes["rankdir"], "LR");
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(graph.nodes[0].attributes["label"], "A \"quoted\"");
+        assert_eq!(graph.nodes[0].attributes["fillcolor"], "red");
+        assert_eq!(graph.nodes[2].attributes["shape"], "box");
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.edges[1].to, "a");
+        assert_eq!(graph.edges[1].attributes["label"], "2");
+        assert!(!graph.edges[2].attributes.contains_key("label"));
+        assert!(matches!(
+            FlatDOTGraph::parse("digraph { a -> }"),
+            Err(DOTParseError::SyntaxError(_))
+        ));
+    }
+}
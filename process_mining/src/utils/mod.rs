@@ -1,3 +1,8 @@
+/// Helper utils regarding DOT (Graphviz) import
+///
+/// __Requires the `graphviz-export` feature to be enabled__
+#[cfg(feature = "graphviz-export")]
+pub mod dot_utils;
 /// Helper utils regarding XML import/export
 pub mod xml_utils;
 
@@ -7,10 +12,55 @@ pub mod xml_utils;
 pub mod test_utils {
     use std::path::PathBuf;
 
+    use crate::event_log::{AttributeValue, Event, EventLog, Trace, XESEditableAttribute};
+
     /// Get the based path for test data.
     ///
     ///  Used for internal testing
     pub fn get_test_data_path() -> PathBuf {
         std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data")
     }
+
+    /// Create an event with the given activity (`concept:name`) and additional attributes
+    ///
+    ///  Used for internal testing
+    pub fn event_with_attributes(activity: &str, attributes: &[(&str, AttributeValue)]) -> Event {
+        let mut event = Event::new(activity.to_string());
+        for (key, value) in attributes {
+            event
+                .attributes
+                .add_to_attributes(key.to_string(), value.clone());
+        }
+        event
+    }
+
+    /// Create an event log with one trace per passed list of events
+    ///
+    ///  Used for internal testing
+    pub fn event_log_from_events(traces: Vec<Vec<Event>>) -> EventLog {
+        let mut log = EventLog::new();
+        for events in traces {
+            let mut trace = Trace::new();
+            trace.events = events;
+            log.traces.push(trace);
+        }
+        log
+    }
+
+    /// Create an event log with one trace per passed activity sequence
+    ///
+    ///  Used for internal testing
+    pub fn event_log_from_activities(traces: &[&[&str]]) -> EventLog {
+        event_log_from_events(
+            traces
+                .iter()
+                .map(|activities| {
+                    activities
+                        .iter()
+                        .map(|a| Event::new(a.to_string()))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
 }
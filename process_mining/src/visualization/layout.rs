@@ -0,0 +1,612 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+///
+/// Direction in which the layers of a [`GraphLayout`] are arranged
+///
+pub enum LayoutDirection {
+    /// Layers are placed from left to right (i.e., edges mostly point to the right)
+    #[default]
+    LeftToRight,
+    /// Layers are placed from top to bottom (i.e., edges mostly point downwards)
+    TopToBottom,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Options for computing a layered graph layout (see [`compute_layered_layout`])
+///
+pub struct LayoutOptions {
+    /// Direction of the layers
+    pub direction: LayoutDirection,
+    /// Distance between two neighboring layers
+    pub layer_spacing: f64,
+    /// Minimal distance between two neighboring nodes of the same layer
+    pub node_spacing: f64,
+    /// Empty space around the drawing
+    pub margin: f64,
+    /// Maximal number of (downward and upward) sweeps used for reducing edge crossings
+    pub crossing_sweeps: usize,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            direction: LayoutDirection::default(),
+            layer_spacing: 50.0,
+            node_spacing: 25.0,
+            margin: 20.0,
+            crossing_sweeps: 12,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+///
+/// Position and size of a node in a [`GraphLayout`]
+///
+pub struct NodeLayout {
+    /// X-coordinate of the center of the node
+    pub x: f64,
+    /// Y-coordinate of the center of the node
+    pub y: f64,
+    /// Width of the node
+    pub width: f64,
+    /// Height of the node
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+///
+/// Layout of a directed graph, as computed by [`compute_layered_layout`]
+///
+pub struct GraphLayout {
+    /// Positions of the nodes (in the order of the input nodes)
+    pub nodes: Vec<NodeLayout>,
+    /// Points of the polyline of every edge (in the order of the input edges)
+    ///
+    /// The first point lies on the border of the source node and the last point on the border of the target node.
+    pub edges: Vec<Vec<(f64, f64)>>,
+    /// Width of the whole drawing (incl. margin)
+    pub width: f64,
+    /// Height of the whole drawing (incl. margin)
+    pub height: f64,
+}
+
+/// Node of the layered graph (the original nodes, followed by dummy nodes of long edges)
+struct LayerNode {
+    /// Extent along the layer axis (i.e., width for [`LayoutDirection::LeftToRight`])
+    layer_extent: f64,
+    /// Extent along the axis inside of a layer (i.e., height for [`LayoutDirection::LeftToRight`])
+    cross_extent: f64,
+    is_dummy: bool,
+}
+
+///
+/// Compute a layered (Sugiyama-style) layout of a directed graph
+///
+/// Nodes are given by their sizes (`(width, height)`), edges by the indices of their source and target node.
+/// The layout is computed in the following phases:
+///
+/// 1. Cycles are broken by reversing the back edges of a depth-first search (starting at nodes without incoming edges)
+/// 2. Nodes are assigned to layers by their longest path from a source node
+/// 3. Edges spanning multiple layers are split using dummy nodes
+/// 4. Edge crossings are reduced by repeated barycenter ordering of the layers
+/// 5. Nodes are placed close to the average position of their neighbors (without overlapping)
+///
+/// For the same input, the resulting layout is always the same.
+/// Self-loops are drawn as small loops next to their node.
+///
+/// Panics if an edge refers to a node index which is out of bounds.
+///
+pub fn compute_layered_layout(
+    node_sizes: &[(f64, f64)],
+    edges: &[(usize, usize)],
+    options: &LayoutOptions,
+) -> GraphLayout {
+    let n = node_sizes.len();
+    let mut nodes: Vec<LayerNode> = node_sizes
+        .iter()
+        .map(|(width, height)| {
+            let (layer_extent, cross_extent) = match options.direction {
+                LayoutDirection::LeftToRight => (*width, *height),
+                LayoutDirection::TopToBottom => (*height, *width),
+            };
+            LayerNode {
+                layer_extent,
+                cross_extent,
+                is_dummy: false,
+            }
+        })
+        .collect();
+
+    // Phase 1: Reverse back edges of a depth-first search
+    let reversed = find_back_edges(n, edges);
+    let oriented: Vec<(usize, usize)> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, (from, to))| {
+            if reversed[i] {
+                (*to, *from)
+            } else {
+                (*from, *to)
+            }
+        })
+        .collect();
+
+    // Phase 2: Longest path layering
+    let mut layer = longest_path_layering(n, &oriented);
+
+    // Phase 3: Split long edges into chains of dummy nodes
+    let mut chains: Vec<Vec<usize>> = Vec::with_capacity(edges.len());
+    for (from, to) in &oriented {
+        let mut chain = vec![*from];
+        if from != to {
+            for l in (layer[*from] + 1)..layer[*to] {
+                nodes.push(LayerNode {
+                    layer_extent: 0.0,
+                    cross_extent: 0.0,
+                    is_dummy: true,
+                });
+                layer.push(l);
+                chain.push(nodes.len() - 1);
+            }
+        }
+        chain.push(*to);
+        chains.push(chain);
+    }
+    let mut upper: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    let mut lower: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for chain in &chains {
+        for pair in chain.windows(2) {
+            if pair[0] != pair[1] {
+                lower[pair[0]].push(pair[1]);
+                upper[pair[1]].push(pair[0]);
+            }
+        }
+    }
+
+    // Phase 4: Crossing reduction
+    let num_layers = layer.iter().max().map(|l| l + 1).unwrap_or_default();
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); num_layers];
+    for (node, l) in layer.iter().enumerate() {
+        layers[*l].push(node);
+    }
+    let layers = reduce_crossings(layers, &upper, &lower, options.crossing_sweeps);
+
+    // Phase 5: Coordinate assignment
+    let cross = assign_cross_coordinates(&nodes, &layers, &upper, &lower, options);
+    let mut layer_center = Vec::with_capacity(num_layers);
+    let mut offset = options.margin;
+    for nodes_of_layer in &layers {
+        let thickness = nodes_of_layer
+            .iter()
+            .map(|v| nodes[*v].layer_extent)
+            .fold(0.0, f64::max);
+        layer_center.push(offset + thickness / 2.0);
+        offset += thickness + options.layer_spacing;
+    }
+
+    let to_xy = |l: f64, c: f64| match options.direction {
+        LayoutDirection::LeftToRight => (l, c),
+        LayoutDirection::TopToBottom => (c, l),
+    };
+    let node_layouts: Vec<NodeLayout> = (0..n)
+        .map(|v| {
+            let (x, y) = to_xy(layer_center[layer[v]], cross[v]);
+            NodeLayout {
+                x,
+                y,
+                width: node_sizes[v].0,
+                height: node_sizes[v].1,
+            }
+        })
+        .collect();
+    let loop_length = options.layer_spacing / 3.0;
+    let edge_layouts: Vec<Vec<(f64, f64)>> = chains
+        .iter()
+        .zip(reversed)
+        .map(|(chain, is_reversed)| {
+            let (first, last) = (chain[0], chain[chain.len() - 1]);
+            let mut points: Vec<(f64, f64)> = if first == last {
+                let l = layer_center[layer[first]] + nodes[first].layer_extent / 2.0;
+                let c = cross[first];
+                let quarter = nodes[first].cross_extent / 4.0;
+                vec![
+                    (l, c - quarter),
+                    (l + loop_length, c - quarter),
+                    (l + loop_length, c + quarter),
+                    (l, c + quarter),
+                ]
+            } else {
+                let mut points = vec![(
+                    layer_center[layer[first]] + nodes[first].layer_extent / 2.0,
+                    cross[first],
+                )];
+                points.extend(
+                    chain[1..chain.len() - 1]
+                        .iter()
+                        .map(|d| (layer_center[layer[*d]], cross[*d])),
+                );
+                points.push((
+                    layer_center[layer[last]] - nodes[last].layer_extent / 2.0,
+                    cross[last],
+                ));
+                points
+            };
+            if is_reversed {
+                points.reverse();
+            }
+            points.into_iter().map(|(l, c)| to_xy(l, c)).collect()
+        })
+        .collect();
+
+    let (mut width, mut height) = (0.0, 0.0);
+    for node in &node_layouts {
+        width = f64::max(width, node.x + node.width / 2.0);
+        height = f64::max(height, node.y + node.height / 2.0);
+    }
+    for (x, y) in edge_layouts.iter().flatten() {
+        width = f64::max(width, *x);
+        height = f64::max(height, *y);
+    }
+    GraphLayout {
+        nodes: node_layouts,
+        edges: edge_layouts,
+        width: width + options.margin,
+        height: height + options.margin,
+    }
+}
+
+/// Determine the edges to reverse for making the graph acyclic
+///
+/// Uses an iterative depth-first search, starting at nodes without incoming edges (in index order).
+/// Self-loops are never reversed.
+fn find_back_edges(n: usize, edges: &[(usize, usize)]) -> Vec<bool> {
+    let mut successors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut has_incoming = vec![false; n];
+    for (i, (from, to)) in edges.iter().enumerate() {
+        if from != to {
+            successors[*from].push((i, *to));
+            has_incoming[*to] = true;
+        }
+    }
+    let roots = (0..n).filter(|v| !has_incoming[*v]).chain(0..n);
+    // 0: unvisited, 1: on stack, 2: finished
+    let mut state = vec![0u8; n];
+    let mut reversed = vec![false; edges.len()];
+    for root in roots {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((node, next_succ)) = stack.last_mut() {
+            if let Some((edge, succ)) = successors[*node].get(*next_succ).copied() {
+                *next_succ += 1;
+                match state[succ] {
+                    0 => {
+                        state[succ] = 1;
+                        stack.push((succ, 0));
+                    }
+                    1 => reversed[edge] = true,
+                    _ => {}
+                }
+            } else {
+                state[*node] = 2;
+                stack.pop();
+            }
+        }
+    }
+    reversed
+}
+
+/// Assign every node to the layer given by the longest path from a source node to it
+fn longest_path_layering(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut in_degree = vec![0usize; n];
+    for (from, to) in edges {
+        if from != to {
+            successors[*from].push(*to);
+            in_degree[*to] += 1;
+        }
+    }
+    let mut layer = vec![0usize; n];
+    let mut queue: VecDeque<usize> = (0..n).filter(|v| in_degree[*v] == 0).collect();
+    while let Some(node) = queue.pop_front() {
+        for succ in &successors[node] {
+            layer[*succ] = layer[*succ].max(layer[node] + 1);
+            in_degree[*succ] -= 1;
+            if in_degree[*succ] == 0 {
+                queue.push_back(*succ);
+            }
+        }
+    }
+    layer
+}
+
+/// Reorder the nodes of every layer by the barycenter of their neighbors in the previous (or next) layer
+///
+/// Alternates between downward and upward sweeps and returns the order with the fewest crossings.
+fn reduce_crossings(
+    mut layers: Vec<Vec<usize>>,
+    upper: &[Vec<usize>],
+    lower: &[Vec<usize>],
+    sweeps: usize,
+) -> Vec<Vec<usize>> {
+    let mut position = vec![0usize; upper.len()];
+    let update_positions = |layers: &Vec<Vec<usize>>, position: &mut Vec<usize>| {
+        for nodes in layers {
+            for (i, v) in nodes.iter().enumerate() {
+                position[*v] = i;
+            }
+        }
+    };
+    let sort_layer = |nodes: &mut Vec<usize>, neighbors: &[Vec<usize>], position: &[usize]| {
+        let mut keyed: Vec<(f64, usize, usize)> = nodes
+            .iter()
+            .map(|v| {
+                let barycenter = if neighbors[*v].is_empty() {
+                    position[*v] as f64
+                } else {
+                    neighbors[*v]
+                        .iter()
+                        .map(|u| position[*u] as f64)
+                        .sum::<f64>()
+                        / neighbors[*v].len() as f64
+                };
+                (barycenter, position[*v], *v)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        *nodes = keyed.into_iter().map(|(_, _, v)| v).collect();
+    };
+
+    // Initial order: one downward sweep, starting from the input order
+    update_positions(&layers, &mut position);
+    for l in 1..layers.len() {
+        sort_layer(&mut layers[l], upper, &position);
+        update_positions(&layers, &mut position);
+    }
+    let mut best = layers.clone();
+    let mut best_crossings = count_crossings(&layers, lower, &position);
+    for _ in 0..sweeps {
+        if best_crossings == 0 {
+            break;
+        }
+        for l in 1..layers.len() {
+            sort_layer(&mut layers[l], upper, &position);
+            update_positions(&layers, &mut position);
+        }
+        for l in (0..layers.len().saturating_sub(1)).rev() {
+            sort_layer(&mut layers[l], lower, &position);
+            update_positions(&layers, &mut position);
+        }
+        let crossings = count_crossings(&layers, lower, &position);
+        if crossings < best_crossings {
+            best_crossings = crossings;
+            best = layers.clone();
+        }
+    }
+    best
+}
+
+/// Count the number of edge crossings between all pairs of neighboring layers
+fn count_crossings(layers: &[Vec<usize>], lower: &[Vec<usize>], position: &[usize]) -> usize {
+    let mut crossings = 0;
+    for nodes in layers {
+        let mut edges: Vec<(usize, usize)> = nodes
+            .iter()
+            .flat_map(|v| lower[*v].iter().map(|w| (position[*v], position[*w])))
+            .collect();
+        edges.sort_unstable();
+        for (i, (_, to)) in edges.iter().enumerate() {
+            crossings += edges[i + 1..]
+                .iter()
+                .filter(|(_, other_to)| other_to < to)
+                .count();
+        }
+    }
+    crossings
+}
+
+/// Assign the coordinates inside of the layers (i.e., y-coordinates for [`LayoutDirection::LeftToRight`])
+///
+/// Nodes are repeatedly moved towards the average position of their neighbors,
+/// while keeping the order and minimal distances inside of their layer.
+fn assign_cross_coordinates(
+    nodes: &[LayerNode],
+    layers: &[Vec<usize>],
+    upper: &[Vec<usize>],
+    lower: &[Vec<usize>],
+    options: &LayoutOptions,
+) -> Vec<f64> {
+    let separation = |a: usize, b: usize| {
+        let spacing = if nodes[a].is_dummy || nodes[b].is_dummy {
+            options.node_spacing / 2.0
+        } else {
+            options.node_spacing
+        };
+        (nodes[a].cross_extent + nodes[b].cross_extent) / 2.0 + spacing
+    };
+    let mut cross = vec![0.0; nodes.len()];
+    for layer in layers {
+        let mut c = 0.0;
+        for (i, v) in layer.iter().enumerate() {
+            if i > 0 {
+                c += separation(layer[i - 1], *v);
+            }
+            cross[*v] = c;
+        }
+        // Center all layers around 0
+        for v in layer {
+            cross[*v] -= c / 2.0;
+        }
+    }
+
+    let place_layer = |layer: &[usize], neighbors: &[&[Vec<usize>]], cross: &mut Vec<f64>| {
+        let desired: Vec<f64> = layer
+            .iter()
+            .map(|v| {
+                let adjacent: Vec<f64> = neighbors
+                    .iter()
+                    .flat_map(|n| n[*v].iter().map(|u| cross[*u]))
+                    .collect();
+                if adjacent.is_empty() {
+                    cross[*v]
+                } else {
+                    adjacent.iter().sum::<f64>() / adjacent.len() as f64
+                }
+            })
+            .collect();
+        let separations: Vec<f64> = (0..layer.len())
+            .map(|i| {
+                if i == 0 {
+                    0.0
+                } else {
+                    separation(layer[i - 1], layer[i])
+                }
+            })
+            .collect();
+        for (v, c) in layer
+            .iter()
+            .zip(place_with_separation(&desired, &separations))
+        {
+            cross[*v] = c;
+        }
+    };
+    for _ in 0..4 {
+        for layer in layers.iter().skip(1) {
+            place_layer(layer, &[upper], &mut cross);
+        }
+        for layer in layers.iter().rev().skip(1) {
+            place_layer(layer, &[lower], &mut cross);
+        }
+    }
+    for _ in 0..2 {
+        for layer in layers {
+            place_layer(layer, &[upper, lower], &mut cross);
+        }
+    }
+
+    // Move the drawing to start at the margin
+    let min = (0..nodes.len())
+        .map(|v| cross[v] - nodes[v].cross_extent / 2.0)
+        .fold(f64::INFINITY, f64::min);
+    if min.is_finite() {
+        for c in &mut cross {
+            *c += options.margin - min;
+        }
+    }
+    cross
+}
+
+/// Find positions as close as possible (least squares) to the desired ones,
+/// such that every position is at least `separations[i]` after the previous one
+///
+/// Solved as an isotonic regression using the pool adjacent violators algorithm.
+fn place_with_separation(desired: &[f64], separations: &[f64]) -> Vec<f64> {
+    let mut offsets = Vec::with_capacity(desired.len());
+    let mut offset = 0.0;
+    for s in separations {
+        offset += s;
+        offsets.push(offset);
+    }
+    // Blocks of (sum of targets, number of positions)
+    let mut blocks: Vec<(f64, usize)> = Vec::new();
+    for (d, o) in desired.iter().zip(&offsets) {
+        blocks.push((d - o, 1));
+        while blocks.len() >= 2 {
+            let (sum, count) = blocks[blocks.len() - 1];
+            let (prev_sum, prev_count) = blocks[blocks.len() - 2];
+            if prev_sum / prev_count as f64 <= sum / count as f64 {
+                break;
+            }
+            blocks.pop();
+            *blocks.last_mut().unwrap() = (prev_sum + sum, prev_count + count);
+        }
+    }
+    blocks
+        .into_iter()
+        .flat_map(|(sum, count)| std::iter::repeat(sum / count as f64).take(count))
+        .zip(offsets)
+        .map(|(value, o)| value + o)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layered_layout() {
+        // 0 -> 1 -> 2 -> 3, 0 -> 3 and the back edge 2 -> 1
+        let sizes = vec![(30.0, 30.0), (80.0, 40.0), (80.0, 40.0), (30.0, 30.0)];
+        let edges = vec![(0, 1), (1, 2), (2, 3), (0, 3), (2, 1), (3, 3)];
+        let options = LayoutOptions::default();
+        let layout = compute_layered_layout(&sizes, &edges, &options);
+        assert_eq!(layout, compute_layered_layout(&sizes, &edges, &options));
+        assert_eq!(layout.nodes.len(), 4);
+        assert_eq!(layout.edges.len(), edges.len());
+        for pair in layout.nodes.windows(2) {
+            assert!(pair[0].x < pair[1].x);
+        }
+        // The long edge 0 -> 3 is routed through two dummy nodes
+        assert_eq!(layout.edges[3].len(), 4);
+        // The back edge starts at the left border of node 2 and ends at the right border of node 1
+        let back_edge = &layout.edges[4];
+        assert_eq!(back_edge[0].0, layout.nodes[2].x - 40.0);
+        assert_eq!(back_edge[back_edge.len() - 1].0, layout.nodes[1].x + 40.0);
+        for node in &layout.nodes {
+            assert!(node.x - node.width / 2.0 >= options.margin);
+            assert!(node.y - node.height / 2.0 >= options.margin);
+            assert!(node.x + node.width / 2.0 <= layout.width);
+            assert!(node.y + node.height / 2.0 <= layout.height);
+        }
+
+        let top_to_bottom = compute_layered_layout(
+            &sizes,
+            &edges,
+            &LayoutOptions {
+                direction: LayoutDirection::TopToBottom,
+                ..Default::default()
+            },
+        );
+        for pair in top_to_bottom.nodes.windows(2) {
+            assert!(pair[0].y < pair[1].y);
+        }
+    }
+
+    #[test]
+    fn no_overlaps_and_crossings() {
+        // Two parallel branches, which are initially ordered such that they cross
+        let sizes = vec![(20.0, 20.0); 6];
+        let edges = vec![(0, 2), (0, 1), (1, 4), (2, 3), (3, 5), (4, 5)];
+        let layout = compute_layered_layout(&sizes, &edges, &LayoutOptions::default());
+        let (n1, n2, n3, n4) = (
+            layout.nodes[1],
+            layout.nodes[2],
+            layout.nodes[3],
+            layout.nodes[4],
+        );
+        assert_eq!(n1.x, n2.x);
+        assert!((n1.y - n2.y).abs() >= 20.0 + LayoutOptions::default().node_spacing);
+        // Both branches keep their side
+        assert_eq!(n1.y < n2.y, n4.y < n3.y);
+        assert_eq!(layout.nodes[0].y, layout.nodes[5].y);
+    }
+
+    #[test]
+    fn separation() {
+        assert_eq!(
+            place_with_separation(&[0.0, 0.0, 0.0], &[0.0, 10.0, 10.0]),
+            vec![-10.0, 0.0, 10.0]
+        );
+        assert_eq!(
+            place_with_separation(&[0.0, 50.0], &[0.0, 10.0]),
+            vec![0.0, 50.0]
+        );
+    }
+}
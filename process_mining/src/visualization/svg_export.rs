@@ -0,0 +1,525 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    dfg::dfg_struct::DirectlyFollowsGraph,
+    event_log::event_log_struct::EventLogClassifier,
+    partial_orders::partial_event_log_struct::{EventHash, PartialOrderTrace},
+    petri_net::petri_net_struct::{ArcType, PetriNet, PlaceID},
+};
+
+use super::layout::{compute_layered_layout, LayoutDirection, LayoutOptions};
+
+/// Diameter of places
+const PLACE_SIZE: f64 = 30.0;
+/// Height of labeled transitions and boxes
+const BOX_HEIGHT: f64 = 36.0;
+/// Thickness of silent transitions
+const SILENT_TRANSITION_SIZE: f64 = 12.0;
+/// Fill color of start activities (same as in the Graphviz export)
+const START_COLOR: &str = "#4B9969";
+/// Fill color of end activities (same as in the Graphviz export)
+const END_COLOR: &str = "#D4001F";
+/// Color of all strokes and texts
+const STROKE_COLOR: &str = "#333333";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Options for exporting SVG images
+///
+pub struct SVGExportOptions {
+    /// Options of the layered layout (e.g., [`LayoutDirection`] or spacing)
+    pub layout: LayoutOptions,
+    /// Font size of all labels
+    pub font_size: f64,
+    /// Font family of all labels
+    pub font_family: String,
+}
+
+impl Default for SVGExportOptions {
+    fn default() -> Self {
+        Self {
+            layout: LayoutOptions::default(),
+            font_size: 12.0,
+            font_family: "sans-serif".to_string(),
+        }
+    }
+}
+
+/// Shape of a node in the SVG image
+enum NodeShape {
+    /// Place of a Petri net
+    Place { is_final: bool, tokens: u64 },
+    /// Box with a centered label
+    Box { label: String, fill: String },
+    /// Silent transition of a Petri net
+    Silent,
+}
+
+/// Node of the SVG image
+struct SVGNode {
+    id: String,
+    shape: NodeShape,
+    width: f64,
+    height: f64,
+}
+
+/// Edge of the SVG image
+struct SVGEdge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    stroke_width: f64,
+}
+
+/// Approximate width of a text (as no font metrics are available)
+fn text_width(text: &str, options: &SVGExportOptions) -> f64 {
+    text.chars().count() as f64 * options.font_size * 0.6
+}
+
+/// Create a box node, which is wide enough for its label
+fn box_node(id: String, label: String, fill: String, options: &SVGExportOptions) -> SVGNode {
+    SVGNode {
+        id,
+        width: f64::max(50.0, text_width(&label, options) + 20.0),
+        height: BOX_HEIGHT,
+        shape: NodeShape::Box { label, fill },
+    }
+}
+
+/// Layout and render nodes and edges as an SVG image
+fn render_svg(nodes: &[SVGNode], edges: &[SVGEdge], options: &SVGExportOptions) -> String {
+    let sizes: Vec<(f64, f64)> = nodes.iter().map(|n| (n.width, n.height)).collect();
+    let edge_indices: Vec<(usize, usize)> = edges.iter().map(|e| (e.from, e.to)).collect();
+    let layout = compute_layered_layout(&sizes, &edge_indices, &options.layout);
+    let font_size = options.font_size;
+
+    // Writing to a String never fails
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.1}" height="{h:.1}" viewBox="0 0 {w:.1} {h:.1}" font-family="{}" font-size="{font_size}">"#,
+        escape(&options.font_family),
+        w = layout.width,
+        h = layout.height,
+    );
+    let _ = writeln!(
+        svg,
+        r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerUnits="userSpaceOnUse" markerWidth="10" markerHeight="10" orient="auto"><path d="M 0 1 L 10 5 L 0 9 z" fill="{STROKE_COLOR}"/></marker>"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<linearGradient id="start-end" x1="0" y1="1" x2="1" y2="0"><stop offset="50%" stop-color="{START_COLOR}"/><stop offset="50%" stop-color="{END_COLOR}"/></linearGradient></defs>"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    svg.push_str("<g class=\"edges\">\n");
+    for (edge, points) in edges.iter().zip(&layout.edges) {
+        let path: Vec<String> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (x, y))| format!("{} {x:.1} {y:.1}", if i == 0 { "M" } else { "L" }))
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<path d="{}" fill="none" stroke="{STROKE_COLOR}" stroke-width="{:.1}" marker-end="url(#arrow)"/>"#,
+            path.join(" "),
+            edge.stroke_width
+        );
+        if let Some(label) = &edge.label {
+            // Place the label above the middle of the middle segment
+            let mid = (points.len() - 1) / 2;
+            let (x, y) = (
+                (points[mid].0 + points[mid + 1].0) / 2.0,
+                (points[mid].1 + points[mid + 1].1) / 2.0,
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{x:.1}" y="{:.1}" text-anchor="middle" fill="{STROKE_COLOR}">{}</text>"#,
+                y - 4.0,
+                escape(label)
+            );
+        }
+    }
+    svg.push_str("</g>\n<g class=\"nodes\">\n");
+    for (node, position) in nodes.iter().zip(&layout.nodes) {
+        let (x, y) = (position.x, position.y);
+        let _ = write!(svg, r#"<g id="{}">"#, escape(&node.id));
+        match &node.shape {
+            NodeShape::Place { is_final, tokens } => {
+                let r = node.width / 2.0;
+                let _ = write!(
+                    svg,
+                    r#"<circle cx="{x:.1}" cy="{y:.1}" r="{r:.1}" fill="white" stroke="{STROKE_COLOR}"/>"#
+                );
+                if *is_final {
+                    let _ = write!(
+                        svg,
+                        r#"<circle cx="{x:.1}" cy="{y:.1}" r="{:.1}" fill="none" stroke="{STROKE_COLOR}"/>"#,
+                        r - 3.0
+                    );
+                }
+                match tokens {
+                    0 => {}
+                    1 => {
+                        let _ = write!(
+                            svg,
+                            r#"<circle cx="{x:.1}" cy="{y:.1}" r="4" fill="{STROKE_COLOR}"/>"#
+                        );
+                    }
+                    _ => {
+                        let _ = write!(
+                            svg,
+                            r#"<text x="{x:.1}" y="{y:.1}" text-anchor="middle" dominant-baseline="central">{tokens}</text>"#
+                        );
+                    }
+                }
+            }
+            NodeShape::Box { label, fill } => {
+                let _ = write!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="3" fill="{fill}" stroke="{STROKE_COLOR}"/>"#,
+                    x - node.width / 2.0,
+                    y - node.height / 2.0,
+                    node.width,
+                    node.height,
+                );
+                let _ = write!(
+                    svg,
+                    r#"<text x="{x:.1}" y="{y:.1}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    escape(label)
+                );
+            }
+            NodeShape::Silent => {
+                let _ = write!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{STROKE_COLOR}" stroke="{STROKE_COLOR}"/>"#,
+                    x - node.width / 2.0,
+                    y - node.height / 2.0,
+                    node.width,
+                    node.height,
+                );
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+///
+/// Render a [`PetriNet`] as an SVG image
+///
+/// Places in the initial marking contain their tokens, places in a final marking are drawn with a double border
+/// and silent transitions are drawn as black bars.
+/// Places and transitions are sorted by their ID, so that the same net always results in the same image.
+///
+/// Also see [`export_petri_net_svg_to_path`]
+///
+pub fn petri_net_to_svg(net: &PetriNet, options: &SVGExportOptions) -> String {
+    let mut place_ids: Vec<&Uuid> = net.places.keys().collect();
+    place_ids.sort();
+    let mut transition_ids: Vec<&Uuid> = net.transitions.keys().collect();
+    transition_ids.sort();
+
+    let mut index: HashMap<Uuid, usize> = HashMap::new();
+    let mut nodes: Vec<SVGNode> = Vec::with_capacity(place_ids.len() + transition_ids.len());
+    for id in place_ids {
+        index.insert(*id, nodes.len());
+        let place_id: PlaceID = (&net.places[id]).into();
+        let tokens = net
+            .initial_marking
+            .as_ref()
+            .and_then(|m| m.get(&place_id))
+            .copied()
+            .unwrap_or_default();
+        nodes.push(SVGNode {
+            id: id.to_string(),
+            shape: NodeShape::Place {
+                is_final: net.is_in_a_final_marking(&place_id),
+                tokens,
+            },
+            width: PLACE_SIZE,
+            height: PLACE_SIZE,
+        });
+    }
+    for id in transition_ids {
+        index.insert(*id, nodes.len());
+        nodes.push(match &net.transitions[id].label {
+            Some(label) => box_node(id.to_string(), label.clone(), "white".to_string(), options),
+            None => {
+                let (width, height) = match options.layout.direction {
+                    LayoutDirection::LeftToRight => (SILENT_TRANSITION_SIZE, BOX_HEIGHT),
+                    LayoutDirection::TopToBottom => (BOX_HEIGHT, SILENT_TRANSITION_SIZE),
+                };
+                SVGNode {
+                    id: id.to_string(),
+                    shape: NodeShape::Silent,
+                    width,
+                    height,
+                }
+            }
+        });
+    }
+
+    let edges: Vec<SVGEdge> = net
+        .arcs
+        .iter()
+        .filter_map(|arc| {
+            let (from, to) = match arc.from_to {
+                ArcType::PlaceTransition(from, to) | ArcType::TransitionPlace(from, to) => {
+                    (from, to)
+                }
+            };
+            Some(SVGEdge {
+                from: *index.get(&from)?,
+                to: *index.get(&to)?,
+                label: (arc.weight != 1).then(|| arc.weight.to_string()),
+                stroke_width: 1.0,
+            })
+        })
+        .collect();
+    render_svg(&nodes, &edges, options)
+}
+
+///
+/// Render a [`DirectlyFollowsGraph`] as an SVG image
+///
+/// Activities are annotated with their frequency and colored like in the Graphviz export
+/// (start activities green, end activities red).
+/// The stroke width of directly-follows relations is scaled by their frequency.
+///
+/// Also see [`export_dfg_svg_to_path`]
+///
+pub fn dfg_to_svg(dfg: &DirectlyFollowsGraph<'_>, options: &SVGExportOptions) -> String {
+    let mut activities: Vec<(&String, &u32)> = dfg.activities.iter().collect();
+    activities.sort();
+    let index: HashMap<&str, usize> = activities
+        .iter()
+        .enumerate()
+        .map(|(i, (act, _))| (act.as_str(), i))
+        .collect();
+    let nodes: Vec<SVGNode> = activities
+        .iter()
+        .map(|(act, frequency)| {
+            let fill = match (dfg.is_start_activity(act), dfg.is_end_activity(act)) {
+                (true, true) => "url(#start-end)",
+                (true, false) => START_COLOR,
+                (false, true) => END_COLOR,
+                (false, false) => "white",
+            };
+            box_node(
+                act.to_string(),
+                format!("{act}: {frequency}"),
+                fill.to_string(),
+                options,
+            )
+        })
+        .collect();
+
+    let mut relations: Vec<(&str, &str, u32)> = dfg
+        .directly_follows_relations
+        .iter()
+        .map(|((from, to), frequency)| (from.as_ref(), to.as_ref(), *frequency))
+        .collect();
+    relations.sort();
+    let max_frequency = relations.iter().map(|r| r.2).max().unwrap_or(1).max(1);
+    let edges: Vec<SVGEdge> = relations
+        .into_iter()
+        .filter_map(|(from, to, frequency)| {
+            Some(SVGEdge {
+                from: *index.get(from)?,
+                to: *index.get(to)?,
+                label: (frequency != 1).then(|| frequency.to_string()),
+                stroke_width: 1.0 + 3.0 * frequency as f64 / max_frequency as f64,
+            })
+        })
+        .collect();
+    render_svg(&nodes, &edges, options)
+}
+
+///
+/// Render a [`PartialOrderTrace`] as an SVG image
+///
+/// Events are labeled using the given [`EventLogClassifier`].
+///
+/// Also see [`export_p_trace_svg_to_path`]
+///
+pub fn p_trace_to_svg(
+    p_trace: &PartialOrderTrace,
+    classifier: &EventLogClassifier,
+    options: &SVGExportOptions,
+) -> String {
+    let mut events: Vec<(String, _)> = p_trace
+        .event_map
+        .iter()
+        .map(|(hash, event)| (classifier.get_class_identity(event), hash))
+        .collect();
+    // Events with the same label are ordered by (a hash of) their event hash
+    let hash_key = |hash: &EventHash| {
+        let mut hasher = DefaultHasher::new();
+        hash.hash(&mut hasher);
+        hasher.finish()
+    };
+    events.sort_by_cached_key(|(label, hash)| (label.clone(), hash_key(hash)));
+    let index: HashMap<_, usize> = events
+        .iter()
+        .enumerate()
+        .map(|(i, (_, hash))| (*hash, i))
+        .collect();
+    let nodes: Vec<SVGNode> = events
+        .iter()
+        .enumerate()
+        .map(|(i, (label, _))| {
+            box_node(
+                format!("event-{i}"),
+                label.clone(),
+                "white".to_string(),
+                options,
+            )
+        })
+        .collect();
+    let mut edges: Vec<SVGEdge> = p_trace
+        .partial_relations
+        .iter()
+        .filter_map(|(from, to)| {
+            Some(SVGEdge {
+                from: *index.get(from)?,
+                to: *index.get(to)?,
+                label: None,
+                stroke_width: 1.0,
+            })
+        })
+        .collect();
+    edges.sort_by_key(|e| (e.from, e.to));
+    render_svg(&nodes, &edges, options)
+}
+
+///
+/// Export the image of a [`PetriNet`] as an SVG file (without requiring Graphviz)
+///
+/// Also see [`petri_net_to_svg`]
+///
+pub fn export_petri_net_svg_to_path<P: AsRef<std::path::Path>>(
+    net: &PetriNet,
+    path: P,
+    options: &SVGExportOptions,
+) -> Result<(), std::io::Error> {
+    std::fs::write(path, petri_net_to_svg(net, options))
+}
+
+///
+/// Export the image of a [`DirectlyFollowsGraph`] as an SVG file (without requiring Graphviz)
+///
+/// Also see [`dfg_to_svg`]
+///
+pub fn export_dfg_svg_to_path<P: AsRef<std::path::Path>>(
+    dfg: &DirectlyFollowsGraph<'_>,
+    path: P,
+    options: &SVGExportOptions,
+) -> Result<(), std::io::Error> {
+    std::fs::write(path, dfg_to_svg(dfg, options))
+}
+
+///
+/// Export the image of a [`PartialOrderTrace`] as an SVG file (without requiring Graphviz)
+///
+/// Also see [`p_trace_to_svg`]
+///
+pub fn export_p_trace_svg_to_path<P: AsRef<std::path::Path>>(
+    p_trace: &PartialOrderTrace,
+    classifier: &EventLogClassifier,
+    path: P,
+    options: &SVGExportOptions,
+) -> Result<(), std::io::Error> {
+    std::fs::write(path, p_trace_to_svg(p_trace, classifier, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        conformance::token_replay::tests::choice_net,
+        event_log::{
+            event_log_struct::EventLogClassifier, AttributeValue, Attributes, Event,
+            XESEditableAttribute,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn petri_net_svg() {
+        let net = choice_net();
+        let svg = petri_net_to_svg(&net, &SVGExportOptions::default());
+        assert_eq!(svg, petri_net_to_svg(&net, &SVGExportOptions::default()));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert_eq!(svg.matches("marker-end=").count(), net.arcs.len());
+        assert!(svg.contains(">a</text>"));
+        // Initial marking (one token) and final marking (double border)
+        assert!(svg.contains(r#"r="4""#));
+        assert!(svg.contains(r#"r="12.0""#));
+
+        let top_to_bottom = petri_net_to_svg(
+            &net,
+            &SVGExportOptions {
+                layout: LayoutOptions {
+                    direction: LayoutDirection::TopToBottom,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        assert_ne!(svg, top_to_bottom);
+    }
+
+    #[test]
+    fn dfg_svg() {
+        let mut dfg = DirectlyFollowsGraph::new();
+        dfg.add_activity("Register & Check".into(), 3);
+        dfg.add_activity("Pay".into(), 2);
+        dfg.add_start_activity("Register & Check".into());
+        dfg.add_end_activity("Pay".into());
+        dfg.add_end_activity("Register & Check".into());
+        dfg.add_df_relation("Register & Check".into(), "Pay".into(), 2);
+        dfg.add_df_relation("Pay".into(), "Pay".into(), 1);
+        let svg = dfg_to_svg(&dfg, &SVGExportOptions::default());
+        assert!(svg.contains(">Register &amp; Check: 3</text>"));
+        assert!(svg.contains(r#"fill="url(#start-end)""#));
+        assert!(svg.contains(&format!(r#"fill="{END_COLOR}""#)));
+        assert_eq!(svg.matches("marker-end=").count(), 2);
+        assert!(svg.contains(">2</text>"));
+    }
+
+    #[test]
+    fn p_trace_svg() {
+        let event = |activity: &str| {
+            let mut attributes = Attributes::new();
+            attributes.add_to_attributes(
+                "concept:name".to_string(),
+                AttributeValue::String(activity.to_string()),
+            );
+            Event { attributes }
+        };
+        let (a, b, c) = (event("a"), event("b"), event("c"));
+        let mut p_trace = PartialOrderTrace::new();
+        for e in [&a, &b, &c] {
+            p_trace.add_event(e);
+        }
+        p_trace.add_partial_relation(&a, &b);
+        p_trace.add_partial_relation(&a, &c);
+        let svg = p_trace_to_svg(
+            &p_trace,
+            &EventLogClassifier::default(),
+            &SVGExportOptions::default(),
+        );
+        assert_eq!(svg.matches("<rect x=").count(), 3);
+        assert_eq!(svg.matches("marker-end=").count(), 2);
+    }
+}
@@ -1,18 +1,38 @@
 from typing import Optional, Tuple
 import pm4py
+from pm4py.objects.petri_net import properties as petri_properties
 from pm4py.objects.petri_net.obj import PetriNet, Marking
 from pm4py.objects.petri_net.utils.petri_utils import add_arc_from_to
 
+# Key in the properties of the net under which the (uninterpreted) tool-specific data is kept
+TOOL_SPECIFIC_PROPERTY = "tool_specific"
+
 def dict_to_petrinet(
     net_dict,
 ) -> Tuple[PetriNet, Optional[Marking], Optional[Marking]]:
-    places = {p["id"]: PetriNet.Place(p["id"]) for p in net_dict["places"].values()}
+    places = dict()
+    for p in net_dict["places"].values():
+        place = PetriNet.Place(p["id"])
+        if p.get("name") is not None:
+            place.properties[petri_properties.PLACE_NAME_TAG] = p["name"]
+        places[p["id"]] = place
     transitions = {
         t["id"]: PetriNet.Transition(t["id"], t["label"])
         for t in net_dict["transitions"].values()
     }
 
+    # Positions (center and optional size) of places and transitions
+    for node_id, position in net_dict.get("positions", dict()).items():
+        node = places.get(node_id) or transitions.get(node_id)
+        if node is not None:
+            node.properties[petri_properties.LAYOUT_INFORMATION_PETRI] = (
+                (position["x"], position["y"]),
+                (position.get("width"), position.get("height")),
+            )
+
     net = PetriNet(None, places.values(), transitions.values())
+    if len(net_dict.get("tool_specific", [])) > 0:
+        net.properties[TOOL_SPECIFIC_PROPERTY] = net_dict["tool_specific"]
     def get_arc_for(arc_dict):
         if arc_dict["from_to"]["type"] == "PlaceTransition":
             fr = places.get(arc_dict["from_to"]["nodes"][0])
@@ -49,18 +69,48 @@ def petrinet_to_dict(
 ) -> dict:
     import uuid
 
+    # Nodes named by a UUID (e.g., created by dict_to_petrinet) keep it, such that tool-specific data still refers to them
+    used_uuids = set()
+
+    def node_uuid(node) -> str:
+        try:
+            node_id = str(uuid.UUID(str(node.name)))
+        except ValueError:
+            node_id = str(uuid.uuid4())
+        if node_id in used_uuids:
+            node_id = str(uuid.uuid4())
+        used_uuids.add(node_id)
+        return node_id
+
     # Used to save a mapping of python ids (e.g., id(p)) to the unique generated uuids (used in the dict)
     pyid_to_uuid = dict()
+    positions = dict()
+
+    def add_position(node, node_id: str):
+        layout = node.properties.get(petri_properties.LAYOUT_INFORMATION_PETRI)
+        if layout is not None:
+            (x, y), (width, height) = layout
+            position = {"x": x, "y": y}
+            if width is not None:
+                position["width"] = width
+            if height is not None:
+                position["height"] = height
+            positions[node_id] = position
+
     places = dict()
     for p in net.places:
-        pid = str(uuid.uuid4())
+        pid = node_uuid(p)
         pyid_to_uuid[id(p)] = pid
         places[pid] = {"id": pid}
+        if p.properties.get(petri_properties.PLACE_NAME_TAG) is not None:
+            places[pid]["name"] = p.properties[petri_properties.PLACE_NAME_TAG]
+        add_position(p, pid)
     transitions = dict()
     for t in net.transitions:
-        tid = str(uuid.uuid4())
+        tid = node_uuid(t)
         pyid_to_uuid[id(t)] = tid
         transitions[tid] = {"id": tid, "label": t.label}
+        add_position(t, tid)
     arcs = [
         {
             "from_to": {
@@ -94,4 +144,6 @@ def petrinet_to_dict(
         "arcs": arcs,
         "initial_marking": initial_marking,
         "final_markings": final_markings,
+        "positions": positions,
+        "tool_specific": net.properties.get(TOOL_SPECIFIC_PROPERTY, []),
     }